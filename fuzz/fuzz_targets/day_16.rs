@@ -0,0 +1,8 @@
+#![no_main]
+
+use advent_of_code_2021::year_2021::day_16::parse_input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = parse_input(input);
+});
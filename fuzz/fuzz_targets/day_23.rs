@@ -0,0 +1,9 @@
+#![no_main]
+
+use advent_of_code_2021::year_2021::day_23::{parse_input, Burrow};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Burrow::try_from(input);
+    let _ = parse_input(input);
+});
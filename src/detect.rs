@@ -0,0 +1,79 @@
+//! Works out which day an unlabelled puzzle input is for, with `--detect <path>`. Each day has a `sniff` function,
+//! registered with [`Day::with_sniff`](crate::solution::Day::with_sniff), that checks the input has the shape its
+//! parser expects, mostly by running the day's `validate`, see [`crate::errors`]. [`detect`] asks every day of the
+//! year.
+//!
+//! A few days share a format, e.g. days 6 and 7 are both one line of comma separated numbers, so their sniffers also
+//! look at the values to tell them apart. That isn't always possible, the examples for days 11 and 15 are both 10 by 10
+//! grids of digits, so all the days that match are reported. With `--solve` the runner then solves the input, if only
+//! one day matched.
+
+use crate::solution::Registry;
+
+/// The days of `year` that `input` could be for, in order
+pub fn detect(registry: &Registry, year: u16, input: &str) -> Vec<u8> {
+    // An empty input is valid for a few of the line based days, but isn't anyone's puzzle input
+    if input.trim().is_empty() {
+        return Vec::new();
+    }
+
+    registry
+        .days(year)
+        .filter(|(_, solution)| solution.sniff(input))
+        .map(|(day, _)| day)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::detect::detect;
+    use crate::solution::Registry;
+    use std::fs;
+
+    /// A fragment of a MONAD program, as day 24 has no example input
+    const DAY_24_PROGRAM: &str =
+        "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z 1\nadd x 12\neql x w\neql x 0";
+
+    #[test]
+    fn can_detect_the_examples() {
+        let registry = Registry::all();
+        for day in 1..=25 {
+            let input = match day {
+                24 => DAY_24_PROGRAM.to_string(),
+                _ => fs::read_to_string(format!("tests/samples/res/day-{}-input", day)).unwrap(),
+            };
+            let expected = match day {
+                // The two examples are both 10 by 10 grids of digits, see the module docs
+                11 | 15 => vec![11, 15],
+                _ => vec![day],
+            };
+            let included: Vec<u8> = expected
+                .into_iter()
+                .filter(|&day| registry.get(2021, day).unwrap().is_included())
+                .collect();
+
+            assert_eq!(detect(&registry, 2021, &input), included, "Day {}", day);
+        }
+    }
+
+    #[test]
+    fn can_tell_similar_inputs_apart() {
+        let registry = Registry::all();
+        let detected = |input: &str| detect(&registry, 2021, input);
+
+        // Only checked when every day is built, as the days left out are never detected
+        if !cfg!(feature = "all-days") {
+            return;
+        }
+
+        assert_eq!(detected("3,4,3,1,2"), vec![6]);
+        assert_eq!(detected("3,4,3,10,2"), vec![7]);
+        assert_eq!(detected("199\n200\n208"), vec![1]);
+        assert_eq!(detected("00100\n11110\n10110"), vec![3]);
+        assert_eq!(detected("[[]]\n[[1,2],3]"), vec![18]);
+        assert_eq!(detected("[]<>\n(())"), vec![10]);
+        assert_eq!(detected("7,4,9\n\n1 2\n3 4"), vec![4]);
+        assert_eq!(detected(""), Vec::<u8>::new());
+        assert_eq!(detected("Not a puzzle input"), Vec::<u8>::new());
+    }
+}
@@ -0,0 +1,229 @@
+//! Checks on the shape of the puzzle input, run by each day before it is parsed. The parsers mostly assume the input
+//! is well-formed and panic with an `unwrap()` part way through if it isn't, which doesn't say much about what was
+//! wrong. Each day has a `validate` function that checks the assumptions its parser makes, e.g. line formats, ranges,
+//! and that grids are rectangular, and returns an [`InputError`] with the line number and reason for each problem.
+//! [`check_input`] runs this for a day and reports all the problems at once. The days' `validate` functions are also
+//! available through [`crate::solution::Solution::validate`], so that the problems can be reported without panicking,
+//! see [`crate::solve`].
+//!
+//! The helpers here cover the common checks. They number lines from 1 within the text they are given, so
+//! [`sections`] and [`offset`] are used to check inputs made up of several blank line separated sections.
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// A problem with a line of the puzzle input
+#[derive(Eq, PartialEq, Debug)]
+pub struct InputError {
+    /// The line the problem is on, counting from 1
+    pub line: usize,
+    /// What is wrong with the line
+    pub reason: String,
+}
+
+impl InputError {
+    pub fn new(line: usize, reason: impl ToString) -> InputError {
+        InputError {
+            line,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.reason)
+    }
+}
+
+/// The result of validating a day's input, with every problem found if it is invalid
+pub type Validation = Result<(), Vec<InputError>>;
+
+/// Turn a list of problems into a [`Validation`], valid if the list is empty
+pub fn to_validation(errors: Vec<InputError>) -> Validation {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Validate a day's input with its `validate` function, which isn't called if the input is empty
+pub fn validate_input(input: &str, validate: fn(&str) -> Validation) -> Validation {
+    if input.trim().is_empty() {
+        Err(vec![InputError::new(1, "The input is empty")])
+    } else {
+        validate(input)
+    }
+}
+
+/// Describe all the problems with a day's input, one per line
+pub fn describe_problems(day: u8, errors: &[InputError]) -> String {
+    let problems: Vec<String> = errors.iter().map(InputError::to_string).collect();
+    format!(
+        "The input for day {} is invalid:\n{}",
+        day,
+        problems.join("\n")
+    )
+}
+
+/// Validate a day's input before solving it, panicking with the full list of problems if there are any
+pub fn check_input(day: u8, input: &str, validate: fn(&str) -> Validation) {
+    if let Err(errors) = validate_input(input, validate) {
+        panic!("{}", describe_problems(day, &errors));
+    }
+}
+
+/// Run `check` against each line with its line number, collecting a problem for each line it returns a reason for
+pub fn check_lines(
+    input: &str,
+    mut check: impl FnMut(usize, &str) -> Option<String>,
+) -> Vec<InputError> {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| check(i + 1, line).map(|reason| InputError::new(i + 1, reason)))
+        .collect()
+}
+
+/// Check every line matches `pattern` in full, describing the expected format with `description`
+pub fn check_pattern(input: &str, pattern: &str, description: &str) -> Vec<InputError> {
+    let regex = Regex::new(&format!("^(?:{})$", pattern)).expect("Invalid pattern");
+    check_lines(input, |_, line| {
+        if regex.is_match(line) {
+            None
+        } else {
+            Some(format!("Expected {}, found '{}'", description, line))
+        }
+    })
+}
+
+/// Check the lines form a rectangular grid, made up only of the `allowed` characters
+pub fn check_grid(input: &str, allowed: &str) -> Vec<InputError> {
+    let allowed: HashSet<char> = allowed.chars().collect();
+    let width = input
+        .lines()
+        .next()
+        .map(|line| line.chars().count())
+        .unwrap_or(0);
+
+    check_lines(input, |_, line| {
+        if let Some(chr) = line.chars().find(|chr| !allowed.contains(chr)) {
+            Some(format!("Unexpected character '{}'", chr))
+        } else if line.chars().count() != width {
+            Some(format!(
+                "Expected {} characters to match the first line, found {}",
+                width,
+                line.chars().count()
+            ))
+        } else {
+            None
+        }
+    })
+}
+
+/// Check the input is just one line, ignoring a trailing line break
+pub fn check_single_line(input: &str) -> Vec<InputError> {
+    check_lines(input, |number, _| {
+        if number == 2 {
+            Some("Expected the input to be a single line".to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// Split the input on blank lines, returning each section with the line number it starts on
+pub fn sections(input: &str) -> Vec<(usize, &str)> {
+    let mut first_line = 1;
+    input
+        .split("\n\n")
+        .map(|section| {
+            let start = first_line;
+            first_line += section.lines().count() + 1;
+            (start, section)
+        })
+        .collect()
+}
+
+/// Renumber the problems found in a section so that they refer to lines of the whole input
+pub fn offset(errors: Vec<InputError>, first_line: usize) -> Vec<InputError> {
+    errors
+        .into_iter()
+        .map(|err| InputError::new(err.line + first_line - 1, err.reason))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::{
+        check_grid, check_input, check_lines, check_pattern, check_single_line, offset, sections,
+        to_validation, InputError,
+    };
+
+    #[test]
+    fn can_check_lines() {
+        let errors = check_lines("1\n2\nthree\n4", |_, line| {
+            line.parse::<u8>()
+                .err()
+                .map(|_| format!("'{}' is not a number", line))
+        });
+        assert_eq!(errors, vec![InputError::new(3, "'three' is not a number")]);
+
+        assert_eq!(
+            check_pattern(
+                "up 1\nleft 2\n",
+                r"(up|down) \d+",
+                "a direction and distance"
+            ),
+            vec![InputError::new(
+                2,
+                "Expected a direction and distance, found 'left 2'"
+            )]
+        );
+
+        assert_eq!(check_single_line("1,2,3\n"), vec![]);
+        assert_eq!(
+            check_single_line("1,2,3\n4\n5"),
+            vec![InputError::new(2, "Expected the input to be a single line")]
+        );
+    }
+
+    #[test]
+    fn can_check_grid() {
+        assert_eq!(check_grid("123\n456\n789\n", "0123456789"), vec![]);
+        assert_eq!(
+            check_grid("123\n4x6\n78\n", "0123456789"),
+            vec![
+                InputError::new(2, "Unexpected character 'x'"),
+                InputError::new(3, "Expected 3 characters to match the first line, found 2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn can_check_sections() {
+        let input = "1,2\n3,4\n\nfold\n\nA\nB\nC";
+        assert_eq!(
+            sections(input),
+            vec![(1, "1,2\n3,4"), (4, "fold"), (6, "A\nB\nC")]
+        );
+
+        let errors = offset(vec![InputError::new(2, "Bad")], 6);
+        assert_eq!(errors, vec![InputError::new(7, "Bad")]);
+        assert_eq!(errors[0].to_string(), "Line 7: Bad");
+
+        assert_eq!(to_validation(vec![]), Ok(()));
+        assert_eq!(
+            to_validation(vec![InputError::new(1, "Bad")]),
+            Err(vec![InputError::new(1, "Bad")])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The input for day 2 is invalid:\nLine 1: The input is empty")]
+    fn rejects_empty_input() {
+        check_input(2, "\n", |_| Ok(()))
+    }
+}
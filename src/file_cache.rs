@@ -0,0 +1,103 @@
+//! Where `--cache` keeps the results of the slower steps, see [`advent_of_code_2021::util::cache`]. Each result is
+//! saved as a JSON file in [`CACHE_DIR`], named after the step and a hash of its key.
+//!
+//! A different input gets a different key, so a stale entry is never used for it, but nothing notices when the code
+//! that produced an entry changes. Delete the directory after changing a cached step, or just run without `--cache`.
+
+use advent_of_code_2021::util::cache::CacheStore;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where the cached results are kept, relative to the directory the runner is started in, like the puzzle inputs
+pub const CACHE_DIR: &str = "target/aoc-cache";
+
+/// Keeps the cached results as files in a directory
+pub struct FileCache {
+    dir: PathBuf,
+}
+
+impl FileCache {
+    pub fn new(dir: &Path) -> FileCache {
+        FileCache {
+            dir: dir.to_path_buf(),
+        }
+    }
+}
+
+impl CacheStore for FileCache {
+    fn load(&self, name: &str, key: &str) -> Option<String> {
+        fs::read_to_string(cache_path(&self.dir, name, key)).ok()
+    }
+
+    fn save(&self, name: &str, key: &str, contents: &str) -> Result<(), String> {
+        let path = cache_path(&self.dir, name, key);
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&path, contents))
+            .map_err(|err| format!("{}: {}", path.display(), err))
+    }
+}
+
+/// The file an entry is saved in, e.g. `day-19-merge-0123456789abcdef.json`
+fn cache_path(dir: &Path, name: &str, key: &str) -> PathBuf {
+    dir.join(format!("{}-{:016x}.json", name, hash_key(key)))
+}
+
+/// A 64-bit FNV-1a hash of the key. The standard library's hasher isn't guaranteed to give the same hash from one
+/// build to the next, which would make every entry stale after a rebuild.
+fn hash_key(key: &str) -> u64 {
+    key.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::file_cache::{cache_path, hash_key, FileCache};
+    use advent_of_code_2021::util::cache::cached_in;
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn can_hash_keys() {
+        // Known FNV-1a values, so the hash can't change between builds
+        assert_eq!(hash_key(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash_key("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(hash_key("input one"), hash_key("input two"));
+
+        assert_eq!(
+            cache_path(Path::new("cache"), "step", ""),
+            Path::new("cache/step-cbf29ce484222325.json")
+        );
+    }
+
+    #[test]
+    fn can_cache_results() {
+        let dir = env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+        let store = FileCache::new(&dir);
+
+        assert_eq!(cached_in(&store, "sum", "1,2,3", || vec![6, 1]), vec![6, 1]);
+        // Loaded from the cache, so not computed again
+        assert_eq!(
+            cached_in(&store, "sum", "1,2,3", || -> Vec<usize> {
+                panic!("Not cached")
+            }),
+            vec![6, 1]
+        );
+        // A different key, or a different step, is computed
+        assert_eq!(cached_in(&store, "sum", "4,5", || vec![9, 2]), vec![9, 2]);
+        assert_eq!(cached_in(&store, "max", "1,2,3", || vec![3]), vec![3]);
+
+        // An entry that can't be read as the type asked for is replaced
+        fs::write(cache_path(&dir, "sum", "1,2,3"), "not json").unwrap();
+        assert_eq!(cached_in(&store, "sum", "1,2,3", || vec![6, 1]), vec![6, 1]);
+        assert_eq!(
+            cached_in(&store, "sum", "1,2,3", || -> (String, bool) {
+                ("typed".to_string(), true)
+            }),
+            ("typed".to_string(), true)
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,269 @@
+//! Generates synthetic puzzle inputs, for running tests and benchmarks where the real inputs aren't available, e.g. in
+//! CI, as the real inputs can't be committed. `cargo run -- geninput --day <n> --seed <n>` prints an input with the
+//! same structure as the real one to stdout, ready to be redirected to `res/day-N-input`. The same seed always gives
+//! the same input. Where the answers are known from how the input was built, they are printed to stderr.
+//!
+//! Only a few days have generators so far:
+//!
+//! - Day 4: the numbers 0 to 99 in a random order, and 100 bingo cards of random numbers, see [`bingo`].
+//! - Day 5: 500 random horizontal, vertical and diagonal vent lines, see [`vents`].
+//! - Day 19: scanners in a chain that each overlap the previous one by at least 12 beacons, see [`scanners`].
+
+use crate::output::{flag_value, Answers};
+use crate::util::random::Random;
+use crate::util::vec3::{Orientation, Vec3};
+use std::collections::HashSet;
+use std::env;
+
+/// The days that inputs can be generated for
+pub const DAYS: [u8; 3] = [4, 5, 19];
+
+/// A generated puzzle input, and the answers it should give, if they are known
+#[derive(Eq, PartialEq, Debug)]
+pub struct GeneratedInput {
+    pub input: String,
+    pub answers: Option<Answers>,
+}
+
+/// The entry point for the `geninput` subcommand. The day is given with `--day <n>`, and the seed with `--seed <n>`,
+/// which defaults to 0.
+pub fn run() {
+    let day: u8 = match flag_value(env::args(), "day") {
+        Some(day) => day
+            .parse()
+            .unwrap_or_else(|_| panic!("Invalid day {}", day)),
+        None => panic!("Pass the day to generate an input for with --day <n>"),
+    };
+    let seed: u64 = match flag_value(env::args(), "seed") {
+        Some(seed) => seed
+            .parse()
+            .unwrap_or_else(|_| panic!("Invalid seed {}", seed)),
+        None => 0,
+    };
+
+    let generated = generate(day, seed).unwrap_or_else(|err| panic!("{}", err));
+    print!("{}", generated.input);
+
+    if let Some(answers) = generated.answers {
+        eprintln!(
+            "Expected answers: {}, {}",
+            answers.part_one.unwrap_or_default(),
+            answers.part_two.unwrap_or_default()
+        );
+    }
+}
+
+/// Generate an input for `day` from `seed`, or an error if there is no generator for that day
+pub fn generate(day: u8, seed: u64) -> Result<GeneratedInput, String> {
+    let mut random = Random::new(seed);
+    match day {
+        4 => Ok(bingo(&mut random)),
+        5 => Ok(vents(&mut random)),
+        19 => Ok(scanners(&mut random, 30)),
+        _ => Err(format!(
+            "There is no input generator for day {}, try one of {}",
+            day,
+            DAYS.map(|day| day.to_string()).join(", ")
+        )),
+    }
+}
+
+/// Day 4's numbers to call, and 100 cards. Every number from 0 to 99 is called, so every card wins eventually.
+fn bingo(random: &mut Random) -> GeneratedInput {
+    let mut numbers: Vec<usize> = (0..100).collect();
+    random.shuffle(&mut numbers);
+
+    let cards: Vec<String> = (0..100)
+        .map(|_| {
+            let mut pool: Vec<usize> = (0..100).collect();
+            random.shuffle(&mut pool);
+            pool[..25]
+                .chunks(5)
+                .map(|row| {
+                    row.iter()
+                        .map(|number| format!("{:>2}", number))
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+        .collect();
+
+    GeneratedInput {
+        input: format!(
+            "{}\n\n{}\n",
+            numbers
+                .iter()
+                .map(|number| number.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+            cards.join("\n\n")
+        ),
+        answers: None,
+    }
+}
+
+/// Day 5's vent lines, a third each of horizontal, vertical and diagonal, all within a 1000 x 1000 grid
+fn vents(random: &mut Random) -> GeneratedInput {
+    let lines: Vec<String> = (0..500)
+        .map(|_| {
+            let (x1, y1) = (random.between(0, 999), random.between(0, 999));
+            let (x2, y2) = match random.below(3) {
+                0 => (random.between(0, 999), y1),
+                1 => (x1, random.between(0, 999)),
+                _ => {
+                    let (dx, dy) = (random.between(0, 1) * 2 - 1, random.between(0, 1) * 2 - 1);
+                    // Stay inside the grid in whichever direction the line goes
+                    let room = |start: isize, direction: isize| {
+                        if direction > 0 {
+                            999 - start
+                        } else {
+                            start
+                        }
+                    };
+                    let length = random.between(0, room(x1, dx).min(room(y1, dy)));
+                    (x1 + dx * length, y1 + dy * length)
+                }
+            };
+            format!("{},{} -> {},{}", x1, y1, x2, y2)
+        })
+        .collect();
+
+    GeneratedInput {
+        input: lines.join("\n") + "\n",
+        answers: None,
+    }
+}
+
+/// How far a day 19 scanner can see along each axis
+const SCANNER_RANGE: isize = 1000;
+
+/// Day 19's scanners. Each is placed 700 to 1200 from the previous one on each axis, and 12 beacons are added where
+/// both can see them, so that each overlaps the previous scanner by at least 12 beacons, then a few more are added
+/// anywhere in its range. Each scanner reports every beacon in its range, relative to itself and turned to a random
+/// orientation. The first scanner is at the origin and isn't turned, so the answers are the number of beacons placed,
+/// and the largest distance between the scanners' positions.
+fn scanners(random: &mut Random, count: usize) -> GeneratedInput {
+    let mut positions = vec![Vec3::default()];
+    while positions.len() < count {
+        let previous = positions[positions.len() - 1];
+        // Far enough along each axis that the scanners don't share much more than the beacons placed to overlap
+        let mut offset = || random.between(700, 1200) * if random.below(2) == 0 { 1 } else { -1 };
+        let step = Vec3::new(offset(), offset(), offset());
+        positions.push(previous + step);
+    }
+
+    let range = Vec3::new(SCANNER_RANGE, SCANNER_RANGE, SCANNER_RANGE);
+    let mut beacons: HashSet<Vec3> = HashSet::new();
+    let mut place = |random: &mut Random, min: Vec3, max: Vec3, count: usize| {
+        let mut placed = 0;
+        while placed < count {
+            let beacon = Vec3::new(
+                random.between(min.x, max.x),
+                random.between(min.y, max.y),
+                random.between(min.z, max.z),
+            );
+            if beacons.insert(beacon) {
+                placed += 1;
+            }
+        }
+    };
+
+    for (index, &position) in positions.iter().enumerate() {
+        if index > 0 {
+            let previous = positions[index - 1];
+            let min = (position - range).component_max(&(previous - range));
+            let max = (position + range).component_min(&(previous + range));
+            place(random, min, max, 12);
+        }
+        place(random, position - range, position + range, 14);
+    }
+
+    let reports: Vec<String> = positions
+        .iter()
+        .enumerate()
+        .map(|(index, &position)| {
+            let orientation = if index == 0 {
+                Orientation::PosXPosY
+            } else {
+                Orientation::ALL[random.below(24)]
+            };
+            let mut seen: Vec<Vec3> = beacons
+                .iter()
+                .map(|&beacon| beacon - position)
+                .filter(|offset| offset.x.abs() <= SCANNER_RANGE && offset.y.abs() <= SCANNER_RANGE)
+                .filter(|offset| offset.z.abs() <= SCANNER_RANGE)
+                .map(|offset| offset.rotate(orientation))
+                .collect();
+            // The set's iteration order isn't repeatable, so sort before shuffling with the seeded generator
+            seen.sort_by_key(|beacon| (beacon.x, beacon.y, beacon.z));
+            random.shuffle(&mut seen);
+
+            let lines: Vec<String> = seen
+                .iter()
+                .map(|beacon| format!("{},{},{}", beacon.x, beacon.y, beacon.z))
+                .collect();
+            format!("--- scanner {} ---\n{}", index, lines.join("\n"))
+        })
+        .collect();
+
+    let largest_distance = positions
+        .iter()
+        .flat_map(|a| positions.iter().map(|b| a.manhattan(b)))
+        .max()
+        .unwrap_or_default();
+
+    GeneratedInput {
+        input: reports.join("\n\n") + "\n",
+        answers: Some(Answers::new(beacons.len(), largest_distance)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geninput::{generate, DAYS};
+
+    #[test]
+    fn can_generate_repeatably() {
+        for day in DAYS {
+            let generated = generate(day, 42).unwrap();
+            assert_eq!(generate(day, 42).unwrap(), generated, "Day {}", day);
+            assert_ne!(
+                generate(day, 43).unwrap().input,
+                generated.input,
+                "Day {}",
+                day
+            );
+        }
+
+        assert_eq!(
+            generate(1, 42),
+            Err("There is no input generator for day 1, try one of 4, 5, 19".to_string())
+        );
+    }
+
+    #[test]
+    fn can_generate_bingo() {
+        let input = generate(4, 1).unwrap().input;
+        let sections: Vec<&str> = input.split("\n\n").collect();
+        assert_eq!(sections.len(), 101);
+
+        let mut numbers: Vec<usize> = sections[0].split(',').map(|n| n.parse().unwrap()).collect();
+        numbers.sort();
+        assert_eq!(numbers, (0..100).collect::<Vec<usize>>());
+
+        for card in &sections[1..] {
+            let numbers: Vec<&str> = card.split_whitespace().collect();
+            assert_eq!(card.lines().count(), 5);
+            assert_eq!(numbers.len(), 25);
+            assert_eq!(
+                numbers
+                    .iter()
+                    .collect::<std::collections::HashSet<_>>()
+                    .len(),
+                25
+            );
+        }
+    }
+}
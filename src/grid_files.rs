@@ -0,0 +1,66 @@
+//! Where `--grid-dir=<path>` saves the grids the days show, see [`advent_of_code_2021::output::show_grid`]. Each grid
+//! is written to `<path>/<name>.txt`, replacing the file from any earlier run.
+
+use advent_of_code_2021::output::flag_value;
+use advent_of_code_2021::say;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Find the last `--grid-dir=<path>` argument, if there is one
+pub fn grid_dir(args: impl Iterator<Item = String>) -> Option<String> {
+    flag_value(args, "grid-dir")
+}
+
+/// Save a grid a day has shown to the directory requested with `--grid-dir`, reporting where it was written
+pub fn save_grid(name: &str, caption: &str, grid: &str) {
+    if let Some(dir) = grid_dir(env::args()) {
+        let path = write_grid(Path::new(&dir), name, grid)
+            .unwrap_or_else(|err| panic!("Failed to save {}: {}", name, err));
+        say!("{} saved to {}", caption, path.display());
+    }
+}
+
+/// Write a grid to `<name>.txt` in `dir`, creating the directory if needed, and return the path written to
+fn write_grid(dir: &Path, name: &str, grid: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.txt", name));
+    fs::write(&path, format!("{}\n", grid))?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid_files::{grid_dir, write_grid};
+    use std::env;
+    use std::fs;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<String>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn can_find_grid_dir() {
+        assert_eq!(grid_dir(args(&["--verbose"])), None);
+        assert_eq!(
+            grid_dir(args(&["--grid-dir=a", "--grid-dir=grids"])),
+            Some("grids".to_string())
+        );
+    }
+
+    #[test]
+    fn can_write_grid() {
+        let dir = env::temp_dir().join("aoc-2021-output-test");
+        let path = write_grid(&dir, "day-13-paper", "#.#\n.#.").unwrap();
+
+        assert_eq!(path, dir.join("day-13-paper.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#.#\n.#.\n");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
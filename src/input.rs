@@ -0,0 +1,219 @@
+//! Shared loading of the puzzle input files. The runner asks for each day's input via [`read_input`], which reads
+//! `<project_root>/res/day-N-input` as before. If that file doesn't exist yet it is downloaded from
+//! adventofcode.com and cached to that path, so subsequent runs work offline. Inputs for years other than 2021 are kept
+//! in a folder for their year, e.g. `res/2022/day-N-input`.
+//!
+//! Puzzle inputs are per-user, so the download needs the value of the `session` cookie from a logged-in browser
+//! session. This is looked up, in order, from:
+//!
+//! - The `AOC_SESSION` environment variable
+//! - The first line of a `.aoc-session` file in the project root (this is git-ignored)
+//!
+//! Rather than pull in an HTTP client and TLS stack for one request per day, the download shells out to `curl`.
+//!
+//! An input can also be given by path with `--input <path>`, see [`read_input_from`], where `-` means stdin, so that
+//! it can be piped in from elsewhere, e.g. `cat input | cargo run -- --day 16 --input -`.
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// The name of the environment variable checked for the session token
+const SESSION_ENV_VAR: &str = "AOC_SESSION";
+/// The config file checked for the session token if the environment variable is not set
+const SESSION_FILE: &str = ".aoc-session";
+/// The path that means read the input from stdin
+pub const STDIN_PATH: &str = "-";
+
+/// Read the puzzle input for a day, downloading and caching it first if needed. Panics with a message explaining
+/// what is missing if the input can't be found or fetched.
+pub fn read_input(year: u16, day: u8) -> String {
+    let path = input_path(year, day);
+
+    if !Path::new(&path).exists() {
+        let contents =
+            download_input(year, day).unwrap_or_else(|err| panic!("Failed to read file: {}", err));
+        if let Some(dir) = Path::new(&path).parent() {
+            fs::create_dir_all(dir).expect("Failed to create res directory");
+        }
+        fs::write(&path, &contents).expect("Failed to cache input file");
+    }
+
+    fs::read_to_string(&path).expect("Failed to read file")
+}
+
+/// Read an input from the file at `path`, or from stdin if it is [`STDIN_PATH`], returning a human readable reason if
+/// that isn't possible
+pub fn read_input_from(path: &str) -> Result<String, String> {
+    read_path(path, io::stdin().lock())
+}
+
+/// As [`read_input_from`], with stdin given as `stdin`
+fn read_path(path: &str, mut stdin: impl Read) -> Result<String, String> {
+    if path == STDIN_PATH {
+        let mut contents = String::new();
+        return stdin
+            .read_to_string(&mut contents)
+            .map(|_| contents)
+            .map_err(|err| format!("Failed to read stdin: {}", err));
+    }
+
+    fs::read_to_string(path).map_err(|err| format!("Failed to read {}: {}", path, err))
+}
+
+/// How to refer to the input at `path` in messages, as `-` isn't very clear
+pub fn describe_path(path: &str) -> &str {
+    match path {
+        STDIN_PATH => "stdin",
+        _ => path,
+    }
+}
+
+/// The path the input for a given day is cached at. 2021's inputs were here before there were other years, so stay at
+/// the top level.
+fn input_path(year: u16, day: u8) -> String {
+    match year {
+        2021 => format!("res/day-{}-input", day),
+        _ => format!("res/{}/day-{}-input", year, day),
+    }
+}
+
+/// The adventofcode.com URL for a given day's input
+fn input_url(year: u16, day: u8) -> String {
+    format!("https://adventofcode.com/{}/day/{}/input", year, day)
+}
+
+/// Find the session token, preferring the environment variable over the config file.
+fn session_token() -> Option<String> {
+    env::var(SESSION_ENV_VAR)
+        .ok()
+        .and_then(|token| parse_token(&token))
+        .or_else(|| {
+            fs::read_to_string(SESSION_FILE)
+                .ok()
+                .and_then(|contents| parse_token(&contents))
+        })
+}
+
+/// Take the first non-blank line as the token. Tolerates the value being copied with the `session=` cookie name
+/// included.
+fn parse_token(contents: &str) -> Option<String> {
+    contents
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .map(|line| line.trim_start_matches("session=").to_string())
+}
+
+/// The curl config line that sends the session cookie, quoted as curl expects
+fn cookie_config(token: &str) -> String {
+    format!(
+        "cookie = \"session={}\"",
+        token.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/// Fetch the input for a day using `curl`, returning a human readable reason if that isn't possible.
+fn download_input(year: u16, day: u8) -> Result<String, String> {
+    let token = session_token().ok_or(format!(
+        "{} is missing and no session token was found in ${} or {}",
+        input_path(year, day),
+        SESSION_ENV_VAR,
+        SESSION_FILE
+    ))?;
+
+    eprintln!("Downloading input for {} day {}...", year, day);
+
+    // The cookie goes to curl as config on stdin, so the token isn't visible in the process list
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail"])
+        .args(["--config", "-"])
+        .args(["--user-agent", "github.com/kamioftea/advent-of-code-2021"])
+        .arg(input_url(year, day))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Failed to run curl: {}", err))?;
+
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    writeln!(stdin, "{}", cookie_config(&token))
+        .map_err(|err| format!("Failed to pass the session token to curl: {}", err))?;
+    drop(stdin);
+
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("Failed to run curl: {}", err))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to download {}: {}",
+            input_url(year, day),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    String::from_utf8(output.stdout).map_err(|err| format!("Input was not valid UTF-8: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::{
+        cookie_config, describe_path, input_path, input_url, parse_token, read_path,
+    };
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn can_build_paths() {
+        assert_eq!(input_path(2021, 7), "res/day-7-input");
+        assert_eq!(input_path(2022, 7), "res/2022/day-7-input");
+        assert_eq!(
+            input_url(2021, 25),
+            "https://adventofcode.com/2021/day/25/input"
+        );
+        assert_eq!(
+            input_url(2022, 1),
+            "https://adventofcode.com/2022/day/1/input"
+        );
+    }
+
+    #[test]
+    fn can_read_from_a_path_or_stdin() {
+        let stdin = "forward 5\ndown 5\n".as_bytes();
+        assert_eq!(read_path("-", stdin), Ok("forward 5\ndown 5\n".to_string()));
+        assert_eq!(describe_path("-"), "stdin");
+
+        let path = env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        fs::write(&path, "199\n200\n").unwrap();
+        let path = path.to_str().unwrap();
+        // Only read from stdin for `-`
+        assert_eq!(
+            read_path(path, "unused".as_bytes()),
+            Ok("199\n200\n".to_string())
+        );
+        assert_eq!(describe_path(path), path);
+        fs::remove_file(path).unwrap();
+
+        assert!(read_path(path, "unused".as_bytes())
+            .unwrap_err()
+            .starts_with(&format!("Failed to read {}: ", path)));
+    }
+
+    #[test]
+    fn can_parse_token() {
+        assert_eq!(parse_token("abc123\n"), Some("abc123".to_string()));
+        assert_eq!(
+            parse_token("\n  session=abc123  \n"),
+            Some("abc123".to_string())
+        );
+        assert_eq!(parse_token("  \n"), None);
+    }
+
+    #[test]
+    fn can_build_cookie_config() {
+        assert_eq!(cookie_config("abc123"), r#"cookie = "session=abc123""#);
+        assert_eq!(cookie_config(r#"a"b\c"#), r#"cookie = "session=a\"b\\c""#);
+    }
+}
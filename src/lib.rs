@@ -0,0 +1,94 @@
+//! The puzzle solutions, and what they share, as a library. Everything here takes the puzzle input as a `&str`, and
+//! leaves reading files and the terminal to the runner in `main.rs`, which loads the inputs, and hands the few days
+//! that write images, cache results, or explore a state interactively what they need through hooks, e.g.
+//! [`util::cache::set_store`]. That keeps the library usable where there are no files, such as a web page, see
+//! [`solve`], and the `wasm` module that exports it when built with the `wasm` feature.
+
+// Parsers and solvers should borrow slices (`&str`, `&[T]`) so they compose with the input loader and test fixtures.
+#![deny(clippy::ptr_arg)]
+// With some days compiled out, parts of the shared utilities will be unused
+#![cfg_attr(not(feature = "all-days"), allow(dead_code, unused_imports))]
+// The write-ups for each day are in the module docs, and link to the private functions they describe, so the docs are
+// built with `--document-private-items`
+#![allow(rustdoc::private_intra_doc_links)]
+
+// Declared first so that `say!` is available to all the days
+#[macro_use]
+pub mod output;
+
+pub mod detect;
+pub mod errors;
+pub mod geninput;
+pub mod solution;
+#[cfg(test)]
+mod testing;
+pub mod util;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod year_2021;
+
+use errors::describe_problems;
+use solution::Registry;
+
+/// Solve one part of a day of the latest year with the given input, without printing the working. This is the entry
+/// point the `wasm` module exports, and returns the reason as an error rather than panicking where it can, e.g. for a day or
+/// part that doesn't exist. The input is validated before it is parsed, and every problem found is returned as the
+/// error, see [`errors`], so only a problem the day's checks miss can still panic.
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, String> {
+    let registry = Registry::all();
+    let year = registry.latest_year().ok_or("No solutions registered")?;
+    let solution = registry
+        .get(year, day)
+        .ok_or_else(|| format!("There is no day {} in {}", day, year))?;
+    if !solution.is_included() {
+        return Err(format!("Day {} is not included in this build", day));
+    }
+    solution
+        .validate(input)
+        .map_err(|problems| describe_problems(day, &problems))?;
+
+    let answers = solution.solve(input, Some(part.to_string().parse()?));
+    let answer = match part {
+        1 => answers.part_one,
+        _ => answers.part_two,
+    };
+
+    answer.ok_or_else(|| format!("Day {} has no answer for part {}", day, part))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve;
+
+    #[test]
+    fn can_solve_a_day() {
+        if !cfg!(feature = "day_1") || !cfg!(feature = "day_25") {
+            return;
+        }
+
+        let depths = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+        assert_eq!(solve(1, 1, depths), Ok("7".to_string()));
+        assert_eq!(solve(1, 2, depths), Ok("5".to_string()));
+
+        assert_eq!(
+            solve(26, 1, depths),
+            Err("There is no day 26 in 2021".to_string())
+        );
+        assert_eq!(
+            solve(1, 3, depths),
+            Err("Unknown part '3', expected 1 or 2".to_string())
+        );
+        assert_eq!(
+            solve(25, 2, "v.\n.>"),
+            Err("Day 25 has no answer for part 2".to_string())
+        );
+        assert_eq!(
+            solve(1, 1, "199\n2OO\nabc"),
+            Err("The input for day 1 is invalid:\nLine 2: Expected a depth, found '2OO'\nLine 3: Expected a depth, found 'abc'".to_string())
+        );
+        assert_eq!(
+            solve(1, 1, ""),
+            Err("The input for day 1 is invalid:\nLine 1: The input is empty".to_string())
+        );
+    }
+}
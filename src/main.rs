@@ -1,85 +1,495 @@
-mod day_1;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_13;
-mod day_14;
-mod day_15;
-mod day_16;
-mod day_17;
-mod day_18;
-mod day_19;
-mod day_2;
-mod day_20;
-mod day_21;
-mod day_22;
-mod day_23;
-mod day_24;
-mod day_25;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-mod day_7;
-mod day_8;
-mod day_9;
-mod util;
-
-use std::io::{self, Write};
-use std::time::Instant;
-
-extern crate core;
-
-extern crate itertools;
-extern crate regex;
+// The runner, which handles the files and the terminal for the solutions in the library, see `lib.rs`
+
+mod file_cache;
+mod grid_files;
+mod input;
+mod timings;
+
+use advent_of_code_2021::detect::detect;
+use advent_of_code_2021::geninput;
+use advent_of_code_2021::output::{
+    self, flag_value, parse_format, to_json, DayResult, Format, Verbosity,
+};
+use advent_of_code_2021::say;
+use advent_of_code_2021::solution::{
+    disagreements, run_in_parallel, Comparison, Part, Registry, Solution,
+};
+use advent_of_code_2021::util::cache;
+use advent_of_code_2021::util::image::{self, Bitmap};
+use advent_of_code_2021::util::progress::{self, Progress};
+use advent_of_code_2021::util::repl;
+use file_cache::{FileCache, CACHE_DIR};
+use input::{describe_path, read_input, read_input_from, STDIN_PATH};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+use std::process;
+use std::time::{Duration, Instant};
+use timings::Timing;
+
 #[macro_use]
 extern crate text_io;
 
+/// The day to run can be given as `--day <n>`, otherwise ask for it. The year defaults to the latest one with any
+/// solutions, and can be picked with `--year <n>`. `--format json` prints the answers as JSON instead of the full
+/// working, see [`output`]. `--list` prints the year's days instead of running one. `--save-timings <path>` and
+/// `--compare-timings <path>` keep track of how long each day takes, see [`timings`]. `--part <n>` runs just one part
+/// of each day, see [`solution::Day`]. `geninput` as the first argument generates a synthetic input for a day instead,
+/// see [`geninput`]. The slower days show their progress on stderr when it is a terminal, unless `--no-progress` is
+/// passed, see [`TerminalProgress`]. `--cache` saves the results of the slowest steps for the next run, see
+/// [`file_cache`]. `--output=<path>` saves the image a day draws, and `--repl` explores a day's state interactively,
+/// see [`image`] and [`repl`]. When running all the days, `--jobs <n>` runs them on `n` threads at once, see
+/// [`solution::run_in_parallel`]. `--detect <path>` works out which day an input file is for, and `--solve` then runs
+/// that day with it, see [`detect`]. `--compare` times the different ways some days have of solving a part, see
+/// [`compare_days`]. `--input <path>` reads the input for a single day from `path` instead of `res/`, where `-` reads
+/// it from stdin, as `--detect -` also does, see [`input::read_input_from`].
 fn main() {
-    print!("Which day? (0 to run all): ");
-    io::stdout().flush().unwrap();
-
-    let day: i32 = read!();
-    let days: Vec<Box<dyn Fn() -> ()>> = vec![
-        Box::new(|| day_1::run()),
-        Box::new(|| day_2::run()),
-        Box::new(|| day_3::run()),
-        Box::new(|| day_4::run()),
-        Box::new(|| day_5::run()),
-        Box::new(|| day_6::run()),
-        Box::new(|| day_7::run()),
-        Box::new(|| day_8::run()),
-        Box::new(|| day_9::run()),
-        Box::new(|| day_10::run()),
-        Box::new(|| day_11::run()),
-        Box::new(|| day_12::run()),
-        Box::new(|| day_13::run()),
-        Box::new(|| day_14::run()),
-        Box::new(|| day_15::run()),
-        Box::new(|| day_16::run()),
-        Box::new(|| day_17::run()),
-        Box::new(|| day_18::run()),
-        Box::new(|| day_19::run()),
-        Box::new(|| day_20::run()),
-        Box::new(|| day_21::run()),
-        Box::new(|| day_22::run()),
-        Box::new(|| day_23::run()),
-        Box::new(|| day_24::run()),
-        Box::new(|| day_25::run()),
-    ];
+    if env::args().nth(1).as_deref() == Some("geninput") {
+        geninput::run();
+        return;
+    }
+
+    let format = parse_format(env::args()).unwrap_or_else(|err| panic!("{}", err));
+    output::set_verbosity(match format {
+        Format::Text => Verbosity::from_args(env::args()),
+        Format::Json => Verbosity::Quiet,
+    });
+    let jobs: usize = match flag_value(env::args(), "jobs") {
+        Some(jobs) => jobs
+            .parse()
+            .ok()
+            .filter(|&jobs| jobs > 0)
+            .unwrap_or_else(|| panic!("Invalid job count {}", jobs)),
+        None => 1,
+    };
+    // Several days drawing on the same line would just be noise
+    if !output::is_quiet()
+        && jobs == 1
+        && io::stderr().is_terminal()
+        && !env::args().any(|arg| arg == "--no-progress")
+    {
+        progress::set_reporter(TerminalProgress::boxed);
+    }
+    if env::args().any(|arg| arg == "--cache") {
+        cache::set_store(Box::new(FileCache::new(Path::new(CACHE_DIR))));
+    }
+    if output_path().is_some() {
+        image::set_writer(save_image);
+    }
+    if grid_files::grid_dir(env::args()).is_some() {
+        output::set_grid_writer(grid_files::save_grid);
+    }
+    if env::args().any(|arg| arg == "--repl") {
+        repl::set_terminal(|| {
+            let input: Box<dyn BufRead> = Box::new(io::stdin().lock());
+            (input, Box::new(io::stdout()))
+        });
+    }
+
+    let registry = Registry::all();
+    let year: u16 = match flag_value(env::args(), "year") {
+        Some(year) => year
+            .parse()
+            .unwrap_or_else(|_| panic!("Invalid year {}", year)),
+        None => registry.latest_year().expect("No solutions registered"),
+    };
+
+    if env::args().any(|arg| arg == "--list") {
+        list_days(&registry, year);
+        return;
+    }
+
+    let part: Option<Part> = flag_value(env::args(), "part")
+        .map(|part| part.parse().unwrap_or_else(|err| panic!("{}", err)));
+
+    if let Some(path) = flag_value(env::args(), "detect") {
+        let solve = env::args().any(|arg| arg == "--solve");
+        let result = detect_day(&registry, year, &path, solve, part);
+        if format == Format::Json {
+            println!("{}", to_json(&Vec::from_iter(result)));
+        }
+        return;
+    }
+
+    let input_path = flag_value(env::args(), "input");
+    let day: i32 = match flag_value(env::args(), "day") {
+        Some(day) => day
+            .parse()
+            .unwrap_or_else(|_| panic!("Invalid day {}", day)),
+        None if input_path.as_deref() == Some(STDIN_PATH) => {
+            panic!("Pass --day with --input -, as the input is read from stdin")
+        }
+        None => {
+            if format == Format::Text {
+                print!("Which day? (0 to run all): ");
+                io::stdout().flush().unwrap();
+            }
+            read!()
+        }
+    };
+
+    if input_path.is_some() && day == 0 {
+        panic!("--input can only be used to run a single day");
+    }
+    // Read up front, as stdin can only be read once
+    let input_override =
+        input_path.map(|path| read_input_from(&path).unwrap_or_else(|err| panic!("{}", err)));
+    let day_input = |day: u8| {
+        input_override
+            .clone()
+            .unwrap_or_else(|| read_input(year, day))
+    };
+
+    if env::args().any(|arg| arg == "--compare") {
+        let runs: u32 = match flag_value(env::args(), "runs") {
+            Some(runs) => runs
+                .parse()
+                .ok()
+                .filter(|&runs| runs > 0)
+                .unwrap_or_else(|| panic!("Invalid run count {}", runs)),
+            None => 1,
+        };
+        if !compare_days(&registry, year, day, runs, day_input) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    // The working is only wanted for the text format. A day left out of the build doesn't need its input, and only
+    // reports how to include it.
+    let answer = |day: u8, solution: &dyn Solution| {
+        let input = match solution.is_included() {
+            true => day_input(day),
+            false => String::new(),
+        };
+        match format {
+            Format::Text => solution.run(&input, part),
+            Format::Json => solution.solve(&input, part),
+        }
+    };
 
     let start = Instant::now();
-    match days.get((day - 1) as usize) {
-        Some(solution) => solution(),
-        None if day == 0 => days.iter().enumerate().for_each(|(i, solution)| {
+    let mut results = Vec::new();
+    match u8::try_from(day)
+        .ok()
+        .and_then(|day| registry.get(year, day))
+    {
+        _ if registry.days(year).next().is_none() => say!("Invalid Year {}", year),
+        Some(solution) => {
+            let answers = answer(day as u8, solution);
+            results.push(DayResult::new(
+                day as usize,
+                answers,
+                start.elapsed().as_micros(),
+            ));
+        }
+        None if day == 0 && jobs > 1 => {
+            let runs = run_in_parallel(registry.days(year).collect(), jobs, answer);
+            let mut total = Duration::ZERO;
+            for run in runs {
+                say!("==== Day {}: {} ====", run.day, run.name);
+                print!("{}", run.text);
+                say!("-- took {:.2?}", run.elapsed);
+                total += run.elapsed;
+                results.push(DayResult::new(
+                    run.day as usize,
+                    run.answers,
+                    run.elapsed.as_micros(),
+                ));
+            }
+            say!("");
+            say!("The days took {:.2?} in total, over {} jobs", total, jobs);
+        }
+        None if day == 0 => registry.days(year).for_each(|(day, solution)| {
             let start = Instant::now();
-            println!("==== Day {} ====", i + 1);
-            solution();
-            println!("-- took {:.2?}", start.elapsed());
+            say!("==== Day {}: {} ====", day, solution.name());
+            let answers = answer(day, solution);
+            say!("-- took {:.2?}", start.elapsed());
+            results.push(DayResult::new(
+                day as usize,
+                answers,
+                start.elapsed().as_micros(),
+            ));
         }),
-        None => println!("Invalid Day {}", day),
+        None => say!("Invalid Day {}", day),
+    }
+
+    match format {
+        Format::Text => {
+            println!();
+            println!("Finished in {:.2?}", start.elapsed());
+        }
+        Format::Json => println!("{}", to_json(&results)),
+    }
+
+    // Days left out of the build don't do anything, so their timings aren't worth keeping
+    let latest: Vec<Timing> = results
+        .iter()
+        .map(|result| Timing::new(year, result.day() as u8, result.micros()))
+        .filter(|timing| {
+            registry
+                .get(timing.year, timing.day)
+                .is_some_and(|solution| solution.is_included())
+        })
+        .collect();
+
+    if let Some(path) = flag_value(env::args(), "compare-timings") {
+        compare_timings(Path::new(&path), &latest, format);
+    }
+
+    if let Some(path) = flag_value(env::args(), "save-timings") {
+        timings::save(Path::new(&path), &latest).unwrap_or_else(|err| panic!("{}", err));
+    }
+}
+
+/// Report the days the file at `path`, or stdin if it is `-`, could be the input for, see [`detect`], and if `solve` is
+/// set and there is only one, run `part` of that day with it, or both parts if `None`.
+fn detect_day(
+    registry: &Registry,
+    year: u16,
+    path: &str,
+    solve: bool,
+    part: Option<Part>,
+) -> Option<DayResult> {
+    let input = read_input_from(path).unwrap_or_else(|err| panic!("{}", err));
+    let path = describe_path(path);
+
+    let days = detect(registry, year, &input);
+    let describe = |day: u8| {
+        let name = registry
+            .get(year, day)
+            .map_or("", |solution| solution.name());
+        format!("day {}: {}", day, name)
+    };
+
+    match days[..] {
+        [] => say!(
+            "{} doesn't look like the input for any day of {}",
+            path,
+            year
+        ),
+        [day] => say!("{} looks like the input for {}", path, describe(day)),
+        _ => {
+            say!("{} could be the input for:", path);
+            days.iter().for_each(|&day| say!("- {}", describe(day)));
+        }
+    }
+
+    match days[..] {
+        [day] if solve => {
+            let solution = registry.get(year, day)?;
+            let start = Instant::now();
+            say!("");
+            say!("==== Day {}: {} ====", day, solution.name());
+            let answers = solution.run(&input, part);
+            Some(DayResult::new(
+                day as usize,
+                answers,
+                start.elapsed().as_micros(),
+            ))
+        }
+        [_, _, ..] if solve => {
+            say!("Not solving it, as it could be more than one day");
+            None
+        }
+        _ => None,
     }
+}
+
+/// Run each of the ways `day` has of solving its parts `runs` times, or those of every day with alternatives if `day`
+/// is 0, and print their answers and average times side by side, see [`Solution::compare`]. Returns false if the ways
+/// of solving any part disagree. The times should be taken from a release build to be meaningful. Each day's input
+/// comes from `input`.
+fn compare_days(
+    registry: &Registry,
+    year: u16,
+    day: i32,
+    runs: u32,
+    input: impl Fn(u8) -> String,
+) -> bool {
+    let days: Vec<(u8, &dyn Solution)> = match day {
+        0 => registry
+            .days(year)
+            .filter(|(_, solution)| solution.is_included() && solution.has_alternatives())
+            .collect(),
+        day => u8::try_from(day)
+            .ok()
+            .and_then(|day| Some((day, registry.get(year, day)?)))
+            .into_iter()
+            .collect(),
+    };
+
+    if days.is_empty() {
+        println!("There are no days to compare for day {} of {}", day, year);
+    }
+
+    let mut agree = true;
+    for (day, solution) in days {
+        println!("==== Day {}: {} ====", day, solution.name());
+        if !solution.has_alternatives() {
+            println!("There is only one way of solving each part");
+            continue;
+        }
+
+        let comparisons = solution.compare(&input(day), runs);
+        print_comparisons(&comparisons);
+        match disagreements(&comparisons)[..] {
+            [] => println!("All {} implementations agree", comparisons.len()),
+            ref parts => {
+                agree = false;
+                parts
+                    .iter()
+                    .for_each(|part| println!("The answers for part {} disagree", part));
+            }
+        }
+    }
+
+    agree
+}
+
+/// A table of the part, name, answer, and average time of each comparison, with the columns lined up
+fn print_comparisons(comparisons: &[Comparison]) {
+    let answers: Vec<&str> = comparisons
+        .iter()
+        .map(|comparison| comparison.answer.as_deref().unwrap_or("-"))
+        .collect();
+    let name_width = comparisons.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    let answer_width = answers.iter().map(|answer| answer.len()).max().unwrap_or(0);
+
+    for (comparison, answer) in comparisons.iter().zip(answers) {
+        println!(
+            "Part {}  {:<name_width$}  {:>answer_width$}  {:.2?}",
+            comparison.part, comparison.name, answer, comparison.elapsed
+        );
+    }
+}
+
+/// The file the user has asked for an image to be written to with `--output=<path>`, if any
+fn output_path() -> Option<String> {
+    env::args()
+        .filter_map(|arg| arg.strip_prefix("--output=").map(|path| path.to_string()))
+        .next_back()
+}
+
+/// Save an image a day has drawn to the path requested with `--output`, reporting the outcome on stdout
+fn save_image(bitmap: &Bitmap) {
+    if let Some(path) = output_path() {
+        match bitmap
+            .encode(Path::new(&path))
+            .and_then(|data| fs::write(&path, data))
+        {
+            Ok(()) => println!("Image written to {}", path),
+            Err(err) => println!("Failed to write image to {}: {}", path, err),
+        }
+    }
+}
+
+/// Print each day registered for `year`, noting those with only one part or that aren't in this build
+fn list_days(registry: &Registry, year: u16) {
+    println!("Advent of Code {}", year);
+    registry.days(year).for_each(|(day, solution)| {
+        let mut notes = Vec::new();
+        if !solution.has_part_two() {
+            notes.push("part one only");
+        }
+        if !solution.is_included() {
+            notes.push("not included in this build");
+        }
+
+        if notes.is_empty() {
+            println!("{:>2}: {}", day, solution.name());
+        } else {
+            println!("{:>2}: {} ({})", day, solution.name(), notes.join(", "));
+        }
+    });
+}
+
+/// Draws a spinner, and a percentage if the total is known, on a single line of stderr, which is cleared when the task
+/// finishes. It is only redrawn every [`TerminalProgress::REDRAW`], so that updating it is cheap enough to call from
+/// inside a search loop.
+struct TerminalProgress {
+    label: &'static str,
+    frame: usize,
+    last_drawn: Option<Instant>,
+}
+
+impl TerminalProgress {
+    const REDRAW: Duration = Duration::from_millis(100);
+    const FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+
+    fn boxed(label: &'static str) -> Box<dyn Progress> {
+        Box::new(TerminalProgress {
+            label,
+            frame: 0,
+            last_drawn: None,
+        })
+    }
+}
+
+impl Progress for TerminalProgress {
+    fn update(&mut self, done: usize, total: Option<usize>) {
+        if self
+            .last_drawn
+            .is_some_and(|drawn| drawn.elapsed() < TerminalProgress::REDRAW)
+        {
+            return;
+        }
+
+        let spinner = TerminalProgress::FRAMES[self.frame % TerminalProgress::FRAMES.len()];
+        let line = match total {
+            Some(total) if total > 0 => format!(
+                "{} {}: {}/{} ({}%)",
+                spinner,
+                self.label,
+                done,
+                total,
+                done * 100 / total
+            ),
+            _ => format!("{} {}: {}", spinner, self.label, done),
+        };
+        eprint!("\r\x1b[2K{}", line);
+        let _ = io::stderr().flush();
+
+        self.frame += 1;
+        self.last_drawn = Some(Instant::now());
+    }
+
+    fn finish(&mut self) {
+        if self.last_drawn.take().is_some() {
+            eprint!("\r\x1b[2K");
+            let _ = io::stderr().flush();
+        }
+    }
+}
+
+/// Report the days that were slower than their saved timings by more than `--timing-threshold <percent>`. With the
+/// JSON format these go to stderr, so that the output is still valid JSON.
+fn compare_timings(path: &Path, latest: &[Timing], format: Format) {
+    let threshold: f64 = match flag_value(env::args(), "timing-threshold") {
+        Some(threshold) => threshold
+            .parse()
+            .unwrap_or_else(|_| panic!("Invalid timing threshold {}", threshold)),
+        None => timings::DEFAULT_THRESHOLD,
+    };
+    let saved = timings::load(path).unwrap_or_else(|err| panic!("{}", err));
+    let regressions = timings::find_regressions(&saved, latest, threshold);
+
+    let report: Vec<String> = if regressions.is_empty() {
+        vec![format!(
+            "No days were more than {}% slower than the timings in {}",
+            threshold,
+            path.display()
+        )]
+    } else {
+        regressions
+            .iter()
+            .map(|regression| regression.to_string())
+            .collect()
+    };
 
-    println!();
-    println!("Finished in {:.2?}", start.elapsed());
+    report.iter().for_each(|line| match format {
+        Format::Text => println!("{}", line),
+        Format::Json => eprintln!("{}", line),
+    });
 }
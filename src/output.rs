@@ -0,0 +1,360 @@
+//! How much each day prints. Each day's parts, e.g. [`part_one`](crate::year_2021::day_1::part_one), print their
+//! working with [`say!`](crate::say!) rather than `println!`, so that it follows the output policy picked on the
+//! command line:
+//!
+//! - `--quiet` prints nothing from the days, just how long they took
+//! - By default the answers are printed, with a line or two of working
+//! - `--verbose` also prints the larger output, such as the grids some days build, with [`verbose!`](crate::verbose!)
+//!
+//! Grids are never printed at the normal level, so they can't end up mixed in with the answers. Instead they go
+//! through [`show_grid`], which prints them with `--verbose`, and hands them to the runner to write to a text file when
+//! given `--grid-dir=<path>`.
+//!
+//! The parts also return their answers, which the runner collects as [`Answers`] so that it can report them in other
+//! formats. `--format json` is quiet, and instead prints a JSON array with an object
+//! for each day that was run, e.g.
+//!
+//! ```text
+//! [
+//!   {
+//!     "day": 1,
+//!     "part1": "1400",
+//!     "part2": "1429",
+//!     "micros": 1234
+//!   }
+//! ]
+//! ```
+//!
+//! The answers are strings, as some days' answers are codes rather than numbers. A part that has no answer, e.g. day 25
+//! only has one part, a part that wasn't run with `--part <n>`, or a day that isn't included in the build, is `null`.
+//!
+//! When the days are run in parallel with `--jobs <n>`, each day's text is held back with [`capture`], so that it can
+//! be printed in day order once they have all finished.
+
+use serde::Serialize;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// How much of the working to print
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
+pub enum Verbosity {
+    /// Nothing from the days
+    Quiet,
+    /// The answers and a summary of the working, the default
+    Normal,
+    /// Everything, including grids
+    Verbose,
+}
+
+impl Verbosity {
+    /// Pick the level from the command line arguments. If both `--quiet` and `--verbose` are given the last one wins.
+    pub fn from_args(args: impl Iterator<Item = String>) -> Verbosity {
+        args.fold(Verbosity::Normal, |level, arg| match arg.as_str() {
+            "--quiet" => Verbosity::Quiet,
+            "--verbose" => Verbosity::Verbose,
+            _ => level,
+        })
+    }
+}
+
+/// The current [`Verbosity`], stored as its discriminant so that it can be shared with every day
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+thread_local! {
+    /// The text printed so far by the day running on this thread, while [`capture`] is holding it back
+    static CAPTURED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Print a line of a day's working, unless the runner has been asked to be quiet. Takes the same arguments as
+/// `println!`.
+#[macro_export]
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_quiet() {
+            $crate::output::print_line(format!($($arg)*))
+        }
+    };
+}
+
+/// Print a line that is only wanted with `--verbose`. Takes the same arguments as `println!`.
+#[macro_export]
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        if $crate::output::is_verbose() {
+            $crate::output::print_line(format!($($arg)*))
+        }
+    };
+}
+
+/// The output policy currently in use
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        _ => Verbosity::Verbose,
+    }
+}
+
+/// Change the output policy, the runner sets this once from the command line arguments
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed)
+}
+
+/// True if [`say!`](crate::say!) should print nothing
+pub fn is_quiet() -> bool {
+    verbosity() == Verbosity::Quiet
+}
+
+/// True if [`verbose!`](crate::verbose!) output is wanted
+pub fn is_verbose() -> bool {
+    verbosity() == Verbosity::Verbose
+}
+
+/// Print a line for [`say!`](crate::say!), or add it to the text being captured on this thread
+pub fn print_line(line: String) {
+    CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+        Some(text) => {
+            text.push_str(&line);
+            text.push('\n');
+        }
+        None => println!("{}", line),
+    })
+}
+
+/// Run `f`, holding back what it prints with [`say!`](crate::say!) on this thread, and return that text along with the
+/// result. Output printed directly with `println!`, e.g. by the `--benchmark` options, isn't captured.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    CAPTURED.with(|captured| *captured.borrow_mut() = Some(String::new()));
+    let result = f();
+    let text = CAPTURED.with(|captured| captured.borrow_mut().take());
+
+    (result, text.unwrap_or_default())
+}
+
+/// The answers to each part of a day's puzzle
+#[derive(Eq, PartialEq, Debug, Default)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    /// The answers to both parts
+    pub fn new(part_one: impl ToString, part_two: impl ToString) -> Answers {
+        Answers {
+            part_one: Some(part_one.to_string()),
+            part_two: Some(part_two.to_string()),
+        }
+    }
+
+    /// For puzzles with only one part
+    #[cfg(test)]
+    pub fn part_one(part_one: impl ToString) -> Answers {
+        Answers {
+            part_one: Some(part_one.to_string()),
+            part_two: None,
+        }
+    }
+}
+
+/// The formats the runner can report the answers in
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Format {
+    /// The full working as text, the default
+    Text,
+    /// Just the answers and timings as a JSON array
+    Json,
+}
+
+/// The answers for a day, and how long it took, as reported by [`Format::Json`]
+#[derive(Eq, PartialEq, Debug, Serialize)]
+pub struct DayResult {
+    day: usize,
+    part1: Option<String>,
+    part2: Option<String>,
+    micros: u128,
+}
+
+impl DayResult {
+    pub fn new(day: usize, answers: Answers, micros: u128) -> DayResult {
+        DayResult {
+            day,
+            part1: answers.part_one,
+            part2: answers.part_two,
+            micros,
+        }
+    }
+
+    pub fn day(&self) -> usize {
+        self.day
+    }
+
+    pub fn micros(&self) -> u128 {
+        self.micros
+    }
+}
+
+/// Saves a grid the user has asked for, given the grid's name, its caption, and the grid itself
+pub type GridWriter = fn(&str, &str, &str);
+
+/// Set once by the runner, if the user has asked for the grids to be saved
+static GRID_WRITER: OnceLock<GridWriter> = OnceLock::new();
+
+/// Pass the grids the days show to `writer` from now on. Only the first writer set is used.
+pub fn set_grid_writer(writer: GridWriter) {
+    let _ = GRID_WRITER.set(writer);
+}
+
+/// Send a grid to wherever the output policy wants it. It is printed under the caption with `--verbose`, and the runner
+/// saves it when given `--grid-dir=<path>`, see [`set_grid_writer`]. Otherwise it is dropped, the days print the
+/// answer it shows as well, e.g. day 13 reads the letters from its grid.
+pub fn show_grid(name: &str, caption: &str, grid: &str) {
+    verbose!("{}:\n{}", caption, grid);
+
+    if let Some(writer) = GRID_WRITER.get() {
+        writer(name, caption, grid);
+    }
+}
+
+/// Find the value of the last `--<name> <value>` or `--<name>=<value>` argument
+pub fn flag_value(args: impl Iterator<Item = String>, name: &str) -> Option<String> {
+    let flag = format!("--{}", name);
+    let mut value = None;
+    let mut args = args.peekable();
+
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            value = args.next();
+        } else if let Some(inline) = arg.strip_prefix(&format!("{}=", flag)) {
+            value = Some(inline.to_string());
+        }
+    }
+
+    value
+}
+
+/// Read the output format from `--format`, defaulting to text
+pub fn parse_format(args: impl Iterator<Item = String>) -> Result<Format, String> {
+    match flag_value(args, "format").as_deref() {
+        None | Some("text") => Ok(Format::Text),
+        Some("json") => Ok(Format::Json),
+        Some(other) => Err(format!("Unknown format '{}', expected text or json", other)),
+    }
+}
+
+/// Render the results as the JSON array described in the module docs
+pub fn to_json(results: &[DayResult]) -> String {
+    serde_json::to_string_pretty(results).expect("Failed to serialise results")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::{
+        capture, flag_value, parse_format, print_line, to_json, Answers, DayResult, Format,
+        Verbosity,
+    };
+    use std::thread;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<String>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn can_pick_verbosity_from_args() {
+        assert_eq!(Verbosity::from_args(args(&[])), Verbosity::Normal);
+        assert_eq!(Verbosity::from_args(args(&["--quiet"])), Verbosity::Quiet);
+        assert_eq!(
+            Verbosity::from_args(args(&["--visualise", "--verbose"])),
+            Verbosity::Verbose
+        );
+        assert_eq!(
+            Verbosity::from_args(args(&["--verbose", "--quiet"])),
+            Verbosity::Quiet
+        );
+    }
+
+    #[test]
+    fn can_find_flag_values() {
+        assert_eq!(flag_value(args(&[]), "day"), None);
+        assert_eq!(
+            flag_value(args(&["--day", "0", "--format", "json"]), "day"),
+            Some("0".to_string())
+        );
+        assert_eq!(
+            flag_value(args(&["--day=12", "--trace"]), "day"),
+            Some("12".to_string())
+        );
+        assert_eq!(flag_value(args(&["--day"]), "day"), None);
+        assert_eq!(flag_value(args(&["--days=3"]), "day"), None);
+    }
+
+    #[test]
+    fn can_parse_format() {
+        assert_eq!(parse_format(args(&[])), Ok(Format::Text));
+        assert_eq!(parse_format(args(&["--format", "text"])), Ok(Format::Text));
+        assert_eq!(parse_format(args(&["--format=json"])), Ok(Format::Json));
+        assert_eq!(
+            parse_format(args(&["--format", "xml"])),
+            Err("Unknown format 'xml', expected text or json".to_string())
+        );
+    }
+
+    #[test]
+    fn can_capture_output() {
+        let (result, text) = capture(|| {
+            print_line("one".to_string());
+            print_line(format!("{} + {}", 1, 1));
+            2
+        });
+        assert_eq!(result, 2);
+        assert_eq!(text, "one\n1 + 1\n");
+
+        // Each thread captures its own output
+        let (texts, text) = capture(|| {
+            print_line("outer".to_string());
+            thread::scope(|scope| {
+                let inner = scope.spawn(|| capture(|| print_line("inner".to_string())).1);
+                inner.join().unwrap()
+            })
+        });
+        assert_eq!(texts, "inner\n");
+        assert_eq!(text, "outer\n");
+    }
+
+    #[test]
+    fn can_serialise_results() {
+        let results = vec![
+            DayResult::new(13, Answers::new(17, "ABCD"), 1500),
+            DayResult::new(25, Answers::part_one(58), 20),
+            DayResult::new(26, Answers::default(), 0),
+        ];
+
+        assert_eq!(
+            to_json(&results),
+            r#"[
+  {
+    "day": 13,
+    "part1": "17",
+    "part2": "ABCD",
+    "micros": 1500
+  },
+  {
+    "day": 25,
+    "part1": "58",
+    "part2": null,
+    "micros": 20
+  },
+  {
+    "day": 26,
+    "part1": null,
+    "part2": null,
+    "micros": 0
+  }
+]"#
+        );
+    }
+}
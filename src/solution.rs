@@ -0,0 +1,633 @@
+//! The runner finds each puzzle's solution in a [`Registry`], keyed by year and day. Each year's days live in their own
+//! module, e.g. [`crate::year_2021`], which adds them to the registry with its `register` function. A later year can be
+//! added alongside the existing one with a new `year_<n>` module that does the same, and is then available with
+//! `--year <n>`.
+//!
+//! Each day is a [`Day`], which splits the solution into parsing the input, and solving each part from the parsed
+//! input. That way `--part <n>` can run just one part, and running both only parses the input once. The solutions are
+//! given their input, rather than reading it themselves, as loading it from a file is left to the runner, so that the
+//! library can be used where there are no files, see the `wasm` module, which is built with the `wasm` feature.
+//!
+//! The days don't depend on each other, so when running all of them `--jobs <n>` shares them between `n` threads with
+//! [`run_in_parallel`].
+//!
+//! Some days have more than one way of solving a part, e.g. a brute force search that was kept once a faster way was
+//! found. These can be given to the [`Day`] as [`Alternative`]s, and `--compare` then runs each of them on the same
+//! parsed input, timing them, and checking they all give the same answer, see [`Solution::compare`].
+
+use crate::errors::{validate_input, Validation};
+use crate::output::{self, Answers, Verbosity};
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// One part of a day's puzzle, picked with `--part <n>` to run just that part
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            other => Err(format!("Unknown part '{}', expected 1 or 2", other)),
+        }
+    }
+}
+
+impl Display for Part {
+    /// The number the part is picked with, see [`Part::from_str`]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A single day's puzzle solution. These are shared between threads when the days are run in parallel.
+pub trait Solution: Sync {
+    /// The title of the day's puzzle
+    fn name(&self) -> &'static str;
+
+    /// False for puzzles with only one part, e.g. the last day of each year
+    fn has_part_two(&self) -> bool {
+        true
+    }
+
+    /// False if the day has been left out of the build by its cargo feature
+    fn is_included(&self) -> bool {
+        true
+    }
+
+    /// Print the working for `part` of the day with the given input, or both parts if `None`, and return the answers.
+    /// The part that wasn't run has no answer.
+    fn run(&self, input: &str, part: Option<Part>) -> Answers;
+
+    /// True if `input` looks like the input for this day, see [`crate::detect`]
+    fn sniff(&self, _input: &str) -> bool {
+        false
+    }
+
+    /// Check `input` is well-formed for this day without solving it, with every problem found if it isn't, see
+    /// [`crate::errors`]. Days that don't check their input accept anything.
+    fn validate(&self, _input: &str) -> Validation {
+        Ok(())
+    }
+
+    /// Just the answers, without printing the working
+    fn solve(&self, input: &str, part: Option<Part>) -> Answers {
+        quietly(|| self.run(input, part))
+    }
+
+    /// True if the day has [`Alternative`]s to compare
+    fn has_alternatives(&self) -> bool {
+        false
+    }
+
+    /// Parse `input` once, then run each of the day's [`Alternative`]s with it `runs` times, without printing the
+    /// working. Empty if the day doesn't have any alternatives.
+    fn compare(&self, _input: &str, _runs: u32) -> Vec<Comparison> {
+        Vec::new()
+    }
+}
+
+/// Run `action` without printing the working
+fn quietly<T>(action: impl FnOnce() -> T) -> T {
+    let verbosity = output::verbosity();
+    output::set_verbosity(Verbosity::Quiet);
+    let result = action();
+    output::set_verbosity(verbosity);
+
+    result
+}
+
+/// One way of solving a part of a day, for `--compare`. Like the parts, it is given the parsed input, and returns the
+/// answer if there is one, but is expected not to print anything. A day lists the implementation its part uses as well
+/// as the alternatives to it, so that there is something to compare them against.
+pub struct Alternative<B: ?Sized> {
+    part: Part,
+    name: &'static str,
+    solve: fn(&B) -> Option<String>,
+}
+
+impl<B: ?Sized> Alternative<B> {
+    pub fn new(part: Part, name: &'static str, solve: fn(&B) -> Option<String>) -> Alternative<B> {
+        Alternative { part, name, solve }
+    }
+}
+
+/// The answer an [`Alternative`] gave with `--compare`, and how long it took on average
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Comparison {
+    pub part: Part,
+    pub name: &'static str,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+}
+
+/// The parts where the [`Comparison`]s don't all have the same answer, in order
+pub fn disagreements(comparisons: &[Comparison]) -> Vec<Part> {
+    [Part::One, Part::Two]
+        .into_iter()
+        .filter(|&part| {
+            let mut answers = comparisons
+                .iter()
+                .filter(|comparison| comparison.part == part)
+                .map(|comparison| &comparison.answer);
+            answers
+                .next()
+                .is_some_and(|first| answers.any(|answer| answer != first))
+        })
+        .collect()
+}
+
+/// A day's solution, written as a function to parse the input, and one for each part, which is how all the 2021 days
+/// are written. Each part prints its working, and returns its answer, if it has one. The parts can take the parsed
+/// input as anything it can be borrowed as, so that a `Vec` can be passed on as a slice.
+pub struct Day<T, B: ?Sized = T> {
+    name: &'static str,
+    parse: fn(&str) -> T,
+    sniff: fn(&str) -> bool,
+    validate: fn(&str) -> Validation,
+    part_one: fn(&B) -> Option<String>,
+    part_two: Option<fn(&B) -> Option<String>>,
+    alternatives: Vec<Alternative<B>>,
+}
+
+impl<T: Borrow<B>, B: ?Sized> Day<T, B> {
+    /// A puzzle with only one part, use [`Day::with_part_two`] to add the second. It won't be detected from its input
+    /// until given a `sniff` function with [`Day::with_sniff`].
+    pub fn new(
+        name: &'static str,
+        parse: fn(&str) -> T,
+        part_one: fn(&B) -> Option<String>,
+    ) -> Day<T, B> {
+        Day {
+            name,
+            parse,
+            sniff: |_| false,
+            validate: |_| Ok(()),
+            part_one,
+            part_two: None,
+            alternatives: Vec::new(),
+        }
+    }
+
+    pub fn with_part_two(self, part_two: fn(&B) -> Option<String>) -> Day<T, B> {
+        Day {
+            part_two: Some(part_two),
+            ..self
+        }
+    }
+
+    /// Recognise the day's input with `sniff`, for `--detect`
+    pub fn with_sniff(self, sniff: fn(&str) -> bool) -> Day<T, B> {
+        Day { sniff, ..self }
+    }
+
+    /// Check the day's input with `validate`, which the day's `parse` also runs before parsing, see [`crate::errors`]
+    pub fn with_validate(self, validate: fn(&str) -> Validation) -> Day<T, B> {
+        Day { validate, ..self }
+    }
+
+    /// The ways of solving the parts to compare with `--compare`, see [`Alternative`]
+    pub fn with_alternatives(self, alternatives: Vec<Alternative<B>>) -> Day<T, B> {
+        Day {
+            alternatives,
+            ..self
+        }
+    }
+
+    /// Parse `input` once, and run the parts asked for, see [`Solution::run`]
+    fn answer(&self, input: &str, part: Option<Part>) -> Answers {
+        let parsed = (self.parse)(input);
+        let input = parsed.borrow();
+        let part_one = match part {
+            None | Some(Part::One) => (self.part_one)(input),
+            Some(Part::Two) => None,
+        };
+        let part_two = match (part, self.part_two) {
+            (None | Some(Part::Two), Some(part_two)) => part_two(input),
+            (Some(Part::Two), None) => {
+                say!("{} only has one part", self.name);
+                None
+            }
+            _ => None,
+        };
+
+        Answers { part_one, part_two }
+    }
+}
+
+impl<T: Borrow<B>, B: ?Sized> Solution for Day<T, B> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn has_part_two(&self) -> bool {
+        self.part_two.is_some()
+    }
+
+    fn run(&self, input: &str, part: Option<Part>) -> Answers {
+        self.answer(input, part)
+    }
+
+    fn sniff(&self, input: &str) -> bool {
+        (self.sniff)(input)
+    }
+
+    fn validate(&self, input: &str) -> Validation {
+        validate_input(input, self.validate)
+    }
+
+    fn has_alternatives(&self) -> bool {
+        !self.alternatives.is_empty()
+    }
+
+    fn compare(&self, input: &str, runs: u32) -> Vec<Comparison> {
+        if self.alternatives.is_empty() {
+            return Vec::new();
+        }
+
+        quietly(|| {
+            let parsed = (self.parse)(input);
+            self.alternatives
+                .iter()
+                .map(|alternative| {
+                    let start = Instant::now();
+                    let mut answer = None;
+                    for _ in 0..runs.max(1) {
+                        answer = (alternative.solve)(parsed.borrow());
+                    }
+
+                    Comparison {
+                        part: alternative.part,
+                        name: alternative.name,
+                        answer,
+                        elapsed: start.elapsed() / runs.max(1),
+                    }
+                })
+                .collect()
+        })
+    }
+}
+
+/// A day that has been left out of the build by its cargo feature, running it explains how to include it. Only
+/// constructed when some days are left out.
+#[cfg_attr(feature = "all-days", allow(dead_code))]
+pub struct NotIncluded {
+    name: &'static str,
+    module: &'static str,
+    feature: &'static str,
+    has_part_two: bool,
+}
+
+#[cfg_attr(feature = "all-days", allow(dead_code))]
+impl NotIncluded {
+    pub fn new(name: &'static str, module: &'static str, feature: &'static str) -> NotIncluded {
+        NotIncluded {
+            name,
+            module,
+            feature,
+            has_part_two: true,
+        }
+    }
+
+    /// For puzzles with only one part
+    pub fn part_one_only(self) -> NotIncluded {
+        NotIncluded {
+            has_part_two: false,
+            ..self
+        }
+    }
+}
+
+impl Solution for NotIncluded {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn has_part_two(&self) -> bool {
+        self.has_part_two
+    }
+
+    fn is_included(&self) -> bool {
+        false
+    }
+
+    fn run(&self, _input: &str, _part: Option<Part>) -> Answers {
+        say!(
+            "{} is not included in this build, enable it with `--features {}`",
+            self.module,
+            self.feature
+        );
+        Answers::default()
+    }
+}
+
+/// All the solutions the runner knows about
+#[derive(Default)]
+pub struct Registry {
+    solutions: BTreeMap<(u16, u8), Box<dyn Solution>>,
+}
+
+impl Registry {
+    /// A registry with every year's solutions
+    pub fn all() -> Registry {
+        let mut registry = Registry::default();
+        crate::year_2021::register(&mut registry);
+
+        registry
+    }
+
+    /// Add the solution for a day, replacing any already registered for it
+    pub fn register(&mut self, year: u16, day: u8, solution: impl Solution + 'static) {
+        self.solutions.insert((year, day), Box::new(solution));
+    }
+
+    /// The solution for a day, if there is one
+    pub fn get(&self, year: u16, day: u8) -> Option<&dyn Solution> {
+        self.solutions
+            .get(&(year, day))
+            .map(|solution| solution.as_ref())
+    }
+
+    /// The solutions for each day of a year, in order
+    pub fn days(&self, year: u16) -> impl Iterator<Item = (u8, &dyn Solution)> {
+        self.solutions
+            .range((year, u8::MIN)..=(year, u8::MAX))
+            .map(|(&(_, day), solution)| (day, solution.as_ref()))
+    }
+
+    /// The most recent year with any solutions, the default when a year isn't given
+    pub fn latest_year(&self) -> Option<u16> {
+        self.solutions.keys().map(|&(year, _)| year).max()
+    }
+}
+
+/// A day run by [`run_in_parallel`], with the text it printed held back to print in order
+pub struct ParallelRun {
+    pub day: u8,
+    pub name: &'static str,
+    pub answers: Answers,
+    pub text: String,
+    pub elapsed: Duration,
+}
+
+/// Run each of the days on up to `jobs` threads, each taking the next day that hasn't been started until there are none
+/// left, so a slow day doesn't hold up the others queued behind it. What each day prints with [`say!`] is captured, see
+/// [`output::capture`], and the runs are returned in day order so they can be printed as if run one at a time. `answer`
+/// is given each day and its solution, so that it can load that day's input.
+pub fn run_in_parallel(
+    days: Vec<(u8, &dyn Solution)>,
+    jobs: usize,
+    answer: impl Fn(u8, &dyn Solution) -> Answers + Sync,
+) -> Vec<ParallelRun> {
+    let next = AtomicUsize::new(0);
+    let runs = Mutex::new(Vec::with_capacity(days.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            scope.spawn(|| {
+                while let Some(&(day, solution)) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let start = Instant::now();
+                    let (answers, text) = output::capture(|| answer(day, solution));
+                    let run = ParallelRun {
+                        day,
+                        name: solution.name(),
+                        answers,
+                        text,
+                        elapsed: start.elapsed(),
+                    };
+                    runs.lock().unwrap().push(run);
+                }
+            });
+        }
+    });
+
+    let mut runs = runs.into_inner().unwrap();
+    runs.sort_by_key(|run| run.day);
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::Answers;
+    use crate::solution::{
+        disagreements, run_in_parallel, Alternative, Comparison, Day, NotIncluded, Part, Registry,
+        Solution,
+    };
+    use std::time::Duration;
+
+    /// A solution that doesn't need an input file, so that the registry can be tested on its own
+    struct Fixed(&'static str);
+
+    impl Solution for Fixed {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn run(&self, _input: &str, _part: Option<Part>) -> Answers {
+            say!("Some working");
+            Answers::new(1, 2)
+        }
+    }
+
+    fn parse(input: &str) -> Vec<usize> {
+        input.lines().flat_map(|line| line.parse()).collect()
+    }
+
+    fn sum(numbers: &[usize]) -> Option<String> {
+        Some(numbers.iter().sum::<usize>().to_string())
+    }
+
+    fn max(numbers: &[usize]) -> Option<String> {
+        numbers.iter().max().map(|max| max.to_string())
+    }
+
+    #[test]
+    fn can_look_up_solutions() {
+        let mut registry = Registry::default();
+        assert_eq!(registry.latest_year(), None);
+
+        registry.register(2022, 1, Fixed("Later"));
+        registry.register(2021, 2, Fixed("Second"));
+        registry.register(2021, 1, Fixed("First"));
+
+        assert_eq!(registry.latest_year(), Some(2022));
+        assert_eq!(registry.get(2021, 2).map(|day| day.name()), Some("Second"));
+        assert_eq!(registry.get(2021, 3).map(|day| day.name()), None);
+        assert_eq!(
+            registry
+                .days(2021)
+                .map(|(day, solution)| (day, solution.name()))
+                .collect::<Vec<_>>(),
+            vec![(1, "First"), (2, "Second")]
+        );
+        assert_eq!(
+            registry.get(2022, 1).unwrap().solve("", None),
+            Answers::new(1, 2)
+        );
+    }
+
+    #[test]
+    fn can_run_days_in_parallel() {
+        let mut registry = Registry::default();
+        for (day, name) in [(3, "Third"), (1, "First"), (2, "Second"), (5, "Fifth")] {
+            registry.register(2021, day, Fixed(name));
+        }
+
+        for jobs in [1, 2, 8] {
+            let runs = run_in_parallel(registry.days(2021).collect(), jobs, |_, solution| {
+                solution.run("", None)
+            });
+            assert_eq!(
+                runs.iter()
+                    .map(|run| (run.day, run.name))
+                    .collect::<Vec<_>>(),
+                vec![(1, "First"), (2, "Second"), (3, "Third"), (5, "Fifth")]
+            );
+            assert!(runs.iter().all(|run| run.answers == Answers::new(1, 2)));
+        }
+    }
+
+    #[test]
+    fn can_run_each_part() {
+        let day = Day::new("Numbers", parse, sum).with_part_two(max);
+        assert!(day.has_part_two());
+        assert_eq!(day.answer("1\n5\n3", None), Answers::new(9, 5));
+        assert_eq!(day.answer("1\n5\n3", Some(Part::One)), Answers::part_one(9));
+        assert_eq!(
+            day.answer("1\n5\n3", Some(Part::Two)),
+            Answers {
+                part_one: None,
+                part_two: Some("5".to_string())
+            }
+        );
+
+        let day = Day::new("Numbers", parse, sum);
+        assert!(!day.has_part_two());
+        assert_eq!(day.answer("1\n5\n3", None), Answers::part_one(9));
+        assert_eq!(day.answer("1\n5\n3", Some(Part::Two)), Answers::default());
+
+        assert_eq!("1".parse(), Ok(Part::One));
+        assert_eq!("2".parse(), Ok(Part::Two));
+        assert!("3".parse::<Part>().is_err());
+        assert_eq!(Part::Two.to_string(), "2");
+    }
+
+    #[test]
+    fn can_compare_alternatives() {
+        let day = Day::new("Numbers", parse, sum)
+            .with_part_two(max)
+            .with_alternatives(vec![
+                Alternative::new(Part::One, "sum", sum),
+                Alternative::new(Part::One, "sum_reversed", |numbers: &[usize]| {
+                    Some(numbers.iter().rev().sum::<usize>().to_string())
+                }),
+                Alternative::new(Part::Two, "max", max),
+            ]);
+
+        let comparisons = day.compare("1\n5\n3", 3);
+        assert_eq!(
+            comparisons
+                .iter()
+                .map(|comparison| (
+                    comparison.part,
+                    comparison.name,
+                    comparison.answer.as_deref()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (Part::One, "sum", Some("9")),
+                (Part::One, "sum_reversed", Some("9")),
+                (Part::Two, "max", Some("5")),
+            ]
+        );
+        assert_eq!(disagreements(&comparisons), vec![]);
+
+        // Days without alternatives have nothing to compare
+        assert!(day.has_alternatives());
+        assert!(!Day::new("Numbers", parse, sum).has_alternatives());
+        assert_eq!(Day::new("Numbers", parse, sum).compare("1", 1), vec![]);
+        assert_eq!(Fixed("Fixed").compare("1", 1), vec![]);
+    }
+
+    #[test]
+    fn can_find_disagreements() {
+        let comparison = |part, answer: Option<&str>| Comparison {
+            part,
+            name: "test",
+            answer: answer.map(|answer| answer.to_string()),
+            elapsed: Duration::ZERO,
+        };
+
+        assert_eq!(disagreements(&[]), vec![]);
+        assert_eq!(
+            disagreements(&[
+                comparison(Part::One, Some("1")),
+                comparison(Part::Two, Some("2")),
+                comparison(Part::One, Some("1")),
+            ]),
+            vec![]
+        );
+        assert_eq!(
+            disagreements(&[
+                comparison(Part::One, Some("1")),
+                comparison(Part::Two, Some("2")),
+                comparison(Part::Two, None),
+            ]),
+            vec![Part::Two]
+        );
+        assert_eq!(
+            disagreements(&[
+                comparison(Part::Two, Some("2")),
+                comparison(Part::One, Some("1")),
+                comparison(Part::One, Some("one")),
+                comparison(Part::Two, Some("3")),
+            ]),
+            vec![Part::One, Part::Two]
+        );
+    }
+
+    #[test]
+    fn can_report_days_not_included() {
+        let day = NotIncluded::new("Missing", "day_1", "day_1");
+        assert!(!day.is_included());
+        assert!(day.has_part_two());
+        assert!(!day.part_one_only().has_part_two());
+        assert_eq!(
+            NotIncluded::new("Missing", "day_1", "day_1").solve("", None),
+            Answers::default()
+        );
+    }
+
+    #[test]
+    fn registers_every_day_of_2021() {
+        let registry = Registry::all();
+        assert_eq!(registry.latest_year(), Some(2021));
+        assert_eq!(
+            registry.days(2021).map(|(day, _)| day).collect::<Vec<_>>(),
+            (1..=25).collect::<Vec<_>>()
+        );
+        assert_eq!(registry.get(2021, 1).unwrap().name(), "Sonar Sweep");
+        assert_eq!(registry.get(2021, 25).unwrap().name(), "Sea Cucumber");
+        assert!(registry.get(2021, 24).unwrap().has_part_two());
+        assert!(!registry.get(2021, 25).unwrap().has_part_two());
+        assert_eq!(
+            registry.get(2021, 9).unwrap().is_included(),
+            cfg!(feature = "day_9")
+        );
+    }
+}
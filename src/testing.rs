@@ -0,0 +1,150 @@
+//! Scaffolding for property-based tests, in the style of quickcheck, but small enough not to need the dependency. A
+//! type that implements [`Arbitrary`] can be generated from a [`Gen`], a seeded source of random numbers, and
+//! [`check`] then runs a property against many generated values. The seeds are fixed, so a failure is reproducible,
+//! and the failing case is included in the panic message.
+//!
+//! [`Grid`] is implemented here, the day specific types, e.g. snailfish numbers, cuboids and burrows, implement it in
+//! their day's tests, as their internals are private to the day.
+
+use crate::util::grid::Grid;
+use std::fmt::Debug;
+
+/// The number of cases [`check`] runs if a test doesn't need a specific number
+pub const DEFAULT_CASES: u64 = 200;
+
+/// A seeded stream of pseudo-random numbers, using [SplitMix64](https://prng.di.unimi.it/splitmix64.c), which is
+/// plenty for generating test cases.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Gen {
+    state: u64,
+}
+
+impl Gen {
+    pub fn new(seed: u64) -> Gen {
+        Gen { state: seed }
+    }
+
+    /// The next number in the stream
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` up to, but not including, `bound`
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// A number from `min` to `max`, inclusive
+    pub fn between(&mut self, min: isize, max: isize) -> isize {
+        min + self.below((max - min + 1) as usize) as isize
+    }
+
+    /// True with a probability of one in `n`
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    /// Put the items in a random order, using the Fisher-Yates shuffle
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Implemented by types that can be generated for property tests
+pub trait Arbitrary: Sized + Debug {
+    fn arbitrary(gen: &mut Gen) -> Self;
+}
+
+impl<A: Arbitrary, B: Arbitrary> Arbitrary for (A, B) {
+    fn arbitrary(gen: &mut Gen) -> Self {
+        (A::arbitrary(gen), B::arbitrary(gen))
+    }
+}
+
+impl Arbitrary for Grid {
+    /// A grid of digits, from 1x1 up to 10x10
+    fn arbitrary(gen: &mut Gen) -> Self {
+        let width = 1 + gen.below(10);
+        let height = 1 + gen.below(10);
+        let numbers = (0..width * height).map(|_| gen.below(10) as u8).collect();
+
+        Grid { numbers, width }
+    }
+}
+
+/// Check that `property` holds for `cases` generated values, using the seeds `0..cases`. Panics with the seed and the
+/// value for the first case that fails.
+pub fn check<T: Arbitrary>(cases: u64, property: impl Fn(&T) -> bool) {
+    for seed in 0..cases {
+        let value = T::arbitrary(&mut Gen::new(seed));
+        if !property(&value) {
+            panic!("Property failed for seed {}: {:?}", seed, value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{check, Arbitrary, Gen, DEFAULT_CASES};
+    use crate::util::grid::Grid;
+
+    #[test]
+    fn can_generate_repeatable_numbers() {
+        let numbers = |seed| {
+            let mut gen = Gen::new(seed);
+            (0..10).map(|_| gen.next_u64()).collect::<Vec<u64>>()
+        };
+
+        assert_eq!(numbers(42), numbers(42));
+        assert_ne!(numbers(42), numbers(43));
+
+        let mut gen = Gen::new(1);
+        for _ in 0..1000 {
+            assert!(gen.below(10) < 10);
+            assert!((-3..=3).contains(&gen.between(-3, 3)));
+        }
+
+        let mut items: Vec<usize> = (0..20).collect();
+        gen.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<usize>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn grids_round_trip_through_print() {
+        check(DEFAULT_CASES, |grid: &Grid| {
+            Grid::from(grid.print().as_str()) == *grid
+        });
+    }
+
+    #[test]
+    fn grid_surrounds_stay_in_the_grid() {
+        check(DEFAULT_CASES, |grid: &Grid| {
+            let (max_y, max_x) = grid.max_coords();
+            grid.iter().all(|((y, x), _)| {
+                grid.get_orthogonal_surrounds(y, x)
+                    .iter()
+                    .all(|&((y1, x1), v)| y1 <= max_y && x1 <= max_x && grid.get(y1, x1) == Some(v))
+            })
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Property failed for seed")]
+    fn reports_failing_case() {
+        check(DEFAULT_CASES, |grid: &Grid| grid.len() < 50);
+    }
+
+    #[test]
+    fn can_generate_pairs() {
+        let (a, b) = <(Grid, Grid)>::arbitrary(&mut Gen::new(7));
+        assert_ne!(a, b);
+    }
+}
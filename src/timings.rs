@@ -0,0 +1,201 @@
+//! Keeps a record of how long each day took, so that a change that makes a day slower can be spotted. Passing
+//! `--save-timings <path>` writes the time taken by each day that was run to a JSON file, keeping the saved times for
+//! any days that weren't run. Passing `--compare-timings <path>` on a later run reports each day that took longer
+//! than its saved time by more than `--timing-threshold <percent>`, 10% by default. Both can be given the same path, in
+//! which case the comparison is made against the previous run before it is overwritten.
+//!
+//! Timings vary between runs, and more so for the days that only take a few microseconds, so these should be taken
+//! from release builds, and a flagged day re-run before reading too much into it.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// The percentage slower than the saved time a day can be before it is reported, if not given
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// How long a day took to run
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Timing {
+    pub year: u16,
+    pub day: u8,
+    pub micros: u128,
+}
+
+impl Timing {
+    pub fn new(year: u16, day: u8, micros: u128) -> Timing {
+        Timing { year, day, micros }
+    }
+}
+
+/// A day that took longer than its saved time by more than the threshold
+#[derive(Eq, PartialEq, Debug)]
+pub struct Regression {
+    pub latest: Timing,
+    pub saved_micros: u128,
+}
+
+impl Regression {
+    /// How much slower the latest run was, as a percentage of the saved time
+    fn percent_slower(&self) -> f64 {
+        (self.latest.micros as f64 / self.saved_micros as f64 - 1.0) * 100.0
+    }
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Day {} took {:.2?}, {:.0}% slower than the saved {:.2?}",
+            self.latest.day,
+            Duration::from_micros(self.latest.micros as u64),
+            self.percent_slower(),
+            Duration::from_micros(self.saved_micros as u64)
+        )
+    }
+}
+
+/// Read the saved timings, a missing file is treated as having no timings saved
+pub fn load(path: &Path) -> Result<Vec<Timing>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+    serde_json::from_str(&contents)
+        .map_err(|err| format!("{} is not a valid timings file: {}", path.display(), err))
+}
+
+/// Write the latest timings to the file, keeping any saved timings for other days
+pub fn save(path: &Path, latest: &[Timing]) -> Result<(), String> {
+    let timings = merge(load(path)?, latest);
+    let contents = serde_json::to_string_pretty(&timings).expect("Failed to serialise timings");
+
+    fs::write(path, contents + "\n")
+        .map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+}
+
+/// Replace the saved timings with the latest ones for the same days, sorted by year and day
+fn merge(saved: Vec<Timing>, latest: &[Timing]) -> Vec<Timing> {
+    let mut timings: Vec<Timing> = saved
+        .into_iter()
+        .filter(|timing| {
+            !latest
+                .iter()
+                .any(|other| (other.year, other.day) == (timing.year, timing.day))
+        })
+        .chain(latest.iter().copied())
+        .collect();
+    timings.sort_by_key(|timing| (timing.year, timing.day));
+
+    timings
+}
+
+/// Find the days that were more than `threshold` percent slower than their saved time. Days without a saved time are
+/// skipped.
+pub fn find_regressions(saved: &[Timing], latest: &[Timing], threshold: f64) -> Vec<Regression> {
+    latest
+        .iter()
+        .filter_map(|&timing| {
+            let saved = saved
+                .iter()
+                .find(|other| (other.year, other.day) == (timing.year, timing.day))?;
+
+            let limit = saved.micros as f64 * (1.0 + threshold / 100.0);
+            (timing.micros as f64 > limit).then_some(Regression {
+                latest: timing,
+                saved_micros: saved.micros,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::timings::{find_regressions, load, merge, save, Regression, Timing};
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn can_merge_timings() {
+        let saved = vec![
+            Timing::new(2021, 3, 300),
+            Timing::new(2021, 1, 100),
+            Timing::new(2021, 2, 200),
+        ];
+        let latest = vec![Timing::new(2021, 2, 250), Timing::new(2021, 4, 400)];
+
+        assert_eq!(
+            merge(saved, &latest),
+            vec![
+                Timing::new(2021, 1, 100),
+                Timing::new(2021, 2, 250),
+                Timing::new(2021, 3, 300),
+                Timing::new(2021, 4, 400),
+            ]
+        );
+    }
+
+    #[test]
+    fn can_find_regressions() {
+        let saved = vec![
+            Timing::new(2021, 1, 1000),
+            Timing::new(2021, 2, 1000),
+            Timing::new(2021, 3, 1000),
+        ];
+        let latest = vec![
+            Timing::new(2021, 1, 1100),
+            Timing::new(2021, 2, 1101),
+            Timing::new(2021, 3, 500),
+            Timing::new(2021, 4, 9999),
+        ];
+
+        let regressions = find_regressions(&saved, &latest, 10.0);
+        assert_eq!(
+            regressions,
+            vec![Regression {
+                latest: Timing::new(2021, 2, 1101),
+                saved_micros: 1000,
+            }]
+        );
+        assert_eq!(find_regressions(&saved, &latest, 0.0).len(), 2);
+
+        let regression = Regression {
+            latest: Timing::new(2021, 5, 15000),
+            saved_micros: 12000,
+        };
+        assert_eq!(
+            regression.to_string(),
+            "Day 5 took 15.00ms, 25% slower than the saved 12.00ms"
+        );
+    }
+
+    #[test]
+    fn can_save_and_load_timings() {
+        let path = env::temp_dir().join(format!("aoc-timings-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(load(&path), Ok(Vec::new()));
+
+        save(
+            &path,
+            &[Timing::new(2021, 1, 100), Timing::new(2021, 2, 200)],
+        )
+        .unwrap();
+        save(&path, &[Timing::new(2021, 2, 150)]).unwrap();
+        assert_eq!(
+            load(&path),
+            Ok(vec![Timing::new(2021, 1, 100), Timing::new(2021, 2, 150)])
+        );
+
+        fs::write(&path, "not json").unwrap();
+        assert!(load(&path)
+            .unwrap_err()
+            .contains("is not a valid timings file"));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,201 @@
+//! A set of small integers stored as the bits of an unsigned integer. [`crate::year_2021::day_3`],
+//! [`crate::year_2021::day_8`] and [`crate::year_2021::day_12`] all ended up using plain integers this way, with the
+//! bitwise operators spread through the puzzle logic. [`SmallSet`] gives those operations names, and displays the set
+//! the way day 8's input writes it, as the letters `a`, `b`, `c`, ... for indices 0, 1, 2, ...
+//!
+//! The set can only hold the indices below the width of the integer it is stored in, see [`Bits::CAPACITY`]. It
+//! defaults to a `u64`, `SmallSet<u128>` holds twice as many, and `SmallSet<usize>` lets day 3 use its values as they
+//! are. Inserting an index that doesn't fit is a bug in the caller, so it is caught by a debug assertion rather than
+//! checked on every insert in release builds. Day 12 needs any number of caves, so it keeps a `Vec` of these as words.
+
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::ops::{BitAnd, BitOr};
+
+/// The unsigned integers that a [`SmallSet`] can be stored in
+pub trait Bits:
+    Copy + Eq + Hash + Debug + Default + BitAnd<Output = Self> + BitOr<Output = Self>
+{
+    /// The number of indices that fit, a set can hold `0..CAPACITY`
+    const CAPACITY: usize;
+
+    /// The integer with just the bit for `index` set
+    fn bit(index: usize) -> Self;
+
+    /// The number of bits that are set
+    fn count(self) -> usize;
+}
+
+macro_rules! impl_bits {
+    ($($int:ty),*) => {
+        $(
+            impl Bits for $int {
+                const CAPACITY: usize = <$int>::BITS as usize;
+
+                fn bit(index: usize) -> Self {
+                    1 << index
+                }
+
+                fn count(self) -> usize {
+                    self.count_ones() as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_bits!(u64, u128, usize);
+
+/// A set of the indices `0..B::CAPACITY`, with index `n` stored as the bit `1 << n`
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug, Default)]
+pub struct SmallSet<B: Bits = u64> {
+    bits: B,
+}
+
+impl<B: Bits> SmallSet<B> {
+    /// Use an existing integer as the set of its bits
+    pub fn from_bits(bits: B) -> SmallSet<B> {
+        SmallSet { bits }
+    }
+
+    /// The set as an integer
+    #[allow(dead_code)]
+    pub fn bits(&self) -> B {
+        self.bits
+    }
+
+    /// Parse a string of lower case letters, `a` is index 0. Characters outside `a`-`z` are ignored.
+    pub fn from_letters(letters: &str) -> SmallSet<B> {
+        letters
+            .chars()
+            .filter(|c| c.is_ascii_lowercase())
+            .map(|c| c as usize - 'a' as usize)
+            .collect()
+    }
+
+    /// Add an index to the set. The index must be less than [`Bits::CAPACITY`].
+    pub fn insert(&mut self, index: usize) {
+        debug_assert!(
+            index < B::CAPACITY,
+            "{} doesn't fit in a set of {} indices",
+            index,
+            B::CAPACITY
+        );
+        self.bits = self.bits | B::bit(index);
+    }
+
+    /// True if the index is in the set. Indices that couldn't be inserted are never in the set.
+    pub fn contains(&self, index: usize) -> bool {
+        index < B::CAPACITY && self.bits & B::bit(index) != B::default()
+    }
+
+    /// True if every index in `other` is also in this set, i.e. `other` is a subset of this one
+    pub fn contains_all(&self, other: &SmallSet<B>) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    /// The indices that are in both sets
+    pub fn intersection(&self, other: &SmallSet<B>) -> SmallSet<B> {
+        SmallSet::from_bits(self.bits & other.bits)
+    }
+
+    /// The number of indices in the set
+    pub fn len(&self) -> usize {
+        self.bits.count()
+    }
+
+    /// True if there are no indices in the set
+    pub fn is_empty(&self) -> bool {
+        self.bits == B::default()
+    }
+
+    /// The indices in the set, smallest first
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..B::CAPACITY).filter(|&index| self.contains(index))
+    }
+}
+
+impl<B: Bits> FromIterator<usize> for SmallSet<B> {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut set = SmallSet::default();
+        for index in iter {
+            set.insert(index);
+        }
+
+        set
+    }
+}
+
+impl<B: Bits> Display for SmallSet<B> {
+    /// Write the set as letters, e.g. `{0, 2, 3}` is written `acd`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for index in self.iter() {
+            let letter = char::from_u32('a' as u32 + index as u32).unwrap_or('?');
+            write!(f, "{}", letter)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::bitset::{Bits, SmallSet};
+
+    #[test]
+    fn can_build_sets() {
+        let set: SmallSet = SmallSet::from_letters("dab");
+        assert_eq!(set, SmallSet::from_bits(0b1011));
+        assert_eq!(set, [0, 1, 3].into_iter().collect());
+        assert_eq!(set.bits(), 0b1011);
+        assert_eq!(set.iter().collect::<Vec<usize>>(), vec![0, 1, 3]);
+        assert_eq!(set.len(), 3);
+        assert!(!set.is_empty());
+        assert!(SmallSet::<u64>::default().is_empty());
+
+        let mut set = set;
+        set.insert(2);
+        set.insert(3);
+        assert_eq!(set, SmallSet::from_letters("abcd"));
+    }
+
+    #[test]
+    fn can_compare_sets() {
+        let four: SmallSet = SmallSet::from_letters("eafb");
+        let nine = SmallSet::from_letters("cefabd");
+        let six = SmallSet::from_letters("cdfgeb");
+
+        assert!(nine.contains_all(&four));
+        assert!(!six.contains_all(&four));
+        assert!(nine.contains(2));
+        assert!(!nine.contains(6));
+        assert!(!nine.contains(100));
+        assert_eq!(six.intersection(&four), SmallSet::from_letters("bef"));
+    }
+
+    #[test]
+    fn can_display_as_letters() {
+        assert_eq!(SmallSet::<u64>::from_letters("gcdfa").to_string(), "acdfg");
+        assert_eq!(SmallSet::<u64>::default().to_string(), "");
+    }
+
+    #[test]
+    fn can_use_the_full_capacity() {
+        assert_eq!(u64::CAPACITY, 64);
+        assert_eq!(u128::CAPACITY, 128);
+
+        let set: SmallSet<u128> = [0, 63, 64, 127].into_iter().collect();
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(64));
+        assert!(set.contains(127));
+        assert!(!set.contains(128));
+        assert_eq!(set.iter().collect::<Vec<usize>>(), vec![0, 63, 64, 127]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "64 doesn't fit in a set of 64 indices")]
+    fn cannot_insert_past_the_capacity() {
+        SmallSet::<u64>::default().insert(64);
+    }
+}
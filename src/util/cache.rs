@@ -0,0 +1,138 @@
+//! An opt-in cache for the results of the slower steps, so that iterating on a later part doesn't mean waiting for
+//! the earlier work on every run. The slow steps are wrapped in [`cached`], which saves each result as JSON in a
+//! [`CacheStore`], keyed by a name for the step and everything it depends on, normally the puzzle input, and loads it
+//! on later runs instead of working it out again.
+//!
+//! Where the results are kept is up to the runner, which sets a store with [`set_store`] when it is started with
+//! `--cache`, keeping the library free of file handling. Without a store, [`cached`] just computes the result.
+//!
+//! Results are saved in whatever form their [`Serialize`] implementation gives. Where that would be large, e.g. the
+//! hundreds of beacon positions in [`crate::year_2021::day_19`]'s result, the type can save its fields in a compact
+//! form from [`crate::util::encode`] instead.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+/// Somewhere the cached results can be kept between runs
+pub trait CacheStore: Send + Sync {
+    /// The result saved for the step `name` with the given `key`, if there is one
+    fn load(&self, name: &str, key: &str) -> Option<String>;
+
+    /// Save a result for the step `name` with the given `key`, returning a description of the problem if it can't be
+    fn save(&self, name: &str, key: &str, contents: &str) -> Result<(), String>;
+}
+
+/// Set once by the runner, if results should be cached
+static STORE: OnceLock<Box<dyn CacheStore>> = OnceLock::new();
+
+/// Keep the results in `store` from now on. Only the first store set is used.
+pub fn set_store(store: Box<dyn CacheStore>) {
+    let _ = STORE.set(store);
+}
+
+/// Return the result saved for `name` and `key` if there is one, otherwise `compute` it and save it for next time. If
+/// the runner hasn't set a [`CacheStore`] this just calls `compute`.
+pub fn cached<T: Serialize + DeserializeOwned>(
+    name: &str,
+    key: &str,
+    compute: impl FnOnce() -> T,
+) -> T {
+    match STORE.get() {
+        Some(store) => cached_in(store.as_ref(), name, key, compute),
+        None => compute(),
+    }
+}
+
+/// As [`cached`], with a given store. An entry that can't be read, e.g. as the type has changed since it was saved, is
+/// replaced, and failing to save an entry only means it will be computed again.
+pub fn cached_in<T: Serialize + DeserializeOwned>(
+    store: &dyn CacheStore,
+    name: &str,
+    key: &str,
+    compute: impl FnOnce() -> T,
+) -> T {
+    let saved = store
+        .load(name, key)
+        .and_then(|contents| serde_json::from_str(&contents).ok());
+    if let Some(value) = saved {
+        return value;
+    }
+
+    let value = compute();
+    let contents = serde_json::to_string(&value).expect("Failed to serialise cache entry");
+    if let Err(err) = store.save(name, key, &contents) {
+        say!("Failed to cache {}: {}", name, err);
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::cache::{cached_in, CacheStore};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Keeps the entries in memory, and can be made to fail to save them
+    #[derive(Default)]
+    struct MemoryStore {
+        entries: Mutex<HashMap<(String, String), String>>,
+        read_only: bool,
+    }
+
+    impl CacheStore for MemoryStore {
+        fn load(&self, name: &str, key: &str) -> Option<String> {
+            let entries = self.entries.lock().unwrap();
+            entries.get(&(name.to_string(), key.to_string())).cloned()
+        }
+
+        fn save(&self, name: &str, key: &str, contents: &str) -> Result<(), String> {
+            if self.read_only {
+                return Err("Read only".to_string());
+            }
+
+            let mut entries = self.entries.lock().unwrap();
+            entries.insert((name.to_string(), key.to_string()), contents.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn can_cache_results() {
+        let store = MemoryStore::default();
+
+        assert_eq!(cached_in(&store, "sum", "1,2,3", || vec![6, 1]), vec![6, 1]);
+        // Loaded from the cache, so not computed again
+        assert_eq!(
+            cached_in(&store, "sum", "1,2,3", || -> Vec<usize> {
+                panic!("Not cached")
+            }),
+            vec![6, 1]
+        );
+        // A different key, or a different step, is computed
+        assert_eq!(cached_in(&store, "sum", "4,5", || vec![9, 2]), vec![9, 2]);
+        assert_eq!(cached_in(&store, "max", "1,2,3", || vec![3]), vec![3]);
+
+        // An entry that can't be read as the type asked for is replaced
+        store.save("sum", "1,2,3", "not json").unwrap();
+        assert_eq!(cached_in(&store, "sum", "1,2,3", || vec![6, 1]), vec![6, 1]);
+        assert_eq!(
+            cached_in(&store, "sum", "1,2,3", || -> (String, bool) {
+                ("typed".to_string(), true)
+            }),
+            ("typed".to_string(), true)
+        );
+    }
+
+    #[test]
+    fn still_computes_if_saving_fails() {
+        let store = MemoryStore {
+            read_only: true,
+            ..MemoryStore::default()
+        };
+
+        assert_eq!(cached_in(&store, "sum", "1,2,3", || vec![6, 1]), vec![6, 1]);
+        assert_eq!(cached_in(&store, "sum", "1,2,3", || vec![6, 2]), vec![6, 2]);
+    }
+}
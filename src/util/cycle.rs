@@ -0,0 +1,204 @@
+//! Cycle detection for the simulations that step a state forward until something happens, e.g.
+//! [`crate::year_2021::day_11`] waiting for the octopuses to synchronise, and [`crate::year_2021::day_25`] waiting for
+//! the sea cucumbers to stop moving. Each step only depends on the current state, so once a state repeats everything
+//! after it repeats too. If what is being waited for hasn't happened by then it never will, and finding the cycle turns
+//! what would be an endless loop into an answer.
+//!
+//! [`find_cycle`] uses [Brent's algorithm](https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm), which only
+//! needs to keep a couple of states at a time, rather than every state seen so far. The simulations that are stepping
+//! their state anyway can instead pass each state to a [`CycleDetector`], so that they only step once, and only need
+//! [`cycle_start`] if they want to know where the cycle began.
+
+use std::fmt::{Display, Formatter};
+
+/// Where a sequence of states starts repeating, and how often
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Cycle {
+    /// The number of steps before the first state that repeats, so `0` if the starting state is part of the cycle
+    pub start: usize,
+    /// The number of steps before a state repeats, a state that never changes has a cycle of length `1`
+    pub length: usize,
+}
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "repeats every {} steps from step {}",
+            self.length, self.start
+        )
+    }
+}
+
+/// Brent's algorithm run a step at a time, for simulations that are already stepping their state and want to notice
+/// when it starts repeating as they go. Each state reached is passed to [`CycleDetector::observe`] in turn, which
+/// returns the length of the cycle once a state repeats. Every state the simulation will reach has been seen by then.
+#[derive(Debug, Clone)]
+pub struct CycleDetector<K> {
+    /// The state the later states are compared against, moved on to the latest state at each power of two
+    tortoise: K,
+    /// The number of steps the tortoise stays put before it is moved on
+    power: usize,
+    /// The number of steps since the tortoise was last moved
+    length: usize,
+}
+
+impl<K: Eq> CycleDetector<K> {
+    /// Start watching a simulation from the key of its `initial` state
+    pub fn new(initial: K) -> CycleDetector<K> {
+        CycleDetector {
+            tortoise: initial,
+            power: 1,
+            length: 1,
+        }
+    }
+
+    /// Pass the key of the state reached by the next step, returning the length of the cycle if it is a repeat.
+    ///
+    /// The tortoise is teleported to the latest state at each power of two. Once the power of two is at least the
+    /// length of the cycle, and the tortoise is inside it, the states will catch it up within one lap.
+    pub fn observe(&mut self, key: K) -> Option<usize> {
+        if key == self.tortoise {
+            return Some(self.length);
+        }
+
+        if self.power == self.length {
+            self.tortoise = key;
+            self.power *= 2;
+            self.length = 0;
+        }
+        self.length += 1;
+
+        None
+    }
+}
+
+/// Find the cycle in the states reached by repeatedly applying `step` to `initial`. States are compared by `key`, so
+/// that details that don't affect later steps, e.g. a count of the steps taken, can be left out. `initial` isn't
+/// changed, the search steps clones of it.
+///
+/// This doesn't return if the states never repeat, so is only suitable for simulations with a finite number of states.
+pub fn find_cycle<S, K>(initial: &S, mut step: impl FnMut(&mut S), key: impl Fn(&S) -> K) -> Cycle
+where
+    S: Clone,
+    K: Eq,
+{
+    let mut detector = CycleDetector::new(key(initial));
+    let mut hare = initial.clone();
+    let length = loop {
+        step(&mut hare);
+        if let Some(length) = detector.observe(key(&hare)) {
+            break length;
+        }
+    };
+
+    Cycle {
+        start: cycle_start(initial, step, key, length),
+        length,
+    }
+}
+
+/// The number of steps from `initial` to the first state in a cycle of the given `length`, e.g. one found with a
+/// [`CycleDetector`]. Moves two states a cycle's length apart forward together, until they match.
+pub fn cycle_start<S, K>(
+    initial: &S,
+    mut step: impl FnMut(&mut S),
+    key: impl Fn(&S) -> K,
+    length: usize,
+) -> usize
+where
+    S: Clone,
+    K: Eq,
+{
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        step(&mut hare);
+    }
+
+    let mut start = 0;
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        start += 1;
+    }
+
+    start
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::cycle::{cycle_start, find_cycle, Cycle, CycleDetector};
+
+    #[test]
+    fn can_find_cycle() {
+        // 0, 1, 2, 3, 4, 5, 6, 7, 3, ...
+        let cycle = find_cycle(&0, |n| *n = if *n == 7 { 3 } else { *n + 1 }, |&n| n);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 3,
+                length: 5
+            }
+        );
+        assert_eq!(cycle.to_string(), "repeats every 5 steps from step 3");
+
+        // Squaring modulo 23 from 2: 2, 4, 16, 3, 9, 12, 6, 13, 8, 18, 2, ...
+        let cycle = find_cycle(&2u32, |n| *n = *n * *n % 23, |&n| n);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 0,
+                length: 10
+            }
+        );
+
+        // A state that stops changing: 10, 5, 2, 1, 1, ...
+        let cycle = find_cycle(&10, |n| *n = (*n / 2).max(1), |&n| n);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 3,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn can_compare_by_key() {
+        // The step count is ignored, so the cycle is just the position going round 0..6
+        let cycle = find_cycle(
+            &(0, 0),
+            |(pos, steps)| {
+                *pos = (*pos + 1) % 6;
+                *steps += 1;
+            },
+            |&(pos, _)| pos,
+        );
+
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 0,
+                length: 6
+            }
+        );
+    }
+
+    #[test]
+    fn can_detect_cycles_a_step_at_a_time() {
+        // 0, 1, 2, 3, 4, 5, 6, 7, 3, ...
+        let step = |n: &mut usize| *n = if *n == 7 { 3 } else { *n + 1 };
+        let mut detector = CycleDetector::new(0);
+        let mut n = 0;
+        let length = loop {
+            step(&mut n);
+            if let Some(length) = detector.observe(n) {
+                break length;
+            }
+        };
+
+        assert_eq!(length, 5);
+        assert_eq!(cycle_start(&0, step, |&n| n, length), 3);
+    }
+}
@@ -0,0 +1,357 @@
+//! A generic implementation of Dijkstra's shortest path algorithm. [`crate::year_2021::day_15`] and
+//! [`crate::year_2021::day_23`] both started with their own copy of the [`BinaryHeap`] example from the standard
+//! library docs, this replaces them with one version that works for any node type, with the graph described by a
+//! `neighbours` function. [`dijkstra_by_key_with_stats`] also counts what happened to the priority queue, see
+//! [`QueueStats`], to give some feedback when trying out ways to prune a search.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// An entry in the priority queue. Only the cost and the order the entry was added are compared, so nodes don't need
+/// to implement [`Ord`]. Ties are broken by expanding the node that was discovered first.
+struct QueueEntry<N> {
+    cost: usize,
+    sequence: usize,
+    node: N,
+}
+
+impl<N> QueueEntry<N> {
+    /// Rust's [`BinaryHeap`] is a max-heap, so reverse both keys to pop the cheapest, then earliest, entry
+    fn key(&self) -> (Reverse<usize>, Reverse<usize>) {
+        (Reverse(self.cost), Reverse(self.sequence))
+    }
+}
+
+impl<N> PartialEq for QueueEntry<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<N> Eq for QueueEntry<N> {}
+
+impl<N> Ord for QueueEntry<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl<N> PartialOrd for QueueEntry<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Counts of what happened to the priority queue during a search
+#[derive(Eq, PartialEq, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct QueueStats {
+    /// Entries added to the queue, including the start
+    pub pushed: usize,
+    /// Entries taken off the queue, including the goal
+    pub popped: usize,
+    /// Entries taken off the queue that were skipped, as a cheaper route to the same key had already been expanded
+    pub skipped: usize,
+    /// The most entries the queue held at once
+    pub max_size: usize,
+}
+
+/// Find the lowest total cost from `start` to any node that satisfies `is_goal`. `neighbours` returns the nodes
+/// reachable from a node, with the cost of each edge. Returns `None` if no goal can be reached.
+pub fn dijkstra<N, I>(
+    start: N,
+    is_goal: impl Fn(&N) -> bool,
+    neighbours: impl FnMut(&N) -> I,
+) -> Option<usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    dijkstra_by_key(start, is_goal, neighbours, N::clone)
+}
+
+/// As [`dijkstra`], but the best known cost is recorded against `key(node)` rather than the node itself. Nodes that
+/// share a key are treated as the same node, so this can be used to merge states that are known to have the same
+/// cost to reach a goal, e.g. mirror images of each other. Only the first node expanded for each key is searched.
+pub fn dijkstra_by_key<N, K, I>(
+    start: N,
+    is_goal: impl Fn(&N) -> bool,
+    neighbours: impl FnMut(&N) -> I,
+    key: impl Fn(&N) -> K,
+) -> Option<usize>
+where
+    K: Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    dijkstra_by_key_with_stats(start, is_goal, neighbours, key).0
+}
+
+/// As [`dijkstra_by_key`], but also returns the [`QueueStats`] for the search
+pub fn dijkstra_by_key_with_stats<N, K, I>(
+    start: N,
+    is_goal: impl Fn(&N) -> bool,
+    neighbours: impl FnMut(&N) -> I,
+    key: impl Fn(&N) -> K,
+) -> (Option<usize>, QueueStats)
+where
+    K: Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    let mut stats = QueueStats::default();
+    let result =
+        search(start, is_goal, neighbours, &key, |_, _| {}, &mut stats).map(|(_, cost, _)| cost);
+    (result, stats)
+}
+
+/// As [`dijkstra_by_key`], but returns the route taken rather than just its cost. Each step is a node, and the total
+/// cost to reach it, from `start` with a cost of `0`, to the goal that was reached with the cost [`dijkstra_by_key`]
+/// would return.
+pub fn dijkstra_path_by_key<N, K, I>(
+    start: N,
+    is_goal: impl Fn(&N) -> bool,
+    neighbours: impl FnMut(&N) -> I,
+    key: impl Fn(&N) -> K,
+) -> Option<Vec<(N, usize)>>
+where
+    N: Clone,
+    K: Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    // The node each key was last reached from with an improved cost, i.e. its parent on the cheapest route
+    let mut parents: HashMap<K, N> = HashMap::new();
+    let (goal, _, dist) = search(
+        start,
+        is_goal,
+        neighbours,
+        &key,
+        |parent, child| {
+            parents.insert(key(child), parent.clone());
+        },
+        &mut QueueStats::default(),
+    )?;
+
+    // Walk back from the goal until reaching the start, which is the only node without a parent
+    let mut path = Vec::new();
+    let mut next = Some(goal);
+    while let Some(node) = next {
+        let node_key = key(&node);
+        next = parents.remove(&node_key);
+        path.push((node, dist[&node_key]));
+    }
+    path.reverse();
+
+    Some(path)
+}
+
+/// The search shared by [`dijkstra_by_key`] and [`dijkstra_path_by_key`]. `improved` is called with the node being
+/// expanded, and a neighbour whose best known cost it has just improved. Returns the goal reached, its cost, and the
+/// best known cost for every key that was discovered. What happens to the queue is counted in `stats`.
+fn search<N, K, I>(
+    start: N,
+    is_goal: impl Fn(&N) -> bool,
+    mut neighbours: impl FnMut(&N) -> I,
+    key: &impl Fn(&N) -> K,
+    mut improved: impl FnMut(&N, &N),
+    stats: &mut QueueStats,
+) -> Option<(N, usize, HashMap<K, usize>)>
+where
+    K: Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    let mut heap = BinaryHeap::new();
+    let mut dist: HashMap<K, usize> = HashMap::new();
+    let mut sequence = 0;
+
+    dist.insert(key(&start), 0);
+    heap.push(QueueEntry {
+        cost: 0,
+        sequence,
+        node: start,
+    });
+    stats.pushed += 1;
+    stats.max_size = 1;
+
+    while let Some(QueueEntry { cost, node, .. }) = heap.pop() {
+        stats.popped += 1;
+        if is_goal(&node) {
+            return Some((node, cost, dist));
+        }
+
+        // A cheaper route to this node has already been expanded
+        if cost > *dist.get(&key(&node)).unwrap_or(&usize::MAX) {
+            stats.skipped += 1;
+            continue;
+        }
+
+        for (next, edge_cost) in neighbours(&node) {
+            let next_cost = cost + edge_cost;
+            let is_improvement = match dist.entry(key(&next)) {
+                Entry::Occupied(mut entry) if next_cost < *entry.get() => {
+                    entry.insert(next_cost);
+                    true
+                }
+                Entry::Occupied(_) => false,
+                Entry::Vacant(entry) => {
+                    entry.insert(next_cost);
+                    true
+                }
+            };
+
+            if is_improvement {
+                improved(&node, &next);
+                sequence += 1;
+                heap.push(QueueEntry {
+                    cost: next_cost,
+                    sequence,
+                    node: next,
+                });
+                stats.pushed += 1;
+            }
+        }
+        stats.max_size = stats.max_size.max(heap.len());
+    }
+
+    // if we exhaust the reachable nodes without reaching a goal, there isn't a solution
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::dijkstra::{
+        dijkstra, dijkstra_by_key, dijkstra_by_key_with_stats, dijkstra_path_by_key, QueueStats,
+    };
+
+    /// A small weighted graph as an adjacency list
+    fn sample_graph(node: &usize) -> Vec<(usize, usize)> {
+        match node {
+            0 => vec![(1, 7), (2, 9), (5, 14)],
+            1 => vec![(0, 7), (2, 10), (3, 15)],
+            2 => vec![(0, 9), (1, 10), (3, 11), (5, 2)],
+            3 => vec![(1, 15), (2, 11), (4, 6)],
+            4 => vec![(3, 6), (5, 9)],
+            5 => vec![(0, 14), (2, 2), (4, 9)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn can_find_shortest_path() {
+        assert_eq!(dijkstra(0, |&n| n == 4, sample_graph), Some(20));
+        assert_eq!(dijkstra(0, |&n| n == 3, sample_graph), Some(20));
+        assert_eq!(dijkstra(0, |&n| n == 0, sample_graph), Some(0));
+        // The first goal reached is the cheapest
+        assert_eq!(dijkstra(0, |&n| n == 3 || n == 5, sample_graph), Some(11));
+    }
+
+    #[test]
+    fn unreachable_goals_return_none() {
+        assert_eq!(dijkstra(0, |&n| n == 6, sample_graph), None);
+        assert_eq!(dijkstra(6, |&n| n == 0, sample_graph), None);
+    }
+
+    #[test]
+    fn ties_are_expanded_in_discovery_order() {
+        let mut expanded = Vec::new();
+        let result = dijkstra(
+            'S',
+            |&n| n == 'G',
+            |&n| {
+                expanded.push(n);
+                match n {
+                    'S' => vec![('C', 1), ('A', 1), ('B', 1)],
+                    'A' | 'B' | 'C' => vec![('G', 5)],
+                    _ => vec![],
+                }
+            },
+        );
+
+        assert_eq!(result, Some(6));
+        // A, B and C all cost 1, so are expanded in the order S listed them
+        assert_eq!(expanded, vec!['S', 'C', 'A', 'B']);
+    }
+
+    #[test]
+    fn can_search_infinite_graphs() {
+        // Steps of 3 or 5 along an unbounded line, only stops because the goal is reachable
+        let result = dijkstra(
+            0isize,
+            |&n| n == 7,
+            |&n| [(n + 3, 1), (n - 5, 1), (n + 5, 1), (n - 3, 1)],
+        );
+
+        // 7 = 5 + 5 - 3
+        assert_eq!(result, Some(3));
+    }
+
+    #[test]
+    fn nodes_sharing_a_key_are_merged() {
+        // Distances along a line where the cost only depends on how far from zero a node is
+        let mut expanded = Vec::new();
+        let neighbours = |&n: &isize| {
+            expanded.push(n);
+            [(n - 1, 1), (n + 1, 1)]
+        };
+        let result = dijkstra_by_key(0isize, |&n| n.abs() == 3, neighbours, |n| n.abs());
+
+        assert_eq!(result, Some(3));
+        // -1 and 1 share a key, so only one of each pair is expanded
+        assert_eq!(expanded, vec![0, -1, -2]);
+    }
+
+    #[test]
+    fn can_count_queue_operations() {
+        let (result, stats) = dijkstra_by_key_with_stats(0, |&n| n == 4, sample_graph, |&n| n);
+        assert_eq!(result, Some(20));
+        // 2 and 5 are found again more cheaply, via 2 and via 2 then 5, leaving stale entries that are skipped when
+        // they come off the queue
+        assert_eq!(
+            stats,
+            QueueStats {
+                pushed: 8,
+                popped: 7,
+                skipped: 1,
+                max_size: 4,
+            }
+        );
+        assert_eq!(stats.pushed - stats.popped, 1);
+
+        let (result, stats) = dijkstra_by_key_with_stats(0, |&n| n == 0, sample_graph, |&n| n);
+        assert_eq!(result, Some(0));
+        assert_eq!(
+            stats,
+            QueueStats {
+                pushed: 1,
+                popped: 1,
+                skipped: 0,
+                max_size: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn can_find_the_route() {
+        assert_eq!(
+            dijkstra_path_by_key(0, |&n| n == 4, sample_graph, |&n| n),
+            Some(vec![(0, 0), (2, 9), (5, 11), (4, 20)])
+        );
+        assert_eq!(
+            dijkstra_path_by_key(0, |&n| n == 0, sample_graph, |&n| n),
+            Some(vec![(0, 0)])
+        );
+        assert_eq!(
+            dijkstra_path_by_key(0, |&n| n == 6, sample_graph, |&n| n),
+            None
+        );
+
+        // With merged keys each step is a node that was actually reached, not just one sharing its key
+        let route = dijkstra_path_by_key(
+            0isize,
+            |&n| n == -3,
+            |&n| [(n - 1, 1), (n + 1, 1)],
+            |n| n.abs(),
+        );
+        assert_eq!(route, Some(vec![(0, 0), (-1, 1), (-2, 2), (-3, 3)]));
+    }
+}
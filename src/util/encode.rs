@@ -0,0 +1,352 @@
+//! Compact encodings for the large intermediate states some days produce, e.g. [`crate::year_2021::day_19`]'s aligned
+//! beacons saved by [`crate::util::cache`], the pixels of a [`crate::year_2021::day_20`] image, or the corners of
+//! [`crate::year_2021::day_22`]'s cuboids. Written out as JSON these are mostly punctuation and repeated digits.
+//!
+//! - [`run_lengths`] collapses repeated values, which suits images, where the same pixel is often repeated along a row.
+//!   [`encode_bits`] packs those runs for a list of `bool`s.
+//! - [`encode_points`] stores a list of points as the difference of each coordinate from the one before as a
+//!   [varint](https://developers.google.com/protocol-buffers/docs/encoding#varints), so that nearby points take a
+//!   byte or two each. Sorting the points first keeps the differences small.
+//! - [`to_text`] turns the bytes into base64, as the cache is kept as text, with [`from_text`] to reverse it.
+
+use std::fmt::{Display, Formatter};
+
+/// The characters used by [`to_text`], the URL safe base64 alphabet, which doesn't need escaping in JSON strings
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The ways decoding can fail, e.g. for a cache entry written by a different version of the code
+#[derive(Eq, PartialEq, Debug)]
+pub enum DecodeError {
+    /// The bytes ran out part way through a value
+    UnexpectedEnd,
+    /// A varint had more bytes than fit in 64 bits
+    Overflow,
+    /// A decoded coordinate was too large to fit in an `isize`
+    CoordinateOverflow,
+    /// There were bytes left over after the declared number of values
+    TrailingBytes(usize),
+    /// The text contained a character that isn't in the base64 alphabet
+    InvalidCharacter(char),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => write!(f, "unexpected end of encoded bytes"),
+            DecodeError::Overflow => write!(f, "varint is too large for 64 bits"),
+            DecodeError::CoordinateOverflow => write!(f, "coordinate is too large for an isize"),
+            DecodeError::TrailingBytes(count) => write!(f, "{} bytes left after decoding", count),
+            DecodeError::InvalidCharacter(c) => write!(f, "'{}' is not a base64 character", c),
+        }
+    }
+}
+
+/// Each run of equal values, and how many times it repeats, in order
+pub fn run_lengths<T: PartialEq + Clone>(values: &[T]) -> Vec<(T, usize)> {
+    let mut runs: Vec<(T, usize)> = Vec::new();
+    for value in values {
+        match runs.last_mut() {
+            Some((last, count)) if last == value => *count += 1,
+            _ => runs.push((value.clone(), 1)),
+        }
+    }
+
+    runs
+}
+
+/// The values [`run_lengths`] was given, from its runs
+pub fn expand_runs<T: Clone>(runs: &[(T, usize)]) -> Vec<T> {
+    runs.iter()
+        .flat_map(|(value, count)| std::iter::repeat_n(value.clone(), *count))
+        .collect()
+}
+
+/// Append `value` as a varint, seven bits to a byte starting with the lowest, with the top bit set on every byte but
+/// the last
+pub fn write_varint(value: u64, out: &mut Vec<u8>) {
+    let mut value = value;
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Read the varint starting at `*pos`, moving `pos` past it
+pub fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u64, DecodeError> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*pos).ok_or(DecodeError::UnexpectedEnd)?;
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte < 0x80 {
+            return Ok(value);
+        }
+    }
+
+    Err(DecodeError::Overflow)
+}
+
+/// Map signed values to unsigned so that small negative numbers are small too, `0, -1, 1, -2, ...` to `0, 1, 2, 3, ...`
+fn zigzag(value: isize) -> u64 {
+    ((value << 1) ^ (value >> (isize::BITS - 1))) as u64
+}
+
+/// Reverse [`zigzag`]
+fn unzigzag(value: u64) -> isize {
+    (value >> 1) as isize ^ -((value & 1) as isize)
+}
+
+/// A list of `bool`s as the lengths of alternating runs, starting with a run of `false`, which is empty if the first
+/// value is `true`
+pub fn encode_bits(bits: &[bool]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_varint(bits.len() as u64, &mut out);
+
+    let mut current = false;
+    for (value, count) in run_lengths(bits) {
+        if value != current {
+            write_varint(0, &mut out);
+        }
+        write_varint(count as u64, &mut out);
+        current = !value;
+    }
+
+    out
+}
+
+/// Reverse [`encode_bits`]
+pub fn decode_bits(bytes: &[u8]) -> Result<Vec<bool>, DecodeError> {
+    let mut pos = 0;
+    let len = read_varint(bytes, &mut pos)? as usize;
+    // The length is untrusted, so only reserve what the bytes could reasonably hold up front
+    let mut bits = Vec::with_capacity(len.min(8 * bytes.len()));
+
+    let mut current = false;
+    while bits.len() < len {
+        let count = read_varint(bytes, &mut pos)? as usize;
+        bits.extend(std::iter::repeat_n(current, count.min(len - bits.len())));
+        current = !current;
+    }
+
+    finish(bytes, pos, bits)
+}
+
+/// A list of points with `N` coordinates each, as the difference of each coordinate from the same coordinate of the
+/// previous point, starting from the origin. The points are kept in the order given.
+pub fn encode_points<const N: usize>(points: &[[isize; N]]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_varint(points.len() as u64, &mut out);
+
+    let mut previous = [0; N];
+    for point in points {
+        for axis in 0..N {
+            write_varint(zigzag(point[axis] - previous[axis]), &mut out);
+        }
+        previous = *point;
+    }
+
+    out
+}
+
+/// Reverse [`encode_points`]
+pub fn decode_points<const N: usize>(bytes: &[u8]) -> Result<Vec<[isize; N]>, DecodeError> {
+    let mut pos = 0;
+    let len = read_varint(bytes, &mut pos)? as usize;
+    let mut points = Vec::with_capacity(len.min(bytes.len()));
+
+    let mut previous = [0isize; N];
+    for _ in 0..len {
+        let mut point = [0; N];
+        for axis in 0..N {
+            point[axis] = previous[axis]
+                .checked_add(unzigzag(read_varint(bytes, &mut pos)?))
+                .ok_or(DecodeError::CoordinateOverflow)?;
+        }
+        points.push(point);
+        previous = point;
+    }
+
+    finish(bytes, pos, points)
+}
+
+/// Return the decoded `values` if all the bytes were used
+fn finish<T>(bytes: &[u8], pos: usize, values: T) -> Result<T, DecodeError> {
+    match bytes.len() - pos {
+        0 => Ok(values),
+        left => Err(DecodeError::TrailingBytes(left)),
+    }
+}
+
+/// The bytes as base64 text, without padding
+pub fn to_text(bytes: &[u8]) -> String {
+    bytes
+        .chunks(3)
+        .flat_map(|chunk| {
+            let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+                group | (byte as u32) << (16 - 8 * i)
+            });
+
+            (0..=chunk.len())
+                .map(move |i| ALPHABET[(group >> (18 - 6 * i)) as usize & 0x3f] as char)
+        })
+        .collect()
+}
+
+/// Reverse [`to_text`]
+pub fn from_text(text: &str) -> Result<Vec<u8>, DecodeError> {
+    let sextets = text
+        .chars()
+        .map(|c| {
+            ALPHABET
+                .iter()
+                .position(|&letter| letter as char == c)
+                .ok_or(DecodeError::InvalidCharacter(c))
+        })
+        .collect::<Result<Vec<usize>, DecodeError>>()?;
+
+    // A group of four characters is three bytes, and a partial group of n characters is n - 1 bytes
+    if sextets.len() % 4 == 1 {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+
+    Ok(sextets
+        .chunks(4)
+        .flat_map(|chunk| {
+            let group = chunk.iter().enumerate().fold(0u32, |group, (i, &sextet)| {
+                group | (sextet as u32) << (18 - 6 * i)
+            });
+
+            (0..chunk.len() - 1).map(move |i| (group >> (16 - 8 * i)) as u8)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::encode::{
+        decode_bits, decode_points, encode_bits, encode_points, expand_runs, from_text,
+        read_varint, run_lengths, to_text, unzigzag, write_varint, zigzag, DecodeError,
+    };
+
+    #[test]
+    fn can_round_trip_runs() {
+        let values = vec!['#', '#', '.', '#', '.', '.', '.'];
+        let runs = run_lengths(&values);
+
+        assert_eq!(runs, vec![('#', 2), ('.', 1), ('#', 1), ('.', 3)]);
+        assert_eq!(expand_runs(&runs), values);
+        assert_eq!(run_lengths::<u8>(&[]), vec![]);
+    }
+
+    #[test]
+    fn can_round_trip_varints() {
+        for value in [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut bytes = Vec::new();
+            write_varint(value, &mut bytes);
+
+            let mut pos = 0;
+            assert_eq!(read_varint(&bytes, &mut pos), Ok(value));
+            assert_eq!(pos, bytes.len());
+        }
+
+        let mut bytes = Vec::new();
+        write_varint(300, &mut bytes);
+        assert_eq!(bytes, vec![0b1010_1100, 0b0000_0010]);
+
+        assert_eq!(
+            read_varint(&[0x80], &mut 0),
+            Err(DecodeError::UnexpectedEnd)
+        );
+        assert_eq!(read_varint(&[0xff; 10], &mut 0), Err(DecodeError::Overflow));
+    }
+
+    #[test]
+    fn can_zigzag() {
+        assert_eq!([0, -1, 1, -2, 2].map(zigzag), [0, 1, 2, 3, 4]);
+        for value in [0, 5, -5, isize::MAX, isize::MIN] {
+            assert_eq!(unzigzag(zigzag(value)), value);
+        }
+    }
+
+    #[test]
+    fn can_round_trip_bits() {
+        for bits in [
+            vec![],
+            vec![true],
+            vec![false, false, true, true, true, false],
+            vec![true; 1000],
+        ] {
+            assert_eq!(decode_bits(&encode_bits(&bits)), Ok(bits));
+        }
+
+        // Length, then runs of 2 off, 3 on, 1 off
+        assert_eq!(
+            encode_bits(&[false, false, true, true, true, false]),
+            vec![6, 2, 3, 1]
+        );
+        assert_eq!(encode_bits(&[true, false]), vec![2, 0, 1, 1]);
+        assert_eq!(decode_bits(&[4, 2]), Err(DecodeError::UnexpectedEnd));
+        let mut huge = Vec::new();
+        write_varint(u64::MAX >> 1, &mut huge);
+        assert_eq!(decode_bits(&huge), Err(DecodeError::UnexpectedEnd));
+    }
+
+    #[test]
+    fn can_round_trip_points() {
+        let points = vec![
+            [-618, -824, -621],
+            [-537, -823, -458],
+            [-447, -329, 318],
+            [0, 0, 0],
+        ];
+        let bytes = encode_points(&points);
+
+        assert_eq!(decode_points::<3>(&bytes), Ok(points.clone()));
+        // Smaller than the JSON for the same points, even as text
+        let json = |points: &[[isize; 3]]| serde_json::to_string(points).unwrap().len();
+        assert!(to_text(&bytes).len() < json(&points));
+        // And much smaller if the points are close together
+        let line: Vec<[isize; 3]> = (0..100).map(|i| [500 + i, 2 * i - 300, -i]).collect();
+        let bytes = encode_points(&line);
+        assert_eq!(decode_points::<3>(&bytes), Ok(line.clone()));
+        assert!(to_text(&bytes).len() * 3 < json(&line));
+
+        assert_eq!(encode_points::<2>(&[[1, -1], [1, -2]]), vec![2, 2, 1, 0, 1]);
+        assert_eq!(decode_points::<2>(&[]), Err(DecodeError::UnexpectedEnd));
+        let mut far = Vec::new();
+        write_varint(2, &mut far);
+        write_varint(zigzag(isize::MAX), &mut far);
+        write_varint(zigzag(1), &mut far);
+        assert_eq!(
+            decode_points::<1>(&far),
+            Err(DecodeError::CoordinateOverflow)
+        );
+        assert_eq!(
+            decode_points::<2>(&[1, 2, 1, 0]),
+            Err(DecodeError::TrailingBytes(1))
+        );
+    }
+
+    #[test]
+    fn can_round_trip_text() {
+        assert_eq!(to_text(b""), "");
+        assert_eq!(to_text(b"f"), "Zg");
+        assert_eq!(to_text(b"fo"), "Zm8");
+        assert_eq!(to_text(b"foo"), "Zm9v");
+        assert_eq!(to_text(b"foobar"), "Zm9vYmFy");
+        assert_eq!(to_text(&[0xfb, 0xff]), "-_8");
+
+        for bytes in [
+            vec![],
+            vec![0],
+            vec![255, 254],
+            (0..=255).collect::<Vec<u8>>(),
+        ] {
+            assert_eq!(from_text(&to_text(&bytes)), Ok(bytes));
+        }
+
+        assert_eq!(from_text("Zm9v!"), Err(DecodeError::InvalidCharacter('!')));
+        assert_eq!(from_text("Zm9vY"), Err(DecodeError::UnexpectedEnd));
+    }
+}
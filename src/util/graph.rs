@@ -0,0 +1,272 @@
+//! A graph stored as an arena of labelled nodes, each with a list of weighted edges to other nodes.
+//! [`crate::year_2021::day_12`] started with its own version, a `Vec` of caves that each held the indices of the caves
+//! they link to, and a map from each cave's label to its index alongside. [`Graph`] is that representation pulled out
+//! so that later graph puzzles have somewhere to start. Nodes are added by label, and then referred to by their index
+//! in the arena, a [`NodeId`], which is cheap to copy and store in sets such as day 12's visited caves. Edges are
+//! directed, so an undirected graph adds each edge both ways, and have a weight, which can be `()` if there isn't one.
+//!
+//! [`crate::year_2021::day_15`] and [`crate::year_2021::day_23`] search graphs that are generated as the search goes,
+//! the cave expanded to 25 tiles, and every arrangement of the amphipods. Building those up front would need far more
+//! memory, so they stay on [`crate::util::dijkstra`], which [`Graph::dijkstra`] also uses, so the search is shared.
+
+use crate::util::dijkstra::dijkstra;
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// The index of a node in a [`Graph`]. Nodes are never removed, so these stay valid.
+pub type NodeId = usize;
+
+/// A graph of nodes labelled with `N`, and directed edges with weights of type `E`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Graph<N: Eq + Hash, E = ()> {
+    /// The label of each node, indexed by [`NodeId`]
+    labels: Vec<N>,
+    /// The [`NodeId`] for each label
+    ids: HashMap<N, NodeId>,
+    /// The edges from each node, in the order they were added, indexed by [`NodeId`]
+    edges: Vec<Vec<(NodeId, E)>>,
+}
+
+impl<N: Eq + Hash, E> Default for Graph<N, E> {
+    fn default() -> Self {
+        Graph {
+            labels: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N: Eq + Hash + Clone, E> Graph<N, E> {
+    /// Create an empty graph
+    pub fn new() -> Graph<N, E> {
+        Graph::default()
+    }
+
+    /// The id of the node with the given label, adding a node with no edges if there isn't one yet
+    pub fn add_node(&mut self, label: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&label) {
+            return id;
+        }
+
+        let id = self.labels.len();
+        self.labels.push(label.clone());
+        self.ids.insert(label, id);
+        self.edges.push(Vec::new());
+        id
+    }
+
+    /// The id of the node with the given label, if it has been added
+    pub fn id<Q>(&self, label: &Q) -> Option<NodeId>
+    where
+        N: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.ids.get(label).copied()
+    }
+
+    /// The label of a node. Panics if there is no such node.
+    pub fn label(&self, id: NodeId) -> &N {
+        &self.labels[id]
+    }
+
+    /// The labels of every node, in order of [`NodeId`]
+    pub fn labels(&self) -> impl Iterator<Item = &N> + '_ {
+        self.labels.iter()
+    }
+
+    /// The number of nodes
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// True if no nodes have been added
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Add an edge from one node to another. Returns false, and leaves the graph unchanged, if there is already an
+    /// edge between them in that direction.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: E) -> bool {
+        if self.has_edge(from, to) {
+            return false;
+        }
+
+        self.edges[from].push((to, weight));
+        true
+    }
+
+    /// Remove the edge from one node to another, returning its weight, or `None` if there was no such edge. The nodes
+    /// are kept, even if they no longer have any edges.
+    pub fn remove_edge(&mut self, from: NodeId, to: NodeId) -> Option<E> {
+        let edges = self.edges.get_mut(from)?;
+        let index = edges.iter().position(|&(next, _)| next == to)?;
+
+        Some(edges.remove(index).1)
+    }
+
+    /// True if there is an edge from one node to the other
+    pub fn has_edge(&self, from: NodeId, to: NodeId) -> bool {
+        self.edges
+            .get(from)
+            .is_some_and(|edges| edges.iter().any(|&(next, _)| next == to))
+    }
+
+    /// The nodes that a node has an edge to, and the weight of each edge, in the order they were added
+    pub fn neighbours(&self, id: NodeId) -> impl Iterator<Item = (NodeId, &E)> + '_ {
+        self.edges[id].iter().map(|(next, weight)| (*next, weight))
+    }
+
+    /// The nodes that can be reached from `start`, in breadth first order, i.e. nearest first by number of edges
+    pub fn bfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        let mut order = Vec::new();
+
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for (next, _) in self.neighbours(id) {
+                if seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        order
+    }
+
+    /// The nodes that can be reached from `start`, in depth first order, each node's neighbours being followed in the
+    /// order their edges were added
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = HashSet::new();
+        let mut stack = vec![start];
+        let mut order = Vec::new();
+
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+
+            order.push(id);
+            // Reversed so that the first neighbour is on the top of the stack
+            let next: Vec<NodeId> = self.neighbours(id).map(|(next, _)| next).collect();
+            stack.extend(next.into_iter().rev().filter(|next| !seen.contains(next)));
+        }
+
+        order
+    }
+}
+
+impl<N: Eq + Hash + Clone, E: Copy + Into<usize>> Graph<N, E> {
+    /// The lowest total weight of the edges on a route from `start` to `goal`, see [`dijkstra`], or `None` if `goal`
+    /// can't be reached
+    pub fn dijkstra(&self, start: NodeId, goal: NodeId) -> Option<usize> {
+        dijkstra(
+            start,
+            |&id| id == goal,
+            |&id| {
+                self.neighbours(id)
+                    .map(|(next, &weight)| (next, weight.into()))
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::graph::Graph;
+
+    /// The same weighted graph as the [`crate::util::dijkstra`] tests, with the edges both ways
+    fn sample_graph() -> Graph<char, u8> {
+        let mut graph = Graph::new();
+        for (a, b, weight) in [
+            ('a', 'b', 7),
+            ('a', 'c', 9),
+            ('a', 'f', 14),
+            ('b', 'c', 10),
+            ('b', 'd', 15),
+            ('c', 'd', 11),
+            ('c', 'f', 2),
+            ('d', 'e', 6),
+            ('e', 'f', 9),
+        ] {
+            let (a, b) = (graph.add_node(a), graph.add_node(b));
+            graph.add_edge(a, b, weight);
+            graph.add_edge(b, a, weight);
+        }
+        graph.add_node('g');
+
+        graph
+    }
+
+    #[test]
+    fn can_add_nodes_by_label() {
+        let mut graph: Graph<String> = Graph::new();
+        assert!(graph.is_empty());
+
+        let start = graph.add_node("start".to_string());
+        let end = graph.add_node("end".to_string());
+        assert_eq!(graph.add_node("start".to_string()), start);
+
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.id("end"), Some(end));
+        assert_eq!(graph.id("nowhere"), None);
+        assert_eq!(graph.label(start), "start");
+        assert_eq!(
+            graph.labels().collect::<Vec<&String>>(),
+            vec!["start", "end"]
+        );
+    }
+
+    #[test]
+    fn can_edit_edges() {
+        let mut graph: Graph<char, u8> = Graph::new();
+        let (a, b, c) = (
+            graph.add_node('a'),
+            graph.add_node('b'),
+            graph.add_node('c'),
+        );
+
+        assert!(graph.add_edge(a, b, 3));
+        assert!(graph.add_edge(a, c, 1));
+        assert!(!graph.add_edge(a, b, 5));
+        assert!(graph.has_edge(a, b));
+        // Edges are directed
+        assert!(!graph.has_edge(b, a));
+        assert_eq!(
+            graph.neighbours(a).collect::<Vec<_>>(),
+            vec![(b, &3), (c, &1)]
+        );
+
+        assert_eq!(graph.remove_edge(a, b), Some(3));
+        assert_eq!(graph.remove_edge(a, b), None);
+        assert_eq!(graph.remove_edge(b, a), None);
+        assert_eq!(graph.neighbours(a).collect::<Vec<_>>(), vec![(c, &1)]);
+        // The node is kept
+        assert_eq!(graph.id(&'b'), Some(b));
+    }
+
+    #[test]
+    fn can_find_shortest_path() {
+        let graph = sample_graph();
+        let id = |label| graph.id(&label).unwrap();
+
+        assert_eq!(graph.dijkstra(id('a'), id('e')), Some(20));
+        assert_eq!(graph.dijkstra(id('a'), id('d')), Some(20));
+        assert_eq!(graph.dijkstra(id('a'), id('a')), Some(0));
+        assert_eq!(graph.dijkstra(id('a'), id('g')), None);
+    }
+
+    #[test]
+    fn can_traverse_in_order() {
+        let graph = sample_graph();
+        let labels =
+            |order: Vec<usize>| -> String { order.into_iter().map(|id| graph.label(id)).collect() };
+        let a = graph.id(&'a').unwrap();
+
+        assert_eq!(labels(graph.bfs(a)), "abcfde");
+        assert_eq!(labels(graph.dfs(a)), "abcdef");
+        assert_eq!(labels(graph.bfs(graph.id(&'g').unwrap())), "g");
+    }
+}
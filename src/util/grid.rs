@@ -1,5 +1,5 @@
-/// A representation of a 2D grid of u8s. Originally implemented for [`crate::day_9`], another grid was needed for
-/// [`crate::day_11`] and so common methods were extracted to this shared module
+/// A representation of a 2D grid of u8s. Originally implemented for [`crate::year_2021::day_9`], another grid was
+/// needed for [`crate::year_2021::day_11`] and so common methods were extracted to this shared module
 #[derive(Debug, Eq, PartialEq)]
 pub struct Grid {
     /// Store the numbers in a 1D list...
@@ -8,9 +8,9 @@ pub struct Grid {
     pub width: usize,
 }
 
-impl From<String> for Grid {
+impl From<&str> for Grid {
     /// Turn the characters into digits and concatenate, caching the width
-    fn from(string: String) -> Self {
+    fn from(string: &str) -> Self {
         let mut width: usize = 0;
 
         let numbers = string
@@ -58,7 +58,7 @@ impl<'a> Iterator for GridCoords<'a> {
 
 impl Grid {
     /// Helper to abstract iterating over the whole grid
-    pub fn iter(&self) -> GridCoords {
+    pub fn iter(&self) -> GridCoords<'_> {
         GridCoords { grid: self, pos: 0 }
     }
 
@@ -105,8 +105,14 @@ impl Grid {
         self.numbers.len()
     }
 
-    /// Used by [`GridCoords::next`] and other iterators over the grid , e.g. [`Grid::iterate_and_flash`] to turn the
-    /// current iterator position into the x/y co-ordinates and the value in that cell.
+    /// True if the grid has no cells
+    pub fn is_empty(&self) -> bool {
+        self.numbers.is_empty()
+    }
+
+    /// Used by [`GridCoords::next`] and other iterators over the grid , e.g.
+    /// [`crate::year_2021::day_11::Octopuses::step`] to turn the current iterator position into the x/y co-ordinates
+    /// and the value in that cell.
     pub fn get_with_coords(&self, pos: usize) -> Option<((usize, usize), u8)> {
         let x = pos % self.width;
         let y = pos / self.width;
@@ -144,7 +150,6 @@ impl Grid {
     }
 
     /// Dump the grid to stdout - useful for visualising the grid when debugging
-    #[allow(dead_code)]
     pub fn print(&self) -> String {
         let (_, out) = self
             .iter()
@@ -170,7 +175,7 @@ impl Grid {
 
 #[cfg(test)]
 mod tests {
-    use crate::day_9::Grid;
+    use crate::util::grid::Grid;
 
     fn sample_input() -> String {
         "12345\n\
@@ -185,7 +190,7 @@ mod tests {
     fn can_print() {
         let input = sample_input();
 
-        let mut grid = Grid::from(input.clone());
+        let mut grid = Grid::from(input.as_str());
 
         assert_eq!(grid.print(), input);
 
@@ -196,7 +201,7 @@ mod tests {
 
     #[test]
     fn set_ignores_out_of_bounds() {
-        let mut grid = Grid::from(sample_input());
+        let mut grid = Grid::from(sample_input().as_str());
 
         assert_eq!(grid.set(5, 0, 9), false);
         assert_eq!(grid.set(0, 5, 9), false);
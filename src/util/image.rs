@@ -0,0 +1,313 @@
+//! A minimal writer for black and white images, so that grids of dots can be viewed outside the terminal. Supports
+//! plain [PBM](https://netpbm.sourceforge.net/doc/pbm.html) and greyscale
+//! [PNG](https://www.w3.org/TR/png/). The PNG data is zlib wrapped, but left uncompressed, which avoids needing a
+//! deflate implementation and is fine for the small images the puzzles produce.
+//!
+//! Days that produce an image accept `--output=<path>`, and the format is picked from the file extension. Writing the
+//! file is left to the runner, which sets an [`ImageWriter`] with [`set_writer`] when an output path is given.
+//!
+//! Tests can compare a rendered image against a checked-in reference with `assert_golden`. When a change to the
+//! output is intended, run the tests with `UPDATE_GOLDEN=1` to rewrite the references instead, and review the diff.
+
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+#[cfg(test)]
+use std::{env, fs};
+
+/// The eight bytes every PNG file starts with
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// The largest amount of data a single uncompressed deflate block can hold
+const MAX_STORED_BLOCK: usize = 0xffff;
+
+/// Setting this environment variable makes [`assert_golden`] update the reference images rather than checking them
+#[cfg(test)]
+const UPDATE_GOLDEN_ENV_VAR: &str = "UPDATE_GOLDEN";
+
+/// A black and white image where `true` pixels are drawn black, as they are with PBM
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Bitmap {
+    /// The width of the image in pixels
+    pub width: usize,
+    /// The height of the image in pixels
+    pub height: usize,
+    /// The pixels in rows, top to bottom
+    pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    /// Create a blank (all white) image
+    pub fn new(width: usize, height: usize) -> Bitmap {
+        Bitmap {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    /// Set the pixel at the given co-ordinates, ignoring co-ordinates outside the image
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = value;
+        }
+    }
+
+    /// Get the pixel at the given co-ordinates, pixels outside the image are white
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.pixels[y * self.width + x]
+    }
+
+    /// Encode as a plain (ASCII) PBM file
+    pub fn to_pbm(&self) -> Vec<u8> {
+        let rows: Vec<String> = (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| if self.get(x, y) { "1" } else { "0" })
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+            })
+            .collect();
+
+        format!("P1\n{} {}\n{}\n", self.width, self.height, rows.join("\n")).into_bytes()
+    }
+
+    /// Encode as an 8-bit greyscale PNG file
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // bit depth 8, greyscale, deflate, adaptive filtering, no interlace
+        header.extend([8, 0, 0, 0, 0]);
+
+        // Each row is prefixed with the filter type, 0 = none
+        let mut raw = Vec::with_capacity((self.width + 1) * self.height);
+        for y in 0..self.height {
+            raw.push(0);
+            raw.extend((0..self.width).map(|x| if self.get(x, y) { 0x00 } else { 0xff }));
+        }
+
+        let mut png = PNG_SIGNATURE.to_vec();
+        png.extend(png_chunk(b"IHDR", &header));
+        png.extend(png_chunk(b"IDAT", &zlib_stored(&raw)));
+        png.extend(png_chunk(b"IEND", &[]));
+        png
+    }
+
+    /// Encode the image for a file, using the extension to choose between PBM and PNG
+    pub fn encode(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+
+        let data = match extension.as_deref() {
+            Some("pbm") => self.to_pbm(),
+            Some("png") => self.to_png(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "Unsupported image format {}, expected .pbm or .png",
+                        path.display()
+                    ),
+                ))
+            }
+        };
+
+        Ok(data)
+    }
+}
+
+/// Saves an image the user has asked for
+pub type ImageWriter = fn(&Bitmap);
+
+/// Set once by the runner, if the user has asked for the images to be saved
+static WRITER: OnceLock<ImageWriter> = OnceLock::new();
+
+/// Pass the images the days produce to `writer` from now on. Only the first writer set is used.
+pub fn set_writer(writer: ImageWriter) {
+    let _ = WRITER.set(writer);
+}
+
+/// Save the image if the user has asked for it, see [`set_writer`]
+pub fn save_if_requested(bitmap: &Bitmap) {
+    if let Some(writer) = WRITER.get() {
+        writer(bitmap);
+    }
+}
+
+/// Check the bitmap matches the reference PBM `tests/golden/<name>.pbm`, or write it there if `UPDATE_GOLDEN` is set.
+/// PBM is used as it is plain text, so changes to the references show up in diffs.
+#[cfg(test)]
+pub fn assert_golden(name: &str, bitmap: &Bitmap) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{}.pbm", name));
+    let actual = String::from_utf8(bitmap.to_pbm()).unwrap();
+
+    if env::var_os(UPDATE_GOLDEN_ENV_VAR).is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "Couldn't read golden image {}: {}. Run with {}=1 to create it.",
+            path.display(),
+            err,
+            UPDATE_GOLDEN_ENV_VAR
+        )
+    });
+
+    assert!(
+        actual == expected,
+        "{} doesn't match the golden image {}, run with {}=1 to update it if the change is intended.\n\
+         expected:\n{}\nactual:\n{}",
+        name,
+        path.display(),
+        UPDATE_GOLDEN_ENV_VAR,
+        expected,
+        actual
+    );
+}
+
+/// Build a PNG chunk: the length, the type, the data, and a CRC of the type and data
+fn png_chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut chunk = Vec::with_capacity(data.len() + 12);
+    chunk.extend((data.len() as u32).to_be_bytes());
+    chunk.extend(chunk_type);
+    chunk.extend(data);
+    chunk.extend(crc32(&chunk[4..]).to_be_bytes());
+    chunk
+}
+
+/// Wrap the data in a zlib stream made of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // deflate with a 32K window, no preset dictionary, and a header check value that makes it a multiple of 31
+    let mut out = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = data.chunks(MAX_STORED_BLOCK).collect();
+
+    if blocks.is_empty() {
+        out.extend([0x01, 0x00, 0x00, 0xff, 0xff]);
+    }
+
+    for (i, block) in blocks.iter().enumerate() {
+        let is_final = i == blocks.len() - 1;
+        let len = block.len() as u16;
+        out.push(is_final as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(*block);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+/// The CRC-32 checksum used by PNG chunks, computed bitwise as the images are small
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(0xffffffffu32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+/// The Adler-32 checksum that ends a zlib stream
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::image::{adler32, crc32, zlib_stored, Bitmap, PNG_SIGNATURE};
+    use std::path::Path;
+
+    fn sample_bitmap() -> Bitmap {
+        let mut bitmap = Bitmap::new(3, 2);
+        bitmap.set(0, 0, true);
+        bitmap.set(2, 1, true);
+        bitmap.set(5, 5, true);
+        bitmap
+    }
+
+    #[test]
+    fn can_set_pixels() {
+        let bitmap = sample_bitmap();
+
+        assert!(bitmap.get(0, 0));
+        assert!(!bitmap.get(1, 0));
+        assert!(bitmap.get(2, 1));
+        assert!(!bitmap.get(5, 5));
+    }
+
+    #[test]
+    fn can_encode_pbm() {
+        assert_eq!(
+            String::from_utf8(sample_bitmap().to_pbm()).unwrap(),
+            "P1\n3 2\n1 0 0\n0 0 1\n"
+        );
+    }
+
+    #[test]
+    fn can_calculate_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        assert_eq!(crc32(b"IEND"), 0xae426082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+    }
+
+    #[test]
+    fn can_wrap_in_zlib() {
+        assert_eq!(
+            zlib_stored(&[1, 2, 3]),
+            vec![0x78, 0x01, 0x01, 0x03, 0x00, 0xfc, 0xff, 1, 2, 3, 0x00, 0x0d, 0x00, 0x07]
+        );
+
+        let large = vec![0; 70000];
+        let wrapped = zlib_stored(&large);
+        assert_eq!(wrapped.len(), 2 + 5 + 65535 + 5 + 4465 + 4);
+        assert_eq!(wrapped[2], 0x00);
+        assert_eq!(wrapped[2 + 5 + 65535], 0x01);
+    }
+
+    #[test]
+    fn can_encode_png() {
+        let png = sample_bitmap().to_png();
+
+        assert_eq!(png[0..8], PNG_SIGNATURE);
+        // IHDR length and type, then the width and height
+        assert_eq!(png[8..16], [0, 0, 0, 13, b'I', b'H', b'D', b'R']);
+        assert_eq!(png[16..24], [0, 0, 0, 3, 0, 0, 0, 2]);
+        // Two rows of a filter byte and three pixels, stored in a single block after the zlib header
+        assert_eq!(
+            png[41..51],
+            [0x78, 0x01, 0x01, 0x08, 0x00, 0xf7, 0xff, 0, 0x00, 0xff]
+        );
+        assert_eq!(
+            png[png.len() - 12..],
+            [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+        );
+    }
+
+    #[test]
+    fn encode_rejects_unknown_formats() {
+        let result = sample_bitmap().encode(Path::new("image.gif"));
+
+        assert!(result.is_err());
+        assert_eq!(
+            sample_bitmap().encode(Path::new("image.PBM")).unwrap(),
+            sample_bitmap().to_pbm()
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! Streaming access to line based input. The days whose input is one item per line parse it with [`parse_lines`], once
+//! [`crate::errors::check_input`] has checked the lines are well-formed. The checks need to see all of the input before
+//! any of it is solved, so the runner reads the input up front, but [`read_lines`] takes a file a line at a time, so a
+//! very large custom input only needs room for what is parsed out of it, rather than the text as well.
+
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Iterate over the lines of a file, reading it through a buffer rather than all at once. Returns an error if the file
+/// can't be opened, and panics if it can't be read part way through.
+pub fn read_lines(path: impl AsRef<Path>) -> io::Result<impl Iterator<Item = String>> {
+    let file = File::open(path)?;
+
+    Ok(BufReader::new(file)
+        .lines()
+        .map(|line| line.expect("Failed to read line")))
+}
+
+/// Parse each non-blank line as a `T`, as the lines are needed. Panics with the line number if a line doesn't parse.
+pub fn parse_lines<T, S>(lines: impl IntoIterator<Item = S>) -> impl Iterator<Item = T>
+where
+    T: FromStr,
+    T::Err: Debug,
+    S: AsRef<str>,
+{
+    lines
+        .into_iter()
+        .enumerate()
+        .filter(|(_, line)| !line.as_ref().trim().is_empty())
+        .map(|(index, line)| {
+            line.as_ref().trim().parse().unwrap_or_else(|err| {
+                panic!(
+                    "Failed to parse line {} '{}': {:?}",
+                    index + 1,
+                    line.as_ref(),
+                    err
+                )
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::input::{parse_lines, read_lines};
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn can_parse_lines() {
+        let numbers: Vec<i32> = parse_lines("199\n200\n\n-3 \n".lines()).collect();
+        assert_eq!(numbers, vec![199, 200, -3]);
+
+        let owned = vec!["1".to_string(), "2".to_string()];
+        assert_eq!(parse_lines::<u8, _>(owned).sum::<u8>(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to parse line 2 'two'")]
+    fn reports_unparsable_line() {
+        parse_lines::<i32, _>("1\ntwo\n3".lines()).for_each(drop);
+    }
+
+    #[test]
+    fn can_read_lines() {
+        let path = env::temp_dir().join("aoc-2021-read-lines-test");
+        fs::write(&path, "forward 5\ndown 5\n").unwrap();
+
+        let lines: Vec<String> = read_lines(&path).unwrap().collect();
+        assert_eq!(lines, vec!["forward 5", "down 5"]);
+
+        fs::remove_file(&path).unwrap();
+        assert!(read_lines(&path).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+//! Reads the capital letters that some puzzles, e.g. [`crate::year_2021::day_13`], draw as their answer. The letters
+//! use the usual Advent of Code font: six pixels high, mostly four wide, and separated by at least one blank column.
+//! The grid is split into characters at the blank columns, and each one is looked up in a table of known glyphs.
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// The height of every glyph in the font
+const GLYPH_HEIGHT: usize = 6;
+
+/// The known glyphs, trimmed of blank columns, drawn with `#` for a dot and `.` for a gap
+const GLYPHS: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The ways reading a grid of dots can fail
+#[derive(Eq, PartialEq, Debug)]
+pub enum OcrError {
+    /// There were no dots to read
+    Empty,
+    /// The dots don't fit the height of the font
+    WrongHeight(usize),
+    /// The character at the given position isn't in the glyph table, includes the glyph as drawn
+    UnknownGlyph(usize, String),
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "There are no dots to read"),
+            OcrError::WrongHeight(height) => write!(
+                f,
+                "Letters are {} dots high, but the dots span {} rows",
+                GLYPH_HEIGHT, height
+            ),
+            OcrError::UnknownGlyph(position, glyph) => write!(
+                f,
+                "Character {} is not a known letter:\n{}",
+                position + 1,
+                glyph
+            ),
+        }
+    }
+}
+
+/// Decode the dots as a string of capital letters. The dots don't need to start at the origin.
+pub fn read_letters(dots: &HashSet<(usize, usize)>) -> Result<String, OcrError> {
+    let min_x = dots.iter().map(|&(x, _)| x).min().ok_or(OcrError::Empty)?;
+    let max_x = dots.iter().map(|&(x, _)| x).max().ok_or(OcrError::Empty)?;
+    let min_y = dots.iter().map(|&(_, y)| y).min().ok_or(OcrError::Empty)?;
+    let max_y = dots.iter().map(|&(_, y)| y).max().ok_or(OcrError::Empty)?;
+
+    if max_y - min_y + 1 != GLYPH_HEIGHT {
+        return Err(OcrError::WrongHeight(max_y - min_y + 1));
+    }
+
+    let draw_column = |x: usize| -> Vec<char> {
+        (min_y..=max_y)
+            .map(|y| if dots.contains(&(x, y)) { '#' } else { '.' })
+            .collect()
+    };
+
+    // Group the non-blank columns into characters, splitting wherever there is a blank column
+    let mut characters: Vec<Vec<Vec<char>>> = Vec::new();
+    let mut current: Vec<Vec<char>> = Vec::new();
+    for x in min_x..=max_x {
+        let column = draw_column(x);
+        if column.contains(&'#') {
+            current.push(column);
+        } else if !current.is_empty() {
+            characters.push(current);
+            current = Vec::new();
+        }
+    }
+    characters.push(current);
+
+    characters
+        .iter()
+        .enumerate()
+        .map(|(position, columns)| {
+            let glyph = (0..GLYPH_HEIGHT)
+                .map(|y| columns.iter().map(|column| column[y]).collect::<String>())
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            GLYPHS
+                .iter()
+                .find(|(_, known)| *known == glyph)
+                .map(|&(letter, _)| letter)
+                .ok_or(OcrError::UnknownGlyph(position, glyph))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::letter_ocr::{read_letters, OcrError, GLYPHS};
+    use std::collections::HashSet;
+
+    /// Turn `#` / `.` art into a set of dots, offset from the origin
+    fn to_dots(art: &str, (dx, dy): (usize, usize)) -> HashSet<(usize, usize)> {
+        art.lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|&(_, chr)| chr == '#')
+                    .map(move |(x, _)| (x + dx, y + dy))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn can_read_each_glyph() {
+        for (letter, glyph) in GLYPHS {
+            assert_eq!(
+                read_letters(&to_dots(glyph, (0, 0))),
+                Ok(letter.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn can_read_letters() {
+        let art = "\
+###..#..#.###..####.####..##..#..#.###.
+#..#.#.#..#..#....#.#....#..#.#..#.#..#
+#..#.##...#..#...#..###..#....####.#..#
+###..#.#..###...#...#....#.##.#..#.###.
+#.#..#.#..#.#..#....#....#..#.#..#.#.#.
+#..#.#..#.#..#.####.#.....###.#..#.#..#";
+
+        assert_eq!(
+            read_letters(&to_dots(art, (0, 0))),
+            Ok("RKRZFGHR".to_string())
+        );
+        assert_eq!(
+            read_letters(&to_dots(art, (3, 7))),
+            Ok("RKRZFGHR".to_string())
+        );
+    }
+
+    #[test]
+    fn can_report_errors() {
+        assert_eq!(read_letters(&HashSet::new()), Err(OcrError::Empty));
+        assert_eq!(
+            read_letters(&to_dots("#\n#\n#", (0, 0))),
+            Err(OcrError::WrongHeight(3))
+        );
+        assert_eq!(
+            read_letters(&to_dots(
+                "####.#\n#..#.#\n#..#.#\n#..#.#\n#..#.#\n####.#",
+                (0, 0)
+            )),
+            Err(OcrError::UnknownGlyph(
+                0,
+                "####\n#..#\n#..#\n#..#\n#..#\n####".to_string()
+            ))
+        );
+    }
+}
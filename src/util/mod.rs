@@ -1 +1,18 @@
-pub(crate) mod grid;
+pub mod bitset;
+pub mod cache;
+pub mod cycle;
+pub mod dijkstra;
+pub mod encode;
+pub mod graph;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod letter_ocr;
+pub mod params;
+pub mod progress;
+pub mod random;
+pub mod render;
+pub mod repl;
+pub mod state_counts;
+pub mod stats;
+pub mod vec3;
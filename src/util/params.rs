@@ -0,0 +1,89 @@
+//! Lets the numbers that the puzzles hard-code, like how many iterations to run, be changed from the command line to
+//! explore "what if" scenarios. Each is passed as `--param <name>=<value>` or `--param=<name>=<value>`, and can be
+//! repeated. The days that support this each have an `Options` struct listing the names they accept, and fall back to
+//! the puzzle's own value for any that are missing or don't parse.
+
+use std::collections::HashMap;
+use std::env;
+use std::str::FromStr;
+
+/// The `--param` values given on the command line, by name
+#[derive(Eq, PartialEq, Debug, Default)]
+pub struct Params {
+    values: HashMap<String, String>,
+}
+
+impl Params {
+    /// Read the params from the command line arguments
+    pub fn from_args() -> Params {
+        Params::parse(env::args())
+    }
+
+    /// Collect the `name=value` pairs that follow `--param`, or are attached as `--param=name=value`. Later values
+    /// replace earlier ones with the same name.
+    pub fn parse(args: impl Iterator<Item = String>) -> Params {
+        let mut values = HashMap::new();
+        let mut args = args;
+
+        while let Some(arg) = args.next() {
+            let pair = match arg.strip_prefix("--param") {
+                Some("") => args.next(),
+                Some(attached) => attached.strip_prefix('=').map(|pair| pair.to_string()),
+                None => None,
+            };
+
+            if let Some((name, value)) = pair.as_deref().and_then(|pair| pair.split_once('=')) {
+                values.insert(name.trim().to_string(), value.trim().to_string());
+            }
+        }
+
+        Params { values }
+    }
+
+    /// The value of the named param, or `default` if it wasn't given or can't be parsed as the required type
+    pub fn get_or<T: FromStr>(&self, name: &str, default: T) -> T {
+        self.values
+            .get(name)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::params::Params;
+
+    fn args(list: &[&str]) -> impl Iterator<Item = String> {
+        list.iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn can_parse_params() {
+        let params = Params::parse(args(&[
+            "--visualise",
+            "--param",
+            "cycles=100",
+            "--param=target = 5000",
+            "--param",
+            "cycles=40",
+            "--param",
+            "broken",
+        ]));
+
+        assert_eq!(params.get_or("cycles", 10usize), 40);
+        assert_eq!(params.get_or("target", 1000usize), 5000);
+        assert_eq!(params.get_or("missing", 21usize), 21);
+        assert_eq!(params.get_or("broken", 7usize), 7);
+    }
+
+    #[test]
+    fn unparsable_values_use_the_default() {
+        let params = Params::parse(args(&["--param", "cycles=lots"]));
+
+        assert_eq!(params.get_or("cycles", 10usize), 10);
+        assert_eq!(params.get_or("cycles", "default".to_string()), "lots");
+    }
+}
@@ -0,0 +1,59 @@
+//! A hook for the slower days to report how far through they are, as [`crate::year_2021::day_19`] and
+//! [`crate::year_2021::day_23`] can run for seconds without printing anything. The heavy loops take a `&mut dyn
+//! Progress`, and call [`Progress::update`] as they go, which does nothing by default. The runner sets a [`Reporter`]
+//! with [`set_reporter`] when there is a terminal to draw on, and the days get a [`Progress`] from it with
+//! [`reporter`], otherwise, e.g. in tests or with `--format json`, they get [`NoProgress`].
+
+use std::sync::OnceLock;
+
+/// Something that can be told how far through a long-running task is
+pub trait Progress {
+    /// `done` units of work have been completed, out of `total` if that is known up front
+    fn update(&mut self, _done: usize, _total: Option<usize>) {}
+
+    /// The task has completed, so anything drawn can be cleared
+    fn finish(&mut self) {}
+}
+
+/// Ignores all updates
+pub struct NoProgress;
+
+impl Progress for NoProgress {}
+
+/// Builds a [`Progress`] for a task with the given label
+pub type Reporter = fn(&'static str) -> Box<dyn Progress>;
+
+/// Set once by the runner, if progress should be shown
+static REPORTER: OnceLock<Reporter> = OnceLock::new();
+
+/// Use `reporter` for all the tasks that are run from now on. Only the first reporter set is used.
+pub fn set_reporter(reporter: Reporter) {
+    let _ = REPORTER.set(reporter);
+}
+
+/// A [`Progress`] for a task called `label`, or [`NoProgress`] if the runner hasn't set a [`Reporter`]
+pub fn reporter(label: &'static str) -> Box<dyn Progress> {
+    match REPORTER.get() {
+        Some(reporter) => reporter(label),
+        None => Box::new(NoProgress),
+    }
+}
+
+/// Keeps every update, for checking what a task reports in tests
+#[cfg(test)]
+#[derive(Default)]
+pub struct RecordedProgress {
+    pub updates: Vec<(usize, Option<usize>)>,
+    pub finished: bool,
+}
+
+#[cfg(test)]
+impl Progress for RecordedProgress {
+    fn update(&mut self, done: usize, total: Option<usize>) {
+        self.updates.push((done, total));
+    }
+
+    fn finish(&mut self) {
+        self.finished = true;
+    }
+}
@@ -0,0 +1,70 @@
+//! A small seeded random number generator for [`crate::geninput`]. The generated inputs need to be the same every time
+//! for a given seed, so that a test or benchmark run in CI is repeatable, and the quality of the randomness doesn't
+//! matter much, so this is [SplitMix64](https://prng.di.unimi.it/splitmix64.c) rather than pulling in a crate.
+
+/// A stream of pseudo-random numbers, which is the same for the same seed
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        Random { state: seed }
+    }
+
+    /// The next number in the stream
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` up to, but not including, `bound`. The modulo makes the smaller numbers very slightly more
+    /// likely, which is fine for generating puzzle inputs.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// A number from `min` to `max`, inclusive
+    pub fn between(&mut self, min: isize, max: isize) -> isize {
+        min + self.below((max - min + 1) as usize) as isize
+    }
+
+    /// Put the items in a random order, using the Fisher-Yates shuffle
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::random::Random;
+
+    #[test]
+    fn can_generate_repeatable_numbers() {
+        let numbers = |seed| {
+            let mut random = Random::new(seed);
+            (0..10).map(|_| random.next_u64()).collect::<Vec<u64>>()
+        };
+
+        assert_eq!(numbers(42), numbers(42));
+        assert_ne!(numbers(42), numbers(43));
+
+        let mut random = Random::new(1);
+        for _ in 0..1000 {
+            assert!(random.below(10) < 10);
+            assert!((-5..=5).contains(&random.between(-5, 5)));
+        }
+
+        let mut items: Vec<usize> = (0..100).collect();
+        random.shuffle(&mut items);
+        assert_ne!(items, (0..100).collect::<Vec<usize>>());
+        items.sort();
+        assert_eq!(items, (0..100).collect::<Vec<usize>>());
+    }
+}
@@ -0,0 +1,202 @@
+//! Helpers for watching the grid based puzzles evolve in the terminal. Running with `--visualise` clears the
+//! terminal and draws each iteration of the grid as a frame, pausing between frames for the delay given by
+//! `--frame-delay=<ms>` (default 100ms). Puzzles that implement [`FrameSource`] can also be fast-forwarded with
+//! `--skip-to=<step>`, which advances to that step without drawing the frames in between.
+
+use std::env;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+/// ANSI escape sequence to clear the screen and move the cursor back to the top left
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// The delay between frames if `--frame-delay` is not provided
+const DEFAULT_FRAME_DELAY_MS: u64 = 100;
+
+/// Implemented by puzzle structs that can be drawn as a single frame of an animation
+pub trait Renderable {
+    /// Render the current state as the lines of text that make up one frame
+    fn render(&self) -> String;
+}
+
+/// Implemented by puzzle structs that evolve one step at a time, so that they can be animated by [`animate`]
+pub trait FrameSource: Renderable {
+    /// Advance the state by one step, returning `false` without changing anything if it has already finished
+    fn step(&mut self) -> bool;
+
+    /// The number of steps taken so far
+    fn current_step(&self) -> usize;
+
+    /// Advance until `step` has been reached without rendering any frames, so animations can start from an
+    /// interesting point without paying for drawing every frame before it. Returns `false` if the state finished
+    /// before reaching the requested step.
+    fn skip_to(&mut self, step: usize) -> bool {
+        while self.current_step() < step {
+            if !self.step() {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// True if the user has asked for the visualisation output mode, which is ignored with `--quiet`
+pub fn visualise_enabled() -> bool {
+    !crate::output::is_quiet() && env::args().any(|arg| arg == "--visualise")
+}
+
+/// The delay between frames as requested by the command line arguments
+pub fn frame_delay() -> Duration {
+    parse_frame_delay(env::args())
+}
+
+/// The step the user has asked to fast-forward to with `--skip-to=<step>`, if any
+pub fn skip_to_step() -> Option<usize> {
+    parse_skip_to(env::args())
+}
+
+/// Find the last `--skip-to=<step>` argument, ignoring unparsable values
+fn parse_skip_to(args: impl Iterator<Item = String>) -> Option<usize> {
+    args.filter_map(|arg| {
+        arg.strip_prefix("--skip-to=")
+            .and_then(|step| step.parse::<usize>().ok())
+    })
+    .last()
+}
+
+/// Find the last `--frame-delay=<ms>` argument, falling back to the default for missing or unparsable values
+fn parse_frame_delay(args: impl Iterator<Item = String>) -> Duration {
+    let millis = args
+        .filter_map(|arg| {
+            arg.strip_prefix("--frame-delay=")
+                .and_then(|ms| ms.parse::<u64>().ok())
+        })
+        .last()
+        .unwrap_or(DEFAULT_FRAME_DELAY_MS);
+
+    Duration::from_millis(millis)
+}
+
+/// Build the text for a complete frame, including the escape code to clear the previous frame and a caption
+fn build_frame(frame: &impl Renderable, caption: &str) -> String {
+    format!("{}{}\n{}\n", CLEAR_SCREEN, caption, frame.render())
+}
+
+/// Draw a single frame to stdout, then wait for the frame delay so the animation can be followed
+pub fn draw_frame(frame: &impl Renderable, caption: &str, delay: Duration) {
+    let mut stdout = io::stdout();
+    write!(stdout, "{}", build_frame(frame, caption)).expect("Failed to draw frame");
+    stdout.flush().expect("Failed to draw frame");
+    thread::sleep(delay);
+}
+
+/// Draw each step of the source as a frame until it finishes, starting from the step requested with `--skip-to` if
+/// there is one. Returns the number of steps taken.
+pub fn animate(source: &mut impl FrameSource, delay: Duration) -> usize {
+    if let Some(step) = skip_to_step() {
+        source.skip_to(step);
+    }
+
+    draw_frame(source, &format!("Step {}", source.current_step()), delay);
+    while source.step() {
+        draw_frame(source, &format!("Step {}", source.current_step()), delay);
+    }
+
+    source.current_step()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::render::{
+        build_frame, parse_frame_delay, parse_skip_to, FrameSource, Renderable, CLEAR_SCREEN,
+    };
+    use std::time::Duration;
+
+    struct Fixed(&'static str);
+
+    impl Renderable for Fixed {
+        fn render(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    /// Counts up to a limit, one per step
+    struct Counter {
+        count: usize,
+        limit: usize,
+    }
+
+    impl Renderable for Counter {
+        fn render(&self) -> String {
+            self.count.to_string()
+        }
+    }
+
+    impl FrameSource for Counter {
+        fn step(&mut self) -> bool {
+            if self.count == self.limit {
+                return false;
+            }
+            self.count += 1;
+            true
+        }
+
+        fn current_step(&self) -> usize {
+            self.count
+        }
+    }
+
+    fn args(list: &[&str]) -> impl Iterator<Item = String> {
+        list.iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn can_build_frame() {
+        assert_eq!(
+            build_frame(&Fixed("#.\n.#"), "Step 1"),
+            format!("{}Step 1\n#.\n.#\n", CLEAR_SCREEN)
+        );
+    }
+
+    #[test]
+    fn can_parse_frame_delay() {
+        assert_eq!(parse_frame_delay(args(&[])), Duration::from_millis(100));
+        assert_eq!(
+            parse_frame_delay(args(&["--visualise", "--frame-delay=25"])),
+            Duration::from_millis(25)
+        );
+        assert_eq!(
+            parse_frame_delay(args(&["--frame-delay=fast"])),
+            Duration::from_millis(100)
+        );
+    }
+
+    #[test]
+    fn can_parse_skip_to() {
+        assert_eq!(parse_skip_to(args(&[])), None);
+        assert_eq!(parse_skip_to(args(&["--skip-to=40"])), Some(40));
+        assert_eq!(parse_skip_to(args(&["--skip-to=end"])), None);
+    }
+
+    #[test]
+    fn can_skip_to_step() {
+        let mut counter = Counter {
+            count: 0,
+            limit: 10,
+        };
+
+        assert!(counter.skip_to(4));
+        assert_eq!(counter.render(), "4");
+        // Skipping backwards leaves the state as is
+        assert!(counter.skip_to(2));
+        assert_eq!(counter.current_step(), 4);
+        // Stops at the end if the step is never reached
+        assert!(!counter.skip_to(20));
+        assert_eq!(counter.current_step(), 10);
+    }
+}
@@ -0,0 +1,277 @@
+//! An interactive mode for exploring the puzzles that evolve one step at a time. Running a supported day with `--repl`
+//! loads the puzzle input, then reads commands from stdin until `quit` or the end of the input:
+//!
+//! - `step [n]` - advance by one step, or `n` steps, stopping early if the state has finished
+//! - `back [n]` - go back one step, or `n` steps, for the states that can, see [`Steppable::step_back`]
+//! - `print` - draw the current state, as it would be drawn for a frame of the visualisation
+//! - `stats` - summarise the current state, see [`Steppable::stats`]
+//! - `reset` - go back to the state as it was loaded
+//! - `help` - list the commands
+//! - `quit` - leave the REPL and carry on with the solution
+//!
+//! The library doesn't read stdin itself, the runner hands over the terminal with [`set_terminal`] when it is started
+//! with `--repl`.
+
+use crate::util::render::FrameSource;
+use std::fmt::{Display, Formatter};
+use std::io::{self, BufRead, Write};
+use std::sync::OnceLock;
+
+/// The prompt printed before each command is read
+const PROMPT: &str = "> ";
+
+/// Implemented by puzzle structs that can be explored with [`run_repl`]. Stepping and drawing the state are shared with
+/// the visualisations, this adds a summary of the state that is too detailed for a frame caption.
+pub trait Steppable: FrameSource + Clone {
+    /// A short description of the current state, e.g. the step reached and counts of interest
+    fn stats(&self) -> String;
+
+    /// Undo the last step, returning `false` if there is nothing to undo. Most states can only go forwards, so by
+    /// default this never goes back.
+    fn step_back(&mut self) -> bool {
+        false
+    }
+}
+
+/// A command entered into the REPL
+#[derive(Eq, PartialEq, Debug)]
+enum Command {
+    Step(usize),
+    Back(usize),
+    Print,
+    Stats,
+    Reset,
+    Help,
+    Quit,
+}
+
+/// The ways parsing a command can fail
+#[derive(Eq, PartialEq, Debug)]
+enum CommandError {
+    /// Nothing was entered
+    Empty,
+    /// The command isn't one of the supported commands
+    Unknown(String),
+    /// The step count given to `step` or `back` isn't a number
+    InvalidCount(String),
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandError::Empty => write!(f, "Enter a command, or help to list them"),
+            CommandError::Unknown(command) => {
+                write!(f, "Unknown command '{}', enter help to list them", command)
+            }
+            CommandError::InvalidCount(count) => {
+                write!(f, "'{}' is not a valid number of steps", count)
+            }
+        }
+    }
+}
+
+impl TryFrom<&str> for Command {
+    type Error = CommandError;
+
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some("step") => Command::Step(parse_count(words.next())?),
+            Some("back") => Command::Back(parse_count(words.next())?),
+            Some("print") => Command::Print,
+            Some("stats") => Command::Stats,
+            Some("reset") => Command::Reset,
+            Some("help") => Command::Help,
+            Some("quit") | Some("exit") => Command::Quit,
+            Some(other) => return Err(CommandError::Unknown(other.to_string())),
+            None => return Err(CommandError::Empty),
+        };
+
+        Ok(command)
+    }
+}
+
+/// The number of steps given to `step` or `back`, which defaults to 1
+fn parse_count(count: Option<&str>) -> Result<usize, CommandError> {
+    match count {
+        Some(count) => count
+            .parse::<usize>()
+            .map_err(|_| CommandError::InvalidCount(count.to_string())),
+        None => Ok(1),
+    }
+}
+
+/// Opens the input to read commands from, and the output to write the responses to
+pub type Terminal = fn() -> (Box<dyn BufRead>, Box<dyn Write>);
+
+/// Set once by the runner, if the user has asked for the REPL
+static TERMINAL: OnceLock<Terminal> = OnceLock::new();
+
+/// Run the REPL on `terminal` for the supported days from now on. Only the first terminal set is used.
+pub fn set_terminal(terminal: Terminal) {
+    let _ = TERMINAL.set(terminal);
+}
+
+/// True if the user has asked to explore the puzzle state interactively
+pub fn repl_enabled() -> bool {
+    TERMINAL.get().is_some()
+}
+
+/// Explore the state interactively, reading commands from the runner's [`Terminal`]. The state passed in is left as
+/// is, so the solution can carry on from the puzzle input afterwards. Does nothing if the REPL isn't enabled.
+pub fn run_repl(initial: &impl Steppable) {
+    if let Some(terminal) = TERMINAL.get() {
+        let (input, mut output) = terminal();
+        run_commands(initial, input, &mut output).expect("Failed to write the REPL's output");
+    }
+}
+
+/// Read and execute commands until `quit` or the input runs out, writing the responses to `output`
+fn run_commands(
+    initial: &impl Steppable,
+    input: impl BufRead,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    let mut state = initial.clone();
+    writeln!(output, "{}", state.stats())?;
+    write!(output, "{}", PROMPT)?;
+    output.flush()?;
+
+    for line in input.lines() {
+        match Command::try_from(line?.as_str()) {
+            Ok(Command::Quit) => break,
+            Ok(command) => writeln!(output, "{}", execute(&mut state, initial, command))?,
+            Err(err) => writeln!(output, "{}", err)?,
+        }
+        write!(output, "{}", PROMPT)?;
+        output.flush()?;
+    }
+
+    writeln!(output)
+}
+
+/// Apply a command to the state, returning the text to show the user
+fn execute<S: Steppable>(state: &mut S, initial: &S, command: Command) -> String {
+    match command {
+        Command::Step(count) => {
+            let start = state.current_step();
+            let target = start + count;
+            if state.skip_to(target) {
+                state.stats()
+            } else {
+                format!(
+                    "Finished after {} of {} steps\n{}",
+                    state.current_step() - start,
+                    count,
+                    state.stats()
+                )
+            }
+        }
+        Command::Back(count) => {
+            let undone = (0..count).take_while(|_| state.step_back()).count();
+            if undone == count {
+                state.stats()
+            } else {
+                format!("Went back {} of {} steps\n{}", undone, count, state.stats())
+            }
+        }
+        Command::Print => state.render(),
+        Command::Stats => state.stats(),
+        Command::Reset => {
+            *state = initial.clone();
+            state.stats()
+        }
+        Command::Help => {
+            "Commands: step [n], back [n], print, stats, reset, help, quit".to_string()
+        }
+        Command::Quit => "".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::render::{FrameSource, Renderable};
+    use crate::util::repl::{run_commands, Command, CommandError, Steppable};
+
+    /// Counts up to a limit, one per step
+    #[derive(Clone)]
+    struct Counter {
+        count: usize,
+        limit: usize,
+    }
+
+    impl Renderable for Counter {
+        fn render(&self) -> String {
+            "#".repeat(self.count)
+        }
+    }
+
+    impl FrameSource for Counter {
+        fn step(&mut self) -> bool {
+            if self.count == self.limit {
+                return false;
+            }
+            self.count += 1;
+            true
+        }
+
+        fn current_step(&self) -> usize {
+            self.count
+        }
+    }
+
+    impl Steppable for Counter {
+        fn stats(&self) -> String {
+            format!("Count: {}", self.count)
+        }
+    }
+
+    #[test]
+    fn can_parse_commands() {
+        assert_eq!(Command::try_from("step"), Ok(Command::Step(1)));
+        assert_eq!(Command::try_from(" step 12 "), Ok(Command::Step(12)));
+        assert_eq!(Command::try_from("back"), Ok(Command::Back(1)));
+        assert_eq!(Command::try_from("back 3"), Ok(Command::Back(3)));
+        assert_eq!(Command::try_from("print"), Ok(Command::Print));
+        assert_eq!(Command::try_from("stats"), Ok(Command::Stats));
+        assert_eq!(Command::try_from("reset"), Ok(Command::Reset));
+        assert_eq!(Command::try_from("help"), Ok(Command::Help));
+        assert_eq!(Command::try_from("quit"), Ok(Command::Quit));
+        assert_eq!(Command::try_from("exit"), Ok(Command::Quit));
+
+        assert_eq!(Command::try_from(""), Err(CommandError::Empty));
+        assert_eq!(
+            Command::try_from("jump"),
+            Err(CommandError::Unknown("jump".to_string()))
+        );
+        assert_eq!(
+            Command::try_from("step lots"),
+            Err(CommandError::InvalidCount("lots".to_string()))
+        );
+    }
+
+    #[test]
+    fn can_run_commands() {
+        let initial = Counter { count: 0, limit: 5 };
+        let input = "step\nstep 2\nprint\nstep 4\nreset\nstats\nback\nfly\nquit\nstep\n";
+        let mut output = Vec::new();
+
+        run_commands(&initial, input.as_bytes(), &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Count: 0\n\
+            > Count: 1\n\
+            > Count: 3\n\
+            > ###\n\
+            > Finished after 2 of 4 steps\nCount: 5\n\
+            > Count: 0\n\
+            > Count: 0\n\
+            > Went back 0 of 1 steps\nCount: 0\n\
+            > Unknown command 'fly', enter help to list them\n\
+            > \n"
+        );
+        // The initial state is left for the solution to use
+        assert_eq!(initial.count, 0);
+    }
+}
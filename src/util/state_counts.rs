@@ -0,0 +1,208 @@
+//! Several puzzles grow far too quickly to simulate each item, but only have a small number of distinct states that
+//! each item can be in. [`crate::year_2021::day_6`], [`crate::year_2021::day_14`] and [`crate::year_2021::day_21`] all
+//! ended up with the same trick of keeping a count of how many items are in each state, and working out the next counts
+//! from each state's transitions. [`CountMap`] is that trick pulled out so it can be reused.
+//!
+//! The counts grow exponentially, and overflow a `u64` within a couple of hundred iterations, so they are stored as a
+//! [`Count`]. That is a `u128` by default, or an arbitrary precision `BigUint` with the `big-counts` feature.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The type used for the number of items in each state
+#[cfg(not(feature = "big-counts"))]
+pub type Count = u128;
+/// The type used for the number of items in each state
+#[cfg(feature = "big-counts")]
+pub type Count = num_bigint::BigUint;
+
+/// Convert a `usize` to a [`Count`], which works the same whichever type is in use
+pub fn to_count(n: usize) -> Count {
+    Count::from(n as u64)
+}
+
+/// The number of items in each distinct state. States with a count of zero are treated the same as missing states.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct CountMap<S: Eq + Hash> {
+    counts: HashMap<S, Count>,
+}
+
+impl<S: Eq + Hash> Default for CountMap<S> {
+    fn default() -> Self {
+        CountMap {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<S: Eq + Hash> CountMap<S> {
+    /// Create an empty map
+    pub fn new() -> CountMap<S> {
+        CountMap::default()
+    }
+
+    /// Add `count` more items in `state`
+    pub fn add(&mut self, state: S, count: Count) {
+        if count != Count::default() {
+            *self.counts.entry(state).or_default() += count;
+        }
+    }
+
+    /// The number of items in `state`
+    #[allow(dead_code)]
+    pub fn get(&self, state: &S) -> Count {
+        self.counts.get(state).cloned().unwrap_or_default()
+    }
+
+    /// The total number of items in all states
+    pub fn total(&self) -> Count {
+        self.counts.values().sum()
+    }
+
+    /// True if there are no items in any state
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The states and their counts, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&S, &Count)> {
+        self.counts.iter()
+    }
+
+    /// Build the counts for the next iteration. `transition` gives the states each state becomes, with a multiplier
+    /// for how many items in the new state each item in the current state becomes. e.g. a state that splits in two
+    /// could return `[(a, 1), (b, 1)]`, or `[(a, 2)]` if both halves are the same. A state that returns nothing is
+    /// dropped.
+    pub fn step<I>(&self, mut transition: impl FnMut(&S) -> I) -> CountMap<S>
+    where
+        I: IntoIterator<Item = (S, usize)>,
+    {
+        let mut next = CountMap::new();
+        for (state, count) in &self.counts {
+            for (next_state, multiplier) in transition(state) {
+                next.add(next_state, count * to_count(multiplier));
+            }
+        }
+
+        next
+    }
+
+    /// Remove all the states that match `predicate`, returning the total count of the items removed. Useful for
+    /// taking finished states out of the map, so they aren't iterated further.
+    pub fn remove_where(&mut self, predicate: impl Fn(&S) -> bool) -> Count {
+        let mut removed = Count::default();
+        self.counts.retain(|state, count| {
+            if predicate(state) {
+                removed += &*count;
+                false
+            } else {
+                true
+            }
+        });
+
+        removed
+    }
+}
+
+impl<S: Eq + Hash> From<HashMap<S, usize>> for CountMap<S> {
+    fn from(counts: HashMap<S, usize>) -> Self {
+        let mut map = CountMap::new();
+        for (state, count) in counts {
+            map.add(state, to_count(count));
+        }
+
+        map
+    }
+}
+
+impl<S: Eq + Hash> FromIterator<S> for CountMap<S> {
+    /// Count the number of times each state appears in the iterator
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        let mut map = CountMap::new();
+        for state in iter {
+            map.add(state, to_count(1));
+        }
+
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::state_counts::{to_count, CountMap};
+    use std::collections::HashMap;
+
+    #[test]
+    fn can_count_states() {
+        let mut counts: CountMap<char> = "abacab".chars().collect();
+        assert_eq!(counts.get(&'a'), to_count(3));
+        assert_eq!(counts.get(&'b'), to_count(2));
+        assert_eq!(counts.get(&'z'), to_count(0));
+        assert_eq!(counts.total(), to_count(6));
+
+        counts.add('z', to_count(4));
+        counts.add('y', to_count(0));
+        assert_eq!(
+            counts,
+            CountMap::from(HashMap::from([('a', 3), ('b', 2), ('c', 1), ('z', 4)]))
+        );
+        assert!(!counts.is_empty());
+        assert!(CountMap::<char>::new().is_empty());
+    }
+
+    #[test]
+    fn can_step() {
+        // Each number either halves if it is even, or splits into two copies of 3n + 1
+        let start: CountMap<usize> = [6, 6, 5].into_iter().collect();
+        let next = start.step(|&n| {
+            if n % 2 == 0 {
+                vec![(n / 2, 1)]
+            } else {
+                vec![(3 * n + 1, 2)]
+            }
+        });
+
+        assert_eq!(next, CountMap::from(HashMap::from([(3, 2), (16, 2)])));
+
+        // States can merge, and be dropped
+        let merged = next.step(|&n| if n == 3 { vec![(1, 1)] } else { vec![] });
+        assert_eq!(merged, CountMap::from(HashMap::from([(1, 2)])));
+    }
+
+    #[test]
+    fn can_remove_states() {
+        let mut counts: CountMap<usize> = [1, 2, 2, 3, 3, 3].into_iter().collect();
+
+        assert_eq!(counts.remove_where(|&n| n >= 2), to_count(5));
+        assert_eq!(counts.total(), to_count(1));
+        assert_eq!(counts.remove_where(|&n| n >= 2), to_count(0));
+    }
+
+    #[test]
+    fn counts_do_not_overflow_u64() {
+        // Doubling every step passes `u64::MAX` after 64 steps
+        let mut counts: CountMap<char> = ['a'].into_iter().collect();
+        for _ in 0..100 {
+            counts = counts.step(|&c| [(c, 2)]);
+        }
+
+        assert_eq!(
+            counts.total().to_string(),
+            "1267650600228229401496703205376"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "big-counts")]
+    fn big_counts_do_not_overflow_u128() {
+        let mut counts: CountMap<char> = ['a'].into_iter().collect();
+        for _ in 0..200 {
+            counts = counts.step(|&c| [(c, 2)]);
+        }
+
+        assert_eq!(
+            counts.total().to_string(),
+            "1606938044258990275541962092341162602522202993782792835301376"
+        );
+    }
+}
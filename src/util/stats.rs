@@ -0,0 +1,170 @@
+//! Summary statistics over slices of integers. [`crate::year_2021::day_7`] and [`crate::year_2021::day_10`] both
+//! sorted their values and skipped to the middle one to find a median, which only works as expected for an odd number
+//! of values. These give the common statistics a name, and each handles an empty slice by returning `None`.
+//!
+//! For an even number of values the [`median`] is the mean of the middle pair, which may not be a whole number. Where
+//! the median has to be one of the values, e.g. a position a crab can move to, [`median_low`] picks the lower of the
+//! pair instead.
+
+use std::collections::BTreeMap;
+
+/// The integer types the statistics can be calculated for
+pub trait Integer: Copy + Ord {
+    /// The value as a float, for the statistics that may not be a whole number
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_integer {
+    ($($int:ty),*) => {
+        $(
+            impl Integer for $int {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, usize, i32, i64, isize);
+
+/// The values in ascending order
+fn sorted<T: Integer>(values: &[T]) -> Vec<T> {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+
+    sorted
+}
+
+/// The number of times each distinct value appears, in ascending order of value
+pub fn histogram<T: Integer>(values: &[T]) -> BTreeMap<T, usize> {
+    let mut counts = BTreeMap::new();
+    for &value in values {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+/// The sum of the values divided by how many there are
+pub fn mean<T: Integer>(values: &[T]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let total: f64 = values.iter().map(|&value| value.to_f64()).sum();
+    Some(total / values.len() as f64)
+}
+
+/// The middle value once sorted, or the mean of the middle two if there are an even number of values
+pub fn median<T: Integer>(values: &[T]) -> Option<f64> {
+    let sorted = sorted(values);
+    let mid = sorted.len() / 2;
+
+    match sorted.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(sorted[mid].to_f64()),
+        _ => Some((sorted[mid - 1].to_f64() + sorted[mid].to_f64()) / 2.0),
+    }
+}
+
+/// The middle value once sorted, or the lower of the middle two if there are an even number of values
+pub fn median_low<T: Integer>(values: &[T]) -> Option<T> {
+    let sorted = sorted(values);
+
+    sorted.len().checked_sub(1).map(|last| sorted[last / 2])
+}
+
+/// The most common value, with ties going to the smallest of them
+pub fn mode<T: Integer>(values: &[T]) -> Option<T> {
+    histogram(values)
+        .into_iter()
+        .max_by_key(|&(value, count)| (count, std::cmp::Reverse(value)))
+        .map(|(value, _)| value)
+}
+
+/// The smallest value that at least `percent` percent of the values are less than or equal to, i.e. the nearest rank
+/// method. The 0th percentile is the smallest value, and the 100th the largest. Panics if `percent` isn't between 0
+/// and 100.
+pub fn percentile<T: Integer>(values: &[T], percent: f64) -> Option<T> {
+    assert!(
+        (0.0..=100.0).contains(&percent),
+        "Percentile {} is not between 0 and 100",
+        percent
+    );
+
+    let sorted = sorted(values);
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+
+    sorted.get(rank.max(1) - 1).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::stats::{histogram, mean, median, median_low, mode, percentile};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn can_summarise_values() {
+        let values: Vec<usize> = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+
+        assert_eq!(
+            histogram(&values),
+            BTreeMap::from([(0, 1), (1, 2), (2, 3), (4, 1), (7, 1), (14, 1), (16, 1)])
+        );
+        assert_eq!(mean(&values), Some(4.9));
+        assert_eq!(mode(&values), Some(2));
+        assert_eq!(median(&values), Some(2.0));
+        assert_eq!(median_low(&values), Some(2));
+    }
+
+    #[test]
+    fn can_find_the_median_of_even_counts() {
+        assert_eq!(median(&[3, 1, 4, 2]), Some(2.5));
+        assert_eq!(median_low(&[3, 1, 4, 2]), Some(2));
+        assert_eq!(median(&[10, 0]), Some(5.0));
+        assert_eq!(median_low(&[10, 0]), Some(0));
+
+        assert_eq!(median(&[3, 1, 2]), Some(2.0));
+        assert_eq!(median_low(&[3, 1, 2]), Some(2));
+        assert_eq!(median(&[-7]), Some(-7.0));
+        assert_eq!(median_low(&[-7]), Some(-7));
+    }
+
+    #[test]
+    fn can_find_percentiles() {
+        let values: Vec<u32> = (1..=20).rev().collect();
+
+        assert_eq!(percentile(&values, 0.0), Some(1));
+        assert_eq!(percentile(&values, 5.0), Some(1));
+        assert_eq!(percentile(&values, 6.0), Some(2));
+        assert_eq!(percentile(&values, 50.0), Some(10));
+        assert_eq!(percentile(&values, 90.0), Some(18));
+        assert_eq!(percentile(&values, 100.0), Some(20));
+        assert_eq!(percentile(&[5, 1], 50.0), median_low(&[5, 1]));
+    }
+
+    #[test]
+    fn ties_go_to_the_smallest_mode() {
+        assert_eq!(mode(&[5, 3, 5, 3, 9]), Some(3));
+        assert_eq!(mode(&[-1, 2]), Some(-1));
+    }
+
+    #[test]
+    fn empty_values_have_no_statistics() {
+        let empty: [usize; 0] = [];
+
+        assert_eq!(histogram(&empty), BTreeMap::new());
+        assert_eq!(mean(&empty), None);
+        assert_eq!(median(&empty), None);
+        assert_eq!(median_low(&empty), None);
+        assert_eq!(mode(&empty), None);
+        assert_eq!(percentile(&empty, 50.0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Percentile 101 is not between 0 and 100")]
+    fn cannot_find_percentiles_over_100() {
+        percentile(&[1, 2, 3], 101.0);
+    }
+}
@@ -0,0 +1,371 @@
+//! A point or offset in 3D space. [`crate::year_2021::day_19`] started out with tuples and a hand written list of the
+//! rotations, and [`crate::year_2021::day_22`] with a separate field for each axis of each corner of a cuboid.
+//! [`Vec3`] gives both the same arithmetic, and [`Orientation`] names the 24 ways a scanner can be rotated so that it
+//! still lines up with the axes. Day 19 also exposed those rotations as matrices, which are now [`ROTATIONS`], with
+//! the same order as [`Orientation::ALL`].
+
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A point, or the offset between two points, in 3D space
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Vec3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+/// The unit vectors along each axis, which [`Orientation`] is written in terms of
+const POS_X: Vec3 = Vec3::new(1, 0, 0);
+const NEG_X: Vec3 = Vec3::new(-1, 0, 0);
+const POS_Y: Vec3 = Vec3::new(0, 1, 0);
+const NEG_Y: Vec3 = Vec3::new(0, -1, 0);
+const POS_Z: Vec3 = Vec3::new(0, 0, 1);
+const NEG_Z: Vec3 = Vec3::new(0, 0, -1);
+
+impl Vec3 {
+    pub const fn new(x: isize, y: isize, z: isize) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    /// The sum of the distances along each axis
+    pub fn manhattan(&self, other: &Vec3) -> usize {
+        let Vec3 { x, y, z } = *self - *other;
+        (x.abs() + y.abs() + z.abs()) as usize
+    }
+
+    /// The size along each axis, ignoring the direction
+    pub fn abs(&self) -> Vec3 {
+        Vec3::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    /// The smaller of each pair of co-ordinates, i.e. the lower corner of a box that contains both points
+    pub fn component_min(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// The larger of each pair of co-ordinates, i.e. the upper corner of a box that contains both points
+    pub fn component_max(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    /// The cross product, perpendicular to both vectors
+    pub const fn cross(&self, other: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Rotate about the origin
+    pub fn rotate(&self, orientation: Orientation) -> Vec3 {
+        orientation.rotation().apply(*self)
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    fn neg(self) -> Vec3 {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<isize> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, scale: isize) -> Vec3 {
+        Vec3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+/// One of the 24 rotations that keep the axes lined up with the axes. Each is named for where it moves the x-axis,
+/// then the y-axis, e.g. `PosYNegX` turns the x-axis to point along +y, and the y-axis along -x, a quarter turn about
+/// z. The z-axis always ends up perpendicular to both, and the other way round would be a reflection, not a rotation.
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub enum Orientation {
+    PosXPosY,
+    PosXNegY,
+    PosXPosZ,
+    PosXNegZ,
+    NegXPosY,
+    NegXNegY,
+    NegXPosZ,
+    NegXNegZ,
+    PosYPosX,
+    PosYNegX,
+    PosYPosZ,
+    PosYNegZ,
+    NegYPosX,
+    NegYNegX,
+    NegYPosZ,
+    NegYNegZ,
+    PosZPosX,
+    PosZNegX,
+    PosZPosY,
+    PosZNegY,
+    NegZPosX,
+    NegZNegX,
+    NegZPosY,
+    NegZNegY,
+}
+
+impl Orientation {
+    /// Every orientation, starting with the one that leaves everything where it is
+    pub const ALL: [Orientation; 24] = [
+        Orientation::PosXPosY,
+        Orientation::PosXNegY,
+        Orientation::PosXPosZ,
+        Orientation::PosXNegZ,
+        Orientation::NegXPosY,
+        Orientation::NegXNegY,
+        Orientation::NegXPosZ,
+        Orientation::NegXNegZ,
+        Orientation::PosYPosX,
+        Orientation::PosYNegX,
+        Orientation::PosYPosZ,
+        Orientation::PosYNegZ,
+        Orientation::NegYPosX,
+        Orientation::NegYNegX,
+        Orientation::NegYPosZ,
+        Orientation::NegYNegZ,
+        Orientation::PosZPosX,
+        Orientation::PosZNegX,
+        Orientation::PosZPosY,
+        Orientation::PosZNegY,
+        Orientation::NegZPosX,
+        Orientation::NegZNegX,
+        Orientation::NegZPosY,
+        Orientation::NegZNegY,
+    ];
+
+    /// The matrix that performs this rotation
+    pub fn rotation(self) -> Rot3 {
+        ROTATIONS[self as usize]
+    }
+
+    /// Where the x-axis and y-axis are moved to
+    const fn axes(self) -> (Vec3, Vec3) {
+        match self {
+            Orientation::PosXPosY => (POS_X, POS_Y),
+            Orientation::PosXNegY => (POS_X, NEG_Y),
+            Orientation::PosXPosZ => (POS_X, POS_Z),
+            Orientation::PosXNegZ => (POS_X, NEG_Z),
+            Orientation::NegXPosY => (NEG_X, POS_Y),
+            Orientation::NegXNegY => (NEG_X, NEG_Y),
+            Orientation::NegXPosZ => (NEG_X, POS_Z),
+            Orientation::NegXNegZ => (NEG_X, NEG_Z),
+            Orientation::PosYPosX => (POS_Y, POS_X),
+            Orientation::PosYNegX => (POS_Y, NEG_X),
+            Orientation::PosYPosZ => (POS_Y, POS_Z),
+            Orientation::PosYNegZ => (POS_Y, NEG_Z),
+            Orientation::NegYPosX => (NEG_Y, POS_X),
+            Orientation::NegYNegX => (NEG_Y, NEG_X),
+            Orientation::NegYPosZ => (NEG_Y, POS_Z),
+            Orientation::NegYNegZ => (NEG_Y, NEG_Z),
+            Orientation::PosZPosX => (POS_Z, POS_X),
+            Orientation::PosZNegX => (POS_Z, NEG_X),
+            Orientation::PosZPosY => (POS_Z, POS_Y),
+            Orientation::PosZNegY => (POS_Z, NEG_Y),
+            Orientation::NegZPosX => (NEG_Z, POS_X),
+            Orientation::NegZNegX => (NEG_Z, NEG_X),
+            Orientation::NegZPosY => (NEG_Z, POS_Y),
+            Orientation::NegZNegY => (NEG_Z, NEG_Y),
+        }
+    }
+}
+
+/// A rotation of 3D space by multiples of 90° about the axes, as the matrix that rotates a [`Vec3`] column vector.
+/// Each row has a single ±1, and the rest are 0.
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
+pub struct Rot3(pub [[isize; 3]; 3]);
+
+impl Rot3 {
+    /// The rotation that leaves everything where it is
+    pub const IDENTITY: Rot3 = Rot3([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    /// The rotation that moves the x-axis to `x_axis` and the y-axis to `y_axis`. The z-axis follows them, i.e. the
+    /// columns of the matrix are where each axis ends up.
+    const fn from_axes(x_axis: Vec3, y_axis: Vec3) -> Rot3 {
+        let z_axis = x_axis.cross(&y_axis);
+        Rot3([
+            [x_axis.x, y_axis.x, z_axis.x],
+            [x_axis.y, y_axis.y, z_axis.y],
+            [x_axis.z, y_axis.z, z_axis.z],
+        ])
+    }
+
+    /// Rotate a point
+    pub fn apply(&self, Vec3 { x, y, z }: Vec3) -> Vec3 {
+        let [a, b, c] = self.0;
+        Vec3::new(
+            a[0] * x + a[1] * y + a[2] * z,
+            b[0] * x + b[1] * y + b[2] * z,
+            c[0] * x + c[1] * y + c[2] * z,
+        )
+    }
+
+    /// +1 for a rotation. The matrices with the same shape and a determinant of -1 are reflections.
+    pub const fn determinant(&self) -> isize {
+        let [a, b, c] = self.0;
+        a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+            + a[2] * (b[0] * c[1] - b[1] * c[0])
+    }
+}
+
+impl Mul for Rot3 {
+    type Output = Rot3;
+
+    /// Compose two rotations, `a * b` is the same as applying `b`, then `a`
+    fn mul(self, other: Rot3) -> Rot3 {
+        let mut matrix = [[0; 3]; 3];
+        for (row, cells) in matrix.iter_mut().enumerate() {
+            for (col, cell) in cells.iter_mut().enumerate() {
+                *cell = (0..3).map(|i| self.0[row][i] * other.0[i][col]).sum();
+            }
+        }
+
+        Rot3(matrix)
+    }
+}
+
+/// The matrix for each of [`Orientation::ALL`], starting with [`Rot3::IDENTITY`]. These are checked to be unique,
+/// closed under composition, and to have a determinant of +1 by the tests, so can be relied on elsewhere without
+/// re-deriving them.
+pub const ROTATIONS: [Rot3; 24] = build_rotations();
+
+/// Build [`ROTATIONS`] from where each [`Orientation`] moves the axes
+const fn build_rotations() -> [Rot3; 24] {
+    let mut rotations = [Rot3::IDENTITY; 24];
+    let mut i = 0;
+    while i < rotations.len() {
+        let (x_axis, y_axis) = Orientation::ALL[i].axes();
+        rotations[i] = Rot3::from_axes(x_axis, y_axis);
+        // Checked when compiling, the z-axis is always the cross product so this can't be a reflection
+        assert!(rotations[i].determinant() == 1);
+        i += 1;
+    }
+
+    rotations
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::vec3::{Orientation, Rot3, Vec3, ROTATIONS};
+    use std::collections::HashSet;
+
+    #[test]
+    fn can_do_arithmetic() {
+        let a = Vec3::new(1, -2, 3);
+        let b = Vec3::new(-4, 5, 6);
+
+        assert_eq!(a + b, Vec3::new(-3, 3, 9));
+        assert_eq!(a - b, Vec3::new(5, -7, -3));
+        assert_eq!(-a, Vec3::new(-1, 2, -3));
+        assert_eq!(a * 2, Vec3::new(2, -4, 6));
+        assert_eq!(a.abs(), Vec3::new(1, 2, 3));
+        assert_eq!(a.manhattan(&b), 15);
+        assert_eq!(a.component_min(&b), Vec3::new(-4, -2, 3));
+        assert_eq!(a.component_max(&b), Vec3::new(1, 5, 6));
+        assert_eq!(
+            Vec3::new(1, 0, 0).cross(&Vec3::new(0, 1, 0)),
+            Vec3::new(0, 0, 1)
+        );
+    }
+
+    #[test]
+    fn can_rotate() {
+        let point = Vec3::new(1, 2, 3);
+        assert_eq!(point.rotate(Orientation::PosXPosY), point);
+        // A quarter turn about z
+        assert_eq!(point.rotate(Orientation::PosYNegX), Vec3::new(-2, 1, 3));
+        // Half a turn about x
+        assert_eq!(point.rotate(Orientation::PosXNegY), Vec3::new(1, -2, -3));
+
+        let rotated: HashSet<Vec3> = Orientation::ALL
+            .iter()
+            .map(|&orientation| point.rotate(orientation))
+            .collect();
+        assert_eq!(rotated.len(), 24);
+
+        for orientation in Orientation::ALL {
+            let (x_axis, y_axis) = orientation.axes();
+            // Rotations keep the axes at right angles, and don't mirror them
+            assert_eq!(
+                x_axis.cross(&y_axis).cross(&x_axis),
+                y_axis,
+                "{:?}",
+                orientation
+            );
+            assert_eq!(
+                point.rotate(orientation).manhattan(&Vec3::default()),
+                6,
+                "{:?}",
+                orientation
+            );
+        }
+    }
+
+    #[test]
+    fn rotations_are_a_group() {
+        let unique: HashSet<Rot3> = ROTATIONS.iter().copied().collect();
+        assert_eq!(unique.len(), 24);
+        assert_eq!(ROTATIONS[0], Rot3::IDENTITY);
+
+        for a in ROTATIONS {
+            assert_eq!(a.determinant(), 1);
+            for b in ROTATIONS {
+                assert!(unique.contains(&(a * b)), "{:?} x {:?}", a, b);
+            }
+            // Every rotation can be undone by another rotation
+            assert!(ROTATIONS.iter().any(|&b| a * b == Rot3::IDENTITY));
+        }
+
+        let mirror = Rot3([[-1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+        assert_eq!(mirror.determinant(), -1);
+        assert!(!unique.contains(&mirror));
+    }
+
+    #[test]
+    fn can_apply_and_compose_rotations() {
+        let quarter_turn_z = Rot3([[0, -1, 0], [1, 0, 0], [0, 0, 1]]);
+        assert_eq!(Orientation::PosYNegX.rotation(), quarter_turn_z);
+        assert_eq!(
+            quarter_turn_z.apply(Vec3::new(1, 2, 3)),
+            Vec3::new(-2, 1, 3)
+        );
+        assert_eq!(
+            (quarter_turn_z * quarter_turn_z).apply(Vec3::new(1, 2, 3)),
+            Vec3::new(-1, -2, 3)
+        );
+        assert_eq!(
+            (0..4).fold(Rot3::IDENTITY, |acc, _| acc * quarter_turn_z),
+            Rot3::IDENTITY
+        );
+    }
+}
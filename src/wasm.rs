@@ -0,0 +1,14 @@
+//! The solutions for a web page, built with the `wasm` feature, e.g. `wasm-pack build --target web -- --features wasm`,
+//! or `cargo build --lib --target wasm32-unknown-unknown --features wasm` and then `wasm-bindgen`. This exports one
+//! function, `solve(day, part, input)`, which returns the answer as a string, or throws the reason there isn't one.
+//!
+//! Nothing is printed, as there is nowhere for it to go, so the working for each day is lost. An input that a day can't
+//! parse panics, which aborts in WebAssembly, so it is worth checking the input is for the right day first.
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Solve `part` (1 or 2) of `day` of the latest year with the puzzle input, see [`crate::solve`]
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, String> {
+    crate::solve(day, part, input)
+}
@@ -0,0 +1,238 @@
+//! This is my solution for [Advent of Code - Day 1 - _Sonar Sweep_](https://adventofcode.com/2021/day/1)
+//!
+//! The challenge today was to analyse a sequence of integer depths and determine a measure of
+//! how often these were increasing.
+//!
+//! For part one, the challenge was to count the number of values that were greater than the
+//! previous value in the list. `count_increments` has been written to do that by chaining
+//! built-in methods on Rust's iterators.
+//!
+//! Part two expands on that, requiring the same count be run on the sums of a moving window of
+//! three consecutive values over the input. [`sum_windows`] provides a vector of these sums, which
+//! is suitable to then be passed to `count_increments` to produce the final answer. For this I
+//! originally included itertools to use their `izip!` macro to zip three iterators together, each
+//! offset by one more. I updated it to use [`slice::windows`] thanks to [@bjgill's](https://github.com/bjgill/advent-of-code-2021/blob/1f086dcb6d5cd9bc1152a9a0db87d16b67d2cdb2/src/bin/day1.rs#L20)
+//! comment on the x-gov slack channel.
+//!
+//! Since then I've learnt that the sums aren't needed at all. Two neighbouring windows share all
+//! but their first and last values, so the later window is larger exactly when its last value is
+//! larger than the earlier window's first. [`count_increases_windowed`] uses this to compare values
+//! `window_size` apart, which works for any size of window without building the sums. The window
+//! sizes for each part can be changed, see [`Options`].
+use crate::errors::{check_input, check_pattern, to_validation, Validation};
+use crate::util::input::parse_lines;
+use crate::util::params::Params;
+
+/// The window size for each part, can be changed with `--param part_one_window=<n>` and
+/// `--param part_two_window=<n>`.
+#[derive(Eq, PartialEq, Debug)]
+struct Options {
+    part_one_window: usize,
+    part_two_window: usize,
+}
+
+impl From<&Params> for Options {
+    fn from(params: &Params) -> Self {
+        Options {
+            part_one_window: params.get_or("part_one_window", 1),
+            part_two_window: params.get_or("part_two_window", 3),
+        }
+    }
+}
+
+/// Check the input is one depth per line, see [`validate`], and parse the depths for [`part_one`]
+/// and [`part_two`].
+pub fn parse(contents: &str) -> Vec<i32> {
+    check_input(1, contents, validate);
+    parse_lines(contents.lines()).collect()
+}
+
+/// Count the windows that increment, using the window size for part one, see [`Options`].
+pub fn part_one(depths: &[i32]) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    Some(report_increases(depths, options.part_one_window).to_string())
+}
+
+/// Count the windows that increment, using the window size for part two, see [`Options`].
+pub fn part_two(depths: &[i32]) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    Some(report_increases(depths, options.part_two_window).to_string())
+}
+
+/// Print and return the count of windows of `window_size` that increment
+fn report_increases(depths: &[i32], window_size: usize) -> usize {
+    let count = count_increases_windowed(depths, window_size);
+    say!(
+        "There are {} windows of {} that increment",
+        count,
+        window_size
+    );
+
+    count
+}
+
+/// Each line should be a depth
+pub fn validate(input: &str) -> Validation {
+    to_validation(check_pattern(input, r"\d+", "a depth"))
+}
+
+/// For `--detect`, see [`crate::detect`]. The digit grids of days 3, 9, 11 and 15 are also lines of
+/// digits, but their rows are longer than a depth, and can start with a 0, which a depth doesn't.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+        && input
+            .lines()
+            .all(|line| line.len() <= 6 && !line.starts_with('0'))
+}
+
+/// Iterate over a moving window of pairs, returning the count where the second number is greater
+/// that the first.
+///
+/// # Example from puzzle specification
+/// ```rust
+/// let input = vec![
+///   199, // N/A - first item
+///   200, // yes
+///   208, // yes
+///   210, // yes
+///   200, // no
+///   207, // yes
+///   240, // yes
+///   269, // yes
+///   260, // no
+///   263  // yes
+/// ];
+///
+/// assert_eq!(count_increments(&input), 7);
+/// ```
+///
+/// [`part_one`] and [`part_two`] now use [`count_increases_windowed`].
+#[cfg(test)]
+fn count_increments(depths: &[i32]) -> usize {
+    count_increases_windowed(depths, 1)
+}
+
+/// Count how many moving windows of `window_size` consecutive values have a larger sum than the
+/// window before. Each window shares all but one value with the previous one, so this only needs
+/// to compare the value entering the window with the value leaving it, i.e. values `window_size`
+/// apart. A `window_size` of `1` is the same as `count_increments`.
+///
+/// # Example from puzzle specification
+/// ```rust
+/// assert_eq!(count_increases_windowed(&input, 1), 7);
+/// assert_eq!(count_increases_windowed(&input, 3), 5);
+/// ```
+fn count_increases_windowed(depths: &[i32], window_size: usize) -> usize {
+    depths
+        .iter()
+        // combine with itself, offset by the window size, so that each pair is the value leaving a
+        // window and the value entering the next one
+        .zip(depths.iter().skip(window_size))
+        // include only those that increment
+        .filter(|(leaving, entering)| entering > leaving)
+        // return the count of number of windows that increment
+        .count()
+}
+
+/// Iterate over a moving window of three consecutive items, returning a vector where each item is
+/// the sum of te current window.
+///
+/// # Example from puzzle specification
+/// ```rust
+/// assert_eq!(
+///   sum_windows(&input),
+///   vec!(
+///     607, // 199 + 200 + 208
+///     618, // 200 + 208 + 210
+///     618, // 208 + 210 + 200
+///     617, // 210 + 200 + 207
+///     647, // 200 + 207 + 240
+///     716, // 207 + 240 + 269
+///     769, // 240 + 269 + 260
+///     792  // 269 + 260 + 263
+///   )
+/// );
+/// ```
+///
+/// No longer used by [`part_two`], see [`count_increases_windowed`].
+#[allow(dead_code)]
+fn sum_windows(depths: &[i32]) -> Vec<i32> {
+    // create the moving window by combining iterators over the input offset by 0, 1, and 2
+    return depths
+        .windows(3)
+        // map those to the sum of the window
+        .map(|window| window.iter().sum())
+        // and coerce to the expected output type
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::params::Params;
+    use crate::year_2021::day_1::{
+        count_increases_windowed, count_increments, sum_windows, Options,
+    };
+
+    #[test]
+    fn can_count_increments() {
+        assert_eq!(count_increments(&test_data()), 7)
+    }
+
+    fn test_data() -> Vec<i32> {
+        vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263]
+    }
+
+    #[test]
+    fn can_iterate_windows() {
+        assert_eq!(
+            sum_windows(&test_data()),
+            vec!(607, 618, 618, 617, 647, 716, 769, 792)
+        );
+        assert_eq!(count_increments(&sum_windows(&test_data())), 5);
+    }
+
+    #[test]
+    fn can_count_increases_windowed() {
+        let depths = test_data();
+        assert_eq!(count_increases_windowed(&depths, 1), 7);
+        assert_eq!(count_increases_windowed(&depths, 3), 5);
+
+        // Check against summing the windows for every size, including sizes too big for any pairs
+        for window_size in 1..=12 {
+            let sums: Vec<i32> = depths
+                .windows(window_size)
+                .map(|window| window.iter().sum())
+                .collect();
+            assert_eq!(
+                count_increases_windowed(&depths, window_size),
+                count_increments(&sums)
+            );
+        }
+
+        assert_eq!(count_increases_windowed(&[], 3), 0);
+    }
+
+    #[test]
+    fn can_build_options() {
+        assert_eq!(
+            Options::from(&Params::default()),
+            Options {
+                part_one_window: 1,
+                part_two_window: 3
+            }
+        );
+
+        let params = Params::parse(
+            ["--param", "part_two_window=5"]
+                .map(String::from)
+                .into_iter(),
+        );
+        assert_eq!(
+            Options::from(&params),
+            Options {
+                part_one_window: 1,
+                part_two_window: 5
+            }
+        );
+    }
+}
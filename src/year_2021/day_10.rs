@@ -0,0 +1,494 @@
+//! This is my solution for [Advent of Code - Day 10 - _Syntax Scoring_](https://adventofcode.com/2021/day/10)
+//!
+//! Today was about matching different types of braces, firstly (part one) to check that the braces matched, and
+//! secondly (part two) where closing braces had been missed off the end of the string to close them. Implementing
+//! the state as a stack of characters seemed sensible. An opening brace triggers putting the expected closing brace
+//! on the stack, a closing brace pops the expected brace off the stack and returns an error if it doesn't match.
+//! Anything left on the stack at the end is returned as the required characters to 'autocomplete' the rest of the
+//! line. I originally implemented this as putting the opening brace on the stack, but that required doing the mapping
+//! from opening to closing brace character both when checking for a match and when returning outstanding characters.
+//! It was cleaner to do the mapping upfront.
+//!
+//! [`check_line`] do most of the work for both parts. It was a good opportunity to use Rust's [`Result`] type, and I
+//! implemented a custom enum [`ParseError`] to capture the errors due to syntax, vs the errors I needed to include
+//! to satisfy edge cases that weren't in the puzzle input - namely characters that weren't any of the 8 braces, and
+//! the case where there is one or more closing braces encountered when the stack is empty. [`sum_errors`] wraps
+//! [`check_line`] for part one, filtering out ny lines that don't return a [`ParseError::MISMATCH`], mapping those
+//! to the correct score, and summing the results. [`score_line_autocomplete`] takes the characters returned from a
+//! successfully parsed line and folds them into the expected score. [`median_autocomplete_score`] handles the
+//! plumbing of getting the list of successful [`check_line`] results, mapping them to the autocomplete score and
+//! returning the median score required for part two's puzzle result.
+//!
+//! To make this useful as a tiny bracket linter, [`diagnose_line`] reports where the first error is, and a
+//! [`Fix`] that corrects it with a single character. Replacing, inserting, or deleting a character can each fix the
+//! error, so each is tried, and the one that lets the rest of the line get furthest before the next error is chosen.
+//!
+//! One final piece of trivia, I looked into using the characters' unicode points to avoid using a hash map, but they
+//! were not consistent. `(` and `)` are consecutive, but the others are all separated by one character.
+//! ```
+//! println!("{}", "()[]{}<>".chars().map(|c| c as usize).join(", "));
+//! // 40, 41, 91, 93, 123, 125, 60, 62
+//! ```
+
+use crate::errors::{check_input, check_pattern, to_validation, Validation};
+use crate::util::stats::median_low;
+use std::collections::HashMap;
+use std::env;
+
+use crate::year_2021::day_10::ParseError::{MISMATCH, UNEXPECTED};
+
+/// Check each line only contains brackets, see [`validate`]. Both [`part_one`] and [`part_two`] work
+/// on the lines directly, so the input is passed on as it is.
+pub fn parse(contents: &str) -> String {
+    check_input(10, contents, validate);
+    contents.to_string()
+}
+
+/// Score the first illegal character on each corrupted line, see [`sum_errors`].
+///
+/// - Pass `--trace` to also print a [`Diagnosis`] for each corrupted line, see [`diagnose_line`].
+pub fn part_one(contents: &str) -> Option<String> {
+    let syntax_error_score = sum_errors(contents.lines());
+    say!("Syntax error score: {}", syntax_error_score);
+
+    if env::args().any(|arg| arg == "--trace") {
+        for (number, line) in contents.lines().enumerate() {
+            if let Some(diagnosis) = diagnose_line(line) {
+                say!("Line {}: {:?}", number + 1, diagnosis);
+            }
+        }
+    }
+
+    Some(syntax_error_score.to_string())
+}
+
+/// Score the completions of the incomplete lines, see [`median_autocomplete_score`]
+pub fn part_two(contents: &str) -> Option<String> {
+    let autocomplete_score = median_autocomplete_score(contents.lines());
+    say!("Autocomplete score: {}", autocomplete_score);
+
+    Some(autocomplete_score.to_string())
+}
+
+/// Each line should only contain brackets
+pub fn validate(input: &str) -> Validation {
+    to_validation(check_pattern(input, r"[()\[\]{}<>]+", "only brackets"))
+}
+
+/// For `--detect`, see [`crate::detect`]. Day 18's pairs also start with brackets, but always contain numbers, so it is
+/// enough for it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// Used to indicate an error when parsing strings of braces
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+enum ParseError {
+    /// A closing brace was encountered that doesn't match the expected character from the corresponding opening brace
+    MISMATCH { expected: char, actual: char },
+    /// Any other unexpected character i.e. not part of one of the four brace pairs, or a closing brace without a
+    /// corresponding opening brace.
+    UNEXPECTED(char),
+}
+
+/// A single character edit to a line, positions are byte indices into the original line
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+enum Fix {
+    /// Replace the character at `position` with `with`
+    Replace { position: usize, with: char },
+    /// Insert `chr` before the character currently at `position`
+    Insert { position: usize, chr: char },
+    /// Remove the character at `position`
+    Delete { position: usize },
+}
+
+impl Fix {
+    /// Return a copy of `line` with the fix applied
+    fn apply(&self, line: &str) -> String {
+        let mut fixed = line.to_string();
+        match *self {
+            Fix::Replace { position, with } => {
+                let len = fixed[position..].chars().next().map_or(0, char::len_utf8);
+                fixed.replace_range(position..position + len, &with.to_string());
+            }
+            Fix::Insert { position, chr } => fixed.insert(position, chr),
+            Fix::Delete { position } => {
+                fixed.remove(position);
+            }
+        }
+        fixed
+    }
+
+    /// The number of bytes the fix adds to (or removes from) the line
+    fn shift(&self, line: &str) -> isize {
+        let len_at =
+            |position: usize| line[position..].chars().next().map_or(0, char::len_utf8) as isize;
+        match *self {
+            Fix::Replace { position, with } => with.len_utf8() as isize - len_at(position),
+            Fix::Insert { chr, .. } => chr.len_utf8() as isize,
+            Fix::Delete { position } => -len_at(position),
+        }
+    }
+}
+
+/// The result of [`diagnose_line`]: the first error in a line, where it is, and how to fix it
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+struct Diagnosis {
+    /// The byte index of the character that caused the error
+    position: usize,
+    error: ParseError,
+    /// A single character change that makes the line valid up to, and including, `position`
+    fix: Fix,
+}
+
+/// Find all the lines in the input that return a mismatch error and sum a score based on the character that was 
+/// incorrect.
+#[rustfmt::skip] // Keep match readable
+fn sum_errors<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> usize {
+    lines
+        .into_iter()
+        .map(|line| check_line(line.as_ref()))
+        .map(|res| match res {
+            Err(MISMATCH { expected: _, actual: ')' }) => 3,
+            Err(MISMATCH { expected: _, actual: ']' }) => 57,
+            Err(MISMATCH { expected: _, actual: '}' }) => 1197,
+            Err(MISMATCH { expected: _, actual: '>' }) => 25137,
+            _ => 0usize,
+        })
+        .sum()
+}
+
+/// Given a string, either return the list of closing braces needed to completely match the opening braces in order,
+/// or return a [`ParseError`] if a closing brace that doesn't match the expected value at any point in the string.
+fn check_line(line: &str) -> Result<Vec<char>, ParseError> {
+    check_line_with_position(line).map_err(|(_, err)| err)
+}
+
+/// As [`check_line`], but errors also include the byte index of the character that caused them.
+fn check_line_with_position(line: &str) -> Result<Vec<char>, (usize, ParseError)> {
+    // Stack of the currently expected closing braces
+    let mut stack: Vec<char> = Vec::new();
+
+    let braces = HashMap::from([('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')]);
+
+    for (position, chr) in line.char_indices() {
+        match chr {
+            // It's easier to map the opening => closing brace here as it keeps it in one place
+            '(' | '[' | '{' | '<' => stack.push(*braces.get(&chr).expect("Unreachable")),
+            ')' | ']' | '}' | '>' => {
+                if let Some(expected) = stack.pop() {
+                    if chr != expected {
+                        return Err((
+                            position,
+                            MISMATCH {
+                                expected,
+                                actual: chr,
+                            },
+                        ));
+                    }
+                } else {
+                    return Err((position, UNEXPECTED(chr)));
+                }
+            }
+            _ => return Err((position, UNEXPECTED(chr))),
+        }
+    }
+
+    // We need t reveser the stack to keep the First In First Out ordering
+    let autocomplete = stack.iter().map(|&c| c).rev().collect();
+
+    return Ok(autocomplete);
+}
+
+/// Find the first error in a line, if any, and suggest a single character [`Fix`]. The candidates are:
+/// - For a mismatch, replace the closing brace with the expected one, or insert the expected one before it
+/// - For a closing brace with nothing to close, insert the matching opening brace before it
+/// - Otherwise, or as well, delete the offending character
+///
+/// Each fixes the error at that position, so they are compared by how far the rest of the fixed line then gets
+/// before the next error. Lines with no more errors are better still, especially if they are also complete. Ties go
+/// to the first candidate in the order above. Returns `None` if the line has no errors, though it may be incomplete.
+fn diagnose_line(line: &str) -> Option<Diagnosis> {
+    let (position, error) = check_line_with_position(line).err()?;
+
+    let mut candidates = Vec::new();
+    match error {
+        MISMATCH { expected, .. } => {
+            candidates.push(Fix::Replace {
+                position,
+                with: expected,
+            });
+            candidates.push(Fix::Insert {
+                position,
+                chr: expected,
+            });
+        }
+        UNEXPECTED(chr) => {
+            let opening = ['(', '[', '{', '<'];
+            if let Some(index) = [')', ']', '}', '>'].iter().position(|&c| c == chr) {
+                candidates.push(Fix::Insert {
+                    position,
+                    chr: opening[index],
+                });
+            }
+        }
+    }
+    candidates.push(Fix::Delete { position });
+
+    // How far through the original line the fixed line gets before the next error. Lines with no more errors are
+    // all better than that, and the fewer braces left to close the better.
+    let reach = |fix: &Fix| match check_line_with_position(&fix.apply(line)) {
+        Ok(remaining) => isize::MAX - remaining.len() as isize,
+        Err((next_position, _)) => next_position as isize - fix.shift(line),
+    };
+
+    // max_by_key returns the last of equal elements, so reverse to prefer the earlier candidates
+    let fix = candidates.into_iter().rev().max_by_key(reach)?;
+
+    Some(Diagnosis {
+        position,
+        error,
+        fix,
+    })
+}
+
+/// Given the list of braces needed to complete a string, fold them into the autocomplete score
+fn score_line_autocomplete(line: Vec<char>) -> usize {
+    line.iter()
+        .flat_map(|c| match c {
+            ')' => Some(1),
+            ']' => Some(2),
+            '}' => Some(3),
+            '>' => Some(4),
+            _ => None,
+        })
+        .fold(0, |acc, score| acc * 5 + score)
+}
+
+/// Find all the lines in the input that are valid, work out the autocomplete score for each, and return the median
+/// score. The puzzle promises an odd number of incomplete lines, otherwise this takes the lower of the middle pair, and
+/// `0` if there are none.
+fn median_autocomplete_score<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> usize {
+    let scores: Vec<usize> = lines
+        .into_iter()
+        .flat_map(|l| check_line(l.as_ref()).ok())
+        .map(score_line_autocomplete)
+        .collect();
+
+    median_low(&scores).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year_2021::day_10::ParseError::{MISMATCH, UNEXPECTED};
+    use crate::year_2021::day_10::{
+        check_line, diagnose_line, median_autocomplete_score, score_line_autocomplete, sum_errors,
+        Diagnosis, Fix,
+    };
+
+    #[test]
+    fn can_check_valid_line() {
+        let valid_lines = [
+            "([])",
+            "{()()()}",
+            "<([{}])>",
+            "[<>({}){}[([])<>]]",
+            "(((((((((())))))))))",
+        ];
+
+        valid_lines
+            .iter()
+            .for_each(|&line| assert_eq!(check_line(line), Ok(vec![])));
+    }
+
+    #[test]
+    fn can_check_invalid_line() {
+        let invalid_lines = [
+            (
+                "{([(<{}[<>[]}>{[]{[(<()>",
+                MISMATCH {
+                    expected: ']',
+                    actual: '}',
+                },
+            ),
+            (
+                "[[<[([]))<([[{}[[()]]]",
+                MISMATCH {
+                    expected: ']',
+                    actual: ')',
+                },
+            ),
+            (
+                "[{[{({}]{}}([{[{{{}}([]",
+                MISMATCH {
+                    expected: ')',
+                    actual: ']',
+                },
+            ),
+            (
+                "[<(<(<(<{}))><([]([]()",
+                MISMATCH {
+                    expected: '>',
+                    actual: ')',
+                },
+            ),
+            (
+                "<{([([[(<>()){}]>(<<{{",
+                MISMATCH {
+                    expected: ']',
+                    actual: '>',
+                },
+            ),
+        ];
+
+        invalid_lines
+            .iter()
+            .for_each(|&(line, err)| assert_eq!(check_line(line), Err(err)));
+    }
+
+    #[test]
+    fn can_check_incomplete_line() {
+        let incomplete_lines = [
+            ("[({(<(())[]>[[{[]{<()<>>", "}}]])})]"),
+            ("[(()[<>])]({[<{<<[]>>(", ")}>]})"),
+            ("(((({<>}<{<{<>}{[]{[]{}", "}}>}>))))"),
+            ("{<[[]]>}<{[{[{[]{()[[[]", "]]}}]}]}>"),
+            ("<{([{{}}[<[[[<>{}]]]>[]]", "])}>"),
+        ];
+
+        incomplete_lines.iter().for_each(|&(line, expected)| {
+            assert_eq!(check_line(line), Ok(expected.chars().collect()))
+        })
+    }
+
+    #[test]
+    fn can_score_incomplete_line() {
+        let incomplete_lines: [(&str, usize); 5] = [
+            ("}}]])})]", 288957),
+            (")}>]})", 5566),
+            ("}}>}>))))", 1480781),
+            ("]]}}]}]}>", 995444),
+            ("])}>", 294),
+        ];
+
+        incomplete_lines.iter().for_each(|&(remaining, expected)| {
+            assert_eq!(
+                score_line_autocomplete(remaining.chars().collect()),
+                expected
+            )
+        })
+    }
+
+    fn sample_input() -> String {
+        "[({(<(())[]>[[{[]{<()<>>\n\
+             [(()[<>])]({[<{<<[]>>(\n\
+             {([(<{}[<>[]}>{[]{[(<()>\n\
+             (((({<>}<{<{<>}{[]{[]{}\n\
+             [[<[([]))<([[{}[[()]]]\n\
+             [{[{({}]{}}([{[{{{}}([]\n\
+             {<[[]]>}<{[{[{[]{()[[[]\n\
+             [<(<(<(<{}))><([]([]()\n\
+             <{([([[(<>()){}]>(<<{{\n\
+             <{([{{}}[<[[[<>{}]]]>[]]"
+            .to_string()
+    }
+
+    #[test]
+    fn can_sum_errors() {
+        assert_eq!(sum_errors(sample_input().lines()), 26397);
+    }
+
+    #[test]
+    fn can_get_median() {
+        assert_eq!(median_autocomplete_score(sample_input().lines()), 288957);
+
+        // Not possible in the puzzle, but there may be an even number of incomplete lines, or none
+        assert_eq!(median_autocomplete_score("(\n<\n[\n{".lines()), 2);
+        assert_eq!(median_autocomplete_score("{]\n(>".lines()), 0);
+        assert_eq!(median_autocomplete_score("".lines()), 0);
+    }
+
+    #[test]
+    fn can_diagnose_line() {
+        assert_eq!(diagnose_line("[({(<(())[]>[[{[]{<()<>>"), None);
+        assert_eq!(diagnose_line("([])"), None);
+
+        // The first corrupted example, replacing the } gets further than inserting a ] before it
+        assert_eq!(
+            diagnose_line("{([(<{}[<>[]}>{[]{[(<()>"),
+            Some(Diagnosis {
+                position: 12,
+                error: MISMATCH {
+                    expected: ']',
+                    actual: '}'
+                },
+                fix: Fix::Replace {
+                    position: 12,
+                    with: ']'
+                }
+            })
+        );
+
+        // A missing closing brace is best fixed by inserting it
+        assert_eq!(
+            diagnose_line("{(<>}").map(|diagnosis| diagnosis.fix),
+            Some(Fix::Insert {
+                position: 4,
+                chr: ')'
+            })
+        );
+
+        // An extra closing brace is best deleted
+        assert_eq!(
+            diagnose_line("{()]}").map(|diagnosis| diagnosis.fix),
+            Some(Fix::Delete { position: 3 })
+        );
+
+        // A closing brace with nothing to close can have its opening brace inserted
+        assert_eq!(
+            diagnose_line("()>"),
+            Some(Diagnosis {
+                position: 2,
+                error: UNEXPECTED('>'),
+                fix: Fix::Insert {
+                    position: 2,
+                    chr: '<'
+                }
+            })
+        );
+
+        // Other characters can only be deleted, positions are in bytes
+        assert_eq!(
+            diagnose_line("(é)"),
+            Some(Diagnosis {
+                position: 1,
+                error: UNEXPECTED('é'),
+                fix: Fix::Delete { position: 1 }
+            })
+        );
+    }
+
+    #[test]
+    fn fixes_make_the_prefix_valid() {
+        for line in [
+            "{([(<{}[<>[]}>{[]{[(<()>",
+            "[[<[([]))<([[{}[[()]]]",
+            "[{[{({}]{}}([{[{{{}}([]",
+            "[<(<(<(<{}))><([]([]()",
+            "<{([([[(<>()){}]>(<<{{",
+            "()>",
+            "(é)",
+        ] {
+            let diagnosis = diagnose_line(line).unwrap();
+            let fixed = diagnosis.fix.apply(line);
+            let end = (diagnosis.position as isize + diagnosis.fix.shift(line)) as usize;
+            let prefix = &fixed[..=end.min(fixed.len() - 1)];
+            assert!(
+                check_line(prefix).is_ok(),
+                "{} fixed to {} is invalid",
+                line,
+                fixed
+            );
+        }
+    }
+}
@@ -0,0 +1,512 @@
+//! This is my solution for [Advent of Code - Day 11 - _Dumbo Octopus_](https://adventofcode.com/2021/day/11)
+//!
+//! Today was again using a grid of digits. This time we had to manage cascading updates as one cell overflowing
+//! beyond 9 caused neighbouring cells to increase, potentially triggering more cells. If you've ever played
+//! [Pandemic](https://boardgamegeek.com/boardgame/30549/pandemic), today's puzzle may give you flashbacks.
+//!
+//! The grid shared a lot of similarities with the grid in [`super::day_9`], and so I reused that implementation
+//! adding extra methods to meet today's needs. Whilst writing the solution I just imported the Grid directly from
+//! [`super::day_9`], but once submitted I did some cleanup refactoring and extracted it into its own module under
+//! [`crate::util`], leaving the puzzle specific methods in their own impl blocks in the relevant module. Having a
+//! comprehensive set of tests made this a pretty easy refactor.
+//!
+//! [`Grid::set`] was needed to allow incrementing the values in the cells as the octopuses powered up.
+//! [`Grid::get_all_surrounds`] was needed separate to [`Grid::get_orthogonal_surrounds`] as today's
+//! requirements considered diagonal cells adjacent. This caused some head scratching when I failed to notice this
+//! difference, but [`Grid::print`] let me visualise the grid and work out what was wrong.
+//!
+//! The bulk of today's solutions is handled by [`Octopuses::step`] which handles a single cycle of incrementing the
+//! octopuses, and resolving any resulting flashes. [`Octopuses::count_flashes`] implements part one by repeatedly
+//! calling [`Octopuses::step`] the required number of times, summing the resulting flash counts.
+//! [`Octopuses::first_synchronised_step`] also repeatedly calls [`Octopuses::step`] until the count of flashes is
+//! equal to the number of cells in the grid, indicating all octopuses flashed in sync, and returns the step it has
+//! reached. Not every grid synchronises, so it also watches for the energy levels repeating with
+//! [`crate::util::cycle`], and gives up once they do, as it has seen every state the octopuses will reach by then.
+//! [`Octopuses::synchronised_period`] carries on from there the same way, to see if they flash together again.
+//!
+//! These were originally methods on [`Grid`] that assumed octopuses flash when their energy goes above 9. To try out
+//! other inputs, they now belong to [`Octopuses`], which wraps a grid of any size with the energy level to flash at,
+//! and keeps count of the steps taken. The threshold and the number of steps for part one can be changed, see
+//! [`Options`].
+
+use crate::errors::{check_grid, check_input, to_validation, Validation};
+use crate::util::cycle::{find_cycle, Cycle, CycleDetector};
+use crate::util::params::Params;
+use crate::util::render::{draw_frame, frame_delay, visualise_enabled, FrameSource, Renderable};
+use crate::util::repl::{repl_enabled, run_repl, Steppable};
+use std::collections::HashSet;
+
+/// The options for the simulation, can be changed with `--param flash_threshold=<n>` and
+/// `--param part_one_steps=<n>`.
+#[derive(Eq, PartialEq, Debug)]
+struct Options {
+    /// Octopuses flash when their energy level goes above this
+    flash_threshold: u8,
+    /// The number of steps to count the flashes for in part one
+    part_one_steps: usize,
+}
+
+impl From<&Params> for Options {
+    fn from(params: &Params) -> Self {
+        Options {
+            flash_threshold: params.get_or("flash_threshold", 9),
+            part_one_steps: params.get_or("part_one_steps", 100),
+        }
+    }
+}
+
+#[doc(inline)]
+pub use crate::util::grid::Grid;
+
+impl Grid {
+    /// Iterate through the four orthogonal cells, collecting the 2 - 4 values into a vector. Include the co-ordinates
+    /// in the returned vector so that [`Grid::get_basin`] can recursively expand the set of cells in the basin.
+    pub fn get_all_surrounds(&self, y: usize, x: usize) -> Vec<((usize, usize), u8)> {
+        [
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ] // NW W SW N S NE E NW
+        .iter()
+        .flat_map(|&(dy, dx)| self.get_relative(y, x, dy, dx))
+        .collect()
+    }
+}
+
+/// A grid of octopuses of any size, that flash once their energy goes above `threshold`
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Octopuses {
+    grid: Grid,
+    threshold: u8,
+    /// The number of steps taken so far
+    steps: usize,
+}
+
+impl Octopuses {
+    /// Start simulating the octopuses with their current energy levels in `grid`
+    fn new(grid: Grid, threshold: u8) -> Octopuses {
+        Octopuses {
+            grid,
+            threshold,
+            steps: 0,
+        }
+    }
+
+    /// This is the core logic to implement a single pass of the octopuses powering up required for both parts. It
+    /// first iterates through all the cells incrementing them by one. Any that are now above the threshold are added to
+    /// a queue of cells that have triggered a flash. We then take cells off the to flash list, adding them to a set of
+    /// all co-ordinates that have flashed this iteration (so that we can 0 them later, and also ensure no cell flashes
+    /// twice). If the cell was new to the flashes set, it also increments its neighbours, adding any that exceed the
+    /// threshold to the trigger queue. Once the queue has been exhausted, we iterate through the resulting set of
+    /// co-ordinates that flashed this iteration, set them to 0 and return the size of the set, as this is the metric
+    /// needed for both parts' solutions.
+    fn step(&mut self) -> usize {
+        let grid = &mut self.grid;
+        let mut flashes: HashSet<(usize, usize)> = HashSet::new();
+        let mut to_flash: Vec<(usize, usize)> = Vec::new();
+
+        for i in 0..grid.numbers.len() {
+            if let Some(((y, x), val)) = grid.get_with_coords(i) {
+                grid.set(y, x, val.saturating_add(1));
+
+                // Any already over the threshold at the start will also flash
+                if val >= self.threshold {
+                    to_flash.push((y, x));
+                }
+            }
+        }
+
+        while let Some((y, x)) = to_flash.pop() {
+            if !flashes.insert((y, x)) {
+                // already flashed
+                continue;
+            }
+
+            for ((y1, x1), val) in grid.get_all_surrounds(y, x) {
+                // Cap the energy so that thresholds near u8::MAX can't overflow
+                grid.set(y1, x1, val.saturating_add(1));
+                if val == self.threshold {
+                    to_flash.push((y1, x1))
+                }
+            }
+        }
+
+        for &(y, x) in &flashes {
+            grid.set(y, x, 0);
+        }
+
+        self.steps += 1;
+        flashes.len()
+    }
+
+    /// Solution to part one. Step the octopuses `<cycles>` times, summing the flashes this causes.
+    fn count_flashes(&mut self, cycles: usize) -> usize {
+        (0..cycles).map(|_| self.step()).sum()
+    }
+
+    /// The cycle of energy levels the octopuses will settle into from their current state
+    fn cycle(&self) -> Cycle {
+        find_cycle(
+            self,
+            |octopuses| {
+                octopuses.step();
+            },
+            |octopuses| octopuses.grid.numbers.clone(),
+        )
+    }
+
+    /// Solution to part two. Step the octopuses until the set of flashes is the same size as the grid, i.e. all cells
+    /// triggered a flash. Return the number of the step that happened on, counting any steps already taken. Each state
+    /// is also passed to a [`CycleDetector`], and by the time the energy levels repeat the octopuses have been through
+    /// every state they can reach, so if they haven't synchronised by then they never will, and this returns `None`.
+    fn first_synchronised_step(&mut self) -> Option<usize> {
+        let target = self.grid.len();
+        let mut detector = CycleDetector::new(self.grid.numbers.clone());
+
+        loop {
+            if self.step() == target {
+                return Some(self.steps);
+            }
+            if detector.observe(self.grid.numbers.clone()).is_some() {
+                return None;
+            }
+        }
+    }
+
+    /// The number of steps between the octopuses flashing together, once they have first synchronised. Straight after
+    /// they all flash every octopus is at 0, and if that state is part of their cycle they will all flash again when it
+    /// comes round, so this carries on stepping from there. If they only synchronise once, on the way into the cycle,
+    /// or never, this returns `None`.
+    fn synchronised_period(&self) -> Option<usize> {
+        let mut octopuses = self.clone();
+        let first = octopuses.first_synchronised_step()?;
+        let next = octopuses.first_synchronised_step()?;
+
+        Some(next - first)
+    }
+}
+
+impl Renderable for Octopuses {
+    /// Octopuses that have just flashed are at 0, draw these as `*` so the flashes stand out
+    fn render(&self) -> String {
+        self.grid.print().replace('0', "*")
+    }
+}
+
+impl FrameSource for Octopuses {
+    /// The octopuses never stop flashing, so there is always another step
+    fn step(&mut self) -> bool {
+        Octopuses::step(self);
+        true
+    }
+
+    fn current_step(&self) -> usize {
+        self.steps
+    }
+}
+
+impl Steppable for Octopuses {
+    /// Octopuses that flashed are reset to 0, so once a step has been taken the zeros are the flashes
+    fn stats(&self) -> String {
+        let zeros = self
+            .grid
+            .numbers
+            .iter()
+            .filter(|&&energy| energy == 0)
+            .count();
+        if self.steps == 0 {
+            format!("Step 0: {} octopuses", self.grid.len())
+        } else {
+            format!(
+                "Step {}: {} of {} octopuses flashed",
+                self.steps,
+                zeros,
+                self.grid.len()
+            )
+        }
+    }
+}
+
+/// Animate the octopuses flashing until `last_step`, when they synchronise
+fn visualise(mut octopuses: Octopuses, last_step: usize) {
+    let delay = frame_delay();
+    draw_frame(&octopuses, "Step 0", delay);
+
+    while octopuses.steps < last_step {
+        let flashes = octopuses.step();
+        let caption = format!("Step {}: {} flashes", octopuses.steps, flashes);
+        draw_frame(&octopuses, &caption, delay);
+    }
+}
+
+/// Check the input is a rectangular grid of energy levels, see [`validate`], and parse it as the
+/// [`Octopuses`] for [`part_one`] and [`part_two`], flashing at the threshold from [`Options`].
+pub fn parse(contents: &str) -> Octopuses {
+    check_input(11, contents, validate);
+    let options = Options::from(&Params::from_args());
+    Octopuses::new(Grid::from(contents), options.flash_threshold)
+}
+
+/// Count the flashes over the number of steps for part one, which can be changed, see [`Options`]
+pub fn part_one(octopuses: &Octopuses) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    let flashes = octopuses.clone().count_flashes(options.part_one_steps);
+    say!(
+        "There were {} flashes in {} cycles",
+        flashes,
+        options.part_one_steps
+    );
+
+    Some(flashes.to_string())
+}
+
+/// Find the first step where all the octopuses flash together, if they ever do.
+///
+/// - Pass `--visualise` to watch the octopuses flash.
+/// - Pass `--repl` to step through the octopuses interactively, see [`crate::util::repl`].
+pub fn part_two(octopuses: &Octopuses) -> Option<String> {
+    let synchronised = octopuses.clone().first_synchronised_step();
+
+    if visualise_enabled() {
+        // If they never synchronise, stop once they have been through every state they will reach
+        let cycle = octopuses.cycle();
+        visualise(
+            octopuses.clone(),
+            synchronised.unwrap_or(cycle.start + cycle.length),
+        );
+    }
+
+    if repl_enabled() {
+        run_repl(octopuses);
+    }
+
+    match synchronised {
+        Some(iterations) => say!(
+            "It took {} cycles for the flashes to synchronise.",
+            iterations
+        ),
+        None => say!(
+            "The flashes never synchronise, the energy levels {}.",
+            octopuses.cycle()
+        ),
+    }
+    if let Some(period) = octopuses.synchronised_period() {
+        say!("After that they synchronise every {} cycles.", period);
+    }
+
+    synchronised.map(|iterations| iterations.to_string())
+}
+
+/// The energy levels should be a rectangular grid of digits
+pub fn validate(input: &str) -> Validation {
+    to_validation(check_grid(input, "0123456789"))
+}
+
+/// For `--detect`, see [`crate::detect`]. Days 3, 9 and 15 are also grids of digits, but the octopuses are always 10 by
+/// 10, and use digits other than 0 and 1. Day 15's example is also a 10 by 10 grid of digits, so the two examples can't
+/// be told apart.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+        && input.contains(|c: char| ('2'..='9').contains(&c))
+        && input.lines().count() == 10
+        && input.lines().all(|line| line.len() == 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::cycle::Cycle;
+    use crate::util::grid::Grid;
+    use crate::util::params::Params;
+    use crate::util::render::Renderable;
+    use crate::year_2021::day_11::{Octopuses, Options};
+    use std::collections::HashSet;
+
+    #[test]
+    fn can_update_grid() {
+        let mut grid = Grid::from(
+            "11111
+19991
+19191
+19991
+11111",
+        );
+        let expected = Grid::from(
+            "21111
+19991
+19291
+19991
+11111",
+        );
+
+        grid.set(0, 0, 2);
+        grid.set(2, 2, 2);
+
+        assert_eq!(grid, expected);
+    }
+
+    #[test]
+    fn can_get_all_surrounds() {
+        let grid = Grid::from("123\n456\n789");
+        let surrounds: HashSet<u8> = grid
+            .get_all_surrounds(1, 1)
+            .iter()
+            .map(|&(_, v)| v)
+            .collect();
+        assert_eq!(surrounds.len(), 8);
+        assert!(!surrounds.contains(&5))
+    }
+
+    #[test]
+    fn can_iterate_and_flash() {
+        let grid = Grid::from(
+            "11111
+19991
+19191
+19991
+11111",
+        );
+
+        let expected = Grid::from(
+            "34543
+40004
+50005
+40004
+34543",
+        );
+
+        let mut octopuses = Octopuses::new(grid, 9);
+        let flashes = octopuses.step();
+
+        assert_eq!(flashes, 9);
+        assert_eq!(octopuses.grid, expected);
+        assert_eq!(octopuses.steps, 1);
+    }
+
+    #[test]
+    fn can_count_flashes() {
+        let grid = Grid::from(
+            "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526",
+        );
+
+        let octopuses = Octopuses::new(grid, 9);
+        assert_eq!(octopuses.clone().count_flashes(10), 204);
+        assert_eq!(octopuses.clone().count_flashes(100), 1656);
+    }
+
+    #[test]
+    fn can_run_until_sync() {
+        let grid = Grid::from(
+            "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526",
+        );
+
+        let octopuses = Octopuses::new(grid, 9);
+        assert_eq!(octopuses.clone().first_synchronised_step(), Some(195));
+        // Once they're all at 0 they go up together, and flash together every 10 steps
+        assert_eq!(octopuses.synchronised_period(), Some(10));
+    }
+
+    #[test]
+    fn can_render() {
+        let grid = Grid::from(
+            "11111
+19991
+19191
+19991
+11111",
+        );
+        let mut octopuses = Octopuses::new(grid, 9);
+        octopuses.step();
+
+        assert_eq!(octopuses.render(), "34543\n4***4\n5***5\n4***4\n34543");
+    }
+
+    #[test]
+    fn can_use_other_thresholds_and_sizes() {
+        // A lower threshold makes the same grid flash more
+        let grid = Grid::from(
+            "11111
+19991
+19191
+19991
+11111",
+        );
+        let mut octopuses = Octopuses::new(grid, 1);
+        assert_eq!(octopuses.step(), 25);
+        assert_eq!(octopuses.render(), "*****\n*****\n*****\n*****\n*****");
+
+        // Non-square grids, stepping on after a sync continues the step count
+        let mut octopuses = Octopuses::new(Grid::from("0000000\n0000000"), 2);
+        assert_eq!(octopuses.first_synchronised_step(), Some(3));
+        assert_eq!(octopuses.first_synchronised_step(), Some(6));
+
+        // Thresholds above 9 take longer to reach, the 7 reaches 21 on step 14, and pushes the 6 over with it
+        let mut octopuses = Octopuses::new(Grid::from("0123\n4567"), 20);
+        assert_eq!(octopuses.count_flashes(13), 0);
+        assert_eq!(octopuses.step(), 2);
+        assert_eq!(octopuses.grid.get(1, 2), Some(0));
+        assert_eq!(octopuses.grid.get(1, 3), Some(0));
+        assert_eq!(octopuses.grid.get(0, 3), Some(19));
+    }
+
+    #[test]
+    fn can_build_options() {
+        assert_eq!(
+            Options::from(&Params::default()),
+            Options {
+                flash_threshold: 9,
+                part_one_steps: 100
+            }
+        );
+
+        let params = Params::parse(
+            ["--param", "flash_threshold=5"]
+                .map(String::from)
+                .into_iter(),
+        );
+        assert_eq!(
+            Options::from(&params),
+            Options {
+                flash_threshold: 5,
+                part_one_steps: 100
+            }
+        );
+    }
+
+    #[test]
+    fn can_detect_octopuses_that_never_synchronise() {
+        // Each octopus flashing leaves the other one step short of flashing with it
+        let octopuses = Octopuses::new(Grid::from("02"), 9);
+        assert_eq!(
+            octopuses.cycle(),
+            Cycle {
+                start: 0,
+                length: 9
+            }
+        );
+        assert_eq!(octopuses.clone().first_synchronised_step(), None);
+        assert_eq!(octopuses.synchronised_period(), None);
+    }
+}
@@ -0,0 +1,611 @@
+//! This is my solution for [Advent of Code - Day 12 - _Passage Pathing_](https://adventofcode.com/2021/day/12)
+//!
+//! Today was graph traversing. The puzzle was to find all the paths to traverse a set of caves, some of which could
+//! be visited any number of times (large), and only once-ish (small). I say ish, because part two's extension was
+//! that exactly one (1) small cave could be revisited in the path. I've previously struggled with graph
+//! representation in Rust, and today was tricky, but I was able to apply the learnings from previous years and get the
+//! borrow checker happy pretty quickly. This was a more impressive feat with my original solution that used the
+//! string representation and a hash map rather than a vector and mapping the labels to indices.
+//!
+//! There are two main  structs used in today's solutions. [`CaveSystem`] owns the graph of caves, and allows passages
+//! to be added and removed with [`CaveSystem::add_edge`] and [`CaveSystem::remove_edge`], caching the path counts
+//! between edits. The graph was originally a `Vec<Cave>`, each cave holding the indices of the caves linked to it,
+//! alongside a mapping of label -> index, which has since been pulled out as [`Graph`]. Whether each cave is large or
+//! small (or one of the special types start and end) is its [`CaveType`], worked out from its label by [`cave_types`].
+//! [`CaveSystem::from`] takes the puzzle input and converts it into a [`Graph`] inside a [`CaveSystem`]. [`Path`]
+//! tracks an in progress path using a [`CaveSet`] of the visited nodes, the current position of the head of the path,
+//! and (for part two) a flag tracking whether it has used its one-off repeat visit. [`CaveSet`] was originally just a
+//! `usize` bitmap, which limited the caves to the pointer width, so it is now a bitmap that grows as needed, one
+//! [`crate::util::bitset::SmallSet`] word at a time.
+//!
+//! [`build_paths`] and [`Path::with_cave`] handle the logic for solving both parts. [`build_paths`] taking a flag to
+//! control which part it is solving. The strategy is to have a stack of paths to analyse, pop one at a time, append
+//! each linked cave to that path in turn using [`Path::with_cave`], and push the valid paths into the completed list
+//! if we've appended 'end', otherwise back onto the stack of pending paths - so doing depth first search. Using a
+//! queue would give breadth first search, but it's a moot point as we need the exhaustive list of paths anyway.
+//! [`count_paths`] does the same search without keeping the paths, memoising the number of ways to finish from each
+//! state, which is what [`CaveSystem::count_paths`] now uses so that denser cave systems can still be counted.
+//!
+//! Today was the worst in terms of initial performance. It was taking ~400ms to run both parts, compared to ~100ms
+//! to run all of days 1 to 11. My initial implementation was using a `HashSet<&str>` for the visited nodes, and a
+//! `HashMap<&str, Cave>` for the cave list. Switching this over to a `usize` bitmap, and `Vec<Cave>` brought it down
+//! to ~130ms, better but still slower than the other days combined. The second optimisation was that I had
+//! previously been tracking the full path up to that point as a `Vec<usize>` (originally `Vec<&str>`.) Because the
+//! paths branch, adding a new cave to a path needs to copy it, so the original is left as is to have the next linked
+//! cave appended. This had been useful for debugging, esp. when it was using `&str`s to track the caves, but wasn't
+//! actually needed to calculate the solution - the visited set and current position are sufficient. Removing this
+//! `Vec` and associated copying brought it down to ~10ms 🎉. I was very glad for the unit tests that let me refactor
+//! each step with confidence. Getting [`Path::with_cave`] right took a few attempts, and the tests quickly helped me
+//! identify where I'd gone wrong.
+
+use crate::errors::{check_input, check_pattern, to_validation, InputError, Validation};
+use crate::util::bitset::SmallSet;
+use crate::util::graph::Graph;
+use std::collections::HashMap;
+
+use crate::year_2021::day_12::CaveType::{END, LARGE, SMALL, START};
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+/// Track the four possible node types that dictate how they can be visited
+enum CaveType {
+    /// First node in all valid paths, cannot be revisited
+    START,
+    /// Last node in all valid paths, path instantly ends on visiting so implicitly can't be revisited
+    END,
+    /// Can only be visited once, except in part two where a path can optionally revisit exactly one small node
+    /// somewhere along its route
+    SMALL,
+    /// Can be revisited any number of times, regardless of mode
+    LARGE,
+}
+
+impl From<&str> for CaveType {
+    /// start and end are specific strings, otherwise large vs small is determined by the case of the string
+    fn from(s: &str) -> Self {
+        match s {
+            "start" => START,
+            "end" => END,
+            label if label.to_uppercase() == label => LARGE,
+            _ => SMALL,
+        }
+    }
+}
+
+/// The type of each cave in the graph, indexed the same as the caves, which determines how many times each can be
+/// visited in a path
+fn cave_types(caves: &Graph<String>) -> Vec<CaveType> {
+    caves.labels().map(|label| CaveType::from(label.as_str())).collect()
+}
+
+/// A set of cave indices, stored as [`SmallSet`] words that grow as needed, so there's no limit on the number of caves.
+/// Trailing empty words are always trimmed, so that equal sets compare and hash the same however they were built.
+#[derive(Eq, PartialEq, Hash, Debug, Clone, Default)]
+struct CaveSet {
+    /// 64 caves per word, cave `i` is index `i % 64` of word `i / 64`
+    words: Vec<SmallSet>,
+}
+
+impl CaveSet {
+    /// A set containing just the given cave
+    fn of(cave: usize) -> CaveSet {
+        CaveSet::default().with(cave)
+    }
+
+    /// Is the given cave in the set
+    fn contains(&self, cave: usize) -> bool {
+        self.words
+            .get(cave / 64)
+            .is_some_and(|word| word.contains(cave % 64))
+    }
+
+    /// A copy of this set with the given cave added
+    fn with(&self, cave: usize) -> CaveSet {
+        let mut words = self.words.clone();
+        if words.len() <= cave / 64 {
+            words.resize(cave / 64 + 1, SmallSet::default());
+        }
+        words[cave / 64].insert(cave % 64);
+
+        CaveSet { words }
+    }
+
+    /// The caves that are in both sets
+    fn intersection(&self, other: &CaveSet) -> CaveSet {
+        let mut words: Vec<SmallSet> = self
+            .words
+            .iter()
+            .zip(other.words.iter())
+            .map(|(a, b)| a.intersection(b))
+            .collect();
+
+        while words.last().is_some_and(SmallSet::is_empty) {
+            words.pop();
+        }
+
+        CaveSet { words }
+    }
+}
+
+/// Represents a path from the start to the node at [`Path::position`].
+#[derive(Eq, PartialEq, Debug)]
+pub struct Path {
+    /// set of visited nodes
+    visited: CaveSet,
+    /// current node index
+    position: usize,
+    /// flag to track if it has used its one allowed small cave revisit
+    can_revisit: bool,
+}
+
+impl Path {
+    /// If visiting the provided cave would be valid, return the path with that cave appended, otherwise None
+    fn with_cave(&self, cave: usize, cave_type: CaveType) -> Option<Path> {
+        let already_visited = self.visited.contains(cave);
+        if cave_type == LARGE // unlimited visits
+            || !already_visited
+            || (self.can_revisit && cave_type == SMALL)
+        // haven't yet used up the allowed revisit
+        {
+            Some(Path {
+                visited: self.visited.with(cave),
+                position: cave,
+                // once unset, can_revisit must stay unset, otherwise unset it only if revisiting a small cave
+                can_revisit: self.can_revisit && (!already_visited || cave_type != SMALL),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Check each line joins two caves, see [`validate`], and parse them as the [`CaveSystem`] both
+/// [`part_one`] and [`part_two`] find paths through.
+pub fn parse(contents: &str) -> CaveSystem {
+    check_input(12, contents, validate);
+    CaveSystem::from(contents)
+}
+
+/// Count the paths that visit each small cave at most once
+pub fn part_one(cave_system: &CaveSystem) -> Option<String> {
+    let paths = cave_system.clone().count_paths(false);
+    say!(
+        "There are {} paths through the {} caves.",
+        paths,
+        cave_system.caves.len()
+    );
+
+    Some(paths.to_string())
+}
+
+/// Count the paths that can visit one small cave twice
+pub fn part_two(cave_system: &CaveSystem) -> Option<String> {
+    let paths_with_revisit = cave_system.clone().count_paths(true);
+    say!(
+        "There are {} paths through the caves with revisit.",
+        paths_with_revisit
+    );
+
+    Some(paths_with_revisit.to_string())
+}
+
+/// Each line should be two caves joined by a `-`, and the caves should include `start` and `end`
+pub fn validate(input: &str) -> Validation {
+    let mut errors = check_pattern(input, r"[A-Za-z]+-[A-Za-z]+", "two caves joined by '-'");
+    let caves: Vec<&str> = input.lines().flat_map(|line| line.split('-')).collect();
+    for required in ["start", "end"] {
+        if !caves.contains(&required) {
+            errors.push(InputError::new(
+                1,
+                format!("There is no '{}' cave", required),
+            ));
+        }
+    }
+
+    to_validation(errors)
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input is a list of caves joined by `-`, so it is enough for it
+/// to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// A cave system that can be edited after it has been parsed. This owns the mapping of label -> index that would
+/// otherwise be thrown away after parsing, so that passages can be added or removed by label. The path counts are
+/// cached, and invalidated whenever the caves are edited, so they're only recalculated when needed.
+#[derive(Debug, Clone)]
+pub struct CaveSystem {
+    /// The caves, labelled by name, with an edge each way for each passage
+    caves: Graph<String>,
+    /// The cached path counts, without and with the one allowed revisit, `None` if not yet calculated
+    path_counts: [Option<usize>; 2],
+}
+
+impl From<&str> for CaveSystem {
+    /// Split each line into the two ends of the passage, and add each as an edge
+    fn from(input: &str) -> Self {
+        let mut cave_system = CaveSystem::new();
+
+        input
+            .lines()
+            .flat_map(|line| line.split_once("-"))
+            .for_each(|(a, b)| {
+                cave_system.add_edge(a, b);
+            });
+
+        cave_system
+    }
+}
+
+impl CaveSystem {
+    fn new() -> CaveSystem {
+        CaveSystem {
+            caves: Graph::new(),
+            path_counts: [None, None],
+        }
+    }
+
+    /// Add a passage between two caves, creating the caves if they don't exist yet. Returns false, and leaves the
+    /// system unchanged, if the passage already exists.
+    pub fn add_edge(&mut self, label_a: &str, label_b: &str) -> bool {
+        let a = self.caves.add_node(label_a.to_string());
+        let b = self.caves.add_node(label_b.to_string());
+
+        if !self.caves.add_edge(a, b, ()) {
+            return false;
+        }
+
+        self.caves.add_edge(b, a, ());
+        self.path_counts = [None, None];
+        true
+    }
+
+    /// Remove the passage between two caves. The caves themselves are kept, even if they're no longer linked to
+    /// anything, so that the existing indices stay valid. Returns false if there was no such passage.
+    pub fn remove_edge(&mut self, label_a: &str, label_b: &str) -> bool {
+        let (a, b) = match (self.caves.id(label_a), self.caves.id(label_b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return false,
+        };
+
+        if self.caves.remove_edge(a, b).is_none() {
+            return false;
+        }
+
+        self.caves.remove_edge(b, a);
+        self.path_counts = [None, None];
+        true
+    }
+
+    /// The number of paths through the caves, using the cached count if the caves haven't changed since it was
+    /// calculated. This uses [`count_paths`], so works for cave systems with too many paths to list.
+    fn count_paths(&mut self, can_revisit: bool) -> usize {
+        let caves = &self.caves;
+        *self.path_counts[can_revisit as usize]
+            .get_or_insert_with(|| count_paths(caves, can_revisit))
+    }
+
+    /// List every path through the caves with [`build_paths`]. Only practical for smaller cave systems.
+    pub fn enumerate_paths(&self, can_revisit: bool) -> Vec<Path> {
+        build_paths(&self.caves, can_revisit)
+    }
+}
+
+/// Parse the input using [`CaveSystem::from`], and return just the caves. The parts now use the [`CaveSystem`] directly
+/// so this is only used in tests.
+#[cfg(test)]
+fn parse_input(input: &str) -> Graph<String> {
+    CaveSystem::from(input).caves
+}
+
+/// Find the start and end indices, initialise a single path at the start position, and an empty list of complete
+/// paths. Take nodes from the stack, iterating through the linked caves and pushing all valid new paths back onto the
+/// stack (if incomplete) or into the list of complete paths if their updated position is the end node, repeat until
+/// the stack is exhausted and return the completed path.
+fn build_paths(caves: &Graph<String>, can_revisit: bool) -> Vec<Path> {
+    // Lookup the start and end for later use
+    let cave_types = cave_types(caves);
+    let start = caves.id("start").expect("No start cave");
+    let end = caves.id("end").expect("No end cave");
+
+    // initialise the stack and result list
+    let mut paths = vec![Path {
+        visited: CaveSet::of(start),
+        position: start,
+        // if revisiting shouldn't be allowed, just don't set the flag in the first place
+        can_revisit,
+    }];
+
+    let mut completed_paths: Vec<Path> = Vec::new();
+
+    while let Some(path) = paths.pop() {
+        // follow each passage from the current node
+        caves
+            .neighbours(path.position)
+            // returns an option, so flat_map here filters out invalid paths
+            .flat_map(|(next_cave, _)| path.with_cave(next_cave, cave_types[next_cave]))
+            // check if path has reached the end and add to the relevant list
+            .for_each(|path| {
+                if path.position == end {
+                    completed_paths.push(path)
+                } else {
+                    paths.push(path)
+                }
+            })
+    }
+
+    return completed_paths;
+}
+
+/// Count the paths through the caves without building them. A depth first search as in [`build_paths`], but the
+/// number of ways to finish from a given point only depends on the current cave, which small caves have been visited,
+/// and whether the revisit has been used. Memoising the count for each of those states means that each is only
+/// explored once, however many paths lead to it.
+fn count_paths(caves: &Graph<String>, can_revisit: bool) -> usize {
+    fn count(
+        caves: &Graph<String>,
+        cave_types: &[CaveType],
+        path: &Path,
+        end: usize,
+        small_mask: &CaveSet,
+        memo: &mut HashMap<(usize, CaveSet, bool), usize>,
+    ) -> usize {
+        if path.position == end {
+            return 1;
+        }
+
+        // Large caves don't limit where the path can go next, so are ignored when keying the memo
+        let key = (
+            path.position,
+            path.visited.intersection(small_mask),
+            path.can_revisit,
+        );
+        if let Some(&total) = memo.get(&key) {
+            return total;
+        }
+
+        let total = caves
+            .neighbours(path.position)
+            .flat_map(|(next_cave, _)| path.with_cave(next_cave, cave_types[next_cave]))
+            .map(|next_path| count(caves, cave_types, &next_path, end, small_mask, memo))
+            .sum();
+
+        memo.insert(key, total);
+        total
+    }
+
+    let cave_types = cave_types(caves);
+    let start = caves.id("start").expect("No start cave");
+    let end = caves.id("end").expect("No end cave");
+
+    let small_mask = cave_types
+        .iter()
+        .enumerate()
+        .filter(|(_, &cave_type)| cave_type != LARGE)
+        .fold(CaveSet::default(), |mask, (i, _)| mask.with(i));
+
+    let path = Path {
+        visited: CaveSet::of(start),
+        position: start,
+        can_revisit,
+    };
+
+    count(
+        caves,
+        &cave_types,
+        &path,
+        end,
+        &small_mask,
+        &mut HashMap::new(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year_2021::day_12::CaveType::{END, LARGE, SMALL, START};
+    use crate::year_2021::day_12::{
+        build_paths, cave_types, count_paths, parse_input, CaveSet, CaveSystem,
+    };
+
+    fn sample_input1() -> String {
+        "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end"
+            .to_string()
+    }
+
+    fn sample_input2() -> String {
+        "dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc"
+            .to_string()
+    }
+
+    fn sample_input3() -> String {
+        "fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW"
+            .to_string()
+    }
+
+    #[test]
+    fn can_parse() {
+        let input = sample_input1();
+        let caves = parse_input(&input);
+        let links = |cave| -> Vec<usize> { caves.neighbours(cave).map(|(link, _)| link).collect() };
+
+        assert_eq!(
+            caves.labels().collect::<Vec<&String>>(),
+            vec!["start", "A", "b", "c", "d", "end"]
+        );
+        assert_eq!(
+            cave_types(&caves),
+            vec![START, LARGE, SMALL, SMALL, SMALL, END]
+        );
+        assert_eq!(links(0), vec![1, 2]); // start
+        assert_eq!(links(1), vec![0, 3, 2, 5]); // A
+        assert_eq!(links(2), vec![0, 1, 4, 5]); // b
+        assert_eq!(links(3), vec![1]); // c
+        assert_eq!(links(4), vec![2]); // d
+        assert_eq!(links(5), vec![1, 2]); // end
+    }
+
+    #[test]
+    fn can_build_paths() {
+        assert_eq!(build_paths(&parse_input(&sample_input1()), false).len(), 10);
+        assert_eq!(build_paths(&parse_input(&sample_input2()), false).len(), 19);
+        assert_eq!(
+            build_paths(&parse_input(&sample_input3()), false).len(),
+            226
+        );
+    }
+
+    #[test]
+    fn can_build_paths_with_revisit() {
+        assert_eq!(build_paths(&parse_input(&sample_input1()), true).len(), 36);
+        assert_eq!(build_paths(&parse_input(&sample_input2()), true).len(), 103);
+        assert_eq!(
+            build_paths(&parse_input(&sample_input3()), true).len(),
+            3509
+        );
+    }
+
+    #[test]
+    fn can_count_paths() {
+        for (input, expected, expected_with_revisit) in [
+            (sample_input1(), 10, 36),
+            (sample_input2(), 19, 103),
+            (sample_input3(), 226, 3509),
+        ] {
+            let caves = parse_input(&input);
+            assert_eq!(count_paths(&caves, false), expected);
+            assert_eq!(count_paths(&caves, true), expected_with_revisit);
+        }
+    }
+
+    #[test]
+    fn can_count_paths_in_dense_cave_systems() {
+        // Every pair of the small caves a - h is linked, as are start and end to all of them
+        let labels = ["a", "b", "c", "d", "e", "f", "g", "h"];
+        let mut cave_system = CaveSystem::new();
+        for (i, a) in labels.iter().enumerate() {
+            cave_system.add_edge("start", a);
+            cave_system.add_edge(a, "end");
+            for b in &labels[i + 1..] {
+                cave_system.add_edge(a, b);
+            }
+        }
+
+        // The paths are the ordered selections of 1 - 8 of the 8 small caves
+        assert_eq!(cave_system.count_paths(false), 109600);
+        assert_eq!(cave_system.enumerate_paths(false).len(), 109600);
+    }
+
+    #[test]
+    fn can_use_cave_sets() {
+        let set = CaveSet::of(3).with(70).with(200);
+        assert!(set.contains(3));
+        assert!(set.contains(70));
+        assert!(set.contains(200));
+        assert!(!set.contains(4));
+        assert!(!set.contains(1000));
+
+        assert_eq!(set.intersection(&CaveSet::of(70)), CaveSet::of(70));
+        // trailing empty words are trimmed, so the intersection equals a freshly built set
+        assert_eq!(set.intersection(&CaveSet::of(3).with(150)), CaveSet::of(3));
+        assert_eq!(set.intersection(&CaveSet::of(5)), CaveSet::default());
+    }
+
+    #[test]
+    fn cave_sets_span_word_boundaries() {
+        // The caves either side of each 64 bit word boundary must not alias each other
+        for cave in [63, 64, 127, 128] {
+            let set = CaveSet::of(cave);
+            assert!(set.contains(cave));
+            assert!(!set.contains(cave - 1));
+            assert!(!set.contains(cave + 1));
+            assert!(!set.contains(cave + 64));
+            assert!(!set.contains(cave.saturating_sub(64)));
+        }
+
+        assert_eq!(
+            CaveSet::of(63).with(64).intersection(&CaveSet::of(64)),
+            CaveSet::of(64)
+        );
+    }
+
+    #[test]
+    fn can_find_paths_through_more_than_64_caves() {
+        // A chain of 150 small caves, with a large cave alongside each link giving a choice of two routes
+        let mut cave_system = CaveSystem::new();
+        let labels: Vec<String> = (0..150).map(|i| format!("c{}", i)).collect();
+        cave_system.add_edge("start", &labels[0]);
+        cave_system.add_edge(&labels[149], "end");
+        for i in 0..5 {
+            cave_system.add_edge(&labels[i * 30], &format!("L{}", i));
+            cave_system.add_edge(&format!("L{}", i), &labels[i * 30 + 1]);
+        }
+        for pair in labels.windows(2) {
+            cave_system.add_edge(&pair[0], &pair[1]);
+        }
+
+        assert_eq!(cave_system.caves.len(), 157);
+        // Each of the 5 large caves is an optional detour, or with a revisit a way to step back and forth
+        assert_eq!(cave_system.count_paths(false), 32);
+        assert_eq!(cave_system.enumerate_paths(false).len(), 32);
+        assert_eq!(
+            cave_system.count_paths(true),
+            cave_system.enumerate_paths(true).len()
+        );
+    }
+
+    #[test]
+    fn can_edit_cave_system() {
+        let mut cave_system = CaveSystem::from(sample_input1().as_str());
+        assert_eq!(cave_system.count_paths(false), 10);
+        assert_eq!(cave_system.count_paths(true), 36);
+
+        // Already linked
+        assert!(!cave_system.add_edge("b", "A"));
+        assert_eq!(cave_system.path_counts, [Some(10), Some(36)]);
+
+        // d is no longer a dead end, so any path that reaches b can go on via d
+        assert!(cave_system.add_edge("d", "end"));
+        assert_eq!(cave_system.path_counts, [None, None]);
+        assert_eq!(cave_system.count_paths(false), 13);
+
+        // Back to the original input
+        assert!(cave_system.remove_edge("end", "d"));
+        assert_eq!(cave_system.count_paths(false), 10);
+        assert_eq!(cave_system.count_paths(true), 36);
+
+        assert!(!cave_system.remove_edge("end", "d"));
+        assert!(!cave_system.remove_edge("start", "nowhere"));
+    }
+}
@@ -0,0 +1,510 @@
+//! This is my solution for [Advent of Code - Day 13 - _Transparent Origami_](https://adventofcode.com/2021/day/13)
+//!
+//! The hardest part of today's solution was parsing the input. I opted for a `HashSet` of
+//! co-ordinates rather than representing the full grid as the dots were very sparse. I also created
+//! a simple enum [`Axis`] to track the axis of each fold. [`parse_input`] is mostly checks to
+//! confirm each line confirms to spec, as Rust is very explicit about making you handle everything.
+//!
+//! [`apply_fold`] iterates through the dots adding them to a new folded set. If they are left of /
+//! above the relevant axis, they are inserted as is, otherwise the new position is calculated and
+//! inserted. The `len()` of the resulting set when applying the first fold gives the answer to part
+//! one. Part two requires two extra functions [`apply_folds`] uses [`apply_fold`] with each fold in
+//! turn, and [`display_dots`] takes the resulting set and renders it as a grid so that the code can
+//! be read by a human. [`crate::util::letter_ocr::read_letters`] can also read it for you.
+//!
+//! [`Paper`] later bundled the dots with their folds. As folds along one axis never move a dot
+//! along the other, [`compose`] collapses any run of folds into a lookup table per axis, so each
+//! dot is moved once however many folds there are. [`Paper::unfold`] goes the other way, working
+//! back through the folds to find every position a dot on the folded paper could have started at.
+
+use crate::errors::{
+    check_input, check_pattern, offset, sections, to_validation, InputError, Validation,
+};
+use crate::output::show_grid;
+use crate::util::image::{save_if_requested, Bitmap};
+use crate::util::letter_ocr::read_letters;
+use crate::year_2021::day_13::Axis::{X, Y};
+use std::collections::HashSet;
+
+/// Controls the axis each fold will be applied using
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+impl From<&str> for Axis {
+    fn from(s: &str) -> Self {
+        match s {
+            "x" => X,
+            "y" => Y,
+            _ => panic!("unexpected axis: {}", s),
+        }
+    }
+}
+
+/// The parsed puzzle input: the dot co-ordinates, and the folds in the order they are applied
+type Instructions = (HashSet<(usize, usize)>, Vec<(Axis, usize)>);
+
+/// Check the input is the dots then the folds, see [`validate`], and parse them as the [`Paper`]
+/// for [`part_one`] and [`part_two`] to fold.
+pub fn parse(contents: &str) -> Paper {
+    check_input(13, contents, validate);
+    Paper::from(contents)
+}
+
+/// Count the dots left after the first fold
+pub fn part_one(paper: &Paper) -> Option<String> {
+    let new_count = paper.apply_prefix(1).len();
+    say!("After the first fold there are {} dots", new_count);
+
+    Some(new_count.to_string())
+}
+
+/// Apply all the folds, and read the code the dots spell out.
+///
+/// - Pass `--output=<path>` with a `.png` or `.pbm` extension to save the folded paper as an
+///   image.
+/// - The folded paper is only printed with `--verbose`, or saved as text with
+///   `--grid-dir=<path>`, see [`crate::output`].
+pub fn part_two(paper: &Paper) -> Option<String> {
+    let folded = paper.apply_prefix(paper.folds.len());
+    show_grid("day-13-paper", "The folded paper", &display_dots(&folded));
+    let code = read_letters(&folded);
+    match &code {
+        Ok(code) => say!("The code is {}", code),
+        Err(err) => say!("Unable to read the code: {}", err),
+    }
+    save_if_requested(&to_bitmap(&folded));
+
+    code.ok()
+}
+
+/// The input should be a section of dots, then a section of folds
+pub fn validate(input: &str) -> Validation {
+    let sections = sections(input);
+    if sections.len() != 2 {
+        return Err(vec![InputError::new(
+            1,
+            "Expected a section of dots and a section of folds separated by a blank line",
+        )]);
+    }
+
+    let (dots_start, dots) = sections[0];
+    let (folds_start, folds) = sections[1];
+    let mut errors = offset(check_pattern(dots, r"\d+,\d+", "a dot 'x,y'"), dots_start);
+    errors.extend(offset(
+        check_pattern(
+            folds,
+            r"fold along [xy]=\d+",
+            "a fold 'fold along <axis>=<n>'",
+        ),
+        folds_start,
+    ));
+
+    to_validation(errors)
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input has folds, so it is enough for it to
+/// be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// The puzzle input is in two sections separated by a blank line. Section one is the initial set of
+/// dot co-ordinates, in the format `x,y`. Section two is a list of folds in the format
+/// `fold along <axis>=<co-ordinate>`.
+fn parse_input(input: &str) -> Instructions {
+    // split on the blank line
+    let (dots, folds) = input
+        .split_once("\n\n")
+        .expect("Invalid input - no section separator");
+    (
+        // for each co-ordinate line
+        dots.lines()
+            .map(|line| {
+                // split at the comma
+                let (x, y) = line
+                    .split_once(",")
+                    .expect(format!("Invalid dot {}", line).as_str());
+                // and parse both as numbers
+                (
+                    x.parse::<usize>()
+                        .expect(format!("Invalid dot x {}", line).as_str()),
+                    y.parse::<usize>()
+                        .expect(format!("Invalid dot y {}", line).as_str()),
+                )
+            })
+            .collect(),
+        // for each fold
+        folds
+            .lines()
+            .map(|line| {
+                // strip the superfluous prefix
+                let definition = line.replace("fold along ", "");
+                // split at the equals
+                let (axis, pos) = definition
+                    .split_once("=")
+                    .expect(format!("Invalid fold {}", line).as_str());
+                // parse as an [`Axis`] and a number
+                (
+                    Axis::from(axis),
+                    pos.parse::<usize>()
+                        .expect(format!("Invalid fold pos {}", line).as_str()),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// The dots on a sheet of transparent paper, and the folds to make in it
+#[derive(Eq, PartialEq, Debug)]
+pub struct Paper {
+    dots: HashSet<(usize, usize)>,
+    folds: Vec<(Axis, usize)>,
+}
+
+impl From<&str> for Paper {
+    fn from(input: &str) -> Self {
+        let (dots, folds) = parse_input(input);
+        Paper { dots, folds }
+    }
+}
+
+impl Paper {
+    /// The width and height of the unfolded paper, i.e. just big enough to hold all the dots
+    fn size(&self) -> (usize, usize) {
+        bounds(&self.dots)
+    }
+
+    /// The dots left after making just the first `count` folds
+    fn apply_prefix(&self, count: usize) -> HashSet<(usize, usize)> {
+        apply_folds(&self.dots, &self.folds[..count.min(self.folds.len())])
+    }
+
+    /// A single function that moves a point on the unfolded paper to where it ends up after all the
+    /// folds
+    pub fn compose(&self) -> impl Fn((usize, usize)) -> (usize, usize) {
+        compose(&self.folds, self.size())
+    }
+
+    /// Every position on the unfolded paper that ends up at `point` once all the folds are made.
+    /// Working backwards, a coordinate left of / above a fold could also have been its reflection,
+    /// as long as that is still on the paper as it was before the fold.
+    pub fn unfold(&self, point: (usize, usize)) -> HashSet<(usize, usize)> {
+        // The size of the paper before each fold is made
+        let (mut width, mut height) = self.size();
+        let mut sizes = Vec::new();
+        for &(axis, position) in &self.folds {
+            sizes.push((width, height));
+            match axis {
+                X => width = position,
+                Y => height = position,
+            }
+        }
+
+        self.folds.iter().zip(sizes).rev().fold(
+            HashSet::from([point]),
+            |candidates, (&(axis, position), (width, height))| {
+                candidates
+                    .iter()
+                    .flat_map(|&(x, y)| {
+                        let reflected = match axis {
+                            X if x < position && 2 * position - x < width => {
+                                Some((2 * position - x, y))
+                            }
+                            Y if y < position && 2 * position - y < height => {
+                                Some((x, 2 * position - y))
+                            }
+                            _ => None,
+                        };
+                        [Some((x, y)), reflected].into_iter().flatten()
+                    })
+                    .collect()
+            },
+        )
+    }
+}
+
+/// The width and height needed to hold a set of dots
+fn bounds(dots: &HashSet<(usize, usize)>) -> (usize, usize) {
+    (
+        dots.iter().map(|&(x, _)| x + 1).max().unwrap_or(0),
+        dots.iter().map(|&(_, y)| y + 1).max().unwrap_or(0),
+    )
+}
+
+/// Where each coordinate from `0..size` along `axis` ends up after the folds along that axis
+fn axis_map(folds: &[(Axis, usize)], axis: Axis, size: usize) -> Vec<usize> {
+    (0..size)
+        .map(|coord| {
+            folds
+                .iter()
+                .filter(|&&(fold_axis, _)| fold_axis == axis)
+                .fold(
+                    coord,
+                    |c, &(_, position)| {
+                        if c > position {
+                            2 * position - c
+                        } else {
+                            c
+                        }
+                    },
+                )
+        })
+        .collect()
+}
+
+/// Combine a list of folds into a single function mapping a point on paper of the given size to its
+/// position after all the folds. Each axis is resolved up front, so mapping a point is then just
+/// two lookups.
+fn compose(
+    folds: &[(Axis, usize)],
+    size: (usize, usize),
+) -> impl Fn((usize, usize)) -> (usize, usize) {
+    let (width, height) = size;
+    let xs = axis_map(folds, X, width);
+    let ys = axis_map(folds, Y, height);
+
+    move |(x, y)| (xs[x], ys[y])
+}
+
+/// Return a new set where the first has been folded along the given axis
+pub fn apply_fold(dots: &HashSet<(usize, usize)>, fold: (Axis, usize)) -> HashSet<(usize, usize)> {
+    let (axis, position) = fold;
+    dots.iter()
+        .map(|&(x, y)| match (axis, (x, y)) {
+            // Folding by y and dot is right of the fold
+            (X, (x1, y1)) if x1 > position => (2 * position - x1, y1),
+            // Folding by y and dot is below the fold
+            (Y, (x1, y1)) if y1 > position => (x1, 2 * position - y1),
+            // otherwise leave as is
+            (_, coords) => coords,
+        })
+        .collect()
+}
+
+/// Fold the list of folds into the starting set of dots #tooManyFolds. Originally this applied
+/// [`apply_fold`] once per fold, it now uses [`compose`] to move each dot once.
+fn apply_folds(dots: &HashSet<(usize, usize)>, folds: &[(Axis, usize)]) -> HashSet<(usize, usize)> {
+    let fold_all = compose(folds, bounds(dots));
+    dots.iter().map(|&dot| fold_all(dot)).collect()
+}
+
+/// This calculates the maximum x and y in the set to determine the grid bounds, then loops through
+/// each row and column outputting a `▮` or ` ` based on if the current coordinate is in the set.
+///
+/// # Example from puzzle specification
+/// ```rust
+/// let dots = HashSet::from([
+///     (6usize, 10usize),
+///     (0usize, 14usize),
+///     (9usize, 10usize),
+///     (0usize, 3usize),
+///     (10usize, 4usize),
+///     (4usize, 11usize),
+///     (6usize, 0usize),
+///     (6usize, 12usize),
+///     (4usize, 1usize),
+///     (0usize, 13usize),
+///     (10usize, 12usize),
+///     (3usize, 4usize),
+///     (3usize, 0usize),
+///     (8usize, 4usize),
+///     (1usize, 10usize),
+///     (2usize, 14usize),
+///     (8usize, 10usize),
+///     (9usize, 0usize),
+/// ]);
+///
+/// let folds = vec![(Y, 7usize), (X, 5usize)];
+///
+/// let expected = "▮▮▮▮▮\n\
+///                 ▮   ▮\n\
+///                 ▮   ▮\n\
+///                 ▮   ▮\n\
+///                 ▮▮▮▮▮\n\
+///                 ".to_string();
+///
+/// assert_eq!(display_dots(&apply_folds(&dots, &folds)), expected);
+/// ```
+fn display_dots(dots: &HashSet<(usize, usize)>) -> String {
+    // get bounds
+    let max_x = dots.iter().map(|&(x, _)| x).max().expect("No dots");
+    let max_y = dots.iter().map(|&(_, y)| y).max().expect("No dots");
+
+    let mut out = "".to_string();
+    for y in 0..=max_y {
+        for x in 0..=max_x {
+            out = format!("{}{}", out, if dots.contains(&(x, y)) { "▮" } else { " " })
+        }
+        out = format!("{}{}", out, "\n")
+    }
+
+    out
+}
+
+/// Draw the dots as black pixels, so the folded paper can be written out with `--output`
+fn to_bitmap(dots: &HashSet<(usize, usize)>) -> Bitmap {
+    let (width, height) = bounds(dots);
+
+    let mut bitmap = Bitmap::new(width, height);
+    for &(x, y) in dots {
+        bitmap.set(x, y, true);
+    }
+
+    bitmap
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year_2021::day_13::Axis::{X, Y};
+    use crate::year_2021::day_13::{
+        apply_fold, apply_folds, display_dots, parse_input, to_bitmap, Instructions, Paper,
+    };
+    use std::collections::HashSet;
+
+    fn sample_puzzle() -> Instructions {
+        (
+            HashSet::from([
+                (6usize, 10usize),
+                (0usize, 14usize),
+                (9usize, 10usize),
+                (0usize, 3usize),
+                (10usize, 4usize),
+                (4usize, 11usize),
+                (6usize, 0usize),
+                (6usize, 12usize),
+                (4usize, 1usize),
+                (0usize, 13usize),
+                (10usize, 12usize),
+                (3usize, 4usize),
+                (3usize, 0usize),
+                (8usize, 4usize),
+                (1usize, 10usize),
+                (2usize, 14usize),
+                (8usize, 10usize),
+                (9usize, 0usize),
+            ]),
+            vec![(Y, 7usize), (X, 5usize)],
+        )
+    }
+    #[test]
+    fn can_parse() {
+        let input = "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5"
+            .to_string();
+
+        let expected = sample_puzzle();
+
+        assert_eq!(parse_input(&input), expected);
+    }
+
+    #[test]
+    fn can_fold() {
+        let (dots, folds) = sample_puzzle();
+        assert_eq!(apply_fold(&dots, folds[0]).len(), 17)
+    }
+
+    #[test]
+    fn can_display_result() {
+        let (dots, folds) = sample_puzzle();
+        let expected = "▮▮▮▮▮\n\
+                               ▮   ▮\n\
+                               ▮   ▮\n\
+                               ▮   ▮\n\
+                               ▮▮▮▮▮\n\
+                               "
+        .to_string();
+        assert_eq!(display_dots(&apply_folds(&dots, &folds)), expected);
+    }
+
+    #[test]
+    fn can_convert_to_bitmap() {
+        let (dots, folds) = sample_puzzle();
+        let bitmap = to_bitmap(&apply_folds(&dots, &folds));
+
+        assert_eq!(
+            String::from_utf8(bitmap.to_pbm()).unwrap(),
+            "P1\n5 5\n1 1 1 1 1\n1 0 0 0 1\n1 0 0 0 1\n1 0 0 0 1\n1 1 1 1 1\n"
+        );
+    }
+
+    fn sample_paper() -> Paper {
+        let (dots, folds) = sample_puzzle();
+        Paper { dots, folds }
+    }
+
+    #[test]
+    fn can_apply_prefix() {
+        let paper = sample_paper();
+
+        assert_eq!(paper.apply_prefix(0), paper.dots);
+        assert_eq!(
+            paper.apply_prefix(1),
+            apply_fold(&paper.dots, paper.folds[0])
+        );
+        assert_eq!(paper.apply_prefix(1).len(), 17);
+        assert_eq!(paper.apply_prefix(2).len(), 16);
+        assert_eq!(paper.apply_prefix(5), paper.apply_prefix(2));
+    }
+
+    #[test]
+    fn can_compose_folds() {
+        let paper = sample_paper();
+        let fold_all = paper.compose();
+
+        for &dot in &paper.dots {
+            let expected = paper
+                .folds
+                .iter()
+                .fold(HashSet::from([dot]), |acc, &fold| apply_fold(&acc, fold));
+            assert_eq!(HashSet::from([fold_all(dot)]), expected);
+        }
+
+        assert_eq!(fold_all((10, 14)), (0, 0));
+        assert_eq!(fold_all((6, 8)), (4, 6));
+    }
+
+    #[test]
+    fn can_unfold() {
+        let paper = sample_paper();
+
+        assert_eq!(
+            paper.unfold((0, 0)),
+            HashSet::from([(0, 0), (10, 0), (0, 14), (10, 14)])
+        );
+        assert_eq!(
+            paper.unfold((4, 6)),
+            HashSet::from([(4, 6), (6, 6), (4, 8), (6, 8)])
+        );
+
+        let fold_all = paper.compose();
+        for &dot in &paper.dots {
+            let candidates = paper.unfold(fold_all(dot));
+            assert!(candidates.contains(&dot));
+            assert!(candidates
+                .iter()
+                .all(|&candidate| fold_all(candidate) == fold_all(dot)));
+        }
+    }
+}
@@ -0,0 +1,548 @@
+//! This is my solution for [Advent of Code - Day 14 - _Extended Polymerization_](https://adventofcode.com/2021/day/14)
+//!
+//! By the end of today I was thinking it was a lot like [`super::day_6`], but I missed how
+//! exponential it was when first reading, so implemented the naive versions for part one, but that
+//! did not complete before it ran out of memory for part two. I ended up noting that each pair
+//! would become two new pairs each step (or stay as the same pair if there was no insertion mapping
+//! for that pair). This meant I could just track the counts of each pair in a given iteration, and
+//! work out the next step by walking through the current pair counts and adding its count to each
+//! of the pairs it maps to in the counts map for the next iteration.
+//!
+//! The types are pretty convoluted today, enough so that I aliased the Polymer as map of pairs ->
+//! counts, and pair mapping of pair -> pairs when iterated into [`Polymer`] and [`PairMap`]
+//! respectively. That said, whilst the types were complex, they were a great guide for what
+//! transformations I needed to do at each step, and once I'd satisfied the type checker the
+//! solutions just worked.
+//!
+//! There's quite a lot of moving parts today, mostly involved in building the internal
+//! representation from the input, and converting the result into the required output.
+//! [`parse_input`] takes the file, transforms the seed polymer into the required map of pair counts
+//! using [`into_pair_counts`], and then takes the remaining lines and creates the PairMap. i.e. a
+//! mapping of `AB -> C` becomes `('A', 'B') => [('A', 'C'), ('C', 'B')]` which means when there is
+//! a pair `AB` the next iteration will instead have two pairs, `AC` and `CB`. [`intersperse`]
+//! handles performing a single insertion cycle, and [`iterate`] recursively calls [`intersperse`]
+//! for the required number of cycles. Finally [`summarise`] works out the counts of each of the
+//! characters. With the current implementation we need to take the counts of both parts of each
+//! pair to account for the first and last characters. This in itself involves some complex type
+//! munging, so has been extracted to [`into_count_by`]. If I was building this again I'd consider
+//! making a struct to hold the polymer, including caching the final character from the seed. This
+//! would allow just counting the first character in each pair and adding 1 to the count that
+//! matches the final character. As it is, this works and is quick enough that it's not worth the
+//! effort.
+//!
+//! Counting pairs rather than building the polymer is the same trick as days 6 and 21, so the
+//! counts are now kept in a [`CountMap`], which does the bookkeeping for [`intersperse`]. The
+//! polymer roughly doubles in length each cycle, so the counts are a [`Count`] rather than a
+//! `usize`, which would overflow after about 60 cycles.
+//!
+//! The pair counts can't be turned back into the polymer, which made it hard to see where a
+//! mapping had gone wrong. [`expand_to_string`] brings back the naive version for inspecting small
+//! cases, building the actual polymer one cycle at a time, but refuses to build one longer than a
+//! given length, so it can't be left running until it runs out of memory again.
+
+use crate::errors::{
+    check_input, check_pattern, offset, sections, to_validation, InputError, Validation,
+};
+use crate::util::params::Params;
+use crate::util::state_counts::{to_count, Count, CountMap};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// The internal representation of polymer as the counts of the distinct consecutive pairs.
+type Polymer = CountMap<(char, char)>;
+/// The internal representation of the insertion map, that returns the two new pairs generated by
+/// inserting the specified character.
+type PairMap = HashMap<(char, char), Vec<(char, char)>>;
+
+/// The number of insertion cycles for each part, can be changed with `--param part_one_cycles=<n>`
+/// and `--param part_two_cycles=<n>`.
+#[derive(Eq, PartialEq, Debug)]
+struct Options {
+    part_one_cycles: usize,
+    part_two_cycles: usize,
+}
+
+impl From<&Params> for Options {
+    fn from(params: &Params) -> Self {
+        Options {
+            part_one_cycles: params.get_or("part_one_cycles", 10),
+            part_two_cycles: params.get_or("part_two_cycles", 40),
+        }
+    }
+}
+
+/// Check the input is the polymer template then the insertion rules, see [`validate`], and parse
+/// them as the pair counts and rules for [`part_one`] and [`part_two`].
+pub fn parse(contents: &str) -> (Polymer, PairMap) {
+    check_input(14, contents, validate);
+    parse_input(contents)
+}
+
+/// The difference between the most and least common elements after the cycles for part one. The
+/// number of cycles can be changed, see [`Options`].
+pub fn part_one((seed, mapping): &(Polymer, PairMap)) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    let polymer = iterate(seed, options.part_one_cycles, mapping);
+    let (_, result) = summarise(&polymer);
+    say!(
+        "The max - min counts after {} cycles = {}.",
+        options.part_one_cycles,
+        result
+    );
+
+    Some(result.to_string())
+}
+
+/// The difference between the most and least common elements after the cycles for part two. The
+/// number of cycles can be changed, see [`Options`].
+pub fn part_two((seed, mapping): &(Polymer, PairMap)) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    let polymer = iterate(seed, options.part_two_cycles, mapping);
+    let (_, result) = summarise(&polymer);
+    let length = polymer_length(&polymer);
+    say!(
+        "The max - min counts after {} cycles = {}, total length {}.",
+        options.part_two_cycles,
+        result,
+        length
+    );
+
+    Some(result.to_string())
+}
+
+/// The input should be the template on one line, then a section of insertion rules
+pub fn validate(input: &str) -> Validation {
+    let sections = sections(input);
+    if sections.len() != 2 {
+        return Err(vec![InputError::new(
+            1,
+            "Expected the template and the rules separated by a blank line",
+        )]);
+    }
+
+    let (template_start, template) = sections[0];
+    let (rules_start, rules) = sections[1];
+    let mut errors = offset(
+        check_pattern(template, "[A-Z]+", "a template of capital letters"),
+        template_start,
+    );
+    errors.extend(offset(
+        check_pattern(rules, "[A-Z]{2} -> [A-Z]", "a rule 'AB -> C'"),
+        rules_start,
+    ));
+
+    to_validation(errors)
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input has insertion rules, so it is enough
+/// for it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// Split a list of characters into the counts of all the consecutive pairs that exist. The hard
+/// work is delegated to library functions [`slice::windows`] to give an iterator of the pairs
+/// and [`CountMap::from_iter`] to reduce that to the required map.
+fn into_pair_counts(polymer_chars: &[char]) -> Polymer {
+    polymer_chars
+        .windows(2)
+        .map(|window| (window[0], window[1]))
+        .collect()
+}
+
+/// The types required to make today's solution work are pretty complex, so there is quite a lot of
+/// work here to take a relatively simple input format into the complex format that makes the logic
+/// efficient. A bunch of the tests need to convert intermediate polymer string representations into
+/// the map of pair counts used internally, so this is delegated to [`into_pair_counts`].
+fn parse_input(input: &str) -> (Polymer, PairMap) {
+    let mut lines = input.lines();
+    let seed = into_pair_counts(
+        &lines
+            .next()
+            .expect("Empty input")
+            .chars()
+            .collect::<Vec<char>>(),
+    );
+    // skip blank
+    lines.next();
+    let mapping: HashMap<(char, char), Vec<(char, char)>> = lines
+        .flat_map(|line| line.split_once(" -> "))
+        .flat_map(|(pair, insert)| {
+            let mut pair_chars = pair.chars();
+            pair_chars
+                .next()
+                .zip(pair_chars.next())
+                .zip(insert.chars().next())
+        })
+        .map(|(pair, c)| (pair, vec![(pair.0, c), (c, pair.1)]))
+        .collect();
+
+    (seed, mapping)
+}
+
+/// The name is a legacy from the naive solution where this was mapping each pair to the new pairs
+/// and building the full polymer in order which failed as the final polymer had ~21 trillion
+/// characters. This takes all the existing pairs and adds their counts to the pair(s) they map to
+/// when the mapped character is inserted.
+///
+/// Consider the seed `BABABA`, in the internal representation this becomes `BA => 3, AB => 2`.
+/// With the mapping `AB -> A` and `BA -> A` this would become `BAAABAAABAA` or `BA => 3, AA => 5,
+/// AB => 2`. Because each pair mapping is independent, we can view the mapping as `AB => AA, AB`,
+/// `BA => BA, AA`. So applying [`intersperse`] to the `BA => 3, AB => 2` polymer:
+/// * `BA => 3` becomes `BA => 3, AA => 3`.
+/// * The new map is empty so both are inserted with a count of 3.
+/// * `AB => 2` becomes `AA => 2, AB => 2`.
+/// * `AA` already exists with a count of 3, so these two are added to give `AA =>5`.
+/// * `AB` isn't in the map, so it is inserted with a count of 2.
+/// * This gives the expected `BA => 3, AA => 5, AB => 2` Polymer.
+fn intersperse(polymer: &Polymer, mapping: &PairMap) -> Polymer {
+    polymer.step(|pair| match mapping.get(pair) {
+        Some(pairs) => pairs.iter().map(|&p| (p, 1)).collect(),
+        // If a pair is not in the map it remains unchanged. This doesn't come up for the sample
+        // input, and I've not checked for the puzzle input, but it's easy enough to implement and
+        // keeps the compiler happy.
+        None => vec![(*pair, 1)],
+    })
+}
+
+/// Returned by [`expand_to_string`] instead of building a polymer longer than it was allowed to
+#[derive(Eq, PartialEq, Debug)]
+pub struct TooLong {
+    /// The cycle that would have made the polymer too long
+    pub cycle: usize,
+    /// The length the polymer would have been after that cycle
+    pub length: usize,
+    /// The limit it was given
+    pub max_length: usize,
+}
+
+impl Display for TooLong {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Cycle {} would make the polymer {} characters long, more than the limit of {}",
+            self.cycle, self.length, self.max_length
+        )
+    }
+}
+
+/// Build the actual polymer after the given number of cycles, by inserting the mapped character
+/// between each pair in turn. This is only practical for small cases, so it stops with [`TooLong`]
+/// rather than build a polymer of more than `max_length` characters. The length after each cycle is
+/// worked out before building it, so the limit is never exceeded, even briefly.
+pub fn expand_to_string(
+    seed: &str,
+    mapping: &PairMap,
+    cycles: usize,
+    max_length: usize,
+) -> Result<String, TooLong> {
+    let mut polymer: Vec<char> = seed.chars().collect();
+
+    for cycle in 1..=cycles {
+        // The mapping holds the two new pairs, the inserted character is the end of the first
+        let insertions: Vec<Option<char>> = polymer
+            .windows(2)
+            .map(|pair| mapping.get(&(pair[0], pair[1])).map(|pairs| pairs[0].1))
+            .collect();
+
+        let length = polymer.len() + insertions.iter().flatten().count();
+        if length > max_length {
+            return Err(TooLong {
+                cycle,
+                length,
+                max_length,
+            });
+        }
+
+        let mut next = Vec::with_capacity(length);
+        for (&chr, insertion) in polymer.iter().zip(insertions) {
+            next.push(chr);
+            next.extend(insertion);
+        }
+        next.extend(polymer.last());
+        polymer = next;
+    }
+
+    Ok(polymer.into_iter().collect())
+}
+
+// Utility for counting the length of the polymer. Since they overlap, the two chars per pair and
+// two pairs per char cancel out, but we need to add one to cover that the first and last character
+// are each only in one pair.
+fn polymer_length(polymer: &Polymer) -> Count {
+    polymer.total() + to_count(1)
+}
+
+/// Recursively apply [`intersperse`] the required number of times
+fn iterate(seed: &Polymer, cycles: usize, mapping: &PairMap) -> Polymer {
+    if cycles == 0 {
+        return seed.clone();
+    }
+
+    iterate(&intersperse(seed, mapping), cycles - 1, mapping)
+}
+
+/// Reduce the pair mapping into a count of characters. This needs to be called twice once for each
+/// element in the pair, to account for the first and last character that are each only in one pair.
+/// The mapping parameter is to capture this difference, and maps a pair count entry from the
+/// Polymer into the character this invocation cares about
+fn into_count_by(
+    polymer: &Polymer,
+    mapping: for<'a> fn(&'a (&(char, char), &Count)) -> char,
+) -> HashMap<char, Count> {
+    polymer
+        .iter()
+        // group by the mapping - the values are now `Vec<((char, char), Count)>
+        .into_grouping_map_by(mapping)
+        // sum just the counts
+        .fold(Count::default(), |acc, _, (_, val)| acc + val)
+}
+
+/// This is responsible for converting the internal representation of a polymer into the data needed
+/// to provide the puzzle solution. It also returns the intermediary hashmap so that this can be
+/// verified in tests against the example provided in the specification. The pair counts don't say
+/// which character is first or last, so if the polymer starts and ends with the same character,
+/// that character's count is one short.
+fn summarise(polymer: &Polymer) -> (HashMap<char, Count>, Count) {
+    // Get the counts bases on the first ...
+    let starts: HashMap<char, Count> = into_count_by(polymer, |((a, _), _)| *a);
+    // ... and second character in the pair
+    let mut ends: HashMap<char, Count> = into_count_by(polymer, |((_, b), _)| *b);
+
+    // For each character take the maximum count from these two maps. The count for the starting
+    // character is one higher as it only appears in the start of the one pair it's in, and vice
+    // versa for the final character.
+    let mut counts: HashMap<char, Count> = starts
+        .into_iter()
+        .map(|(chr, count)| (chr, count.max(ends.remove(&chr).unwrap_or_default())))
+        .collect();
+    // A character that is only at the end of the polymer isn't the start of any pair, comparing
+    // against [`expand_to_string`] showed these were being left out.
+    counts.extend(ends);
+
+    // For obtaining the min and max character counts the character doesn't matter so can just use
+    // [`Itertools::minmax`] directly on the values, without the more complex mapping
+    // needed in [`into_count_by`].
+    let (min, max) = counts
+        .values()
+        .minmax()
+        .into_option()
+        .expect("Not enough chars");
+    let result = max - min;
+
+    (counts, result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::params::Params;
+    use crate::util::state_counts::to_count;
+    use crate::year_2021::day_14::{
+        expand_to_string, intersperse, into_pair_counts, iterate, parse_input, polymer_length,
+        summarise, Options, Polymer, TooLong,
+    };
+    use std::collections::HashMap;
+
+    fn sample_input() -> String {
+        "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C"
+            .to_string()
+    }
+
+    #[test]
+    fn can_parse() {
+        let (seed, mapping) = parse_input(&sample_input());
+        assert_eq!(
+            seed,
+            Polymer::from(HashMap::from([
+                (('N', 'N'), 1),
+                (('N', 'C'), 1),
+                (('C', 'B'), 1),
+            ]))
+        );
+        assert_eq!(
+            mapping,
+            HashMap::from([
+                (('C', 'H'), vec![('C', 'B'), ('B', 'H')]),
+                (('H', 'H'), vec![('H', 'N'), ('N', 'H')]),
+                (('C', 'B'), vec![('C', 'H'), ('H', 'B')]),
+                (('N', 'H'), vec![('N', 'C'), ('C', 'H')]),
+                (('H', 'B'), vec![('H', 'C'), ('C', 'B')]),
+                (('H', 'C'), vec![('H', 'B'), ('B', 'C')]),
+                (('H', 'N'), vec![('H', 'C'), ('C', 'N')]),
+                (('N', 'N'), vec![('N', 'C'), ('C', 'N')]),
+                (('B', 'H'), vec![('B', 'H'), ('H', 'H')]),
+                (('N', 'C'), vec![('N', 'B'), ('B', 'C')]),
+                (('N', 'B'), vec![('N', 'B'), ('B', 'B')]),
+                (('B', 'N'), vec![('B', 'B'), ('B', 'N')]),
+                (('B', 'B'), vec![('B', 'N'), ('N', 'B')]),
+                (('B', 'C'), vec![('B', 'B'), ('B', 'C')]),
+                (('C', 'C'), vec![('C', 'N'), ('N', 'C')]),
+                (('C', 'N'), vec![('C', 'C'), ('C', 'N')]),
+            ])
+        );
+    }
+
+    #[test]
+    fn can_intersperse() {
+        let (seed_counts, mapping) = parse_input(&sample_input());
+
+        let pass_1 = intersperse(&seed_counts, &mapping);
+        let pass_2 = intersperse(&pass_1, &mapping);
+        let pass_3 = intersperse(&pass_2, &mapping);
+        let pass_4 = intersperse(&pass_3, &mapping);
+
+        let expected_1 = into_pair_counts(&"NCNBCHB".chars().collect::<Vec<char>>());
+        let expected_2 = into_pair_counts(&"NBCCNBBBCBHCB".chars().collect::<Vec<char>>());
+        let expected_3 =
+            into_pair_counts(&"NBBBCNCCNBBNBNBBCHBHHBCHB".chars().collect::<Vec<char>>());
+        let expected_4 = into_pair_counts(
+            &"NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB"
+                .chars()
+                .collect::<Vec<char>>(),
+        );
+
+        assert_eq!(pass_1, expected_1);
+        assert_eq!(pass_2, expected_2);
+        assert_eq!(pass_3, expected_3);
+        assert_eq!(pass_4, expected_4);
+    }
+
+    #[test]
+    fn can_iterate() {
+        let (seed, mapping) = parse_input(&sample_input());
+
+        assert_eq!(polymer_length(&iterate(&seed, 5, &mapping)), to_count(97));
+        assert_eq!(
+            polymer_length(&iterate(&seed, 10, &mapping)),
+            to_count(3073)
+        );
+    }
+
+    #[test]
+    fn can_summarise() {
+        let (seed, mapping) = parse_input(&sample_input());
+        let polymer = iterate(&seed, 10, &mapping);
+        let summary = summarise(&polymer);
+        assert_eq!(
+            summary,
+            (
+                HashMap::from([
+                    ('B', to_count(1749)),
+                    ('C', to_count(298)),
+                    ('H', to_count(161)),
+                    ('N', to_count(865))
+                ]),
+                to_count(1588)
+            )
+        );
+
+        let polymer2 = iterate(&polymer, 30, &mapping);
+        let (counts, result) = summarise(&polymer2);
+        assert_eq!(counts.get(&'B'), Some(&to_count(2192039569602)));
+        assert_eq!(counts.get(&'H'), Some(&to_count(3849876073)));
+        assert_eq!(result, to_count(2188189693529));
+    }
+
+    #[test]
+    fn can_iterate_past_u64() {
+        let (seed, mapping) = parse_input(&sample_input());
+        let polymer = iterate(&seed, 100, &mapping);
+        let (_, result) = summarise(&polymer);
+
+        assert_eq!(
+            polymer_length(&polymer).to_string(),
+            "3802951800684688204490109616129"
+        );
+        assert_eq!(result.to_string(), "2535296262066596202993060773164");
+    }
+
+    #[test]
+    fn can_expand_to_string() {
+        let (_, mapping) = parse_input(&sample_input());
+        let expand = |cycles| expand_to_string("NNCB", &mapping, cycles, 100);
+
+        assert_eq!(expand(0), Ok("NNCB".to_string()));
+        assert_eq!(expand(1), Ok("NCNBCHB".to_string()));
+        assert_eq!(expand(2), Ok("NBCCNBBBCBHCB".to_string()));
+        assert_eq!(expand(3), Ok("NBBBCNCCNBBNBNBBCHBHHBCHB".to_string()));
+        assert_eq!(
+            expand(4),
+            Ok("NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB".to_string())
+        );
+        // Pairs without a mapping are left as they are
+        assert_eq!(
+            expand_to_string("NNXB", &mapping, 1, 100),
+            Ok("NCNXB".to_string())
+        );
+
+        let too_long = TooLong {
+            cycle: 6,
+            length: 193,
+            max_length: 100,
+        };
+        assert_eq!(expand(6), Err(too_long));
+        assert_eq!(
+            expand(6).unwrap_err().to_string(),
+            "Cycle 6 would make the polymer 193 characters long, more than the limit of 100"
+        );
+        assert_eq!(
+            expand_to_string("NNCB", &mapping, 10, 3073).map(|polymer| polymer.len()),
+            Ok(3073)
+        );
+    }
+
+    #[test]
+    fn expanded_strings_match_the_pair_counts() {
+        let (seed, mapping) = parse_input(&sample_input());
+
+        for cycles in 0..=10 {
+            let expanded = expand_to_string("NNCB", &mapping, cycles, 10_000).unwrap();
+            let polymer = iterate(&seed, cycles, &mapping);
+            assert_eq!(
+                polymer,
+                into_pair_counts(&expanded.chars().collect::<Vec<char>>())
+            );
+            assert_eq!(polymer_length(&polymer), to_count(expanded.len()));
+
+            let mut counts = HashMap::new();
+            for chr in expanded.chars() {
+                *counts.entry(chr).or_insert_with(|| to_count(0)) += to_count(1);
+            }
+            assert_eq!(summarise(&polymer).0, counts, "After {} cycles", cycles);
+        }
+    }
+
+    #[test]
+    fn can_build_options() {
+        let params = Params::parse(
+            ["--param", "part_two_cycles=100"]
+                .map(String::from)
+                .into_iter(),
+        );
+        assert_eq!(
+            Options::from(&params),
+            Options {
+                part_one_cycles: 10,
+                part_two_cycles: 100
+            }
+        );
+    }
+}
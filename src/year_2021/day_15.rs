@@ -0,0 +1,392 @@
+//! This is my solution for [Advent of Code - Day 15 - _Chiton_](https://adventofcode.com/2021/day/15)
+//!
+//! I picked up pretty quickly that this needed a shortest-path graph traversal algorithm, and (very) vaguely
+//! remembered Dijkstra's from when it was covered in my A-Level math course. I did some googling to refresh my memory,
+//! noted the advice to use a [`std::collections::BinaryHeap`], found that the Rust std implementation had Dijkstra's
+//! as it's main example in the docs. I imported [`Grid`] from previous days, and updated the
+//! example code to work with co-ordinates, and it just worked: [`find_shortest_path`]. Day 23 needed the same
+//! algorithm, so it has since been extracted to [`crate::util::dijkstra`].
+//!
+//! For part two I didn't want to store the much bigger and repeated graph in memory, so I wrote a wrapper
+//! [`ExpandedGrid`] that would provide implementations for all the methods used by [`find_shortest_path`] and work out
+//! how to translate that to methods on the underlying sub-grid, the key method being
+//! [`ExpandedGrid::get`]. The wrapper ended up a little messy, but it'll do for AoC. If I was planning to need to
+//! maintain this code, I'd maybe look into extracting some parts to a trait so that I'm not repeating code from
+//! [`Grid`]. Only the original tile is ever stored, the risk of every other cell is worked out when the search asks for
+//! it, so part two needs no more memory than part one for the grid itself.
+//!
+//! To check the route itself, not just its cost, [`find_shortest_route`] asks the shared search for the cells it passed
+//! through, and [`format_route`] draws the risk grid with those cells highlighted, in the same way the puzzle
+//! description shows the example route. Run with `--trace --verbose` to print these for both parts.
+
+use crate::errors::{check_grid, check_input, to_validation, Validation};
+use crate::output::show_grid;
+use crate::util::dijkstra::{dijkstra, dijkstra_path_by_key};
+use crate::util::grid::Grid;
+use std::collections::HashSet;
+use std::env;
+
+/// ANSI escape sequence to start drawing bold, bright white text, used to pick out cells on the route
+const HIGHLIGHT_START: &str = "\x1b[1;97m";
+/// ANSI escape sequence to reset the text style after a highlighted cell
+const HIGHLIGHT_END: &str = "\x1b[0m";
+
+/// A wrapper around [`Grid`] that handles tiling a smaller sub-grid.
+struct ExpandedGrid<'a> {
+    /// The wrapped sub-grid
+    grid: &'a Grid,
+    /// Cache the calculated height
+    sub_grid_height: usize,
+    /// Number of times the grid is tiled in the y axis
+    copies_y: usize,
+    /// Number of times the grid is tiled in the x axis
+    copies_x: usize,
+}
+
+impl<'a> From<&'a Grid> for ExpandedGrid<'a> {
+    /// Build an untiled wrapper from a given sub-grid. See also [`ExpandedGrid::with_copies`]
+    fn from(grid: &'a Grid) -> Self {
+        let (_, max_y) = grid.max_coords();
+
+        return ExpandedGrid {
+            grid,
+            copies_y: 1,
+            copies_x: 1,
+            sub_grid_height: max_y + 1,
+        };
+    }
+}
+
+impl<'a> ExpandedGrid<'a> {
+    /// Return a new wrapper with different tiling
+    fn with_copies(&self, copies_y: usize, copies_x: usize) -> ExpandedGrid<'a> {
+        ExpandedGrid {
+            grid: self.grid,
+            sub_grid_height: self.sub_grid_height,
+            copies_y,
+            copies_x,
+        }
+    }
+
+    /// The number of cells in the grid
+    fn len(&self) -> usize {
+        self.grid.len() * self.copies_y * self.copies_x
+    }
+
+    /// The total width of the grid
+    fn width(&self) -> usize {
+        self.grid.width * self.copies_x
+    }
+
+    /// The co-ordinates of the bottom right corner of the grid in (y, x) format
+    fn max_coords(&self) -> (usize, usize) {
+        ((self.len() - 1) / self.width(), self.width() - 1)
+    }
+
+    /// Find the meta-co-ordinates of the tile a cell co-ordinate is on
+    fn tile_coords(&self, y: usize, x: usize) -> (usize, usize) {
+        (y / self.sub_grid_height, x / self.grid.width)
+    }
+
+    /// Translate a grid-levey co-ordinates to the sub-grid co-ordinates, i.e. the co-ordinates within the current tile.
+    fn sub_grid_coords(&self, y: usize, x: usize) -> (usize, usize) {
+        (y % self.sub_grid_height, x % self.grid.width)
+    }
+
+    /// Given grid co-ordinates, get the value from the referenced cell in the sub-grid, and apply the cost modifier
+    /// based on the tile position.
+    fn get(&self, y: usize, x: usize) -> Option<u8> {
+        let (tile_y, tile_x) = self.tile_coords(y, x);
+        let (sub_grid_y, sub_grid_x) = self.sub_grid_coords(y, x);
+
+        if tile_y >= self.copies_y || tile_x >= self.copies_x {
+            return None;
+        }
+
+        self.grid
+            .get(sub_grid_y, sub_grid_x)
+            // + offset based on tile Manhattan distance - note this overflows to 1 not 0
+            .map(|v| (((v as usize - 1) + tile_y + tile_x) % 9) as u8 + 1)
+    }
+
+    //noinspection DuplicatedCode
+    /// Copied from grid, but needs to use the [`ExpandedGrid::get_relative`] to manage crossing tile boundaries
+    fn get_orthogonal_surrounds(&self, y: usize, x: usize) -> Vec<((usize, usize), u8)> {
+        [(-1, 0), (0, 1), (1, 0), (0, -1)] // N E S W
+            .iter()
+            .flat_map(|&(dy, dx)| self.get_relative(y, x, dy, dx))
+            .collect()
+    }
+
+    //noinspection DuplicatedCode
+    /// Copied from grid, but needs to use the [`ExpandedGrid::get`] to manage crossing tile boundaries
+    fn get_relative(
+        &self,
+        y: usize,
+        x: usize,
+        dy: isize,
+        dx: isize,
+    ) -> Option<((usize, usize), u8)> {
+        let y1 = (y as isize) + dy;
+        let x1 = (x as isize) + dx;
+
+        if y1 >= 0 && x1 >= 0 {
+            self.get(y1 as usize, x1 as usize)
+                .map(|val| ((y1 as usize, x1 as usize), val))
+        } else {
+            None
+        }
+    }
+}
+
+/// Check the input is a rectangular grid of risk levels, see [`validate`], and parse it as the
+/// [`Grid`] both [`part_one`] and [`part_two`] find a route across.
+pub fn parse(contents: &str) -> Grid {
+    check_input(15, contents, validate);
+    Grid::from(contents)
+}
+
+/// The lowest total risk to cross the grid.
+///
+/// - Pass `--trace` to also show the route taken, see [`format_route`]. As it is a grid, it is only
+///   printed with `--verbose`, or saved with `--grid-dir=<path>`, see [`crate::output`].
+pub fn part_one(sub_grid: &Grid) -> Option<String> {
+    traverse(
+        &ExpandedGrid::from(sub_grid),
+        "The cost to traverse the grid is",
+        ("day-15-route", "The route"),
+    )
+}
+
+/// The lowest total risk to cross the grid tiled five times in each direction.
+///
+/// - Pass `--trace` to also show the route taken, as for [`part_one`].
+pub fn part_two(sub_grid: &Grid) -> Option<String> {
+    traverse(
+        &ExpandedGrid::from(sub_grid).with_copies(5, 5),
+        "The cost to traverse the grid tiles is",
+        ("day-15-tiled-route", "The route through the tiles"),
+    )
+}
+
+/// Find, print and return the lowest total risk to cross `grid`. If `--trace` was passed, the route is also shown as a
+/// grid, with the name and caption given by `route`, see [`show_grid`].
+fn traverse(grid: &ExpandedGrid, description: &str, route: (&str, &str)) -> Option<String> {
+    let cost = find_shortest_path(grid, (0, 0), grid.max_coords());
+    say!("{}: {:?}", description, cost);
+    if env::args().any(|arg| arg == "--trace") {
+        let (name, caption) = route;
+        let path = find_shortest_route(grid, (0, 0), grid.max_coords()).unwrap();
+        show_grid(name, caption, &format_route(grid, &path));
+    }
+
+    cost.map(|cost| cost.to_string())
+}
+
+/// The risk levels should be a rectangular grid of digits
+pub fn validate(input: &str) -> Validation {
+    to_validation(check_grid(input, "0123456789"))
+}
+
+/// For `--detect`, see [`crate::detect`]. Days 3, 9 and 11 are also grids of digits. Risk levels are from 1 to 9, so
+/// there are no 0s, which the heightmaps of day 9 have, and digits other than 1, unlike day 3's binary. Day 11's
+/// example is also a 10 by 10 grid of digits, so the two examples can't be told apart.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+        && !input.contains('0')
+        && input.contains(|c: char| ('2'..='9').contains(&c))
+}
+
+/// Implement Dijkstra's shortest path algorithm. Originally copied from the [`std::collections::BinaryHeap`] example
+/// and modified to get the edge costs from the provided grid, now it just describes the grid to the shared
+/// [`dijkstra`] implementation. Originally accepted  [`Grid`] but it was easier to use one type/method for both parts
+/// and the [`ExpandedGrid`] works the same as a [`Grid`] if it only has one tile on each axis.
+fn find_shortest_path(
+    grid: &ExpandedGrid,
+    start: (usize, usize),
+    goal: (usize, usize),
+) -> Option<usize> {
+    dijkstra(
+        start,
+        |&coords| coords == goal,
+        |&(y, x)| neighbours(grid, y, x),
+    )
+}
+
+/// As [`find_shortest_path`], but returns the co-ordinates of each cell on the route, and the total risk to reach that
+/// cell, starting with `start` at a risk of `0`.
+fn find_shortest_route(
+    grid: &ExpandedGrid,
+    start: (usize, usize),
+    goal: (usize, usize),
+) -> Option<Vec<((usize, usize), usize)>> {
+    dijkstra_path_by_key(
+        start,
+        |&coords| coords == goal,
+        |&(y, x)| neighbours(grid, y, x),
+        |&coords| coords,
+    )
+}
+
+/// The cells that can be moved to from a cell, with the risk of entering them as the cost
+fn neighbours(
+    grid: &ExpandedGrid,
+    y: usize,
+    x: usize,
+) -> impl Iterator<Item = ((usize, usize), usize)> {
+    grid.get_orthogonal_surrounds(y, x)
+        .into_iter()
+        .map(|(coords, v)| (coords, v as usize))
+}
+
+/// Draw the risk grid one row per line, with the cells on the route highlighted.
+fn format_route(grid: &ExpandedGrid, route: &[((usize, usize), usize)]) -> String {
+    let on_route: HashSet<(usize, usize)> = route.iter().map(|&(coords, _)| coords).collect();
+    let (max_y, max_x) = grid.max_coords();
+
+    (0..=max_y)
+        .map(|y| {
+            (0..=max_x)
+                .map(|x| {
+                    let risk = grid.get(y, x).unwrap();
+                    if on_route.contains(&(y, x)) {
+                        format!("{}{}{}", HIGHLIGHT_START, risk, HIGHLIGHT_END)
+                    } else {
+                        risk.to_string()
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::grid::Grid;
+    use crate::year_2021::day_15::{
+        find_shortest_path, find_shortest_route, format_route, ExpandedGrid, HIGHLIGHT_END,
+        HIGHLIGHT_START,
+    };
+
+    fn sample_input() -> String {
+        "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"
+            .to_string()
+    }
+
+    #[test]
+    fn can_find_path() {
+        let input = sample_input();
+
+        let sub_grid = Grid::from(input.as_str());
+        let grid = ExpandedGrid::from(&sub_grid);
+        assert_eq!(
+            find_shortest_path(&grid, (0, 0), grid.max_coords()),
+            Some(40)
+        );
+
+        let grid2 = grid.with_copies(5, 5);
+
+        assert_eq!(
+            find_shortest_path(&grid2, (0, 0), grid2.max_coords()),
+            Some(315)
+        );
+    }
+
+    #[test]
+    fn can_expand_grid() {
+        let input = sample_input();
+        let sub_grid = Grid::from(input.as_str());
+        let grid = ExpandedGrid::from(&sub_grid).with_copies(5, 5);
+
+        assert_eq!(grid.len(), 2500);
+        assert_eq!(grid.max_coords(), (49, 49));
+
+        // Rows from the expanded example in the puzzle description, including the wrap from 9 back to 1
+        let row = |y: usize| -> String {
+            (0..50)
+                .map(|x| grid.get(y, x).unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(row(0), "11637517422274862853338597396444961841755517295286");
+        assert_eq!(row(9), "23119445813422155692453326671356443778246755488935");
+        assert_eq!(
+            row(10),
+            "22748628533385973964449618417555172952866628316397"
+        );
+        assert_eq!(
+            row(49),
+            "67554889357866599146897761125791887223681299833479"
+        );
+
+        // Outside the tiles
+        assert_eq!(grid.get(50, 0), None);
+        assert_eq!(grid.get(0, 50), None);
+        assert_eq!(sub_grid.len(), 100);
+    }
+
+    #[test]
+    fn can_find_route() {
+        let input = sample_input();
+        let sub_grid = Grid::from(input.as_str());
+
+        for grid in [
+            ExpandedGrid::from(&sub_grid),
+            ExpandedGrid::from(&sub_grid).with_copies(5, 5),
+        ] {
+            let goal = grid.max_coords();
+            let route = find_shortest_route(&grid, (0, 0), goal).unwrap();
+
+            assert_eq!(route.first(), Some(&((0, 0), 0)));
+            assert_eq!(route.last().map(|&(coords, _)| coords), Some(goal));
+            assert_eq!(
+                route.last().map(|&(_, risk)| risk),
+                find_shortest_path(&grid, (0, 0), goal)
+            );
+
+            // Each step moves to an adjacent cell, and adds that cell's risk to the total
+            for window in route.windows(2) {
+                let ((y0, x0), risk0) = window[0];
+                let ((y1, x1), risk1) = window[1];
+                assert_eq!(y0.abs_diff(y1) + x0.abs_diff(x1), 1);
+                assert_eq!(risk1 - risk0, grid.get(y1, x1).unwrap() as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn can_format_route() {
+        let input = sample_input();
+        let sub_grid = Grid::from(input.as_str());
+        let grid = ExpandedGrid::from(&sub_grid);
+        let route = find_shortest_route(&grid, (0, 0), grid.max_coords()).unwrap();
+
+        // Swap the escape codes for brackets to make the expected output readable
+        let formatted = format_route(&grid, &route)
+            .replace(HIGHLIGHT_START, "[")
+            .replace(HIGHLIGHT_END, "]");
+
+        assert_eq!(
+            formatted,
+            "[1]163751742
+[1]381373672
+[2][1][3][6][5][1][1]328
+369493[1][5]69
+7463417[1][1]1
+13191281[3]7
+13599124[2]1
+31254216[3]9
+12931385[2][1]
+231194458[1]"
+        );
+    }
+}
@@ -0,0 +1,1178 @@
+//! This is my solution for [Advent of Code - Day 16 - _Packet Decoder_](https://adventofcode.com/2021/day/16)
+//!
+//! After yesterday's very technical/theory heavy solution today was working through a fairly
+//! complex specification, but the face-value implementation was efficient once all the parts were
+//! in place.
+//!
+//! The bulk of the work is in parsing the input into the hierarchy of packets, [`parse_input`].
+//! This is the entry point for a number of functions that are involved in the parsing process.
+//! [`to_bits`] is a bit clunky, but returns the bits as a `Vec<bool>` in reverse order so that the
+//! bits can be consumed with [`Vec::pop`] which is much more efficient than taking them from the
+//! head of the `Vec`. [`take_bits`] consumes a specified number of bits from the tail, interpreting
+//! them as a number. [`parse_packet`] consumes the version and [`PacketType`], then delegates to
+//! [`parse_literal`] and [`parse_sub_packets`] based on the type. Each uses [`take_bits`] as
+//! appropriate to consume and interpret the required bits according to the spec, and keeps track of
+//! bits consumed to report back to any parent operation packet that is reading in bit length mode.
+//! Rather than panicking, each step returns a [`PacketError`] if the input is malformed, so this
+//! can be used to decode transmissions that aren't known to be valid. The nesting is limited to
+//! [`MAX_DEPTH`] so that the recursion can't overflow the stack either, which the fuzz target in
+//! `fuzz/` checks with arbitrary input.
+//!
+//! Once that was done both part one [`Packet::version_sum`], and part two [`Packet::compute`]
+//! walk the packet tree compiling the appropriate solution. These were originally recursive, but
+//! now keep their own stack of packets to visit, so that a maliciously deep transmission can't
+//! overflow the call stack. [`Packet::compute_explained`] walks the tree recursively, but stops
+//! at a given depth, and also describes how the result was reached down to there. Both share
+//! [`PacketType::apply`] for the actual operations. [`Packet::to_expression`] renders the tree
+//! without evaluating it, and is printed when running with `--debug`.
+//!
+//! [`Packet::simplify`] is an optimiser pass over a parsed tree. It folds the literals in sums and
+//! products into a single literal, merges sums nested in sums and products nested in products,
+//! replaces operators with a single sub-packet by that sub-packet, and puts comparisons into a
+//! canonical form. It returns a new tree, along with [`SimplifyStats`] on what it changed. Running
+//! with `--debug` also prints the simplified expression, and its encoding.
+//!
+//! [`Packet::encode`] is the inverse of [`parse_input`], writing a tree back out as a hexadecimal
+//! transmission, so the module is a complete codec rather than only a decoder. Operators use the
+//! sub-packet count for their length whenever it fits, as it takes four fewer bits than the bit
+//! length.
+use crate::errors::{check_input, check_pattern, check_single_line, to_validation, Validation};
+use std::env;
+use std::fmt::{Display, Formatter};
+
+/// The eight possible packet types
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+enum PacketType {
+    /// Operation: Sum all contained packets
+    Sum,
+    /// Operation: Multiply all contained packets
+    Product,
+    /// Operation: Return the minimum of all contained packets
+    Min,
+    /// Operation: Return the maximum of all contained packets
+    Max,
+    /// Literal: This packet represents a literal number value
+    Literal,
+    /// Operation: Compare the first and only two sub packets, returns `1` if first is greater than
+    /// second, `0` otherwise.
+    GreaterThan,
+    /// Operation: Compare the first and only two sub packets, returns `1` if first is less than
+    /// second, `0` otherwise.
+    LessThan,
+    /// Operation: Compare the first and only two sub packets, returns `1` if first is equal to
+    /// second, `0` otherwise.
+    Equal,
+}
+
+impl PacketType {
+    /// The name used for this operation in [`Packet::compute_explained`] and
+    /// [`Packet::to_expression`]
+    fn name(&self) -> &'static str {
+        match self {
+            PacketType::Sum => "sum",
+            PacketType::Product => "product",
+            PacketType::Min => "min",
+            PacketType::Max => "max",
+            PacketType::Literal => "lit",
+            PacketType::GreaterThan => "gt",
+            PacketType::LessThan => "lt",
+            PacketType::Equal => "eq",
+        }
+    }
+
+    /// Apply this operation to the already computed values of the sub-packets. This is the one
+    /// place the operations are defined so that [`Packet::compute`] and
+    /// [`Packet::compute_explained`] can't diverge. Literals have no sub-packets, so their value is
+    /// handled by the caller. Sums and products are checked, as the literals can be large enough
+    /// for them to overflow.
+    fn apply(&self, values: &[usize]) -> Result<usize, PacketError> {
+        match self {
+            PacketType::Sum => values
+                .iter()
+                .try_fold(0usize, |acc, &value| acc.checked_add(value))
+                .ok_or(PacketError::ValueOverflow),
+            PacketType::Product => values
+                .iter()
+                .try_fold(1usize, |acc, &value| acc.checked_mul(value))
+                .ok_or(PacketError::ValueOverflow),
+            PacketType::Min => Ok(*values.iter().min().unwrap()),
+            PacketType::Max => Ok(*values.iter().max().unwrap()),
+            PacketType::Literal => panic!("Literal packets have no operation to apply"),
+            PacketType::GreaterThan => Ok((values[0] > values[1]) as usize),
+            PacketType::LessThan => Ok((values[0] < values[1]) as usize),
+            PacketType::Equal => Ok((values[0] == values[1]) as usize),
+        }
+    }
+}
+
+impl From<PacketType> for usize {
+    /// The type ID used for this packet type in a transmission, the inverse of
+    /// `PacketType::try_from`
+    fn from(packet_type: PacketType) -> Self {
+        match packet_type {
+            PacketType::Sum => 0,
+            PacketType::Product => 1,
+            PacketType::Min => 2,
+            PacketType::Max => 3,
+            PacketType::Literal => 4,
+            PacketType::GreaterThan => 5,
+            PacketType::LessThan => 6,
+            PacketType::Equal => 7,
+        }
+    }
+}
+
+impl TryFrom<usize> for PacketType {
+    type Error = PacketError;
+
+    fn try_from(num: usize) -> Result<Self, Self::Error> {
+        match num {
+            0 => Ok(PacketType::Sum),
+            1 => Ok(PacketType::Product),
+            2 => Ok(PacketType::Min),
+            3 => Ok(PacketType::Max),
+            4 => Ok(PacketType::Literal),
+            5 => Ok(PacketType::GreaterThan),
+            6 => Ok(PacketType::LessThan),
+            7 => Ok(PacketType::Equal),
+            _ => Err(PacketError::InvalidType(num)),
+        }
+    }
+}
+
+/// How deeply operators can be nested when decoding. Far deeper than the puzzle input goes, but
+/// shallow enough that the parser and the recursive functions on the tree can't overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+/// The ways decoding a BITS transmission can fail
+#[derive(Eq, PartialEq, Debug)]
+pub enum PacketError {
+    /// The input contained a character that isn't a hexadecimal digit
+    InvalidHex(char),
+    /// The bit stream ran out part way through a packet
+    UnexpectedEnd,
+    /// The packet type ID was not one of the eight known types
+    InvalidType(usize),
+    /// The sub-packets of an operator packet overran the bit length it declared
+    LengthOverrun,
+    /// A literal value was too large to fit in a `usize`
+    LiteralOverflow,
+    /// An operator packet had a number of sub-packets its operation can't be applied to, e.g. a
+    /// comparison that doesn't have exactly two.
+    InvalidSubPacketCount(usize),
+    /// A packet's version doesn't fit in the three bits allowed for it, when encoding
+    InvalidVersion(usize),
+    /// An operator had too many sub-packets for either length type to describe, when encoding
+    TooManySubPackets(usize),
+    /// Packets were nested more than [`MAX_DEPTH`] deep
+    TooDeep,
+    /// A sum or product was too large to fit in a `usize`, when computing
+    ValueOverflow,
+}
+
+impl Display for PacketError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PacketError::InvalidHex(c) => write!(f, "'{}' is not a hexadecimal digit", c),
+            PacketError::UnexpectedEnd => write!(f, "unexpected end of bit stream"),
+            PacketError::InvalidType(id) => write!(f, "invalid packet type {}", id),
+            PacketError::LengthOverrun => {
+                write!(f, "sub-packets overran the declared bit length")
+            }
+            PacketError::LiteralOverflow => write!(f, "literal value is too large"),
+            PacketError::ValueOverflow => write!(f, "computed value is too large"),
+            PacketError::InvalidSubPacketCount(count) => {
+                write!(
+                    f,
+                    "operator has an invalid number of sub-packets: {}",
+                    count
+                )
+            }
+            PacketError::InvalidVersion(version) => {
+                write!(f, "version {} doesn't fit in three bits", version)
+            }
+            PacketError::TooManySubPackets(count) => {
+                write!(f, "operator has too many sub-packets to encode: {}", count)
+            }
+            PacketError::TooDeep => write!(f, "packets are nested more than {} deep", MAX_DEPTH),
+        }
+    }
+}
+
+/// Represents a packet in BITS
+#[derive(Eq, PartialEq, Debug)]
+pub struct Packet {
+    /// The version (0-7)
+    version: usize,
+    /// Indicates what this packet represents
+    packet_type: PacketType,
+    /// List of sub-packets. For PacketType::Literal this will be empty
+    sub_packets: Vec<Packet>,
+    /// The literal value, for PacketTypes other than PacketType::Literal
+    value: usize,
+}
+
+/// What [`Packet::simplify`] changed
+#[derive(Eq, PartialEq, Debug, Default)]
+struct SimplifyStats {
+    /// The number of packets in the tree before simplifying
+    nodes_before: usize,
+    /// The number of packets in the simplified tree
+    nodes_after: usize,
+    /// Literals in a sum or product that were combined with another literal
+    folded: usize,
+    /// Sums in a sum, or products in a product, whose sub-packets were moved into the outer one
+    flattened: usize,
+    /// Operators with a single sub-packet that were replaced by it
+    collapsed: usize,
+    /// Comparisons that were rewritten into their canonical form
+    canonicalised: usize,
+}
+
+impl SimplifyStats {
+    /// How many fewer packets the simplified tree has
+    fn eliminated(&self) -> usize {
+        self.nodes_before - self.nodes_after
+    }
+}
+
+impl Drop for Packet {
+    /// The default drop recurses into the sub-packets, and so can overflow the stack for the same
+    /// deeply nested packets that [`Packet::compute`] handles. Flatten the tree as it is dropped
+    /// instead, so that each packet has no sub-packets left by the time it is dropped.
+    fn drop(&mut self) {
+        let mut pending = std::mem::take(&mut self.sub_packets);
+        while let Some(mut packet) = pending.pop() {
+            pending.append(&mut packet.sub_packets);
+        }
+    }
+}
+
+impl Packet {
+    /// create a packet representing an operation on sub packets
+    fn new_operator(version: usize, packet_type: PacketType, sub_packets: Vec<Packet>) -> Packet {
+        Packet {
+            version,
+            packet_type,
+            sub_packets,
+            value: 0,
+        }
+    }
+
+    /// Create a packet representing a literal number
+    fn new_literal(version: usize, value: usize) -> Packet {
+        Packet {
+            version,
+            packet_type: PacketType::Literal,
+            sub_packets: Vec::new(),
+            value,
+        }
+    }
+
+    /// The number of packets in the tree, including this one
+    fn node_count(&self) -> usize {
+        let mut pending: Vec<&Packet> = Vec::from([self]);
+        let mut count = 0;
+
+        while let Some(packet) = pending.pop() {
+            count += 1;
+            pending.extend(packet.sub_packets.iter());
+        }
+
+        count
+    }
+
+    /// Solution to part one. Returns the sum of this packet's version and the version sum of all
+    /// sub-packets. The order doesn't matter, so this just works through a stack of the packets
+    /// still to visit.
+    fn version_sum(&self) -> usize {
+        let mut pending: Vec<&Packet> = Vec::from([self]);
+        let mut sum = 0;
+
+        while let Some(packet) = pending.pop() {
+            sum += packet.version;
+            pending.extend(packet.sub_packets.iter());
+        }
+
+        sum
+    }
+
+    /// Solution to part two. Compute the value of applying the current operation to the contained
+    /// sub-packets' computed values, or return the value in the case of a literal node.
+    ///
+    /// This is a post-order traversal, but using explicit stacks rather than recursing so that
+    /// deeply nested packets can't overflow the call stack. Each operator is visited twice, the
+    /// first time it queues its sub-packets to be computed, then once their values are all on the
+    /// operand stack, it replaces them with its own value. Fails if a sum or product overflows.
+    fn compute(&self) -> Result<usize, PacketError> {
+        // Packets still to visit, and whether their sub-packets have already been queued
+        let mut pending: Vec<(&Packet, bool)> = Vec::from([(self, false)]);
+        let mut operands: Vec<usize> = Vec::new();
+
+        while let Some((packet, expanded)) = pending.pop() {
+            match (&packet.packet_type, expanded) {
+                (PacketType::Literal, _) => operands.push(packet.value),
+                (_, false) => {
+                    pending.push((packet, true));
+                    // Reversed so that the first sub-packet is computed, and its value pushed,
+                    // first
+                    pending.extend(packet.sub_packets.iter().rev().map(|sub| (sub, false)));
+                }
+                (packet_type, true) => {
+                    let values = operands.split_off(operands.len() - packet.sub_packets.len());
+                    operands.push(packet_type.apply(&values)?);
+                }
+            }
+        }
+
+        Ok(operands
+            .pop()
+            .expect("The root packet always leaves a value"))
+    }
+
+    /// As [`Packet::compute`], but also build a nested explanation of how the value was reached,
+    /// e.g. `sum(2, product(3, 4)=12)=14`. Operations nested deeper than `max_depth` are only shown
+    /// as their value, and the explanation for those sub-trees is never built.
+    fn compute_explained(&self, max_depth: usize) -> Result<(usize, String), PacketError> {
+        if self.packet_type == PacketType::Literal || max_depth == 0 {
+            let value = self.compute()?;
+            return Ok((value, value.to_string()));
+        }
+
+        let (values, explanations): (Vec<usize>, Vec<String>) = self
+            .sub_packets
+            .iter()
+            .map(|packet| packet.compute_explained(max_depth - 1))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+
+        let value = self.packet_type.apply(&values)?;
+        let explanation = format!(
+            "{}({})={}",
+            self.packet_type.name(),
+            explanations.join(", "),
+            value
+        );
+
+        Ok((value, explanation))
+    }
+
+    /// Build an equivalent tree with fewer packets, which computes the same value. Each packet is
+    /// simplified after its sub-packets, using the same explicit stacks as [`Packet::compute`] so
+    /// that deeply nested packets can't overflow the call stack, see [`simplify_operator`] for the
+    /// rules applied. The packets that replace others keep the replaced packet's version, so the
+    /// version sum isn't preserved.
+    fn simplify(&self) -> (Packet, SimplifyStats) {
+        let mut stats = SimplifyStats {
+            nodes_before: self.node_count(),
+            ..SimplifyStats::default()
+        };
+        let mut pending: Vec<(&Packet, bool)> = Vec::from([(self, false)]);
+        let mut simplified: Vec<Packet> = Vec::new();
+
+        while let Some((packet, expanded)) = pending.pop() {
+            match (packet.packet_type, expanded) {
+                (PacketType::Literal, _) => {
+                    simplified.push(Packet::new_literal(packet.version, packet.value))
+                }
+                (_, false) => {
+                    pending.push((packet, true));
+                    pending.extend(packet.sub_packets.iter().rev().map(|sub| (sub, false)));
+                }
+                (packet_type, true) => {
+                    let sub_packets =
+                        simplified.split_off(simplified.len() - packet.sub_packets.len());
+                    simplified.push(simplify_operator(
+                        packet.version,
+                        packet_type,
+                        sub_packets,
+                        &mut stats,
+                    ));
+                }
+            }
+        }
+
+        let root = simplified
+            .pop()
+            .expect("The root packet always leaves a packet");
+        stats.nodes_after = root.node_count();
+
+        (root, stats)
+    }
+
+    /// Encode the packet as a hexadecimal BITS transmission, padded with `0` bits to a whole number
+    /// of hex digits, so that [`parse_input`] gives back an equal packet. Fails if a version or
+    /// sub-packet count can't be represented, which can't happen for a parsed packet.
+    fn encode(&self) -> Result<String, PacketError> {
+        let mut bits = Vec::new();
+        self.write_bits(&mut bits)?;
+        bits.resize(bits.len().div_ceil(4) * 4, false);
+
+        Ok(bits
+            .chunks(4)
+            .map(|digit| {
+                let value = digit.iter().fold(0, |acc, &bit| (acc << 1) + bit as u32);
+                char::from_digit(value, 16).unwrap().to_ascii_uppercase()
+            })
+            .collect())
+    }
+
+    /// Append the bits for this packet, the mirror of [`parse_packet`]. This recurses into the
+    /// sub-packets, as the parser does.
+    fn write_bits(&self, bits: &mut Vec<bool>) -> Result<(), PacketError> {
+        if self.version > 7 {
+            return Err(PacketError::InvalidVersion(self.version));
+        }
+        push_bits(bits, self.version, 3);
+        push_bits(bits, self.packet_type.into(), 3);
+
+        if self.packet_type == PacketType::Literal {
+            write_literal(bits, self.value);
+            return Ok(());
+        }
+
+        let count = self.sub_packets.len();
+        let mut sub_bits = Vec::new();
+        for sub_packet in &self.sub_packets {
+            sub_packet.write_bits(&mut sub_bits)?;
+        }
+
+        if count < 1 << 11 {
+            bits.push(true);
+            push_bits(bits, count, 11);
+        } else if sub_bits.len() < 1 << 15 {
+            bits.push(false);
+            push_bits(bits, sub_bits.len(), 15);
+        } else {
+            return Err(PacketError::TooManySubPackets(count));
+        }
+        bits.append(&mut sub_bits);
+
+        Ok(())
+    }
+
+    /// Render the packet hierarchy as a prefix expression, e.g. `max(sum(1, 2), product(3, 4))`, to
+    /// show what the operator tree computes.
+    fn to_expression(&self) -> String {
+        match self.packet_type {
+            PacketType::Literal => self.value.to_string(),
+            _ => format!(
+                "{}({})",
+                self.packet_type.name(),
+                self.sub_packets
+                    .iter()
+                    .map(Packet::to_expression)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// Simplify an operator whose sub-packets have already been simplified, in this order:
+///
+/// - A sum's sub-packets that are also sums are replaced by their own sub-packets, and the same for
+///   products, as the order they're applied in doesn't change the result.
+/// - The literals in a sum or product are folded into a single literal, which comes after the other
+///   sub-packets. It is left out if it is `0` for a sum or `1` for a product, and there are other
+///   sub-packets. Only sums and products are folded, the other operations are left for
+///   [`Packet::compute`], so that the expression still shows what is being compared.
+/// - A sum, product, minimum or maximum with a single sub-packet is replaced by that sub-packet.
+/// - A less-than is turned around into a greater-than, and if only the first sub-packet of an
+///   equals is a literal, they are swapped, so that equivalent comparisons are written the same
+///   way.
+fn simplify_operator(
+    version: usize,
+    packet_type: PacketType,
+    sub_packets: Vec<Packet>,
+    stats: &mut SimplifyStats,
+) -> Packet {
+    let mut sub_packets = sub_packets;
+
+    if matches!(packet_type, PacketType::Sum | PacketType::Product) {
+        let mut merged = Vec::new();
+        for mut sub_packet in sub_packets {
+            if sub_packet.packet_type == packet_type {
+                stats.flattened += 1;
+                merged.append(&mut sub_packet.sub_packets);
+            } else {
+                merged.push(sub_packet);
+            }
+        }
+
+        let (literals, mut others): (Vec<Packet>, Vec<Packet>) = merged
+            .into_iter()
+            .partition(|packet| packet.packet_type == PacketType::Literal);
+        let identity = packet_type.apply(&[]).expect("An empty sum or product can't overflow");
+        let values: Vec<usize> = literals.iter().map(|literal| literal.value).collect();
+        match (literals.first(), packet_type.apply(&values)) {
+            (Some(first), Ok(value)) => {
+                stats.folded += literals.len() - 1;
+                if value != identity || others.is_empty() {
+                    others.push(Packet::new_literal(first.version, value));
+                } else {
+                    stats.folded += 1;
+                }
+            }
+            // Leave literals that overflow when folded for computing to report
+            (Some(_), Err(_)) => others.extend(literals),
+            (None, _) => {}
+        }
+        sub_packets = others;
+    }
+
+    match packet_type {
+        PacketType::Sum | PacketType::Product | PacketType::Min | PacketType::Max
+            if sub_packets.len() == 1 =>
+        {
+            stats.collapsed += 1;
+            sub_packets.pop().unwrap()
+        }
+        PacketType::LessThan => {
+            stats.canonicalised += 1;
+            sub_packets.reverse();
+            Packet::new_operator(version, PacketType::GreaterThan, sub_packets)
+        }
+        PacketType::Equal
+            if sub_packets[0].packet_type == PacketType::Literal
+                && sub_packets[1].packet_type != PacketType::Literal =>
+        {
+            stats.canonicalised += 1;
+            sub_packets.reverse();
+            Packet::new_operator(version, packet_type, sub_packets)
+        }
+        _ => Packet::new_operator(version, packet_type, sub_packets),
+    }
+}
+
+/// Check the input is a single line of hexadecimal, see [`validate`], and decode the outermost
+/// [`Packet`] from it for [`part_one`] and [`part_two`], see [`parse_input`].
+pub fn parse(contents: &str) -> Packet {
+    check_input(16, contents, validate);
+    parse_input(contents).unwrap_or_else(|err| panic!("Invalid packet: {}", err))
+}
+
+/// Sum the versions of every packet, see [`Packet::version_sum`]
+pub fn part_one(root: &Packet) -> Option<String> {
+    let version_sum = root.version_sum();
+    say!("The version sum is: {}", version_sum);
+
+    Some(version_sum.to_string())
+}
+
+/// Evaluate the expression the packets represent, see [`Packet::compute`].
+///
+/// - If the program was run with `--debug` the full expression is also printed, along with the
+///   simplified expression, see [`Packet::simplify`], and that re-encoded, see [`Packet::encode`].
+pub fn part_two(root: &Packet) -> Option<String> {
+    let (result, explanation) = match root.compute_explained(2) {
+        Ok(computed) => computed,
+        Err(err) => {
+            say!("The operation can't be computed: {}", err);
+            return None;
+        }
+    };
+    say!("The result of the operation is: {}", result);
+    say!("Which is: {}", explanation);
+
+    if env::args().any(|arg| arg == "--debug") {
+        say!("The full expression is: {}", root.to_expression());
+        let (simplified, stats) = root.simplify();
+        say!(
+            "Which simplifies to: {} ({} of {} packets eliminated)",
+            simplified.to_expression(),
+            stats.eliminated(),
+            stats.nodes_before
+        );
+        match simplified.encode() {
+            Ok(encoded) => say!("Which can be sent as: {}", encoded),
+            Err(err) => say!("Which can't be encoded: {}", err),
+        }
+    }
+
+    Some(result.to_string())
+}
+
+/// The input should be a single line of hexadecimal
+pub fn validate(input: &str) -> Validation {
+    let mut errors = check_single_line(input);
+    errors.extend(check_pattern(input, "[0-9A-F]+", "hexadecimal digits"));
+    to_validation(errors)
+}
+
+/// For `--detect`, see [`crate::detect`]. A transmission could be all decimal digits, but it is
+/// long enough that it won't be, and requiring a letter stops day 1's depths being read as one.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok() && input.contains(|c: char| ('A'..='F').contains(&c))
+}
+
+/// Parse a hexadecimal string as a sequence of bits. The returned list is reversed for ease of
+/// consuming the bits via [`Vec::pop`].
+fn to_bits(input: &str) -> Result<Vec<bool>, PacketError> {
+    let mut bits = Vec::with_capacity(input.len() * 4);
+    for c in input.chars() {
+        let num = c.to_digit(16).ok_or(PacketError::InvalidHex(c))?;
+        bits.extend([num & 8 == 8, num & 4 == 4, num & 2 == 2, num & 1 == 1]);
+    }
+    bits.reverse();
+
+    Ok(bits)
+}
+
+/// Consume the last `count` bits from the end of the provided vector, interpreting them as a binary
+/// representation of a usize.
+fn take_bits(bits: &mut Vec<bool>, count: usize) -> Result<usize, PacketError> {
+    let mut out: usize = 0;
+    for _ in 0..count {
+        // Shift the next bit onto the left
+        out = (out << 1) + (bits.pop().ok_or(PacketError::UnexpectedEnd)? as usize)
+    }
+
+    Ok(out)
+}
+
+/// Append the lowest `count` bits of `value`, most significant first, the inverse of [`take_bits`]
+fn push_bits(bits: &mut Vec<bool>, value: usize, count: usize) {
+    bits.extend((0..count).rev().map(|shift| (value >> shift) & 1 == 1));
+}
+
+/// Append a literal value in as few five bit chunks as will hold it, the inverse of
+/// [`parse_literal`]. Each chunk has a flag that is `1` if there are more chunks to follow, and the
+/// next four bits of the value.
+fn write_literal(bits: &mut Vec<bool>, value: usize) {
+    let significant_bits = (usize::BITS - value.leading_zeros()) as usize;
+    let chunks = significant_bits.div_ceil(4).max(1);
+    for chunk in (0..chunks).rev() {
+        bits.push(chunk > 0);
+        push_bits(bits, value >> (chunk * 4), 4);
+    }
+}
+
+/// Parse the section of a literal packet representing the number. This will be in chunks of 5 bits,
+/// the first being a flag that indicates if parsing should continue after this chunk, the next four
+/// being the next four bits in the number. Once the continue flag is `0` indicating this is the
+/// final chunk, all four-bit sections should be concatenated and interpreted as the binary
+/// representation of a usize. Returns the value and number of bits consumed.
+fn parse_literal(bits: &mut Vec<bool>) -> Result<(usize, usize), PacketError> {
+    let mut value: usize = 0;
+    let mut bit_count = 0;
+
+    loop {
+        // Consume the next continue flag
+        let last = take_bits(bits, 1)? == 0;
+        // Shift the next four bits left from the bit stream, checking there is room to do so.
+        if value.leading_zeros() < 4 {
+            return Err(PacketError::LiteralOverflow);
+        }
+        value = (value << 4) + take_bits(bits, 4)?;
+        bit_count += 5;
+        if last {
+            break;
+        }
+    }
+
+    Ok((value, bit_count))
+}
+
+/// Parse the sub-packets section of an operation packet.
+/// 1. Consume one bit indicating the mode of consuming sub packets
+///     * If `0` consume the next 15 bits as a bit length
+///     * If `1` consume the nect 11 bits as a packet count
+/// 2. Consume one sub-packet at a time using [`parse_packet`].
+///     * Decrement the bit counter by the number of bits consumed, or the packet counter by `1` as
+///       each packet is consumed.
+///     * Keep a running total of bits consumed.
+/// 3. Return the list of parsed packets, and the total bits consumed
+fn parse_sub_packets(
+    bits: &mut Vec<bool>,
+    depth: usize,
+) -> Result<(Vec<Packet>, usize), PacketError> {
+    let mut bit_count: usize = 0;
+    let mut sub_packets = Vec::new();
+
+    let length_is_bits = take_bits(bits, 1)? == 0;
+    bit_count += 1;
+
+    if length_is_bits {
+        let mut bits_to_take = take_bits(bits, 15)?;
+        bit_count += 15;
+
+        while bits_to_take > 0 {
+            let (sub_packet, bit_length) = parse_packet(bits, depth + 1)?;
+            sub_packets.push(sub_packet);
+            bit_count += bit_length;
+            bits_to_take = bits_to_take
+                .checked_sub(bit_length)
+                .ok_or(PacketError::LengthOverrun)?;
+        }
+    } else {
+        let mut packets_to_take = take_bits(bits, 11)?;
+        bit_count += 11;
+
+        while packets_to_take > 0 {
+            let (sub_packet, bit_length) = parse_packet(bits, depth + 1)?;
+            sub_packets.push(sub_packet);
+            bit_count += bit_length;
+            packets_to_take -= 1;
+        }
+    }
+    Ok((sub_packets, bit_count))
+}
+
+/// Check an operator has a number of sub-packets that [`PacketType::apply`] can handle, so that
+/// computing a successfully parsed packet can't panic.
+fn validate_sub_packet_count(packet_type: &PacketType, count: usize) -> Result<(), PacketError> {
+    let valid = match packet_type {
+        PacketType::Sum | PacketType::Product => true,
+        PacketType::Min | PacketType::Max => count > 0,
+        PacketType::GreaterThan | PacketType::LessThan | PacketType::Equal => count == 2,
+        PacketType::Literal => count == 0,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(PacketError::InvalidSubPacketCount(count))
+    }
+}
+
+/// Read the packet header (version: 3 bits, type: 3 bits). Then based of the type delegate the
+/// parsing of the payload to either [`parse_literal`] or [`parse_sub_packets`]. Return the parsed
+/// [`Packet`] and number of bits consumed. `depth` is how many operators this is nested in.
+fn parse_packet(bits: &mut Vec<bool>, depth: usize) -> Result<(Packet, usize), PacketError> {
+    if depth > MAX_DEPTH {
+        return Err(PacketError::TooDeep);
+    }
+    let version = take_bits(bits, 3)?;
+    let packet_type = PacketType::try_from(take_bits(bits, 3)?)?;
+    let root_bit_count = 6usize;
+    if packet_type == PacketType::Literal {
+        let (value, literal_bit_count) = parse_literal(bits)?;
+        Ok((
+            Packet {
+                version,
+                packet_type,
+                sub_packets: Vec::new(),
+                value,
+            },
+            root_bit_count + literal_bit_count,
+        ))
+    } else {
+        let (sub_packets, sub_bit_count) = parse_sub_packets(bits, depth)?;
+        validate_sub_packet_count(&packet_type, sub_packets.len())?;
+        Ok((
+            Packet {
+                version,
+                packet_type,
+                sub_packets,
+                value: 0,
+            },
+            root_bit_count + sub_bit_count,
+        ))
+    }
+}
+
+/// Decode a hexadecimal BITS transmission into its outermost packet. Surrounding whitespace is
+/// ignored, and any trailing bits after the outermost packet are assumed to be padding.
+pub fn parse_input(input: &str) -> Result<Packet, PacketError> {
+    let mut bits: Vec<bool> = to_bits(input.trim())?;
+    let (packet, _) = parse_packet(&mut bits, 0)?;
+    Ok(packet)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::random::Random;
+    use crate::year_2021::day_16::{
+        parse_input, take_bits, to_bits, Packet, PacketError, PacketType, SimplifyStats, MAX_DEPTH,
+    };
+
+    fn sample_literal() -> Vec<bool> {
+        "110100101111111000101000"
+            .chars()
+            .map(|c| c == '1')
+            .rev()
+            .collect::<Vec<bool>>()
+    }
+
+    #[test]
+    fn can_parse_to_bits() {
+        assert_eq!(to_bits("D2FE28"), Ok(sample_literal()));
+    }
+
+    #[test]
+    fn can_take_bits() {
+        let mut bits: Vec<bool> = sample_literal();
+        assert_eq!(take_bits(&mut bits, 3), Ok(6usize));
+        assert_eq!(take_bits(&mut bits, 3), Ok(4usize));
+        assert_eq!(take_bits(&mut bits, 1), Ok(1usize));
+        assert_eq!(take_bits(&mut bits, 4), Ok(7usize));
+        assert_eq!(take_bits(&mut bits, 1), Ok(1usize));
+        assert_eq!(take_bits(&mut bits, 4), Ok(14usize));
+        assert_eq!(take_bits(&mut bits, 1), Ok(0usize));
+        assert_eq!(take_bits(&mut bits, 4), Ok(5usize));
+    }
+
+    #[test]
+    fn can_parse_literal() {
+        assert_eq!(parse_input("D2FE28").unwrap(), Packet::new_literal(6, 2021))
+    }
+
+    #[test]
+    fn can_parse_operator_with_bit_length() {
+        assert_eq!(
+            parse_input("38006F45291200").unwrap(),
+            Packet::new_operator(
+                1,
+                PacketType::LessThan,
+                Vec::from([Packet::new_literal(6, 10), Packet::new_literal(2, 20)])
+            )
+        )
+    }
+
+    #[test]
+    fn can_parse_operator_with_packet_length() {
+        assert_eq!(
+            parse_input("EE00D40C823060").unwrap(),
+            Packet::new_operator(
+                7,
+                PacketType::Max,
+                Vec::from([
+                    Packet::new_literal(2, 1),
+                    Packet::new_literal(4, 2),
+                    Packet::new_literal(1, 3),
+                ])
+            )
+        )
+    }
+
+    #[test]
+    fn can_sum_versions() {
+        assert_eq!(parse_input("8A004A801A8002F478").unwrap().version_sum(), 16);
+        assert_eq!(
+            parse_input("620080001611562C8802118E34")
+                .unwrap()
+                .version_sum(),
+            12
+        );
+        assert_eq!(
+            parse_input("C0015000016115A2E0802F182340")
+                .unwrap()
+                .version_sum(),
+            23
+        );
+        assert_eq!(
+            parse_input("A0016C880162017C3686B18A3D4780")
+                .unwrap()
+                .version_sum(),
+            31
+        );
+    }
+
+    #[test]
+    fn can_compute() {
+        assert_eq!(parse_input("C200B40A82").unwrap().compute(), Ok(3));
+        assert_eq!(parse_input("04005AC33890").unwrap().compute(), Ok(54));
+        assert_eq!(parse_input("880086C3E88112").unwrap().compute(), Ok(7));
+        assert_eq!(parse_input("CE00C43D881120").unwrap().compute(), Ok(9));
+        assert_eq!(parse_input("D8005AC2A8F0").unwrap().compute(), Ok(1));
+        assert_eq!(parse_input("F600BC2D8F").unwrap().compute(), Ok(0));
+        assert_eq!(parse_input("9C005AC2F8F0").unwrap().compute(), Ok(0));
+        assert_eq!(
+            parse_input("9C0141080250320F1802104A08").unwrap().compute(),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn can_compute_explained() {
+        let packet = Packet::new_operator(
+            0,
+            PacketType::Sum,
+            Vec::from([
+                Packet::new_literal(0, 2),
+                Packet::new_operator(
+                    0,
+                    PacketType::Product,
+                    Vec::from([Packet::new_literal(0, 3), Packet::new_literal(0, 4)]),
+                ),
+            ]),
+        );
+
+        assert_eq!(
+            packet.compute_explained(5),
+            Ok((14, "sum(2, product(3, 4)=12)=14".to_string()))
+        );
+        assert_eq!(
+            packet.compute_explained(1),
+            Ok((14, "sum(2, 12)=14".to_string()))
+        );
+        assert_eq!(packet.compute_explained(0), Ok((14, "14".to_string())));
+
+        assert_eq!(
+            parse_input("9C0141080250320F1802104A08")
+                .unwrap()
+                .compute_explained(2),
+            Ok((1, "eq(sum(1, 3)=4, product(2, 2)=4)=1".to_string()))
+        );
+    }
+
+    #[test]
+    fn can_format_expression() {
+        assert_eq!(parse_input("D2FE28").unwrap().to_expression(), "2021");
+        assert_eq!(
+            parse_input("9C0141080250320F1802104A08")
+                .unwrap()
+                .to_expression(),
+            "eq(sum(1, 3), product(2, 2))"
+        );
+        assert_eq!(
+            parse_input("EE00D40C823060").unwrap().to_expression(),
+            "max(1, 2, 3)"
+        );
+    }
+
+    #[test]
+    fn can_simplify() {
+        let literal = |value| Packet::new_literal(0, value);
+        let operator = |packet_type, sub_packets| Packet::new_operator(0, packet_type, sub_packets);
+
+        // sum(max(1, 2), sum(min(3), max(4, 5), 5), 0, lt(product(2, 3), 7), eq(1, max(1, 1)))
+        let packet = operator(
+            PacketType::Sum,
+            Vec::from([
+                operator(PacketType::Max, Vec::from([literal(1), literal(2)])),
+                operator(
+                    PacketType::Sum,
+                    Vec::from([
+                        operator(PacketType::Min, Vec::from([literal(3)])),
+                        operator(PacketType::Max, Vec::from([literal(4), literal(5)])),
+                        literal(5),
+                    ]),
+                ),
+                literal(0),
+                operator(
+                    PacketType::LessThan,
+                    Vec::from([
+                        operator(PacketType::Product, Vec::from([literal(2), literal(3)])),
+                        literal(7),
+                    ]),
+                ),
+                operator(
+                    PacketType::Equal,
+                    Vec::from([
+                        literal(1),
+                        operator(PacketType::Max, Vec::from([literal(1), literal(1)])),
+                    ]),
+                ),
+            ]),
+        );
+
+        let (simplified, stats) = packet.simplify();
+        assert_eq!(
+            simplified.to_expression(),
+            "sum(max(1, 2), max(4, 5), gt(7, 6), eq(max(1, 1), 1), 8)"
+        );
+        assert_eq!(simplified.compute(), packet.compute());
+        assert_eq!(
+            stats,
+            SimplifyStats {
+                nodes_before: 22,
+                nodes_after: 16,
+                folded: 3,
+                flattened: 1,
+                collapsed: 2,
+                canonicalised: 2,
+            }
+        );
+        assert_eq!(stats.eliminated(), 6);
+
+        // An identity is only left out if there is something else to add to
+        assert_eq!(
+            operator(PacketType::Product, Vec::from([literal(1)]))
+                .simplify()
+                .0,
+            literal(1)
+        );
+
+        for input in [
+            "C200B40A82",
+            "04005AC33890",
+            "880086C3E88112",
+            "CE00C43D881120",
+            "D8005AC2A8F0",
+            "F600BC2D8F",
+            "9C005AC2F8F0",
+            "9C0141080250320F1802104A08",
+        ] {
+            let packet = parse_input(input).unwrap();
+            let (simplified, stats) = packet.simplify();
+            assert_eq!(simplified.compute(), packet.compute(), "{}", input);
+            assert_eq!(stats.nodes_after, simplified.node_count());
+        }
+        assert_eq!(
+            parse_input("9C0141080250320F1802104A08")
+                .unwrap()
+                .simplify()
+                .0
+                .to_expression(),
+            "eq(4, 4)"
+        );
+    }
+
+    /// A random tree of valid packets, nested at most `depth` deep
+    fn random_packet(random: &mut Random, depth: usize) -> Packet {
+        let version = random.below(8);
+        if depth == 0 || random.below(3) == 0 {
+            // Mostly small values, but sometimes the full range
+            let value = match random.below(4) {
+                0 => random.next_u64() as usize,
+                _ => random.below(100),
+            };
+            return Packet::new_literal(version, value);
+        }
+
+        let (packet_type, count) = match random.below(7) {
+            0 => (PacketType::Sum, random.below(4)),
+            1 => (PacketType::Product, random.below(4)),
+            2 => (PacketType::Min, random.below(3) + 1),
+            3 => (PacketType::Max, random.below(3) + 1),
+            4 => (PacketType::GreaterThan, 2),
+            5 => (PacketType::LessThan, 2),
+            _ => (PacketType::Equal, 2),
+        };
+        let sub_packets = (0..count)
+            .map(|_| random_packet(random, depth - 1))
+            .collect();
+
+        Packet::new_operator(version, packet_type, sub_packets)
+    }
+
+    #[test]
+    fn can_encode() {
+        // The literal example is already as short as it can be
+        assert_eq!(parse_input("D2FE28").unwrap().encode(), Ok("D2FE28".to_string()));
+        assert_eq!(
+            Packet::new_literal(0, 0).encode(),
+            Ok("100".to_string())
+        );
+
+        // The examples might use the other length type, or have extra padding, so only check that
+        // they decode the same
+        for input in [
+            "38006F45291200",
+            "EE00D40C823060",
+            "8A004A801A8002F478",
+            "620080001611562C8802118E34",
+            "C0015000016115A2E0802F182340",
+            "A0016C880162017C3686B18A3D4780",
+            "9C0141080250320F1802104A08",
+        ] {
+            let packet = parse_input(input).unwrap();
+            let encoded = packet.encode().unwrap();
+            assert_eq!(parse_input(&encoded), Ok(packet), "{}", input);
+            assert!(encoded.len() <= input.len(), "{}", input);
+        }
+
+        let mut random = Random::new(16);
+        for _ in 0..500 {
+            let packet = random_packet(&mut random, 5);
+            let encoded = packet.encode().unwrap();
+            assert_eq!(parse_input(&encoded), Ok(packet));
+        }
+
+        // Too many sub-packets to count in 11 bits falls back to the bit length
+        let packet = Packet::new_operator(
+            0,
+            PacketType::Sum,
+            (0..2048).map(|i| Packet::new_literal(0, i % 16)).collect(),
+        );
+        let encoded = packet.encode().unwrap();
+        // The length type 0 is followed by 2048 * 11 = 22528 as 15 bits, which starts with a 1
+        assert_eq!(&encoded[..2], "01");
+        assert_eq!(parse_input(&encoded), Ok(packet));
+
+        assert_eq!(
+            Packet::new_literal(8, 1).encode(),
+            Err(PacketError::InvalidVersion(8))
+        );
+        assert_eq!(
+            Packet::new_operator(
+                0,
+                PacketType::Sum,
+                (0..3000).map(|i| Packet::new_literal(0, i)).collect(),
+            )
+            .encode(),
+            Err(PacketError::TooManySubPackets(3000))
+        );
+    }
+
+    #[test]
+    fn can_report_malformed_input() {
+        assert_eq!(to_bits("D2FG28"), Err(PacketError::InvalidHex('G')));
+        assert_eq!(parse_input("D2F"), Err(PacketError::UnexpectedEnd));
+        assert_eq!(parse_input("38006F45291200\n").unwrap().compute(), Ok(1));
+
+        let mut bits = sample_literal();
+        assert_eq!(take_bits(&mut bits, 25), Err(PacketError::UnexpectedEnd));
+
+        // A literal of 17 * 4 = 68 bits can't fit in a usize
+        assert_eq!(
+            parse_input("D3FFFFFFFFFFFFFFFFFFFFE0"),
+            Err(PacketError::LiteralOverflow)
+        );
+        // Literals that fit can still overflow when summed or multiplied
+        let overflowing = Packet::new_operator(
+            0,
+            PacketType::Product,
+            Vec::from([Packet::new_literal(0, usize::MAX), Packet::new_literal(0, 2)]),
+        );
+        assert_eq!(overflowing.compute(), Err(PacketError::ValueOverflow));
+        assert_eq!(
+            overflowing.compute_explained(2),
+            Err(PacketError::ValueOverflow)
+        );
+        assert_eq!(overflowing.simplify().0, overflowing);
+
+        // An operator declaring 10 bits of sub-packets, containing an 11 bit literal
+        assert_eq!(parse_input("380028408"), Err(PacketError::LengthOverrun));
+        // A greater-than operator with only one sub-packet
+        assert_eq!(
+            parse_input("16004408"),
+            Err(PacketError::InvalidSubPacketCount(1))
+        );
+
+        // Nesting past the limit is rejected before it can overflow the stack
+        let nested = |depth: usize| {
+            (0..depth).fold(Packet::new_literal(1, 5), |inner, _| {
+                Packet::new_operator(1, PacketType::Sum, vec![inner])
+            })
+        };
+        let limit = nested(MAX_DEPTH).encode().unwrap();
+        assert_eq!(parse_input(&limit).unwrap().compute(), Ok(5));
+        let too_deep = nested(MAX_DEPTH + 1).encode().unwrap();
+        assert_eq!(parse_input(&too_deep), Err(PacketError::TooDeep));
+    }
+
+    #[test]
+    fn can_evaluate_deeply_nested_packets() {
+        // Each level adds one to the packet it wraps, deep enough that recursing per level would
+        // overflow the stack
+        let depth = 100_000;
+        let packet = (0..depth).fold(Packet::new_literal(1, 5), |inner, _| {
+            Packet::new_operator(
+                1,
+                PacketType::Sum,
+                Vec::from([inner, Packet::new_literal(0, 1)]),
+            )
+        });
+
+        assert_eq!(packet.version_sum(), depth + 1);
+        assert_eq!(packet.compute(), Ok(depth + 5));
+
+        let (simplified, stats) = packet.simplify();
+        assert_eq!(simplified, Packet::new_literal(1, depth + 5));
+        assert_eq!(stats.eliminated(), 2 * depth);
+    }
+}
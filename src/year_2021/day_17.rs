@@ -0,0 +1,711 @@
+//! This is my solution for [Advent of Code - Day 17 - _Trick Shot_](https://adventofcode.com/2021/day/17)
+//!
+//! Today involved quite a bit of maths, and some brute-force looping through possible results. The
+//! aim was to calculate whether a probe would hit a target area given a starting trajectory that
+//! slowed down in the x direction (drag) and increased in the -ve y direction due to gravity.
+//!
+//! The first part could just be solved with maths [`highest_point`]. The second part I just brute
+//! force calculated all permutations within upper and lower bounds for x and y,
+//! [`all_trajectories`]. Working out a lower bound for x was interesting, but it doesn't save much
+//! time over just using 1.
+//!
+//! Later I did the more efficient version I'd noted in [`all_trajectories`]. As the x and y axes
+//! don't affect each other, [`x_step_ranges`] and [`y_step_ranges`] work out the range of steps
+//! each starting velocity spends in the target on its own axis, then
+//! [`count_trajectories_analytic`] counts the pairs of velocities whose ranges overlap. This is now
+//! used for part two, pass `--compare` to compare it with the brute-force version.
+//!
+//! All of that assumed the target is below and to the right of the origin, as it is in the puzzle.
+//! The bounds on the velocities to try are now worked out for targets in any direction, see
+//! [`x_velocity_bounds`] and [`y_velocity_bounds`], and the highest point is found by trying the y
+//! velocities from the top down, rather than the formula only holding for targets below. If the
+//! target spans `y = 0`, and the probe can come to a stop within it on the x-axis, it can be
+//! launched as high as you like and still fall back into the target, so there is no answer to
+//! either part.
+
+use crate::errors::{check_input, check_single_line, to_validation, InputError, Validation};
+use crate::solution::{Alternative, Part};
+use std::collections::HashSet;
+
+/// Check the input describes the target area, see [`validate`], and parse it for [`part_one`] and
+/// [`part_two`].
+pub fn parse(contents: &str) -> Target {
+    check_input(17, contents, validate);
+    parse_target(contents).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// The highest point a trajectory that hits the target can reach, see [`highest_point`]. If the
+/// probe can go as high as you like there is no answer.
+pub fn part_one(target: &Target) -> Option<String> {
+    let highest = highest_point(*target);
+    match highest {
+        Some(highest) => say!("The highest point reached is {}.", highest),
+        None => say!("The probe can be launched as high as you like."),
+    }
+
+    highest.map(|highest| highest.to_string())
+}
+
+/// Count the trajectories that hit the target, see [`count_trajectories_analytic`]. If there are
+/// infinitely many there is no answer.
+///
+/// - Pass `--compare` to compare the timings of the two ways of counting trajectories, see
+///   [`alternatives`].
+pub fn part_two(target: &Target) -> Option<String> {
+    let trajectories = count_trajectories_analytic(*target);
+    match trajectories {
+        Some(trajectories) => say!("The count of valid trajectories is {}.", trajectories),
+        None => say!("There are infinitely many valid trajectories."),
+    }
+
+    trajectories.map(|trajectories| trajectories.to_string())
+}
+
+/// The input should be a single line describing the target, see [`parse_target`]
+pub fn validate(input: &str) -> Validation {
+    to_validation(check_single_line(input))?;
+    parse_target(input)
+        .map(|_| ())
+        .map_err(|err| vec![InputError::new(1, err)])
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input is a target area, so it is enough
+/// for it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// Define a target area in the form `((x_min, x_max), (y_min, y_max))`
+type Target = ((isize, isize), (isize, isize));
+
+/// This is mostly discarding the unwanted syntax that makes this readable to humans. Whitespace is
+/// ignored, so hand-typed targets don't need to match the puzzle input exactly, and reversed ranges
+/// are normalised so that the result is always `(min, max)`.
+/// # Example from puzzle specification
+/// ```rust
+/// assert_eq!(
+///     parse_target("target area: x=20..30, y=-10..-5\n"),
+///     Ok(((20, 30), (-10, -5)))
+/// )
+/// ```
+fn parse_target(input: &str) -> Result<Target, String> {
+    fn parse_range(axis: &str, range: &str) -> Result<(isize, isize), String> {
+        let (a, b) = range
+            .strip_prefix(&format!("{}=", axis))
+            .and_then(|range| range.split_once(".."))
+            .ok_or(format!("Expected {}=<min>..<max>, found '{}'", axis, range))?;
+
+        let parse_bound = |bound: &str| {
+            bound
+                .parse::<isize>()
+                .map_err(|_| format!("Invalid {} bound '{}' in '{}'", axis, bound, range))
+        };
+        let (a, b) = (parse_bound(a)?, parse_bound(b)?);
+
+        Ok((a.min(b), a.max(b)))
+    }
+
+    let compact: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    let (x, y) = compact
+        .strip_prefix("targetarea:")
+        .and_then(|ranges| ranges.split_once(','))
+        .ok_or(format!(
+            "Expected 'target area: x=<min>..<max>, y=<min>..<max>', found '{}'",
+            input.trim()
+        ))?;
+
+    Ok((parse_range("x", x)?, parse_range("y", y)?))
+}
+
+/// The delta on the y-axis of -1 is such that the y co-ordinates of the points on the downward
+/// portion of the trajectory are the same as the upward portion, so there will be a co-ordinate
+/// on the x-axis for each trajectory that starts upwards. Further the trajectory(ies) with the
+/// highest peak will be travelling fastest at that point. As the target area is below the origin,
+/// The fastest possible trajectory at that point will be the x-axis followed by the lowest altitude
+/// of the target area (y_min). As the delta is -1 the height of the peak is a triangular number
+/// (`n * (n + 1) / 2`), where n is the distance of that final step 0..y_min. This is the distance
+/// between the peak and y_min. To get the height above the origin, we need to go back one step
+/// (`(n-1) * ((n-1) + 1) / 2`), simplified and with -y_min  substituted for n: `(-y_min - 1) *
+/// -y_min / 2`.
+///
+/// That relies on there being an x velocity that comes to a stop above the target, and the target
+/// being below the origin. In general, this tries the y velocities from the top of the range given
+/// by [`y_velocity_bounds`] down, and the first that hits the target with any x velocity goes the
+/// highest. For the puzzle input that is the first one tried, `-y_min - 1`, with the same result
+/// as the formula. There is always a hit, as launching at the near corner of the target reaches it
+/// on the first step. Returns `None` if there are infinitely many trajectories, so the probe can go
+/// as high as you like.
+fn highest_point(target: Target) -> Option<isize> {
+    let x_ranges = x_step_ranges(target);
+    let hits = |y_ranges: &[StepRange]| {
+        y_ranges.iter().any(|&y_range| {
+            x_ranges
+                .iter()
+                .any(|&(_, x_range)| overlaps(x_range, y_range))
+        })
+    };
+
+    y_step_ranges(target)?
+        .iter()
+        .rev()
+        .find(|(_, y_ranges)| hits(y_ranges))
+        // The probe doesn't go any higher than the origin if it isn't launched upwards
+        .map(|&(dy, _)| dy.max(0) * (dy.max(0) + 1) / 2)
+}
+
+/// Determine if a given trajectory hits the target by recursively stepping through the co-ordinates
+/// it covers.
+fn is_hit(
+    (pos_x, pos_y): (isize, isize),
+    (dx, dy): (isize, isize),
+    ((x1, x2), (y1, y2)): Target,
+) -> bool {
+    // If the probe is below the area and falling, or has gone beyond it in the direction it's
+    // moving, or stopped to one side of it, this was a miss
+    if (pos_y < y1 && dy < 0) || (pos_x > x2 && dx >= 0) || (pos_x < x1 && dx <= 0) {
+        return false;
+    }
+
+    // if the co-ordinates are on or within the target area bounds, this was a hit
+    if pos_x >= x1 && pos_x <= x2 && pos_y >= y1 && pos_y <= y2 {
+        return true;
+    }
+
+    // otherwise apply and update the deltas and continue, drag slows the probe towards 0 in
+    // whichever direction it is moving
+    is_hit(
+        (pos_x + dx, pos_y + dy),
+        (dx - dx.signum(), dy - 1),
+        ((x1, x2), (y1, y2)),
+    )
+}
+
+/// Calculate an upper and lower bound for x and y co-ordinates, then brute-force iterate through
+/// each permutation. There is probably a more efficient solution in working the sets of steps each
+/// relevant x and y magnitude will be in the target area and intersecting those, but the
+/// brute-force method runs in 1-2ms so is good enough.
+///
+/// The lower bound for y, and the upper bound for x is the trajectory that hits the bottom right of
+/// the target area after it's first step. As noted in [`highest_point`] the highest positive y
+/// trajectory is `-target_y_min - 1` so that on it's way back down it will hit the x-axis, then the
+/// gravity will adjust the speed downwards 1 so that it hits target_y_min next. The lowest x needs
+/// to be large enough that it reaches target_x_min before decaying to a delta of 0.
+///
+/// so:
+/// ```text
+/// x (x + 1)                               | max x distance for starting x is
+/// ---------   >= target_x_min             | triangular number for x
+///     2                                   |
+///                                         |
+/// x (x + 1)   >= target_x_min * 2         | multiply both sides by 2
+///                                         |
+/// x² + x      >= target_x_min * 2         | re-arrange
+///                                         |
+/// x² + 2x + 1 >  target_x_min * 2         | x is >= 0, so adding x + 1  
+///                                         | is still > RHS
+///                                         |
+/// (x + 1)²    >  target_x_min * 2         | re-arrange, (x + 1)(x + 1) ===
+///                                         | x² + 2x + 1
+///                                         |
+///                 /----------------       |
+/// x + 1       > \/ target_x_min * 2       | square root both sides
+///                                         |
+///                 /----------------       |
+/// x           > \/ target_x_min * 2  - 1  | -1 both sides
+/// ```
+///
+/// These bounds are now worked out for targets in any direction by [`x_velocity_bounds`] and
+/// [`y_velocity_bounds`]. Each trajectory is checked from the end of its first step, as the probe
+/// is launched from the origin, so only counts as hitting a target around the origin if it comes
+/// back to it. Panics if there are infinitely many trajectories, see [`y_velocity_bounds`].
+fn all_trajectories(target: Target) -> HashSet<(isize, isize)> {
+    let mut out = HashSet::new();
+
+    let (x_min, x_max) = x_velocity_bounds(target);
+    let (y_min, y_max) = y_velocity_bounds(target).expect("There are infinitely many trajectories");
+
+    for x in x_min..=x_max {
+        for y in y_min..=y_max {
+            if is_hit((x, y), (x - x.signum(), y - 1), target) {
+                out.insert((x, y));
+            }
+        }
+    }
+
+    out
+}
+
+/// The smallest starting x velocity from which the probe can travel `distance` before drag stops
+/// it, see [`all_trajectories`] for the maths.
+fn slowest_to_reach(distance: isize) -> isize {
+    ((distance as f64 * 2.0).sqrt().ceil() - 1.0) as isize
+}
+
+/// The range of starting x velocities that could reach the target. Velocities further from 0 than
+/// the far edge of the target overshoot it on the first step, and for a target to one side of the
+/// origin, velocities too close to 0 stop short of it. If the target spans `x = 0`, the probe can
+/// go either way, or not move at all.
+fn x_velocity_bounds(((x1, x2), _): Target) -> (isize, isize) {
+    if x1 > 0 {
+        (slowest_to_reach(x1), x2)
+    } else if x2 < 0 {
+        (x1, -slowest_to_reach(-x2))
+    } else {
+        (x1, x2)
+    }
+}
+
+/// The range of starting y velocities that could reach the target, or `None` if there are
+/// infinitely many trajectories that hit it.
+///
+/// - If the target is below the origin, the bounds are as in [`all_trajectories`].
+/// - If the target is above, the probe must be launched upwards, and no faster than the top of the
+///   target, as the probe passes back down through the same heights it went up through.
+/// - If the target spans `y = 0`, a probe launched faster than the target is tall will only be in
+///   it when it comes back down through `y = 0`, after `2 * dy + 1` steps. If there is an x
+///   velocity that stops within the target, that is a hit however fast it was launched. Otherwise
+///   it has to be before the last step any x velocity spends in the target.
+fn y_velocity_bounds(target: Target) -> Option<(isize, isize)> {
+    let (_, (y1, y2)) = target;
+    if y2 < 0 {
+        return Some((y1, -y1 - 1));
+    }
+    if y1 > 0 {
+        return Some((1, y2));
+    }
+
+    let last_step = x_step_ranges(target)
+        .iter()
+        .map(|&(_, (_, last))| last)
+        .try_fold(0, |latest, last| last.map(|last| latest.max(last)))?;
+
+    Some((y1, y2.max(-y1).max(last_step as isize / 2)))
+}
+
+/// The steps after launch that a probe is within the target on one axis, from the first to the
+/// last, inclusive. `None` for the last step means it stays in the target forever.
+type StepRange = (usize, Option<usize>);
+
+/// For each starting x velocity within [`x_velocity_bounds`], the steps where the probe's x
+/// co-ordinate is within the target, if there are any. Drag only ever slows the probe down, so once
+/// x has passed the target it won't come back, and if the probe stops within the target it stays
+/// there.
+fn x_step_ranges(target: Target) -> Vec<(isize, StepRange)> {
+    let ((x1, x2), _) = target;
+    let (min_dx, max_dx) = x_velocity_bounds(target);
+
+    (min_dx..=max_dx)
+        .filter_map(|dx| {
+            let (mut x, mut speed, mut step) = (0, dx, 0);
+            let mut range: Option<StepRange> = None;
+            loop {
+                x += speed;
+                speed -= speed.signum();
+                step += 1;
+
+                if (x1..=x2).contains(&x) {
+                    let first = range.map_or(step, |(first, _)| first);
+                    range = Some((first, if speed == 0 { None } else { Some(step) }));
+                }
+                if speed == 0 || (speed > 0 && x > x2) || (speed < 0 && x < x1) {
+                    return range.map(|range| (dx, range));
+                }
+            }
+        })
+        .collect()
+}
+
+/// For each starting y velocity within [`y_velocity_bounds`], the steps where the probe's y
+/// co-ordinate is within the target, if there are any, or `None` if there are infinitely many
+/// trajectories. A probe can pass through a target that spans or is above the origin twice, once on
+/// the way up and once on the way down, so each velocity can have two ranges of steps.
+fn y_step_ranges(target: Target) -> Option<Vec<(isize, Vec<StepRange>)>> {
+    let (_, (y1, y2)) = target;
+    let (min_dy, max_dy) = y_velocity_bounds(target)?;
+
+    let ranges = (min_dy..=max_dy)
+        .filter_map(|dy| {
+            let (mut y, mut speed, mut step) = (0, dy, 0);
+            let mut ranges: Vec<StepRange> = Vec::new();
+            // Once the probe is below the target and falling, it won't come back
+            while y >= y1 || speed >= 0 {
+                y += speed;
+                speed -= 1;
+                step += 1;
+
+                if (y1..=y2).contains(&y) {
+                    match ranges.last_mut() {
+                        Some((_, Some(last))) if *last == step - 1 => *last = step,
+                        _ => ranges.push((step, Some(step))),
+                    }
+                }
+            }
+            (!ranges.is_empty()).then_some((dy, ranges))
+        })
+        .collect();
+
+    Some(ranges)
+}
+
+/// True if two ranges of steps have a step in common
+fn overlaps((first_a, last_a): StepRange, (first_b, last_b): StepRange) -> bool {
+    let starts_before_end = |first, last: Option<usize>| last.is_none_or(|last| first <= last);
+    starts_before_end(first_b, last_a) && starts_before_end(first_a, last_b)
+}
+
+/// Count the trajectories that hit the target without simulating each one. A pair of starting
+/// velocities hits the target if there is a step where both x and y are within it, so this
+/// intersects the step ranges from [`x_step_ranges`] and [`y_step_ranges`]. Each range is worked
+/// out once per velocity, rather than once per pair as [`all_trajectories`] does. Returns `None` if
+/// there are infinitely many trajectories.
+fn count_trajectories_analytic(target: Target) -> Option<usize> {
+    let x_ranges = x_step_ranges(target);
+    let y_ranges = y_step_ranges(target)?;
+
+    let count = y_ranges
+        .iter()
+        .map(|(_, y_ranges)| {
+            x_ranges
+                .iter()
+                .filter(|&&(_, x_range)| {
+                    y_ranges
+                        .iter()
+                        .any(|&y_range| overlaps(x_range, y_range))
+                })
+                .count()
+        })
+        .sum();
+
+    Some(count)
+}
+
+/// The two ways of counting the trajectories for part two, to compare with `--compare`. The brute
+/// force search can't cope with infinitely many trajectories, so gives no answer for those, as
+/// [`count_trajectories_analytic`] does.
+pub fn alternatives() -> Vec<Alternative<Target>> {
+    vec![
+        Alternative::new(Part::Two, "count_trajectories_analytic", |&target| {
+            count_trajectories_analytic(target).map(|count| count.to_string())
+        }),
+        Alternative::new(Part::Two, "all_trajectories", |&target| {
+            y_velocity_bounds(target).map(|_| all_trajectories(target).len().to_string())
+        }),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::InputError;
+    use crate::year_2021::day_17::{
+        all_trajectories, count_trajectories_analytic, highest_point, is_hit, parse_target,
+        validate, x_step_ranges, y_step_ranges,
+    };
+    use std::collections::HashSet;
+
+    #[test]
+    fn can_parse() {
+        assert_eq!(
+            parse_target("target area: x=20..30, y=-10..-5\n"),
+            Ok(((20, 30), (-10, -5)))
+        )
+    }
+
+    #[test]
+    fn can_parse_variations() {
+        let expected = Ok(((20, 30), (-10, -5)));
+
+        assert_eq!(parse_target("target area: x=20..30, y=-10..-5"), expected);
+        assert_eq!(parse_target("target area: x=30..20, y=-5..-10"), expected);
+        assert_eq!(
+            parse_target("  target area:x = 20 .. 30 ,\ty=-10..-5 \r\n"),
+            expected
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed_targets() {
+        assert_eq!(
+            parse_target("x=20..30, y=-10..-5"),
+            Err(
+                "Expected 'target area: x=<min>..<max>, y=<min>..<max>', found 'x=20..30, y=-10..-5'"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            parse_target("target area: y=-10..-5, x=20..30"),
+            Err("Expected x=<min>..<max>, found 'y=-10..-5'".to_string())
+        );
+        assert_eq!(
+            parse_target("target area: x=20..30, y=-10"),
+            Err("Expected y=<min>..<max>, found 'y=-10'".to_string())
+        );
+        assert_eq!(
+            parse_target("target area: x=20..3a, y=-10..-5"),
+            Err("Invalid x bound '3a' in 'x=20..3a'".to_string())
+        );
+    }
+
+    #[test]
+    fn can_calc_highest() {
+        let target = ((20, 30), (-10, -5));
+        assert_eq!(highest_point(target), Some(45))
+    }
+
+    #[test]
+    fn can_calc_hit() {
+        let target = ((20, 30), (-10, -5));
+        assert_eq!(is_hit((0, 0), (23, -10), target), true);
+        assert_eq!(is_hit((0, 0), (23, -11), target), false);
+    }
+
+    #[test]
+    fn can_calc_all_hits() {
+        let target = ((20, 30), (-10, -5));
+        let actual = all_trajectories(target);
+        let expected = HashSet::from([
+            (23, -10),
+            (25, -9),
+            (27, -5),
+            (29, -6),
+            (22, -6),
+            (21, -7),
+            (9, 0),
+            (27, -7),
+            (24, -5),
+            (25, -7),
+            (26, -6),
+            (25, -5),
+            (6, 8),
+            (11, -2),
+            (20, -5),
+            (29, -10),
+            (6, 3),
+            (28, -7),
+            (8, 0),
+            (30, -6),
+            (29, -8),
+            (20, -10),
+            (6, 7),
+            (6, 4),
+            (6, 1),
+            (14, -4),
+            (21, -6),
+            (26, -10),
+            (7, -1),
+            (7, 7),
+            (8, -1),
+            (21, -9),
+            (6, 2),
+            (20, -7),
+            (30, -10),
+            (14, -3),
+            (20, -8),
+            (13, -2),
+            (7, 3),
+            (28, -8),
+            (29, -9),
+            (15, -3),
+            (22, -5),
+            (26, -8),
+            (25, -8),
+            (25, -6),
+            (15, -4),
+            (9, -2),
+            (15, -2),
+            (12, -2),
+            (28, -9),
+            (12, -3),
+            (24, -6),
+            (23, -7),
+            (25, -10),
+            (7, 8),
+            (11, -3),
+            (26, -7),
+            (7, 1),
+            (23, -9),
+            (6, 0),
+            (22, -10),
+            (27, -6),
+            (8, 1),
+            (22, -8),
+            (13, -4),
+            (7, 6),
+            (28, -6),
+            (11, -4),
+            (12, -4),
+            (26, -9),
+            (7, 4),
+            (24, -10),
+            (23, -8),
+            (30, -8),
+            (7, 0),
+            (9, -1),
+            (10, -1),
+            (26, -5),
+            (22, -9),
+            (6, 5),
+            (7, 5),
+            (23, -6),
+            (28, -10),
+            (10, -2),
+            (11, -1),
+            (20, -9),
+            (14, -2),
+            (29, -7),
+            (13, -3),
+            (23, -5),
+            (24, -8),
+            (27, -9),
+            (30, -7),
+            (28, -5),
+            (21, -10),
+            (7, 9),
+            (6, 6),
+            (21, -5),
+            (27, -10),
+            (7, 2),
+            (30, -9),
+            (21, -8),
+            (22, -7),
+            (24, -9),
+            (20, -6),
+            (6, 9),
+            (29, -5),
+            (8, -2),
+            (27, -8),
+            (30, -5),
+            (24, -7),
+        ]);
+
+        let diff: HashSet<&(isize, isize)> = if expected.len() > actual.len() {
+            expected.difference(&actual).collect()
+        } else {
+            actual.difference(&expected).collect()
+        };
+
+        assert_eq!(diff, HashSet::new())
+    }
+
+    #[test]
+    fn can_find_step_ranges() {
+        let target = ((20, 30), (-10, -5));
+
+        let x_ranges = x_step_ranges(target);
+        // 6 and 7 stop within the target, at 21 and 28
+        assert_eq!(x_ranges[0], (6, (5, None)));
+        assert_eq!(x_ranges[1], (7, (4, None)));
+        // 8 reaches 21 after 3 steps, then 26 and 30, then overshoots to 33
+        assert_eq!(x_ranges[2], (8, (3, Some(5))));
+        assert_eq!(x_ranges.last(), Some(&(30, (1, Some(1)))));
+        assert!(x_ranges.iter().all(|&(dx, _)| dx != 16));
+
+        let y_ranges = y_step_ranges(target).unwrap();
+        assert_eq!(y_ranges.first(), Some(&(-10, vec![(1, Some(1))])));
+        // 9 peaks at 45 after 9 steps, is back at 0 after 19, then drops straight to -10
+        assert_eq!(y_ranges.last(), Some(&(9, vec![(20, Some(20))])));
+        assert!(y_ranges.contains(&(0, vec![(4, Some(5))])));
+
+        // Passing up through a target above the origin, then back down through it
+        let y_ranges = y_step_ranges(((20, 30), (5, 10))).unwrap();
+        assert!(y_ranges.contains(&(4, vec![(2, Some(7))])));
+        assert!(y_ranges.contains(&(5, vec![(1, Some(2)), (9, Some(10))])));
+        assert_eq!(x_step_ranges(((-30, -20), (5, 10)))[0], (-30, (1, Some(1))));
+    }
+
+    #[test]
+    fn can_count_trajectories_analytically() {
+        let target = ((20, 30), (-10, -5));
+        assert_eq!(count_trajectories_analytic(target), Some(112));
+
+        for target in [
+            ((1, 1), (-1, -1)),
+            ((5, 40), (-3, -1)),
+            ((34, 67), (-186, -141)),
+            ((150, 171), (-129, -70)),
+            ((244, 303), (-91, -54)),
+        ] {
+            assert_eq!(
+                count_trajectories_analytic(target),
+                Some(all_trajectories(target).len()),
+                "{:?}",
+                target
+            );
+        }
+    }
+
+    #[test]
+    fn can_target_any_quadrant() {
+        // The example mirrored to the left of the origin
+        let target = ((-30, -20), (-10, -5));
+        assert_eq!(highest_point(target), Some(45));
+        assert_eq!(count_trajectories_analytic(target), Some(112));
+        assert!(all_trajectories(target).contains(&(-23, -10)));
+
+        // Above the origin, the fastest launch reaches the top of the target on the first step
+        for target in [((20, 30), (5, 10)), ((-30, -20), (5, 10))] {
+            assert_eq!(highest_point(target), Some(55), "{:?}", target);
+            assert!(is_hit((0, 0), (25 * target.0 .0.signum(), 10), target));
+            assert!(!is_hit((0, 0), (25 * target.0 .0.signum(), 11), target));
+        }
+
+        // Targets spanning an axis
+        for target in [
+            ((20, 30), (5, 10)),
+            ((-30, -20), (5, 10)),
+            ((-5, 5), (-10, -5)),
+            ((-5, 5), (3, 8)),
+            ((37, 44), (-5, 5)),
+            ((-44, -37), (-5, 5)),
+            ((2, 2), (0, 0)),
+        ] {
+            let trajectories = all_trajectories(target);
+            assert_eq!(
+                count_trajectories_analytic(target),
+                Some(trajectories.len()),
+                "{:?}",
+                target
+            );
+            // The highest is the fastest upward launch that hits
+            let fastest = trajectories.iter().map(|&(_, dy)| dy.max(0)).max().unwrap();
+            assert_eq!(
+                highest_point(target),
+                Some(fastest * (fastest + 1) / 2),
+                "{:?}",
+                target
+            );
+        }
+
+        // 37 to 44 has no triangular numbers, so the probe can't stop in it, but 6 stops at 21
+        assert_eq!(count_trajectories_analytic(((20, 30), (-5, 5))), None);
+        assert_eq!(highest_point(((20, 30), (-5, 5))), None);
+        assert_eq!(count_trajectories_analytic(((-5, 5), (-3, 3))), None);
+        assert_eq!(count_trajectories_analytic(((1, 1), (0, 0))), None);
+
+        // Too far away to reach while still going up, but it can always be hit on the first step
+        let target = ((1000, 1000), (-1, -1));
+        assert_eq!(highest_point(target), Some(0));
+        assert_eq!(
+            count_trajectories_analytic(target),
+            Some(all_trajectories(target).len())
+        );
+    }
+
+    #[test]
+    fn can_validate() {
+        assert_eq!(validate("target area: x=20..30, y=-10..-5\n"), Ok(()));
+        assert_eq!(
+            validate("target area: x=20..30, y=-10\n"),
+            Err(vec![InputError::new(
+                1,
+                "Expected y=<min>..<max>, found 'y=-10'"
+            )])
+        );
+        assert_eq!(
+            validate("target area: x=20..30, y=-10..-5\ntarget area: x=1..2, y=3..4\n"),
+            Err(vec![InputError::new(
+                2,
+                "Expected the input to be a single line"
+            )])
+        );
+    }
+}
@@ -0,0 +1,1204 @@
+//! This is my solution for [Advent of Code - Day 18 - _Snailfish_](https://adventofcode.com/2021/day/18)
+//!
+//! Today was doing convoluted arithmetic on 'Snailfish Numbers', which are made up of a binary tree with digits at the
+//! leaves. The representation was easy enough, but I had to box the non-leaf nodes to prevent the compiler
+//! complaining about the type being potentially infinite. I tried just using references, but it made satisfying the
+//! borrow checker very difficult. This resulted in [`SnailfishNumber`] and [`SnailfishNumber::try_from`], a recursive
+//! function that parses a line of the input (each is one number). It returns a [`ParseError`] rather than panicking on
+//! a malformed line, and limits the nesting to [`MAX_DEPTH`], so that the fuzz target in `fuzz/` can give it arbitrary
+//! input.
+//!
+//! The main difficulty of today was implementing [`SnailfishNumber::add`]. The actual addition is simple, but the
+//! resulting checks that the invariants on the new [`SnailfishNumber`] hold: no pair deeper than level 4, no
+//! leaves that are not single digits, and resolving them if they don't involved some complex recursion and pointer
+//! manipulation. This was originally two recursive walks over the tree, one exploding pairs that were too deep, and
+//! passing the numbers of that pair left/right as it unwound the recursion, the other splitting leaves with numbers
+//! that are not single digits into the relevant pair. These are now done with the [`Cursor`] described below, see
+//! [`Cursor::explode_next`] and [`Cursor::split_next`].
+//!
+//! Finally [`SnailfishNumber::magnitude`] implements recursively reducing a [`SnailfishNumber`] tree into a single
+//! number for calculating the result. With these in place, [`add_numbers`] folds each line of the input into the first
+//! number using [`SnailfishNumber::add`] for the solution to part one. [`max_sum`] uses [Itertools::permutations] to
+//! match up each pair of numbers in both orders, map them to the magnitude of the sum, and reduce that to the maximum.
+//!
+//! Adding the boxed trees meant cloning both of them for every addition, and the reduction walks the tree from the
+//! root for every explosion and split. Part two does ~10,000 additions, so [`FlatSnailfishNumber`] was added later as
+//! a faster alternative. It stores just the leaves left to right, each with how deeply nested it is. That has enough
+//! information to rebuild the tree, but means adding is just joining two lists, exploding and splitting only need to
+//! look at neighbouring leaves, and the magnitude can be folded up with a stack. [`add_numbers`] and [`max_sum`] now
+//! use it, converting back to a [`SnailfishNumber`] where the tree is needed. For 100 numbers in a release build this
+//! takes [`max_sum`] from ~60ms to ~10ms. The permutations are also independent, so [`max_sum`] now splits them
+//! between threads, the number of which can be set with `--threads=<n>`. Passing `--benchmark` times it with one
+//! thread against the full thread count.
+//!
+//! To make it easier to build and edit numbers in tests and experiments without going via strings, [`Cursor`] is a
+//! zipper over a [`SnailfishNumber`]. It focuses on one node, and keeps the rest of the tree as a trail of the siblings
+//! passed on the way down, so it can move around, replace the focused subtree, and explode or split in place. An
+//! explosion only needs to reach the nearest leaf either side, which is always the closest leaf of a sibling in that
+//! trail, so nothing has to be searched for from the root. [`SnailfishNumber::add`] now uses it for the reduction,
+//! walking the tree with [`Cursor::advance`] to find the next pair to explode or leaf to split.
+//!
+//! As an experiment, [`try_subtract`] searches for the numbers that could have been added to get a given sum. It can
+//! only brute force small numbers using [`enumerate_numbers`], so is far from complete.
+
+use crate::errors::{check_input, check_lines, to_validation, Validation};
+use itertools::Itertools;
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::str::Chars;
+use std::thread;
+use std::time::Instant;
+
+use crate::year_2021::day_18::Direction::{LEFT, RIGHT};
+use crate::year_2021::day_18::SnailfishNumber::{Num, Pair};
+
+/// Represents a snailfish number as a binary tree
+#[derive(Eq, PartialEq, Hash, Debug, Clone)]
+pub enum SnailfishNumber {
+    /// Leaf node
+    Num(u8),
+    /// Branch node - branches need to be boxed so that it has a constant size
+    Pair(Box<SnailfishNumber>, Box<SnailfishNumber>),
+}
+
+/// When a pair is exploding due to being too deep, each half is added to the nearest leaf on its side. This indicates
+/// which way it is travelling / which half of the pair it came from.
+#[derive(Eq, PartialEq, Debug)]
+enum Direction {
+    LEFT,
+    RIGHT,
+}
+
+/// How deeply pairs can be nested when parsing. Reduced numbers are never more than four deep, this only stops
+/// arbitrary input overflowing the stack, in the parser or the other recursive functions on the tree.
+const MAX_DEPTH: usize = 64;
+
+/// The ways a line can fail to parse as a [`SnailfishNumber`]
+#[derive(Eq, PartialEq, Debug)]
+pub enum ParseError {
+    /// The line ended part way through a number
+    UnexpectedEnd,
+    /// A character that doesn't belong where it was found, along with what was expected instead
+    UnexpectedChar(char, &'static str),
+    /// There was more of the line after the end of the number
+    TrailingInput(String),
+    /// Pairs were nested more than [`MAX_DEPTH`] deep
+    TooDeep,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd => write!(f, "unexpected end of line"),
+            ParseError::UnexpectedChar(chr, expected) => write!(f, "expected {}, found '{}'", expected, chr),
+            ParseError::TrailingInput(rest) => write!(f, "unexpected \"{}\" after the number", rest),
+            ParseError::TooDeep => write!(f, "pairs are nested more than {} deep", MAX_DEPTH),
+        }
+    }
+}
+
+impl TryFrom<&str> for SnailfishNumber {
+    type Error = ParseError;
+
+    /// Parse a line of the input as a [`SnailfishNumber`]
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        fn expect(chars: &mut Chars, expected: char, description: &'static str) -> Result<(), ParseError> {
+            match chars.next() {
+                Some(chr) if chr == expected => Ok(()),
+                Some(chr) => Err(ParseError::UnexpectedChar(chr, description)),
+                None => Err(ParseError::UnexpectedEnd),
+            }
+        }
+
+        fn iter(chars: &mut Chars, depth: usize) -> Result<SnailfishNumber, ParseError> {
+            match chars.next() {
+                Some('[') if depth == MAX_DEPTH => Err(ParseError::TooDeep),
+                // Start of a pair, recursively build each side
+                Some('[') => {
+                    let first = iter(chars, depth + 1)?;
+                    expect(chars, ',', "','")?;
+                    let second = iter(chars, depth + 1)?;
+                    expect(chars, ']', "']'")?;
+                    Ok(Pair(Box::new(first), Box::new(second)))
+                }
+                Some(chr) => chr
+                    .to_digit(10)
+                    .map(|num| Num(num as u8))
+                    .ok_or(ParseError::UnexpectedChar(chr, "a digit or '['")),
+                None => Err(ParseError::UnexpectedEnd),
+            }
+        }
+
+        let mut chars = s.chars();
+        let number = iter(&mut chars, 0)?;
+        match chars.as_str() {
+            "" => Ok(number),
+            rest => Err(ParseError::TrailingInput(rest.to_string())),
+        }
+    }
+}
+
+impl SnailfishNumber {
+    /// Build a [`SnailfishNumber::Pair`] without needing to box each half
+    pub fn pair(left: SnailfishNumber, right: SnailfishNumber) -> SnailfishNumber {
+        Pair(Box::new(left), Box::new(right))
+    }
+
+    /// Combine the two halves into a new [`SnailfishNumber::Pair`], then repeatedly call [`Cursor::explode_next`] and
+    /// [`Cursor::split_next`] until neither change the tree.
+    pub fn add(&self, other: &SnailfishNumber) -> SnailfishNumber {
+        let mut cursor = Cursor::new(SnailfishNumber::pair(self.clone(), other.clone()));
+        while cursor.explode_next() || cursor.split_next() {}
+        cursor.into_number()
+    }
+
+    /// Utility used by [`Cursor::explode_at`] to find the leaf one half of an exploding pair is added to. `dir`
+    /// indicates which way the number is travelling, and the leaf is the closest one in this subtree from that side.
+    fn nearest_leaf(&mut self, dir: Direction) -> &mut u8 {
+        // Slightly confusing, but of the digit is travelling leftward, i.e. it came from the left-hand side of a pair
+        // then the next number leftwards will be on the right-hand side of its pair (if it's in one)
+        match (self, dir) {
+            (Pair(_, b), LEFT) => b.nearest_leaf(LEFT),
+            (Pair(a, _), RIGHT) => a.nearest_leaf(RIGHT),
+            (Num(n), _) => n,
+        }
+    }
+
+    /// Recursively combine pairs into a single number using the formula `lhs x 3 + rhs x 2`.
+    pub fn magnitude(&self) -> usize {
+        match self {
+            Pair(a, b) => 3 * a.magnitude() + 2 * b.magnitude(),
+            Num(n) => *n as usize,
+        }
+    }
+}
+
+/// A sibling left behind when a [`Cursor`] moves down into one half of a pair, so that the pair can be rebuilt on the
+/// way back up.
+#[derive(Eq, PartialEq, Debug, Clone)]
+enum Crumb {
+    /// The cursor went into the left half, this is the right half
+    Left(SnailfishNumber),
+    /// The cursor went into the right half, this is the left half
+    Right(SnailfishNumber),
+}
+
+/// A zipper for moving around and editing a [`SnailfishNumber`] in place. The cursor starts at the root, and
+/// [`Cursor::into_number`] rebuilds the edited number. Moves return `false` and leave the cursor where it was if there
+/// is nowhere to go in that direction.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Cursor {
+    /// The subtree currently being looked at
+    focus: SnailfishNumber,
+    /// The siblings of each node between the root and the focus, the last being the focus's own sibling
+    trail: Vec<Crumb>,
+}
+
+impl Cursor {
+    /// Start a cursor at the root of `number`
+    pub fn new(number: SnailfishNumber) -> Cursor {
+        Cursor {
+            focus: number,
+            trail: Vec::new(),
+        }
+    }
+
+    /// The subtree the cursor is currently at
+    pub fn focus(&self) -> &SnailfishNumber {
+        &self.focus
+    }
+
+    /// The number of pairs the focus is nested inside
+    pub fn depth(&self) -> usize {
+        self.trail.len()
+    }
+
+    /// Move to the left half of the focused pair
+    pub fn down_left(&mut self) -> bool {
+        self.down(true)
+    }
+
+    /// Move to the right half of the focused pair
+    pub fn down_right(&mut self) -> bool {
+        self.down(false)
+    }
+
+    /// Shared implementation of [`Cursor::down_left`] and [`Cursor::down_right`]
+    fn down(&mut self, into_left: bool) -> bool {
+        match std::mem::replace(&mut self.focus, Num(0)) {
+            Pair(left, right) => {
+                if into_left {
+                    self.focus = *left;
+                    self.trail.push(Crumb::Left(*right));
+                } else {
+                    self.focus = *right;
+                    self.trail.push(Crumb::Right(*left));
+                }
+                true
+            }
+            leaf => {
+                self.focus = leaf;
+                false
+            }
+        }
+    }
+
+    /// Move up to the pair containing the focus
+    pub fn up(&mut self) -> bool {
+        let focus = std::mem::replace(&mut self.focus, Num(0));
+        self.focus = match self.trail.pop() {
+            Some(Crumb::Left(right)) => Pair(Box::new(focus), Box::new(right)),
+            Some(Crumb::Right(left)) => Pair(Box::new(left), Box::new(focus)),
+            None => {
+                self.focus = focus;
+                return false;
+            }
+        };
+        true
+    }
+
+    /// Move from the right half of a pair to the left half
+    pub fn left(&mut self) -> bool {
+        matches!(self.trail.last(), Some(Crumb::Right(_))) && self.up() && self.down_left()
+    }
+
+    /// Move from the left half of a pair to the right half
+    pub fn right(&mut self) -> bool {
+        matches!(self.trail.last(), Some(Crumb::Left(_))) && self.up() && self.down_right()
+    }
+
+    /// Swap the focused subtree for `number`, returning the subtree that was replaced
+    pub fn replace(&mut self, number: SnailfishNumber) -> SnailfishNumber {
+        std::mem::replace(&mut self.focus, number)
+    }
+
+    /// Move to the next subtree in a depth-first, left to right walk of the whole number. At the end of the walk the
+    /// cursor is back at the root and this returns `false`.
+    pub fn advance(&mut self) -> bool {
+        if self.down_left() {
+            return true;
+        }
+        while !self.right() {
+            if !self.up() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// If the focus is a pair of two regular numbers, replace it with `0`, and add its halves to the nearest leaf on
+    /// each side. The nearest leaf to the left is the right-most leaf of the closest left sibling in the trail, and
+    /// vice versa. Returns `false` without changing anything if the focus can't explode, or if either leaf would
+    /// overflow.
+    pub fn explode_at(&mut self) -> bool {
+        let (to_left, to_right) = match &self.focus {
+            Pair(left, right) => match (left.as_ref(), right.as_ref()) {
+                (Num(l), Num(r)) => (*l, *r),
+                _ => return false,
+            },
+            Num(_) => return false,
+        };
+
+        let mut left_leaf = None;
+        let mut right_leaf = None;
+        for crumb in self.trail.iter_mut().rev() {
+            match crumb {
+                Crumb::Right(sibling) if left_leaf.is_none() => left_leaf = Some(sibling.nearest_leaf(LEFT)),
+                Crumb::Left(sibling) if right_leaf.is_none() => right_leaf = Some(sibling.nearest_leaf(RIGHT)),
+                _ => {}
+            }
+        }
+
+        let left_sum = left_leaf.as_ref().map(|leaf| leaf.checked_add(to_left));
+        let right_sum = right_leaf.as_ref().map(|leaf| leaf.checked_add(to_right));
+        if left_sum == Some(None) || right_sum == Some(None) {
+            return false;
+        }
+
+        if let (Some(leaf), Some(Some(sum))) = (left_leaf, left_sum) {
+            *leaf = sum;
+        }
+        if let (Some(leaf), Some(Some(sum))) = (right_leaf, right_sum) {
+            *leaf = sum;
+        }
+
+        self.focus = Num(0);
+        true
+    }
+
+    /// Walk the number from the root, and explode the first pair nested four deep, see [`Cursor::explode_at`].
+    /// Returns `false` if there was no pair to explode.
+    pub fn explode_next(&mut self) -> bool {
+        self.top();
+        loop {
+            if self.depth() >= 4 && self.explode_at() {
+                return true;
+            }
+            if !self.advance() {
+                return false;
+            }
+        }
+    }
+
+    /// If the focus is a regular number, split it into a pair of its halves, rounding down on the left and up on the
+    /// right. Unlike the reduction rules this doesn't check the number is more than 9. Returns `false` without
+    /// changing anything if the focus is a pair.
+    pub fn split_at(&mut self) -> bool {
+        match self.focus {
+            Num(n) => {
+                self.focus = Pair(Box::new(Num(n / 2)), Box::new(Num(n / 2 + n % 2)));
+                true
+            }
+            Pair(_, _) => false,
+        }
+    }
+
+    /// Walk the number from the root, and split the first regular number that is more than 9, see
+    /// [`Cursor::split_at`]. Returns `false` if every leaf was a single digit.
+    pub fn split_next(&mut self) -> bool {
+        self.top();
+        loop {
+            if matches!(self.focus, Num(n) if n > 9) {
+                return self.split_at();
+            }
+            if !self.advance() {
+                return false;
+            }
+        }
+    }
+
+    /// Move back up to the root
+    pub fn top(&mut self) {
+        while self.up() {}
+    }
+
+    /// Move back up to the root, and return the edited number
+    pub fn into_number(mut self) -> SnailfishNumber {
+        self.top();
+        self.focus
+    }
+}
+
+/// One leaf of a [`FlatSnailfishNumber`]
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+struct Leaf {
+    /// The regular number at this leaf
+    value: u8,
+    /// The number of pairs this leaf is nested inside
+    depth: u8,
+}
+
+/// A snailfish number stored as its leaves in order, rather than as a tree. See [`SnailfishNumber`] for the tree
+/// version, the two can be converted between with [`From`].
+#[derive(Eq, PartialEq, Debug, Clone)]
+struct FlatSnailfishNumber {
+    leaves: Vec<Leaf>,
+}
+
+impl From<&SnailfishNumber> for FlatSnailfishNumber {
+    /// Walk the tree, recording the depth each leaf is found at
+    fn from(number: &SnailfishNumber) -> Self {
+        fn iter(number: &SnailfishNumber, depth: u8, leaves: &mut Vec<Leaf>) {
+            match number {
+                Pair(left, right) => {
+                    iter(left, depth + 1, leaves);
+                    iter(right, depth + 1, leaves);
+                }
+                Num(value) => leaves.push(Leaf {
+                    value: *value,
+                    depth,
+                }),
+            }
+        }
+
+        let mut leaves = Vec::new();
+        iter(number, 0, &mut leaves);
+        FlatSnailfishNumber { leaves }
+    }
+}
+
+impl From<&str> for FlatSnailfishNumber {
+    /// Parse a line of the input, tracking the depth from the brackets
+    fn from(s: &str) -> Self {
+        let mut depth = 0;
+        let mut leaves = Vec::new();
+        for chr in s.chars() {
+            match chr {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                ',' => {}
+                digit => leaves.push(Leaf {
+                    value: digit.to_digit(10).unwrap() as u8,
+                    depth,
+                }),
+            }
+        }
+
+        FlatSnailfishNumber { leaves }
+    }
+}
+
+impl From<&FlatSnailfishNumber> for SnailfishNumber {
+    /// Rebuild the tree. Each pair is built from the next leaves in the list, and a leaf belongs at the current
+    /// position in the tree once it is as deep as the depth being built.
+    fn from(number: &FlatSnailfishNumber) -> Self {
+        fn iter(
+            leaves: &mut std::iter::Peekable<std::slice::Iter<Leaf>>,
+            depth: u8,
+        ) -> SnailfishNumber {
+            match leaves.peek() {
+                Some(leaf) if leaf.depth == depth => Num(leaves.next().unwrap().value),
+                Some(_) => {
+                    let left = iter(leaves, depth + 1);
+                    let right = iter(leaves, depth + 1);
+                    Pair(Box::new(left), Box::new(right))
+                }
+                None => panic!("Ran out of leaves building snailfish number"),
+            }
+        }
+
+        iter(&mut number.leaves.iter().peekable(), 0)
+    }
+}
+
+impl FlatSnailfishNumber {
+    /// Join the two lists of leaves, one level deeper, then reduce. Equivalent to [`SnailfishNumber::add`]
+    fn add(&self, other: &FlatSnailfishNumber) -> FlatSnailfishNumber {
+        let mut combined = FlatSnailfishNumber {
+            leaves: self
+                .leaves
+                .iter()
+                .chain(other.leaves.iter())
+                .map(|leaf| Leaf {
+                    value: leaf.value,
+                    depth: leaf.depth + 1,
+                })
+                .collect(),
+        };
+        combined.reduce();
+        combined
+    }
+
+    /// Explode every pair that is too deep, then split the first oversized leaf, repeating until neither are needed.
+    /// This is the same order [`SnailfishNumber::add`] applies them in.
+    fn reduce(&mut self) {
+        loop {
+            while self.explode() {}
+            if !self.split() {
+                return;
+            }
+        }
+    }
+
+    /// Find the first pair nested inside four others, i.e. two leaves at depth five, and replace it with a 0 one level
+    /// up, adding the values to the neighbouring leaves. Returns true if a pair exploded.
+    fn explode(&mut self) -> bool {
+        match self.leaves.iter().position(|leaf| leaf.depth > 4) {
+            Some(i) => {
+                let left = self.leaves[i];
+                let right = self.leaves.remove(i + 1);
+                if i > 0 {
+                    self.leaves[i - 1].value += left.value;
+                }
+                if let Some(next) = self.leaves.get_mut(i + 1) {
+                    next.value += right.value;
+                }
+                self.leaves[i] = Leaf {
+                    value: 0,
+                    depth: left.depth - 1,
+                };
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Split the first leaf that isn't a single digit into a pair of leaves. Returns true if a leaf was split.
+    fn split(&mut self) -> bool {
+        match self.leaves.iter().position(|leaf| leaf.value > 9) {
+            Some(i) => {
+                let Leaf { value, depth } = self.leaves[i];
+                self.leaves[i] = Leaf {
+                    value: value / 2,
+                    depth: depth + 1,
+                };
+                self.leaves.insert(
+                    i + 1,
+                    Leaf {
+                        value: value / 2 + value % 2,
+                        depth: depth + 1,
+                    },
+                );
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Equivalent to [`SnailfishNumber::magnitude`]. Leaves are pushed onto a stack, and whenever the top two are at
+    /// the same depth they must be the two halves of a pair, so can be combined into one value a level up.
+    fn magnitude(&self) -> usize {
+        let mut stack: Vec<(usize, u8)> = Vec::new();
+        for leaf in &self.leaves {
+            let mut current = (leaf.value as usize, leaf.depth);
+            while let Some(&(left, depth)) = stack.last() {
+                if depth != current.1 {
+                    break;
+                }
+                stack.pop();
+                current = (3 * left + 2 * current.0, depth - 1);
+            }
+            stack.push(current);
+        }
+
+        stack.first().map(|&(magnitude, _)| magnitude).unwrap_or(0)
+    }
+}
+
+/// All the snailfish numbers that have single digit leaves, and pairs nested at most `max_depth` deep. At the top level
+/// this includes plain numbers, which aren't valid on their own, but are needed to build the pairs. The count grows
+/// very quickly, `10`, `110`, `12110`, ~`1.5 x 10^8`, so this is only practical for a `max_depth` of 2 or less.
+pub fn enumerate_numbers(max_depth: usize) -> Vec<SnailfishNumber> {
+    let mut numbers: Vec<SnailfishNumber> = (0..10).map(Num).collect();
+
+    if max_depth > 0 {
+        let inner = enumerate_numbers(max_depth - 1);
+        numbers.extend(
+            inner
+                .iter()
+                .cartesian_product(inner.iter())
+                .map(|(a, b)| Pair(Box::new(a.clone()), Box::new(b.clone()))),
+        );
+    }
+
+    numbers
+}
+
+/// Experimental inverse of [`SnailfishNumber::add`]. Find numbers `x` such that `addend.add(x)` reduces to `sum`.
+///
+/// This is very incomplete. Reduction loses information, so there can be many answers, or none, and there is no
+/// smarter way to find them than trying them. This checks:
+/// - The right hand side of `sum`, which is the answer if no reduction was needed.
+/// - Every pair from [`enumerate_numbers`] nested at most `max_depth` deep.
+///
+/// All the candidates found are returned, any answer deeper than the bound will be missed.
+pub fn try_subtract(
+    sum: &SnailfishNumber,
+    addend: &SnailfishNumber,
+    max_depth: usize,
+) -> Vec<SnailfishNumber> {
+    let unreduced = match sum {
+        Pair(left, right) if left.as_ref() == addend => Some(right.as_ref().clone()),
+        _ => None,
+    };
+
+    unreduced
+        .into_iter()
+        .chain(
+            enumerate_numbers(max_depth)
+                .into_iter()
+                .filter(|x| matches!(x, Pair(_, _))),
+        )
+        .filter(|x| &addend.add(x) == sum)
+        .unique()
+        .collect()
+}
+
+/// Check each line is a snailfish number, see [`validate`], and parse them for [`part_one`] and [`part_two`] to add
+/// up.
+pub fn parse(contents: &str) -> Vec<SnailfishNumber> {
+    check_input(18, contents, validate);
+    parse_input(contents).unwrap_or_else(|err| panic!("Invalid snailfish number: {}", err))
+}
+
+/// The magnitude of the sum of all the numbers, see [`add_numbers`]
+pub fn part_one(numbers: &[SnailfishNumber]) -> Option<String> {
+    let sum = add_numbers(numbers);
+    say!("The magnitude of the sum is: {}.", sum.magnitude());
+
+    Some(sum.magnitude().to_string())
+}
+
+/// The largest magnitude of the sum of any two numbers, see [`max_sum`].
+///
+/// - Pass `--benchmark` to compare the timings of finding it with and without threads.
+pub fn part_two(numbers: &[SnailfishNumber]) -> Option<String> {
+    let max_sum = max_sum(numbers);
+    say!("The maximum sum of the permutations is: {}.", max_sum);
+
+    if env::args().any(|arg| arg == "--benchmark") {
+        benchmark_max_sum(numbers);
+    }
+
+    Some(max_sum.to_string())
+}
+
+/// Each line should be a pair of single digits or pairs, with balanced brackets
+pub fn validate(input: &str) -> Validation {
+    to_validation(check_lines(input, |_, line| {
+        let mut depth = 0;
+        for chr in line.chars() {
+            match chr {
+                '[' => depth += 1,
+                ']' if depth == 0 => return Some("Unbalanced ']'".to_string()),
+                ']' => depth -= 1,
+                ',' | '0'..='9' => {}
+                other => return Some(format!("Unexpected character '{}'", other)),
+            }
+        }
+
+        match depth {
+            0 if line.starts_with('[') => None,
+            0 => Some("Expected the line to be a pair".to_string()),
+            _ => Some(format!("{} unclosed '['", depth)),
+        }
+    }))
+}
+
+/// For `--detect`, see [`crate::detect`]. Day 10's lines of brackets could pass [`validate`] as nested empty pairs, so
+/// there must also be some numbers.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok() && input.contains(|c: char| c.is_ascii_digit())
+}
+
+/// Compare the time taken by [`max_sum`] using a single thread, and using the thread count from [`thread_count`].
+/// Enabled by passing `--benchmark`. This should be run with a release build to be meaningful.
+fn benchmark_max_sum(numbers: &[SnailfishNumber]) {
+    const RUNS: u32 = 20;
+    let threads = thread_count(env::args());
+
+    for threads in [1, threads] {
+        let start = Instant::now();
+        for _ in 0..RUNS {
+            max_sum_with_threads(numbers, threads);
+        }
+        say!(
+            "max_sum with {} thread(s) took {:.2?} on average",
+            threads,
+            start.elapsed() / RUNS
+        );
+    }
+}
+
+/// The number of threads to use for [`max_sum`], set with `--threads=<n>`, and defaulting to the number of available
+/// cores. WebAssembly can't spawn threads, so there it is always 1.
+fn thread_count(args: impl Iterator<Item = String>) -> usize {
+    if cfg!(target_arch = "wasm32") {
+        return 1;
+    }
+
+    args.filter_map(|arg| {
+        arg.strip_prefix("--threads=")
+            .and_then(|n| n.parse::<usize>().ok())
+    })
+    .last()
+    .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+    .max(1)
+}
+
+/// Split the input into lines and parse each with [`SnailfishNumber::try_from`], stopping at the first that fails
+pub fn parse_input(input: &str) -> Result<Vec<SnailfishNumber>, ParseError> {
+    input.lines().map(SnailfishNumber::try_from).collect()
+}
+
+/// The solution to part one - fold the list of numbers into the first and return the resulting number. The puzzle
+/// solution then converts this to its magnitude, but returning the full tree allows unit tests to compare this to the
+/// expectation. The addition is done with [`FlatSnailfishNumber`]s.
+fn add_numbers(numbers: &[SnailfishNumber]) -> SnailfishNumber {
+    let mut iter = numbers.iter().map(FlatSnailfishNumber::from);
+    let first = iter.next().unwrap();
+    let sum = iter.fold(first, |acc, num| acc.add(&num));
+    SnailfishNumber::from(&sum)
+}
+
+/// The solution to part two - uses [Itertools::permutations] to match up each pair of numbers in both orders, map
+/// them to the magnitude of the sum, and reduce that to the maximum. The numbers are converted to
+/// [`FlatSnailfishNumber`]s first, as it is much quicker at adding. The permutations are shared between the number of
+/// threads given by [`thread_count`].
+fn max_sum(numbers: &[SnailfishNumber]) -> usize {
+    max_sum_with_threads(numbers, thread_count(env::args()))
+}
+
+/// As [`max_sum`], but with an explicit thread count. Each thread takes a contiguous chunk of the permutations and
+/// finds the maximum within it, then the maximum of those is taken. With one thread no extra threads are spawned.
+fn max_sum_with_threads(numbers: &[SnailfishNumber], threads: usize) -> usize {
+    let flat: Vec<FlatSnailfishNumber> = numbers.iter().map(FlatSnailfishNumber::from).collect();
+    let permutations: Vec<Vec<&FlatSnailfishNumber>> = flat.iter().permutations(2).collect();
+
+    let chunk_max = |chunk: &[Vec<&FlatSnailfishNumber>]| {
+        chunk
+            .iter()
+            .map(|permutation| permutation[0].add(permutation[1]).magnitude())
+            .max()
+    };
+
+    if threads <= 1 {
+        return chunk_max(&permutations).unwrap();
+    }
+
+    let chunk_size = permutations.len().div_ceil(threads).max(1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = permutations
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk_max(chunk)))
+            .collect();
+
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().expect("Snailfish thread panicked"))
+            .max()
+            .unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{check, Arbitrary, Gen, DEFAULT_CASES};
+    use crate::year_2021::day_18::SnailfishNumber::{Num, Pair};
+    use crate::year_2021::day_18::{add_numbers, enumerate_numbers, parse_input, try_subtract};
+    use crate::year_2021::day_18::{max_sum, FlatSnailfishNumber, Leaf, SnailfishNumber};
+    use crate::year_2021::day_18::{max_sum_with_threads, thread_count, Cursor};
+    use crate::year_2021::day_18::{ParseError, MAX_DEPTH};
+
+    impl Arbitrary for SnailfishNumber {
+        /// A reduced snailfish number, i.e. no leaf is above 9 and no pair is nested inside four pairs
+        fn arbitrary(gen: &mut Gen) -> Self {
+            fn build(gen: &mut Gen, depth: usize) -> SnailfishNumber {
+                if depth == 4 || (depth > 0 && gen.one_in(3)) {
+                    Num(gen.below(10) as u8)
+                } else {
+                    Pair(
+                        Box::new(build(gen, depth + 1)),
+                        Box::new(build(gen, depth + 1)),
+                    )
+                }
+            }
+
+            build(gen, 0)
+        }
+    }
+
+    /// The largest leaf, and the number of pairs the deepest leaf is nested in
+    fn largest_leaf_and_depth(number: &SnailfishNumber) -> (u8, usize) {
+        match number {
+            Num(n) => (*n, 0),
+            Pair(a, b) => {
+                let (leaf_a, depth_a) = largest_leaf_and_depth(a);
+                let (leaf_b, depth_b) = largest_leaf_and_depth(b);
+                (leaf_a.max(leaf_b), depth_a.max(depth_b) + 1)
+            }
+        }
+    }
+
+    #[test]
+    fn can_parse() {
+        let input = "[1,2]
+[[1,2],3]
+[9,[8,7]]
+[[1,9],[8,5]]
+[[[[1,2],[3,4]],[[5,6],[7,8]]],9]"
+            .to_string();
+
+        let expected = Vec::from([
+            Pair(Box::new(Num(1)), Box::new(Num(2))),
+            Pair(
+                Box::new(Pair(Box::new(Num(1)), Box::new(Num(2)))),
+                Box::new(Num(3)),
+            ),
+            Pair(
+                Box::new(Num(9)),
+                Box::new(Pair(Box::new(Num(8)), Box::new(Num(7)))),
+            ),
+            Pair(
+                Box::new(Pair(Box::new(Num(1)), Box::new(Num(9)))),
+                Box::new(Pair(Box::new(Num(8)), Box::new(Num(5)))),
+            ),
+            Pair(
+                Box::new(Pair(
+                    Box::new(Pair(
+                        Box::new(Pair(Box::new(Num(1)), Box::new(Num(2)))),
+                        Box::new(Pair(Box::new(Num(3)), Box::new(Num(4)))),
+                    )),
+                    Box::new(Pair(
+                        Box::new(Pair(Box::new(Num(5)), Box::new(Num(6)))),
+                        Box::new(Pair(Box::new(Num(7)), Box::new(Num(8)))),
+                    )),
+                )),
+                Box::new(Num(9)),
+            ),
+        ]);
+
+        parse_input(&input).unwrap()
+            .iter()
+            .zip(expected.iter())
+            .for_each(|(actual, expected)| assert_eq!(actual, expected))
+    }
+
+    #[test]
+    fn can_report_malformed_numbers() {
+        let parse = |line: &str| SnailfishNumber::try_from(line);
+
+        assert_eq!(parse(""), Err(ParseError::UnexpectedEnd));
+        assert_eq!(parse("[1,2"), Err(ParseError::UnexpectedEnd));
+        assert_eq!(parse("[1;2]"), Err(ParseError::UnexpectedChar(';', "','")));
+        assert_eq!(parse("[10,2]"), Err(ParseError::UnexpectedChar('0', "','")));
+        assert_eq!(parse("[a,2]"), Err(ParseError::UnexpectedChar('a', "a digit or '['")));
+        assert_eq!(parse("[1,2]]"), Err(ParseError::TrailingInput("]".to_string())));
+        assert_eq!(parse_input("[1,2]\n[3,"), Err(ParseError::UnexpectedEnd));
+
+        let nested = |depth: usize| format!("{}1{}", "[".repeat(depth), ",1]".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(parse(&nested(MAX_DEPTH + 1)), Err(ParseError::TooDeep));
+    }
+
+    #[test]
+    fn can_explode() {
+        let explode = |input: &str| {
+            let mut cursor = Cursor::new(SnailfishNumber::try_from(input).unwrap());
+            assert!(cursor.explode_next());
+            cursor.into_number()
+        };
+
+        assert_eq!(
+            explode("[[[[[9,8],1],2],3],4]"),
+            SnailfishNumber::try_from("[[[[0,9],2],3],4]").unwrap()
+        );
+        assert_eq!(
+            explode("[[6,[5,[4,[3,2]]]],1]"),
+            SnailfishNumber::try_from("[[6,[5,[7,0]]],3]").unwrap()
+        );
+        assert_eq!(
+            explode("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]"),
+            SnailfishNumber::try_from("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]").unwrap()
+        );
+
+        let mut shallow = Cursor::new(SnailfishNumber::try_from("[[[[1,2],3],4],5]").unwrap());
+        assert!(!shallow.explode_next());
+    }
+
+    #[test]
+    fn can_check_numbers() {
+        let split = |number: SnailfishNumber| {
+            let mut cursor = Cursor::new(number);
+            let changed = cursor.split_next();
+            (changed, cursor.into_number())
+        };
+
+        assert_eq!(split(Num(9)), (false, Num(9)));
+        assert_eq!(split(Num(10)), (true, SnailfishNumber::pair(Num(5), Num(5))));
+        assert_eq!(split(Num(11)), (true, SnailfishNumber::pair(Num(5), Num(6))));
+        assert_eq!(
+            split(SnailfishNumber::pair(Num(1), Num(12))),
+            (
+                true,
+                SnailfishNumber::pair(Num(1), SnailfishNumber::pair(Num(6), Num(6)))
+            )
+        );
+    }
+
+    #[test]
+    fn can_add() {
+        let lhs = SnailfishNumber::try_from("[[[[4,3],4],4],[7,[[8,4],9]]]").unwrap();
+        let rhs = SnailfishNumber::try_from("[1,1]").unwrap();
+        let result = lhs.add(&rhs);
+        assert_eq!(
+            result,
+            SnailfishNumber::try_from("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").unwrap()
+        )
+    }
+
+    #[test]
+    fn can_add_lines() {
+        let input = "[1,1]
+[2,2]
+[3,3]
+[4,4]"
+            .to_string();
+        assert_eq!(
+            add_numbers(&parse_input(&input).unwrap()),
+            SnailfishNumber::try_from("[[[[1,1],[2,2]],[3,3]],[4,4]]").unwrap()
+        );
+
+        let input2 = "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
+[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]
+[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]
+[[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]
+[7,[5,[[3,8],[1,4]]]]
+[[2,[2,2]],[8,[8,1]]]
+[2,9]
+[1,[[[9,3],9],[[9,0],[0,7]]]]
+[[[5,[7,4]],7],1]
+[[[[4,2],2],6],[8,7]]"
+            .to_string();
+
+        assert_eq!(
+            add_numbers(&parse_input(&input2).unwrap()),
+            SnailfishNumber::try_from("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").unwrap()
+        );
+    }
+
+    #[test]
+    fn can_calculate_magnitude() {
+        Vec::from([
+            (SnailfishNumber::try_from("[[1,2],[[3,4],5]]").unwrap(), 143usize),
+            (
+                SnailfishNumber::try_from("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").unwrap(),
+                1384,
+            ),
+            (SnailfishNumber::try_from("[[[[1,1],[2,2]],[3,3]],[4,4]]").unwrap(), 445),
+            (SnailfishNumber::try_from("[[[[3,0],[5,3]],[4,4]],[5,5]]").unwrap(), 791),
+            (SnailfishNumber::try_from("[[[[5,0],[7,4]],[5,5]],[6,6]]").unwrap(), 1137),
+            (
+                SnailfishNumber::try_from("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").unwrap(),
+                3488,
+            ),
+        ])
+        .iter()
+        .for_each(|(num, result)| assert_eq!(num.magnitude(), *result));
+
+        let homework = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]"
+            .to_string();
+
+        assert_eq!(add_numbers(&parse_input(&homework).unwrap()).magnitude(), 4140);
+    }
+
+    #[test]
+    fn can_find_max_sum() {
+        let homework = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]"
+            .to_string();
+
+        assert_eq!(max_sum(&parse_input(&homework).unwrap()), 3993);
+        for threads in [1, 2, 3, 8, 200] {
+            assert_eq!(max_sum_with_threads(&parse_input(&homework).unwrap(), threads), 3993);
+        }
+    }
+
+    #[test]
+    fn can_parse_thread_count() {
+        let args = |list: &[&str]| {
+            list.iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>()
+                .into_iter()
+        };
+
+        assert_eq!(thread_count(args(&["--threads=3"])), 3);
+        assert_eq!(thread_count(args(&["--threads=0"])), 1);
+        assert!(thread_count(args(&[])) >= 1);
+        assert!(thread_count(args(&["--threads=many"])) >= 1);
+    }
+
+    #[test]
+    fn can_enumerate_numbers() {
+        assert_eq!(enumerate_numbers(0).len(), 10);
+        assert_eq!(enumerate_numbers(1).len(), 110);
+        assert!(enumerate_numbers(1).contains(&SnailfishNumber::try_from("[9,0]").unwrap()));
+    }
+
+    #[test]
+    fn can_try_subtract() {
+        let addend = SnailfishNumber::try_from("[1,2]").unwrap();
+        let sum = SnailfishNumber::try_from("[[1,2],[[3,4],5]]").unwrap();
+        // Found without reduction, even though it's beyond the search depth
+        assert_eq!(
+            try_subtract(&sum, &addend, 0),
+            vec![SnailfishNumber::try_from("[[3,4],5]").unwrap()]
+        );
+
+        let addend = SnailfishNumber::try_from("[[[[4,3],4],4],[7,[[8,4],9]]]").unwrap();
+        let sum = SnailfishNumber::try_from("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").unwrap();
+        let candidates = try_subtract(&sum, &addend, 1);
+        assert!(candidates.contains(&SnailfishNumber::try_from("[1,1]").unwrap()));
+        for candidate in candidates {
+            assert_eq!(addend.add(&candidate), sum);
+        }
+
+        // The left hand side must be a reduced form of the addend
+        assert_eq!(
+            try_subtract(&sum, &SnailfishNumber::try_from("[1,1]").unwrap(), 1),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn add_never_leaves_a_leaf_over_nine() {
+        check(
+            DEFAULT_CASES,
+            |(a, b): &(SnailfishNumber, SnailfishNumber)| {
+                let (leaf, depth) = largest_leaf_and_depth(&a.add(b));
+                leaf <= 9 && depth <= 4
+            },
+        );
+    }
+
+    #[test]
+    fn can_flatten() {
+        let flat = FlatSnailfishNumber::from("[[1,2],[[3,4],5]]");
+        let leaf = |value, depth| Leaf { value, depth };
+        assert_eq!(
+            flat.leaves,
+            vec![leaf(1, 2), leaf(2, 2), leaf(3, 3), leaf(4, 3), leaf(5, 2)]
+        );
+
+        for line in [
+            "[1,2]",
+            "[9,[8,7]]",
+            "[[[[1,2],[3,4]],[[5,6],[7,8]]],9]",
+            "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]",
+        ] {
+            let tree = SnailfishNumber::try_from(line).unwrap();
+            let flat = FlatSnailfishNumber::from(line);
+            assert_eq!(FlatSnailfishNumber::from(&tree), flat);
+            assert_eq!(SnailfishNumber::from(&flat), tree);
+            assert_eq!(flat.magnitude(), tree.magnitude());
+        }
+    }
+
+    #[test]
+    fn flat_numbers_reduce_like_trees() {
+        let mut flat = FlatSnailfishNumber::from("[[6,[5,[4,[3,2]]]],1]");
+        assert!(flat.explode());
+        assert_eq!(flat, FlatSnailfishNumber::from("[[6,[5,[7,0]]],3]"));
+
+        let lhs = "[[[[4,3],4],4],[7,[[8,4],9]]]";
+        let rhs = "[1,1]";
+        assert_eq!(
+            FlatSnailfishNumber::from(lhs).add(&FlatSnailfishNumber::from(rhs)),
+            FlatSnailfishNumber::from("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")
+        );
+
+        // Check every pair of the homework gives the same result both ways
+        let homework = parse_input(
+            "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]",
+        ).unwrap();
+        for a in &homework {
+            for b in &homework {
+                let flat_sum = FlatSnailfishNumber::from(a).add(&FlatSnailfishNumber::from(b));
+                assert_eq!(SnailfishNumber::from(&flat_sum), a.add(b));
+            }
+        }
+    }
+
+    #[test]
+    fn can_move_cursor() {
+        let mut cursor = Cursor::new(SnailfishNumber::try_from("[[1,2],[[3,4],5]]").unwrap());
+        assert_eq!(cursor.depth(), 0);
+        assert!(!cursor.up());
+        assert!(!cursor.left());
+        assert!(!cursor.right());
+
+        assert!(cursor.down_right());
+        assert_eq!(cursor.focus(), &SnailfishNumber::try_from("[[3,4],5]").unwrap());
+        assert!(!cursor.right());
+        assert!(cursor.left());
+        assert_eq!(cursor.focus(), &SnailfishNumber::try_from("[1,2]").unwrap());
+        assert!(cursor.down_left());
+        assert_eq!(cursor.focus(), &Num(1));
+        assert_eq!(cursor.depth(), 2);
+        assert!(!cursor.down_left());
+        assert!(!cursor.down_right());
+        assert_eq!(cursor.focus(), &Num(1));
+        assert!(cursor.right());
+        assert_eq!(cursor.focus(), &Num(2));
+        assert!(cursor.up());
+        assert!(cursor.up());
+        assert_eq!(cursor.focus(), &SnailfishNumber::try_from("[[1,2],[[3,4],5]]").unwrap());
+    }
+
+    #[test]
+    fn can_edit_with_cursor() {
+        let mut cursor = Cursor::new(SnailfishNumber::try_from("[[1,2],3]").unwrap());
+        cursor.down_right();
+        assert_eq!(
+            cursor.replace(SnailfishNumber::pair(Num(4), Num(5))),
+            Num(3)
+        );
+        cursor.down_left();
+        assert!(cursor.split_at());
+        assert!(!cursor.split_at());
+        assert_eq!(
+            cursor.into_number(),
+            SnailfishNumber::try_from("[[1,2],[[2,2],5]]").unwrap()
+        );
+
+        // The explode examples from the puzzle, with the cursor moved to the exploding pair by hand
+        let explode = |input: &str, moves: &[fn(&mut Cursor) -> bool]| {
+            let mut cursor = Cursor::new(SnailfishNumber::try_from(input).unwrap());
+            for step in moves {
+                assert!(step(&mut cursor));
+            }
+            assert!(cursor.explode_at());
+            cursor.into_number()
+        };
+
+        assert_eq!(
+            explode(
+                "[[[[[9,8],1],2],3],4]",
+                &[
+                    Cursor::down_left,
+                    Cursor::down_left,
+                    Cursor::down_left,
+                    Cursor::down_left
+                ]
+            ),
+            SnailfishNumber::try_from("[[[[0,9],2],3],4]").unwrap()
+        );
+        assert_eq!(
+            explode(
+                "[7,[6,[5,[4,[3,2]]]]]",
+                &[
+                    Cursor::down_right,
+                    Cursor::down_right,
+                    Cursor::down_right,
+                    Cursor::down_right
+                ]
+            ),
+            SnailfishNumber::try_from("[7,[6,[5,[7,0]]]]").unwrap()
+        );
+        assert_eq!(
+            explode(
+                "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+                &[
+                    Cursor::down_left,
+                    Cursor::down_right,
+                    Cursor::down_right,
+                    Cursor::down_right
+                ]
+            ),
+            SnailfishNumber::try_from("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]").unwrap()
+        );
+
+        let mut cursor = Cursor::new(SnailfishNumber::try_from("[[1,2],[3,4]]").unwrap());
+        assert!(!cursor.explode_at());
+        cursor.down_left();
+        cursor.down_left();
+        assert!(!cursor.explode_at());
+
+        let overflowing = SnailfishNumber::pair(Num(250), SnailfishNumber::pair(Num(9), Num(1)));
+        let mut cursor = Cursor::new(overflowing.clone());
+        cursor.down_right();
+        assert!(!cursor.explode_at());
+        assert_eq!(cursor.into_number(), overflowing);
+    }
+}
@@ -0,0 +1,1160 @@
+//! This is my solution for [Advent of Code - Day 19 - _Beacon Scanner_](https://adventofcode.com/2021/day/19)
+//!
+//! Today was horrible. I find 3D geometry really hard as I can't visualise it very well, and that hinders me
+//! reasoning about it. It was a very similar puzzle last year that caused me to stall, so I'm very glad I worked
+//! through this one and got to a solution. It is not very efficient, it takes 1s-1.5s to run which is a lot given
+//! all previous days run in ~300-400ms combined. But I don't think I have a decent enough idea how to improve it
+//! that I'm just happy to have solved it and will take the speed hit.
+//!
+//! [`parse_scanners`] is fairly simple, it splits the input on the double line breaks between scanner inputs, and
+//! for each then returns the list of relative beacon co-ordinates. [`find_alignment`] does all the heavy lifting, it
+//! takes a set of fixed beacons, and a scanner, and tries for each possible rotation to position the beacons so that
+//! there is an overlap of twelve beacons. If it succeeds it returns the rotated beacons and the offset of the scanner.
+//!
+//! Originally `try_merge` merged the translated beacons into the growing set of fixed beacons, and `merge_all`
+//! repeatedly scanned the remaining scanners until one merged. That made each check slower as the set grew, and the
+//! order scanners were found in was quite unbalanced. Now [`merge_tree`] compares pairs of scanners directly, using a
+//! [`UnionFind`] to skip pairs that are already connected, and walks the resulting spanning tree breadth first.
+//! [`merge_with_tree`] then positions each scanner by aligning it with its already positioned parent in the tree, and
+//! returns the tree alongside the beacons and scanner offsets as a [`MergeResult`]. Building the tree is the slow part,
+//! so it reports each scanner that joins as a [`Progress`], which the runner shows when there is a terminal.
+//!
+//! Trying all 24 rotations against every pairing of beacons was still the slow part, so there is now a second way to
+//! align two scanners, [`find_alignment_by_fingerprint`]. The differences between each pair of beacons a scanner can
+//! see are summarised by a [`fingerprint`] that is the same however the scanner is rotated. Scanners that don't share
+//! enough fingerprints can't overlap by twelve beacons, so are rejected without any further work. Otherwise a pair of
+//! beacons with the same fingerprint in both scanners is enough for [`solve_rotation`] to work out the rotation
+//! directly, and from that the offset, which is then checked against the rest of the beacons. This is what the merge
+//! now uses, pass `--compare` to compare it with [`find_alignment`].
+//!
+//! Part one is solved by just taking the length of the set of beacons returned by [`merge_with_tree`]. For part two
+//! [`largest_distance`] takes the set of all scanner offsets, iterates through the pair combinations, mapping each
+//! pair to their manhatten distance, then takes the max of those.
+//!
+//! The positions were originally tuples, with the rotations typed out by hand, and later as a list of rotation
+//! matrices. They now use [`Vec3`], and its [`Orientation`]s for the rotations, which are shared with
+//! [`super::day_22`]. The matrices moved along with them, see [`crate::util::vec3::ROTATIONS`].
+
+use crate::errors::{check_input, check_lines, offset, sections, to_validation, Validation};
+use crate::solution::{Alternative, Part};
+use crate::util::cache::cached;
+use crate::util::encode::{decode_points, encode_points, from_text, to_text};
+use crate::util::progress::{self, NoProgress, Progress};
+use crate::util::vec3::{Orientation, Vec3};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use itertools::Itertools;
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Type alias for a 3D co-ordinate, used for beacon and scanner offsets.
+type Position = Vec3;
+/// Type alias for the data set of one scanner. A list of the relative positions of all beacons the scanner can detect.
+type Scanner = Vec<Position>;
+/// A way of aligning a scanner with a set of fixed beacons, returning the scanner's beacons rotated to match the fixed
+/// beacons, and the offset of the scanner, see [`find_alignment`] and [`find_alignment_by_fingerprint`].
+type Aligner = fn(&HashSet<Position>, &Scanner) -> Option<(Scanner, Position)>;
+/// The sizes of the differences between two beacons along each axis, sorted so that rotating the scanner doesn't
+/// change it.
+type Fingerprint = [isize; 3];
+
+/// The number of pairs of beacons there are when twelve beacons overlap, i.e. 12 choose 2
+const MIN_SHARED_PAIRS: usize = 66;
+
+/// Check the input is a list of scanners with the beacons they can see, see [`validate`], and parse them. Aligning
+/// the scanners is most of the work, and both [`part_one`] and [`part_two`] need it, so that is done here too,
+/// returning the scanners as read and the [`MergeResult`].
+///
+/// - Pass `--cache` to save the aligned scanners, and load them on later runs with the same input, see
+///   [`crate::util::cache`].
+pub fn parse(contents: &str) -> (Vec<Scanner>, MergeResult) {
+    check_input(19, contents, validate);
+    let scanners = parse_scanners(contents);
+    let result = cached("day-19-merge", contents, || {
+        merge_with_tree(&scanners, progress::reporter("Merging scanners").as_mut())
+    });
+    say!(
+        "The scanners were aligned via a merge tree of depth {}.",
+        result.tree_depth()
+    );
+
+    (scanners, result)
+}
+
+/// Count the distinct beacons across all the scanners.
+///
+/// - Pass `--compare` to compare the timings of the two ways of aligning scanners, see [`alternatives`].
+pub fn part_one((_, result): &(Vec<Scanner>, MergeResult)) -> Option<String> {
+    say!("There are {} distinct beacons.", result.beacons.len());
+
+    Some(result.beacons.len().to_string())
+}
+
+/// The greatest distance between any two scanners, see [`largest_distance`]
+pub fn part_two((_, result): &(Vec<Scanner>, MergeResult)) -> Option<String> {
+    let distance = largest_distance(&result.scanner_positions);
+    say!(
+        "The greatest manhattan distance between scanners is {}.",
+        distance
+    );
+
+    Some(distance.to_string())
+}
+
+/// Each section should be a scanner heading, then the positions of the beacons it can see
+pub fn validate(input: &str) -> Validation {
+    let heading = Regex::new(r"^--- scanner \d+ ---$").unwrap();
+    let beacon = Regex::new(r"^-?\d+,-?\d+,-?\d+$").unwrap();
+
+    let errors = sections(input)
+        .into_iter()
+        .flat_map(|(first_line, section)| {
+            let errors = check_lines(section, |number, line| match number {
+                1 if !heading.is_match(line) => {
+                    Some(format!("Expected '--- scanner <n> ---', found '{}'", line))
+                }
+                1 => None,
+                _ if !beacon.is_match(line) => Some(format!("Expected 'x,y,z', found '{}'", line)),
+                _ => None,
+            });
+            offset(errors, first_line)
+        })
+        .collect();
+
+    to_validation(errors)
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input is a list of scanners, so it is enough for it to be
+/// valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// Split the input on the double line breaks between scanner inputs, and for each then builds the list of relative
+/// beacon co-ordinates.
+fn parse_scanners(input: &str) -> Vec<Scanner> {
+    input
+        .split("\n\n")
+        .map(|scanner| {
+            scanner
+                .lines()
+                // Ignore the `--- scanner x ---` header line
+                .skip(1)
+                .map(|line| {
+                    let coords: Vec<isize> = line
+                        .split(",")
+                        .map(|c| c.parse::<isize>().unwrap())
+                        .collect();
+                    Vec3::new(coords[0], coords[1], coords[2])
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Expand a scanner into each of the 24 possible rotations. I started off trying to build the set of rotation
+/// functions as a static vector of closures that could be cached using `lazy_static!` but I was wasting too much
+/// time trying to satisfy the compiler so ended up inlining the 6 valid combinations for each ±x, ±y permutation.
+/// These are now the [`Orientation`]s.
+fn rotations(scanner: &Scanner) -> Vec<Scanner> {
+    Orientation::ALL
+        .iter()
+        .map(|&orientation| {
+            scanner
+                .iter()
+                .map(|beacon| beacon.rotate(orientation))
+                .collect()
+        })
+        .collect()
+}
+
+/// Explode the scanner into its 24 rotations, then for each, pair each up with every element in the fixed beacon set,
+/// and work out the position delta needed to make them match up. If we can find 12 or more point pairs that share the
+/// same delta, that delta gives a translation for the current rotation that has enough overlap to be confident that
+/// is is a match. Take the first rotation (if any) that produces a match, returning that rotation of the scanner data
+/// and the delta. [Itertools::cartesian_product], [`Itertools::counts`], and [`Iterator::find_map`] respectively do
+/// the pairing of scanner points with the existing beacon set, grouping by delta, and finding the first match (if any)
+/// both for the rotations, and delta groups.
+fn find_alignment(
+    beacon_set: &HashSet<Position>,
+    scanner: &Scanner,
+) -> Option<(Scanner, Position)> {
+    rotations(scanner).into_iter().find_map(|scanner| {
+        let delta = beacon_set
+            .iter()
+            .cartesian_product(&scanner)
+            .map(|(&fixed, &beacon)| fixed - beacon)
+            .counts()
+            .into_iter()
+            .find_map(|(k, v)| if v >= 12 { Some(k) } else { None });
+
+        delta.map(|delta| (scanner, delta))
+    })
+}
+
+/// Use [`find_alignment`] to find a rotation and delta with enough overlap with the fixed beacons. If a match is found,
+/// apply that delta to the current rotation of the scanner data, and merge those points with the existing fixed set.
+/// Then return the delta as that is also the scanner position. This was originally used to greedily merge scanners
+/// one at a time, which has been replaced by [`merge_with_tree`].
+#[cfg(test)]
+fn try_merge(beacon_set: &mut HashSet<Position>, scanner: &Scanner) -> Option<Position> {
+    let (scanner, delta) = find_alignment(beacon_set, scanner)?;
+
+    // Insert it into the existing beacon set
+    scanner.iter().for_each(|&beacon| {
+        beacon_set.insert(beacon + delta);
+    });
+
+    Some(delta)
+}
+
+/// Summarise the difference between two beacons in a way that doesn't depend on the rotation of the scanner
+fn fingerprint(a: Position, b: Position) -> Fingerprint {
+    let size = (a - b).abs();
+    let mut sizes = [size.x, size.y, size.z];
+    sizes.sort();
+    sizes
+}
+
+/// Group every pair of beacons by their [`fingerprint`]
+fn fingerprints<'a>(
+    beacons: impl Iterator<Item = &'a Position> + Clone,
+) -> HashMap<Fingerprint, Vec<(Position, Position)>> {
+    let mut groups: HashMap<Fingerprint, Vec<(Position, Position)>> = HashMap::new();
+    for (&a, &b) in beacons.tuple_combinations() {
+        groups.entry(fingerprint(a, b)).or_default().push((a, b));
+    }
+
+    groups
+}
+
+/// Given the difference between the same two beacons as seen by two scanners, work out the rotation that turns `from`
+/// into `to`. This only identifies the rotation if the sizes along each axis are all different and non-zero,
+/// otherwise which axis goes where is ambiguous, so `None` is returned. `None` is also returned if only a reflection
+/// fits, which happens when the second scanner has the pair of beacons the other way around.
+fn solve_rotation(from: Position, to: Position) -> Option<Orientation> {
+    let Vec3 { x, y, z } = from.abs();
+    let mut sizes = [x, y, z];
+    sizes.sort();
+    if sizes[0] == 0 || sizes[0] == sizes[1] || sizes[1] == sizes[2] {
+        return None;
+    }
+
+    Orientation::ALL
+        .into_iter()
+        .find(|&orientation| from.rotate(orientation) == to)
+}
+
+/// Two sets of beacons can only overlap by twelve beacons if they have at least [`MIN_SHARED_PAIRS`] pairs of beacons
+/// with the same [`fingerprint`] in common.
+fn could_overlap(
+    a: &HashMap<Fingerprint, Vec<(Position, Position)>>,
+    b: &HashMap<Fingerprint, Vec<(Position, Position)>>,
+) -> bool {
+    let shared: usize = a
+        .iter()
+        .filter_map(|(key, pairs)| b.get(key).map(|other| pairs.len().min(other.len())))
+        .sum();
+
+    shared >= MIN_SHARED_PAIRS
+}
+
+/// An alternative to [`find_alignment`] that works out the rotation from the beacons rather than trying them all.
+/// First check there are enough pairs of beacons with the same [`fingerprint`] in both sets for twelve beacons to
+/// overlap. Then for each pair of beacons that share a fingerprint, try both ways round to [`solve_rotation`], and
+/// the offset that moves the first beacon into place. Return the first of these that lines up at least twelve beacons.
+fn find_alignment_by_fingerprint(
+    beacon_set: &HashSet<Position>,
+    scanner: &Scanner,
+) -> Option<(Scanner, Position)> {
+    let fixed_pairs = fingerprints(beacon_set.iter());
+    let scanner_pairs = fingerprints(scanner.iter());
+
+    if !could_overlap(&fixed_pairs, &scanner_pairs) {
+        return None;
+    }
+
+    scanner_pairs
+        .iter()
+        .filter_map(|(key, pairs)| fixed_pairs.get(key).map(|fixed| (fixed, pairs)))
+        .flat_map(|(fixed, pairs)| fixed.iter().cartesian_product(pairs.iter()))
+        .find_map(|(&(p1, p2), &(q1, q2))| {
+            [(q1, q2), (q2, q1)].into_iter().find_map(|(q1, q2)| {
+                let orientation = solve_rotation(q2 - q1, p2 - p1)?;
+                let delta = p1 - q1.rotate(orientation);
+                let rotated: Scanner = scanner
+                    .iter()
+                    .map(|beacon| beacon.rotate(orientation))
+                    .collect();
+
+                let overlap = rotated
+                    .iter()
+                    .filter(|&&beacon| beacon_set.contains(&(beacon + delta)))
+                    .count();
+
+                if overlap >= 12 {
+                    Some((rotated, delta))
+                } else {
+                    None
+                }
+            })
+        })
+}
+
+/// An edge in the merge tree built by [`merge_tree`], `(parent, child)` where the scanner at index `child` was
+/// positioned by aligning it with the scanner at index `parent`.
+type MergeEdge = (usize, usize);
+
+/// The output of [`merge_with_tree`]. As well as the results needed for the puzzle, this includes the merge tree that
+/// was used to position all the scanners, for inspecting how the scanners connect together. Saved by
+/// [`crate::util::cache`] when running with `--cache`, with the positions encoded to keep the entry small, see
+/// [`serialize_positions`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergeResult {
+    /// All the distinct beacons, relative to the first scanner
+    #[serde(
+        serialize_with = "serialize_positions",
+        deserialize_with = "deserialize_positions"
+    )]
+    beacons: HashSet<Position>,
+    /// The positions of all the scanners that could be aligned, relative to the first scanner
+    #[serde(
+        serialize_with = "serialize_positions",
+        deserialize_with = "deserialize_positions"
+    )]
+    scanner_positions: HashSet<Position>,
+    /// The edges of the merge tree, in the order they were applied. Every parent has already been positioned before it
+    /// appears in an edge.
+    merge_tree: Vec<MergeEdge>,
+}
+
+impl MergeResult {
+    /// The length of the longest chain of alignments from the first scanner to any other
+    fn tree_depth(&self) -> usize {
+        let mut depths: HashMap<usize, usize> = HashMap::from([(0, 0)]);
+        for &(parent, child) in &self.merge_tree {
+            depths.insert(child, depths[&parent] + 1);
+        }
+
+        depths.values().copied().max().unwrap_or(0)
+    }
+}
+
+/// Save a set of positions as the text of [`encode_points`], rather than a JSON object for each one. They are sorted
+/// first, so that each is close to the one before and its offset takes fewer bytes.
+fn serialize_positions<S: Serializer>(
+    positions: &HashSet<Position>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut points: Vec<[isize; 3]> = positions.iter().map(|p| [p.x, p.y, p.z]).collect();
+    points.sort_unstable();
+
+    serializer.serialize_str(&to_text(&encode_points(&points)))
+}
+
+/// Reverse [`serialize_positions`]
+fn deserialize_positions<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HashSet<Position>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    let points = from_text(&text)
+        .and_then(|bytes| decode_points::<3>(&bytes))
+        .map_err(serde::de::Error::custom)?;
+
+    Ok(points
+        .into_iter()
+        .map(|[x, y, z]| Vec3::new(x, y, z))
+        .collect())
+}
+
+/// A minimal disjoint-set forest with path halving, used to track which scanners are already known to be connected
+/// when building the [`merge_tree`].
+struct UnionFind {
+    parents: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(size: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..size).collect(),
+        }
+    }
+
+    /// Find the representative of the set containing `node`
+    fn find(&mut self, mut node: usize) -> usize {
+        while self.parents[node] != node {
+            self.parents[node] = self.parents[self.parents[node]];
+            node = self.parents[node];
+        }
+
+        node
+    }
+
+    /// Join the sets containing `a` and `b`, returning false if they were already the same set.
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return false;
+        }
+
+        self.parents[root_b] = root_a;
+        true
+    }
+}
+
+/// Build a spanning tree over the graph of scanners that overlap each other. Each pair of scanners is only compared if
+/// they are not already connected, which is tracked with a [`UnionFind`]. This only needs each scanner's own beacons
+/// rather than the ever growing set of merged beacons, so each check is much cheaper. Each scanner's [`fingerprints`]
+/// are also worked out once up front, so pairs that [`could_overlap`] rules out are skipped without running the
+/// aligner. The tree is then walked breadth first from the first scanner, so that each scanner is positioned from as
+/// short a chain as possible. Each scanner that joins the tree is reported to `progress`.
+fn merge_tree(scanners: &[Scanner], align: Aligner, progress: &mut dyn Progress) -> Vec<MergeEdge> {
+    let beacon_sets: Vec<HashSet<Position>> = scanners
+        .iter()
+        .map(|scanner| scanner.iter().copied().collect())
+        .collect();
+    let scanner_fingerprints: Vec<HashMap<Fingerprint, Vec<(Position, Position)>>> = scanners
+        .iter()
+        .map(|scanner| fingerprints(scanner.iter()))
+        .collect();
+
+    let mut components = UnionFind::new(scanners.len());
+    let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); scanners.len()];
+    let mut merged = 0;
+
+    for (a, b) in (0..scanners.len()).tuple_combinations() {
+        if components.find(a) == components.find(b)
+            || !could_overlap(&scanner_fingerprints[a], &scanner_fingerprints[b])
+        {
+            continue;
+        }
+
+        if align(&beacon_sets[a], &scanners[b]).is_some() {
+            components.union(a, b);
+            adjacent[a].push(b);
+            adjacent[b].push(a);
+
+            merged += 1;
+            progress.update(merged, Some(scanners.len() - 1));
+        }
+    }
+    progress.finish();
+
+    let mut edges = Vec::new();
+    let mut visited = vec![false; scanners.len()];
+    let mut queue = VecDeque::from([0]);
+    visited[0] = true;
+
+    while let Some(parent) = queue.pop_front() {
+        for &child in &adjacent[parent] {
+            if !visited[child] {
+                visited[child] = true;
+                edges.push((parent, child));
+                queue.push_back(child);
+            }
+        }
+    }
+
+    edges
+}
+
+/// Position all the scanners relative to the first by following the [`merge_tree`]. Each child is aligned against the
+/// beacons of its parent, which have already been translated into the first scanner's frame, so the transforms stay
+/// integer-exact without needing to compose rotations. Return the set of beacons that results in, the scanner
+/// offsets, and the tree used.
+fn merge_with_tree(scanners: &[Scanner], progress: &mut dyn Progress) -> MergeResult {
+    merge_with_tree_using(scanners, find_alignment_by_fingerprint, progress)
+}
+
+/// [`merge_with_tree`] with a choice of how each pair of scanners is aligned
+fn merge_with_tree_using(
+    scanners: &[Scanner],
+    align: Aligner,
+    progress: &mut dyn Progress,
+) -> MergeResult {
+    let merge_tree = merge_tree(scanners, align, progress);
+
+    let mut positioned: Vec<Option<HashSet<Position>>> = vec![None; scanners.len()];
+    positioned[0] = Some(scanners[0].iter().copied().collect());
+    // The first scanner is the reference point, so is at the origin by definition.
+    let mut scanner_positions: HashSet<Position> = HashSet::from([Vec3::default()]);
+
+    for &(parent, child) in &merge_tree {
+        let parent_beacons = positioned[parent].as_ref().unwrap();
+        let (rotated, delta) = align(parent_beacons, &scanners[child]).unwrap();
+
+        positioned[child] = Some(rotated.iter().map(|&beacon| beacon + delta).collect());
+        scanner_positions.insert(delta);
+    }
+
+    let beacons = positioned.into_iter().flatten().flatten().collect();
+
+    MergeResult {
+        beacons,
+        scanner_positions,
+        merge_tree,
+    }
+}
+
+/// The two ways of aligning the scanners, to compare with `--compare`. Each merges all the scanners again, ignoring
+/// the result [`parse`] worked out, and counts the beacons for part one.
+pub fn alternatives() -> Vec<Alternative<(Vec<Scanner>, MergeResult)>> {
+    fn count_beacons(scanners: &[Scanner], aligner: Aligner) -> Option<String> {
+        let result = merge_with_tree_using(scanners, aligner, &mut NoProgress);
+        Some(result.beacons.len().to_string())
+    }
+
+    vec![
+        Alternative::new(Part::One, "find_alignment_by_fingerprint", |(scanners, _)| {
+            count_beacons(scanners, find_alignment_by_fingerprint)
+        }),
+        Alternative::new(Part::One, "find_alignment", |(scanners, _)| {
+            count_beacons(scanners, find_alignment)
+        }),
+    ]
+}
+
+/// Solve both parts by merging via the [`merge_tree`], returning the set of beacons, and the list of scanner offsets.
+#[cfg(test)]
+fn merge_all(scanners: &[Scanner]) -> (HashSet<Position>, HashSet<Position>) {
+    let result = merge_with_tree(scanners, &mut NoProgress);
+    (result.beacons, result.scanner_positions)
+}
+
+/// Take the set of scanner offsets returned by [`merge_with_tree`], explode into all combinations of pairs with
+/// [`Itertools::tuple_combinations`], map those to the manhattan distance, and take the maximum.
+fn largest_distance(scanner_positions: &HashSet<Position>) -> usize {
+    scanner_positions
+        .iter()
+        .tuple_combinations::<(_, _)>()
+        .map(|(a, b)| a.manhattan(b))
+        .max()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::geninput::generate;
+    use crate::output::Answers;
+    use crate::util::progress::{NoProgress, RecordedProgress};
+    use crate::year_2021::day_19::{
+        could_overlap, find_alignment, find_alignment_by_fingerprint, fingerprint, fingerprints,
+        largest_distance, merge_all, merge_tree, merge_with_tree, parse_scanners, rotations,
+        solve_rotation, try_merge, validate, MergeResult, Position, Scanner,
+    };
+    use crate::util::vec3::{Orientation, Vec3};
+    use itertools::Itertools;
+
+    fn sample_input() -> String {
+        "--- scanner 0 ---
+404,-588,-901
+528,-643,409
+-838,591,734
+390,-675,-793
+-537,-823,-458
+-485,-357,347
+-345,-311,381
+-661,-816,-575
+-876,649,763
+-618,-824,-621
+553,345,-567
+474,580,667
+-447,-329,318
+-584,868,-557
+544,-627,-890
+564,392,-477
+455,729,728
+-892,524,684
+-689,845,-530
+423,-701,434
+7,-33,-71
+630,319,-379
+443,580,662
+-789,900,-551
+459,-707,401
+
+--- scanner 1 ---
+686,422,578
+605,423,415
+515,917,-361
+-336,658,858
+95,138,22
+-476,619,847
+-340,-569,-846
+567,-361,727
+-460,603,-452
+669,-402,600
+729,430,532
+-500,-761,534
+-322,571,750
+-466,-666,-811
+-429,-592,574
+-355,545,-477
+703,-491,-529
+-328,-685,520
+413,935,-424
+-391,539,-444
+586,-435,557
+-364,-763,-893
+807,-499,-711
+755,-354,-619
+553,889,-390
+
+--- scanner 2 ---
+649,640,665
+682,-795,504
+-784,533,-524
+-644,584,-595
+-588,-843,648
+-30,6,44
+-674,560,763
+500,723,-460
+609,671,-379
+-555,-800,653
+-675,-892,-343
+697,-426,-610
+578,704,681
+493,664,-388
+-671,-858,530
+-667,343,800
+571,-461,-707
+-138,-166,112
+-889,563,-600
+646,-828,498
+640,759,510
+-630,509,768
+-681,-892,-333
+673,-379,-804
+-742,-814,-386
+577,-820,562
+
+--- scanner 3 ---
+-589,542,597
+605,-692,669
+-500,565,-823
+-660,373,557
+-458,-679,-417
+-488,449,543
+-626,468,-788
+338,-750,-386
+528,-832,-391
+562,-778,733
+-938,-730,414
+543,643,-506
+-524,371,-870
+407,773,750
+-104,29,83
+378,-903,-323
+-778,-728,485
+426,699,580
+-438,-605,-362
+-469,-447,-387
+509,732,623
+647,635,-688
+-868,-804,481
+614,-800,639
+595,780,-596
+
+--- scanner 4 ---
+727,592,562
+-293,-554,779
+441,611,-461
+-714,465,-776
+-743,427,-804
+-660,-479,-426
+832,-632,460
+927,-485,-438
+408,393,-506
+466,436,-512
+110,16,151
+-258,-428,682
+-393,719,612
+-211,-452,876
+808,-476,-593
+-575,615,604
+-485,667,467
+-680,325,-822
+-627,-443,-432
+872,-547,-609
+833,512,582
+807,604,487
+839,-516,451
+891,-625,532
+-652,-548,-490
+30,-46,-14"
+            .to_string()
+    }
+
+    #[test]
+    fn can_parse() {
+        let input = "--- scanner 0 ---
+-1,-1,1
+-2,-2,2
+-3,-3,3
+-2,-3,1
+5,6,-4
+8,0,7
+
+--- scanner 1 ---
+1,-1,1
+2,-2,2
+3,-3,3
+2,-1,3
+-5,4,-6
+-8,-7,0
+"
+        .to_string();
+
+        assert_eq!(
+            parse_scanners(&input),
+            Vec::from([
+                Vec::from([
+                    Vec3::new(-1, -1, 1),
+                    Vec3::new(-2, -2, 2),
+                    Vec3::new(-3, -3, 3),
+                    Vec3::new(-2, -3, 1),
+                    Vec3::new(5, 6, -4),
+                    Vec3::new(8, 0, 7),
+                ]),
+                Vec::from([
+                    Vec3::new(1, -1, 1),
+                    Vec3::new(2, -2, 2),
+                    Vec3::new(3, -3, 3),
+                    Vec3::new(2, -1, 3),
+                    Vec3::new(-5, 4, -6),
+                    Vec3::new(-8, -7, 0),
+                ])
+            ])
+        )
+    }
+
+    #[test]
+    fn can_merge() {
+        let scanners = parse_scanners(&sample_input());
+        let mut beacon_set = scanners.get(0).unwrap().iter().map(|&a| a).collect();
+
+        let to_merge_first = scanners.get(1).unwrap();
+        assert_eq!(
+            try_merge(&mut beacon_set, &to_merge_first),
+            Some(Vec3::new(68, -1246, -43))
+        );
+
+        let to_merge_second = scanners.get(4).unwrap();
+        assert_eq!(
+            try_merge(&mut beacon_set, &to_merge_second),
+            Some(Vec3::new(-20, -1133, 1061))
+        );
+
+        let to_merge_third = scanners.get(2).unwrap();
+        assert_eq!(
+            try_merge(&mut beacon_set, &to_merge_third),
+            Some(Vec3::new(1105, -1205, 1229))
+        );
+
+        let to_merge_fourth = scanners.get(3).unwrap();
+        assert_eq!(
+            try_merge(&mut beacon_set, &to_merge_fourth),
+            Some(Vec3::new(-92, -2380, -20))
+        );
+    }
+
+    /// The tuple based rotations these days were written with before [`Vec3`], kept to check they agree
+    fn tuple_rotations(
+        scanner: &[(isize, isize, isize)],
+    ) -> Vec<Vec<(isize, isize, isize)>> {
+        let signs = Vec::from([-1isize, 1isize]);
+        signs
+            .clone()
+            .iter()
+            .cartesian_product(signs)
+            .flat_map(|(&sign_x, sign_y)| {
+                let sign_z = if sign_x == sign_y { 1 } else { -1 };
+
+                Vec::from([
+                    scanner
+                        .iter()
+                        .map(|(x, y, z)| (x * sign_x, y * sign_y, z * sign_z))
+                        .collect(),
+                    scanner
+                        .iter()
+                        .map(|(x, y, z)| (x * sign_x, z * -sign_z, y * sign_y))
+                        .collect(),
+                    scanner
+                        .iter()
+                        .map(|(x, y, z)| (y * sign_y, x * sign_x, z * -sign_z))
+                        .collect(),
+                    scanner
+                        .iter()
+                        .map(|(x, y, z)| (y * sign_y, z * sign_z, x * sign_x))
+                        .collect(),
+                    scanner
+                        .iter()
+                        .map(|(x, y, z)| (z * sign_z, x * sign_x, y * sign_y))
+                        .collect(),
+                    scanner
+                        .iter()
+                        .map(|(x, y, z)| (z * -sign_z, y * sign_y, x * sign_x))
+                        .collect(),
+                ])
+            })
+            .collect()
+    }
+
+    /// The tuple based merge these days were written with before [`Vec3`], kept to check they agree
+    fn tuple_try_merge(
+        beacon_set: &mut HashSet<(isize, isize, isize)>,
+        scanner: &[(isize, isize, isize)],
+    ) -> Option<(isize, isize, isize)> {
+        let (scanner, (dx, dy, dz)) =
+            tuple_rotations(scanner).into_iter().find_map(|scanner| {
+                let delta = beacon_set
+                    .iter()
+                    .cartesian_product(&scanner)
+                    .map(|((x1, y1, z1), (x2, y2, z2))| (x1 - x2, y1 - y2, z1 - z2))
+                    .counts()
+                    .into_iter()
+                    .find_map(|(k, v)| if v >= 12 { Some(k) } else { None });
+
+                delta.map(|delta| (scanner, delta))
+            })?;
+
+        scanner.iter().for_each(|(x, y, z)| {
+            beacon_set.insert((x + dx, y + dy, z + dz));
+        });
+
+        Some((dx, dy, dz))
+    }
+
+    fn to_tuple(Vec3 { x, y, z }: Vec3) -> (isize, isize, isize) {
+        (x, y, z)
+    }
+
+    #[test]
+    fn rotations_match_tuple_rotations() {
+        for scanner in parse_scanners(&sample_input()) {
+            let tuples: Vec<(isize, isize, isize)> = scanner.iter().map(|&b| to_tuple(b)).collect();
+            let expected: HashSet<Vec<(isize, isize, isize)>> =
+                tuple_rotations(&tuples).into_iter().collect();
+            let actual: HashSet<Vec<(isize, isize, isize)>> = rotations(&scanner)
+                .iter()
+                .map(|rotated| rotated.iter().map(|&b| to_tuple(b)).collect())
+                .collect();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn merge_matches_tuple_merge() {
+        let scanners = parse_scanners(&sample_input());
+        let mut beacon_set: HashSet<Position> = scanners[0].iter().copied().collect();
+        let mut tuple_set: HashSet<(isize, isize, isize)> =
+            scanners[0].iter().map(|&b| to_tuple(b)).collect();
+
+        for index in [1, 4, 2, 3] {
+            let tuples: Vec<(isize, isize, isize)> =
+                scanners[index].iter().map(|&b| to_tuple(b)).collect();
+            assert_eq!(
+                try_merge(&mut beacon_set, &scanners[index]).map(to_tuple),
+                tuple_try_merge(&mut tuple_set, &tuples)
+            );
+        }
+
+        assert_eq!(
+            beacon_set.into_iter().map(to_tuple).collect::<HashSet<_>>(),
+            tuple_set
+        );
+    }
+
+    #[test]
+    fn can_rotate() {
+        let scanner: Scanner = Vec::from([Vec3::new(1, 2, 3)]);
+        let rotations: HashSet<Position> = rotations(&scanner)
+            .iter()
+            .flat_map(|a| a.get(0).map(|&a| a))
+            .collect();
+        let expected: HashSet<Position> = HashSet::from([
+            Vec3::new(1, 2, 3),
+            Vec3::new(2, -1, 3),
+            Vec3::new(-1, -2, 3),
+            Vec3::new(-2, 1, 3),
+            Vec3::new(3, 2, -1),
+            Vec3::new(2, -3, -1),
+            Vec3::new(-3, -2, -1),
+            Vec3::new(-2, 3, -1),
+            Vec3::new(3, -1, -2),
+            Vec3::new(-1, -3, -2),
+            Vec3::new(-3, 1, -2),
+            Vec3::new(1, 3, -2),
+            Vec3::new(3, -2, 1),
+            Vec3::new(-2, -3, 1),
+            Vec3::new(-3, 2, 1),
+            Vec3::new(2, 3, 1),
+            Vec3::new(3, 1, 2),
+            Vec3::new(1, -3, 2),
+            Vec3::new(-3, -1, 2),
+            Vec3::new(-1, 3, 2),
+            Vec3::new(-1, 2, -3),
+            Vec3::new(2, 1, -3),
+            Vec3::new(1, -2, -3),
+            Vec3::new(-2, -1, -3),
+        ]);
+
+        assert_eq!(rotations, expected);
+    }
+
+    #[test]
+    fn can_merge_all() {
+        let scanners = parse_scanners(&sample_input());
+        let (beacons, _) = merge_all(&scanners);
+        assert_eq!(beacons.len(), 79);
+        assert_eq!(
+            beacons,
+            HashSet::from([
+                Vec3::new(-892, 524, 684),
+                Vec3::new(-876, 649, 763),
+                Vec3::new(-838, 591, 734),
+                Vec3::new(-789, 900, -551),
+                Vec3::new(-739, -1745, 668),
+                Vec3::new(-706, -3180, -659),
+                Vec3::new(-697, -3072, -689),
+                Vec3::new(-689, 845, -530),
+                Vec3::new(-687, -1600, 576),
+                Vec3::new(-661, -816, -575),
+                Vec3::new(-654, -3158, -753),
+                Vec3::new(-635, -1737, 486),
+                Vec3::new(-631, -672, 1502),
+                Vec3::new(-624, -1620, 1868),
+                Vec3::new(-620, -3212, 371),
+                Vec3::new(-618, -824, -621),
+                Vec3::new(-612, -1695, 1788),
+                Vec3::new(-601, -1648, -643),
+                Vec3::new(-584, 868, -557),
+                Vec3::new(-537, -823, -458),
+                Vec3::new(-532, -1715, 1894),
+                Vec3::new(-518, -1681, -600),
+                Vec3::new(-499, -1607, -770),
+                Vec3::new(-485, -357, 347),
+                Vec3::new(-470, -3283, 303),
+                Vec3::new(-456, -621, 1527),
+                Vec3::new(-447, -329, 318),
+                Vec3::new(-430, -3130, 366),
+                Vec3::new(-413, -627, 1469),
+                Vec3::new(-345, -311, 381),
+                Vec3::new(-36, -1284, 1171),
+                Vec3::new(-27, -1108, -65),
+                Vec3::new(7, -33, -71),
+                Vec3::new(12, -2351, -103),
+                Vec3::new(26, -1119, 1091),
+                Vec3::new(346, -2985, 342),
+                Vec3::new(366, -3059, 397),
+                Vec3::new(377, -2827, 367),
+                Vec3::new(390, -675, -793),
+                Vec3::new(396, -1931, -563),
+                Vec3::new(404, -588, -901),
+                Vec3::new(408, -1815, 803),
+                Vec3::new(423, -701, 434),
+                Vec3::new(432, -2009, 850),
+                Vec3::new(443, 580, 662),
+                Vec3::new(455, 729, 728),
+                Vec3::new(456, -540, 1869),
+                Vec3::new(459, -707, 401),
+                Vec3::new(465, -695, 1988),
+                Vec3::new(474, 580, 667),
+                Vec3::new(496, -1584, 1900),
+                Vec3::new(497, -1838, -617),
+                Vec3::new(527, -524, 1933),
+                Vec3::new(528, -643, 409),
+                Vec3::new(534, -1912, 768),
+                Vec3::new(544, -627, -890),
+                Vec3::new(553, 345, -567),
+                Vec3::new(564, 392, -477),
+                Vec3::new(568, -2007, -577),
+                Vec3::new(605, -1665, 1952),
+                Vec3::new(612, -1593, 1893),
+                Vec3::new(630, 319, -379),
+                Vec3::new(686, -3108, -505),
+                Vec3::new(776, -3184, -501),
+                Vec3::new(846, -3110, -434),
+                Vec3::new(1135, -1161, 1235),
+                Vec3::new(1243, -1093, 1063),
+                Vec3::new(1660, -552, 429),
+                Vec3::new(1693, -557, 386),
+                Vec3::new(1735, -437, 1738),
+                Vec3::new(1749, -1800, 1813),
+                Vec3::new(1772, -405, 1572),
+                Vec3::new(1776, -675, 371),
+                Vec3::new(1779, -442, 1789),
+                Vec3::new(1780, -1548, 337),
+                Vec3::new(1786, -1538, 337),
+                Vec3::new(1847, -1591, 415),
+                Vec3::new(1889, -1729, 1762),
+                Vec3::new(1994, -1805, 1792),
+            ])
+        );
+    }
+
+    #[test]
+    fn can_build_merge_tree() {
+        let scanners = parse_scanners(&sample_input());
+        assert_eq!(
+            merge_tree(&scanners, find_alignment, &mut NoProgress),
+            vec![(0, 1), (1, 3), (1, 4), (4, 2)]
+        );
+        assert_eq!(
+            merge_tree(&scanners, find_alignment_by_fingerprint, &mut NoProgress),
+            vec![(0, 1), (1, 3), (1, 4), (4, 2)]
+        );
+
+        let mut progress = RecordedProgress::default();
+        let result = merge_with_tree(&scanners, &mut progress);
+        assert_eq!(result.beacons.len(), 79);
+        assert_eq!(result.tree_depth(), 3);
+        assert_eq!(
+            progress.updates,
+            vec![(1, Some(4)), (2, Some(4)), (3, Some(4)), (4, Some(4))]
+        );
+        assert!(progress.finished);
+        assert_eq!(
+            result.scanner_positions,
+            HashSet::from([
+                Vec3::new(0, 0, 0),
+                Vec3::new(68, -1246, -43),
+                Vec3::new(1105, -1205, 1229),
+                Vec3::new(-92, -2380, -20),
+                Vec3::new(-20, -1133, 1061),
+            ])
+        );
+    }
+
+    #[test]
+    fn can_cache_merge_result_compactly() {
+        let scanners = parse_scanners(&sample_input());
+        let result = merge_with_tree(&scanners, &mut NoProgress);
+
+        let json = serde_json::to_string(&result).unwrap();
+        let loaded: MergeResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.beacons, result.beacons);
+        assert_eq!(loaded.scanner_positions, result.scanner_positions);
+        assert_eq!(loaded.merge_tree, result.merge_tree);
+
+        // The positions are saved as text rather than as objects with x, y and z
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["beacons"].is_string());
+        assert!(!json.contains("\"x\""));
+
+        // An entry from before the positions were encoded isn't read, so is replaced
+        assert!(serde_json::from_str::<MergeResult>(
+            r#"{"beacons":[{"x":1,"y":2,"z":3}],"scanner_positions":[],"merge_tree":[]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn can_find_largest_distance() {
+        let scanners = parse_scanners(&sample_input());
+        let (_, scanner_positions) = merge_all(&scanners);
+        assert_eq!(largest_distance(&scanner_positions), 3621);
+    }
+
+    #[test]
+    fn can_solve_rotation() {
+        // A quarter turn about z: x -> y, y -> -x
+        let rotation = solve_rotation(Vec3::new(1, 2, 3), Vec3::new(-2, 1, 3)).unwrap();
+        assert_eq!(rotation, Orientation::PosYNegX);
+        assert_eq!(Vec3::new(5, 7, 11).rotate(rotation), Vec3::new(-7, 5, 11));
+
+        // Every one of the 24 rotations can be recovered
+        for rotated in rotations(&vec![Vec3::new(1, 2, 3)]) {
+            let rotation = solve_rotation(Vec3::new(1, 2, 3), rotated[0]).unwrap();
+            assert_eq!(Vec3::new(1, 2, 3).rotate(rotation), rotated[0]);
+        }
+
+        // Reflections aren't rotations
+        assert_eq!(solve_rotation(Vec3::new(1, 2, 3), Vec3::new(-1, 2, 3)), None);
+        // Ambiguous when sizes are repeated or zero
+        assert_eq!(solve_rotation(Vec3::new(1, 1, 3), Vec3::new(1, 1, 3)), None);
+        assert_eq!(solve_rotation(Vec3::new(0, 2, 3), Vec3::new(0, 2, 3)), None);
+    }
+
+    #[test]
+    fn can_fingerprint_pairs() {
+        let a = Vec3::new(404, -588, -901);
+        let b = Vec3::new(528, -643, 409);
+        for rotated in rotations(&vec![a, b]) {
+            assert_eq!(fingerprint(rotated[0], rotated[1]), fingerprint(a, b));
+        }
+        assert_eq!(fingerprint(a, b), [55, 124, 1310]);
+    }
+
+    #[test]
+    fn can_align_by_fingerprint() {
+        let scanners = parse_scanners(&sample_input());
+        let sets: Vec<HashSet<Position>> = scanners
+            .iter()
+            .map(|scanner| scanner.iter().copied().collect())
+            .collect();
+
+        let (_, delta) = find_alignment_by_fingerprint(&sets[0], &scanners[1]).unwrap();
+        assert_eq!(delta, Vec3::new(68, -1246, -43));
+
+        // Agrees with trying all the rotations for every pair of scanners, including those that don't overlap
+        for (a, b) in (0..scanners.len()).flat_map(|a| (0..scanners.len()).map(move |b| (a, b))) {
+            if a == b {
+                continue;
+            }
+
+            let expected = find_alignment(&sets[a], &scanners[b]).map(|(rotated, delta)| {
+                (rotated.into_iter().collect::<HashSet<Position>>(), delta)
+            });
+            let actual =
+                find_alignment_by_fingerprint(&sets[a], &scanners[b]).map(|(rotated, delta)| {
+                    (rotated.into_iter().collect::<HashSet<Position>>(), delta)
+                });
+            assert_eq!(actual, expected, "Aligning {} with {}", b, a);
+        }
+    }
+
+    #[test]
+    fn can_rule_out_overlaps() {
+        let scanners = parse_scanners(&sample_input());
+        let sets: Vec<HashSet<Position>> = scanners
+            .iter()
+            .map(|scanner| scanner.iter().copied().collect())
+            .collect();
+
+        for (a, b) in (0..scanners.len()).flat_map(|a| (0..scanners.len()).map(move |b| (a, b))) {
+            if a == b {
+                continue;
+            }
+
+            let plausible = could_overlap(
+                &fingerprints(scanners[a].iter()),
+                &fingerprints(scanners[b].iter()),
+            );
+            let aligned = find_alignment(&sets[a], &scanners[b]).is_some();
+            // Never rules out a real overlap, and for the sample rules out all the others
+            assert_eq!(plausible, aligned, "Comparing {} with {}", a, b);
+        }
+    }
+
+    #[test]
+    fn can_solve_generated_input() {
+        let generated = generate(19, 1).unwrap();
+        assert_eq!(validate(&generated.input), Ok(()));
+
+        let scanners = parse_scanners(&generated.input);
+        assert_eq!(scanners.len(), 30);
+
+        let result = merge_with_tree(&scanners, &mut NoProgress);
+        assert_eq!(
+            Some(Answers::new(
+                result.beacons.len(),
+                largest_distance(&result.scanner_positions)
+            )),
+            generated.answers
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! This is my solution for [Advent of Code - Day 2 - _Dive!_](https://adventofcode.com/2021/day/2)
+//!
+//! Today involves parsing a sequence of instructions that direct the submarine to move on a grid.
+//! First [`parse_line`] is used to parse the string input into an internal representation
+//! [`Instruction`]. Rust has powerful pattern matching but requires that it is exhaustive. Front
+//! loading parsing the strings both makes the code more understandable, but also simplifies the
+//! match statements as we don't need to repeatedly handle possible bad input.
+//!
+//! The two parts differ in how the input should be interpreted, see [`Rules`]. I originally
+//! implemented both as a separate fold over the sequence of instructions. Part one takes the
+//! instructions at face value. Part two tracks a third variable 'aim', but is otherwise very
+//! similar, and the aim changes exactly as part one's depth does. So [`parse`] now follows the
+//! instructions once as a [`Course`], tracking the horizontal position, depth and aim together, and
+//! each part reads its answer from the final [`Position`].
+//!
+//! To try out other command streams, the course keeps a history of its [`Position`] after each
+//! step so it can be asked where it was at any of them. It also understands two commands the puzzle
+//! doesn't use, `reverse` and `hold`, see [`Direction`].
+
+use crate::errors::{check_input, check_pattern, to_validation, Validation};
+use crate::year_2021::day_2::Direction::{DOWN, FORWARD, HOLD, REVERSE, UP};
+
+/// There are three direction strings expected in the input. Parsing those into an Enum type helps
+/// doing exhaustive matches later. The last two aren't in the puzzle, but can be used in custom
+/// inputs:
+/// - `reverse X` is the opposite of `forward X`
+/// - `hold X` holds position for X units of time, so doesn't move the submarine, but takes X steps
+///   in its history, where every other command takes one
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Direction {
+    FORWARD,
+    UP,
+    DOWN,
+    REVERSE,
+    HOLD,
+}
+
+/// Which part's interpretation of the instructions to read from a [`Position`]
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+enum Rules {
+    /// Part one, the submarine starts at the origin, and moves using the following rules:
+    /// - _Forward_: Increase the horizontal position by the magnitude
+    /// - _Up_: Decrease the depth by the magnitude
+    /// - _Down_: Increase the depth by the magnitude
+    Simple,
+    /// Part two, the submarine starts at the origin, with a third variable 'aim' also set to 0. The
+    /// position and aim are updated using the following rules:
+    /// - _Forward_: Increase the horizontal position by the magnitude, increase the depth by
+    ///   `(magnitude x current aim)`
+    /// - _Up_: Decrease the aim by the magnitude
+    /// - _Down_: Increase the aim by the magnitude
+    Aiming,
+}
+
+/// Where the submarine is after a number of commands, following both sets of [`Rules`] at once.
+/// `depth` is the depth when [`Rules::Aiming`], and `aim` doubles as the depth when
+/// [`Rules::Simple`], as up and down change them in the same way, and nothing else does.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Default)]
+pub struct Position {
+    pub horizontal: isize,
+    pub depth: isize,
+    pub aim: isize,
+}
+
+/// The course the submarine follows from the origin, recording its position after each step
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Course {
+    /// The position at each step, starting with the origin before any commands
+    history: Vec<Position>,
+}
+
+/// Each line of the input is a pair of direction and magnitude - alias this for clarity
+pub type Instruction = (Direction, isize);
+
+/// Check each line is a direction and a distance, see [`validate`], parse them with [`parse_line`],
+/// and follow them as a [`Course`] that both [`part_one`] and [`part_two`] read their answers
+/// from.
+pub fn parse(input: &str) -> Course {
+    check_input(2, input, validate);
+    let instructions: Vec<Instruction> = input.lines().map(parse_line).collect();
+
+    Course::follow(&instructions)
+}
+
+/// Read the course as movements, see [`Rules::Simple`]
+pub fn part_one(course: &Course) -> Option<String> {
+    let (h1, d1) = course.final_position(Rules::Simple);
+    say!("Final position ({}, {}) = {}", h1, d1, h1 * d1);
+
+    Some((h1 * d1).to_string())
+}
+
+/// Read the course as changes to the aim, see [`Rules::Aiming`]
+pub fn part_two(course: &Course) -> Option<String> {
+    let (h2, d2) = course.final_position(Rules::Aiming);
+    say!("Final position with aiming ({}, {}) = {}", h2, d2, h2 * d2);
+
+    Some((h2 * d2).to_string())
+}
+
+/// Each line should be a command, and the distance to move
+pub fn validate(input: &str) -> Validation {
+    to_validation(check_pattern(
+        input,
+        r"(forward|down|up|reverse|hold) \d+",
+        "a direction and a distance",
+    ))
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input is a list of commands, so it is
+/// enough for it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// Parses a line in the format `(forward|up|down) \d+` into the internal representation
+/// [`Instruction`]. Will panic if the provided line does not match the expected format.
+///
+/// # Example from puzzle specification
+/// ```rust
+/// assert_eq!(parse_line("forward 5"), (FORWARD, 5));
+/// assert_eq!(parse_line("down 5"),    (DOWN,    5));
+/// assert_eq!(parse_line("forward 8"), (FORWARD, 8));
+/// assert_eq!(parse_line("up 3"),      (UP,      3));
+/// assert_eq!(parse_line("down 8"),    (DOWN,    8));
+/// assert_eq!(parse_line("forward 2"), (FORWARD, 2));
+/// ```
+fn parse_line(line: &str) -> Instruction {
+    if let Some((direction, magnitude)) = line.split_once(" ") {
+        return (
+            match direction {
+                "forward" => FORWARD,
+                "up" => UP,
+                "down" => DOWN,
+                "reverse" => REVERSE,
+                "hold" => HOLD,
+                unexpected => panic!("Unexpected direction {}", unexpected),
+            },
+            magnitude
+                .parse::<isize>()
+                .expect("Magnitude was not a number"),
+        );
+    }
+
+    panic!("Line '{}' was not in the expected format", line)
+}
+
+impl Position {
+    /// The horizontal position and depth following the given [`Rules`]
+    fn following(&self, rules: Rules) -> (isize, isize) {
+        match rules {
+            Rules::Simple => (self.horizontal, self.aim),
+            Rules::Aiming => (self.horizontal, self.depth),
+        }
+    }
+
+    /// Work out where a command moves the submarine to from this position
+    fn apply(self, &(direction, magnitude): &Instruction) -> Position {
+        let Position {
+            horizontal,
+            depth,
+            aim,
+        } = self;
+
+        match direction {
+            HOLD => self,
+            FORWARD => Position {
+                horizontal: horizontal + magnitude,
+                depth: depth + (aim * magnitude),
+                aim,
+            },
+            REVERSE => Position {
+                horizontal: horizontal - magnitude,
+                depth: depth - (aim * magnitude),
+                aim,
+            },
+            UP => Position {
+                aim: aim - magnitude,
+                ..self
+            },
+            DOWN => Position {
+                aim: aim + magnitude,
+                ..self
+            },
+        }
+    }
+}
+
+impl Course {
+    /// A course at the origin, that hasn't executed any commands
+    fn new() -> Course {
+        Course {
+            history: vec![Position::default()],
+        }
+    }
+
+    /// A course that has followed the given commands from the origin
+    pub fn follow(commands: &[Instruction]) -> Course {
+        let mut course = Course::new();
+        course.execute(commands);
+
+        course
+    }
+
+    /// Follow each of the commands in order, recording the position after each step. `hold X` is X
+    /// steps, the rest are one each.
+    pub fn execute(&mut self, commands: &[Instruction]) {
+        for command in commands {
+            let steps = match command {
+                (HOLD, time) => *time as usize,
+                _ => 1,
+            };
+            let next = self.position().apply(command);
+            self.history.extend(std::iter::repeat_n(next, steps));
+        }
+    }
+
+    /// The current position, i.e. after the last command executed
+    fn position(&self) -> Position {
+        *self.history.last().unwrap()
+    }
+
+    /// The position after `step` steps, where step `0` is the origin, or `None` if the commands
+    /// executed haven't taken that many steps, see [`Course::execute`]
+    pub fn position_at(&self, step: usize) -> Option<Position> {
+        self.history.get(step).copied()
+    }
+
+    /// The current position as a tuple `(horizontal_position, depth)`, following the given rules
+    ///
+    /// # Example from puzzle specification
+    /// ```rust
+    /// let course = Course::follow(&[
+    ///     (FORWARD, 5),
+    ///     (DOWN, 5),
+    ///     (FORWARD, 8),
+    ///     (UP, 3),
+    ///     (DOWN, 8),
+    ///     (FORWARD, 2),
+    /// ]);
+    /// assert_eq!(course.final_position(Rules::Simple), (15, 10));
+    /// assert_eq!(course.final_position(Rules::Aiming), (15, 60));
+    /// ```
+    fn final_position(&self, rules: Rules) -> (isize, isize) {
+        self.position().following(rules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year_2021::day_2::Direction::*;
+    use crate::year_2021::day_2::{parse_line, Course, Instruction, Position, Rules};
+
+    #[test]
+    fn can_parse() {
+        assert_eq!(parse_line("forward 5"), (FORWARD, 5));
+        assert_eq!(parse_line("down 5"), (DOWN, 5));
+        assert_eq!(parse_line("forward 8"), (FORWARD, 8));
+        assert_eq!(parse_line("up 3"), (UP, 3));
+        assert_eq!(parse_line("down 8"), (DOWN, 8));
+        assert_eq!(parse_line("forward 2"), (FORWARD, 2));
+        assert_eq!(parse_line("reverse 4"), (REVERSE, 4));
+        assert_eq!(parse_line("hold 3"), (HOLD, 3));
+    }
+
+    #[test]
+    fn can_navigate() {
+        let course = Course::follow(&test_data());
+        assert_eq!(course.final_position(Rules::Simple), (15, 10))
+    }
+
+    #[test]
+    fn can_navigate_and_aim() {
+        let course = Course::follow(&test_data());
+        assert_eq!(course.final_position(Rules::Aiming), (15, 60));
+        assert_eq!(course.position().aim, 10)
+    }
+
+    fn test_data() -> Vec<Instruction> {
+        vec![
+            (FORWARD, 5),
+            (DOWN, 5),
+            (FORWARD, 8),
+            (UP, 3),
+            (DOWN, 8),
+            (FORWARD, 2),
+        ]
+    }
+
+    #[test]
+    fn can_record_history() {
+        let position = |horizontal, depth, aim| Position {
+            horizontal,
+            depth,
+            aim,
+        };
+
+        let mut course = Course::new();
+        course.execute(&test_data());
+
+        assert_eq!(course.position_at(0), Some(position(0, 0, 0)));
+        assert_eq!(course.position_at(1), Some(position(5, 0, 0)));
+        assert_eq!(course.position_at(2), Some(position(5, 0, 5)));
+        assert_eq!(course.position_at(3), Some(position(13, 40, 5)));
+        assert_eq!(course.position_at(6), Some(position(15, 60, 10)));
+        assert_eq!(course.position_at(7), None);
+
+        // Further commands carry on from where it got to
+        course.execute(&[(HOLD, 5), (REVERSE, 3), (UP, 10), (FORWARD, 1)]);
+        assert_eq!(course.position_at(7), Some(position(15, 60, 10)));
+        assert_eq!(course.position_at(11), Some(position(15, 60, 10)));
+        assert_eq!(course.position_at(12), Some(position(12, 30, 10)));
+        assert_eq!(course.position_at(14), Some(position(13, 30, 0)));
+        assert_eq!(course.position_at(15), None);
+        assert_eq!(course.position(), position(13, 30, 0));
+
+        let course = Course::follow(&[(FORWARD, 5), (DOWN, 2), (HOLD, 9), (REVERSE, 8), (UP, 1)]);
+        assert_eq!(
+            course.position_at(3).map(|p| p.following(Rules::Simple)),
+            Some((5, 2))
+        );
+        assert_eq!(course.history.len(), 14);
+        assert_eq!(course.final_position(Rules::Simple), (-3, 1));
+        assert_eq!(course.final_position(Rules::Aiming), (-3, -16));
+    }
+
+    #[test]
+    fn both_rules_come_from_one_pass() {
+        // Every step of the history answers both parts, e.g. part one's depth at each step
+        let course = Course::follow(&test_data());
+        let simple_depths: Vec<isize> = course
+            .history
+            .iter()
+            .map(|p| p.following(Rules::Simple).1)
+            .collect();
+
+        assert_eq!(simple_depths, vec![0, 0, 5, 5, 2, 10, 10]);
+    }
+}
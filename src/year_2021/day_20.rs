@@ -0,0 +1,936 @@
+//! This is my solution for [Advent of Code - Day 20 - _Trench Map_](https://adventofcode.com/2021/day/20)
+//!
+//! Today was iterating a grid, with the twist being that the puzzle input exhibited behaviour that
+//! the sample input didn't. This feels unfair, maybe it's just to test debugging skills, but those
+//! will be challenged throughout anyway. I don't like that it seemed to be purposefully setting
+//! people up to fail.
+//!
+//! Luckily I noticed the trick. The first bit of the puzzle bitmap being set means that all the
+//! infinite co-ordinates beyond the edge of the image data, which are initially set to `0` will all
+//! flip to `1`, on each odd iteration. They will then flip back to `0` on even iterations as the
+//! final bit of the bitmap is set to `0`. Having spotted this I decided to implement the grid as
+//! a set of co-ordinates that are set within the known central area, the current bounds of the
+//! known area, and the default value of every bit beyond that. [`Image`] and the methods
+//! implemented for it make up the bulk of today's solution.
+//!
+//! [`parse_input`] takes the first line and transforms it into the bitmap to lookup new pixel
+//! values, then passes the rest of the lines to [`Image::from`] to parse the rest into the seed
+//! image. I originally had this inlined in [`parse_input`] but extracted it and included variables
+//! for the origin as a way to parse the examples of iterated images in the specification when
+//! writing tests.
+//!
+//! [`Image::map_pixel`] handles iterating a single pixel by looking up its surrounds, building them
+//! into the bitmap index, and returning the relevant bit. [`Image::iterate`] handles a single
+//! iteration of the image. This grows the image area by 1 (only pixels adjacent to the existing
+//! image data will be affected by it), map all the pixels in the new area, and set the new default
+//! value for pixels outside the area. Finally [`Image::iterate_n`] iterates the image the required
+//! number of times, two for part one, fifty for part two.
+//!
+//! The image data was originally a `HashSet` of the active pixels, which spent most of the time
+//! hashing co-ordinates. It is now a bitset with a row of `u64`s for each row of the image, and
+//! [`Image::iterate`] slides a window along each row, so each pixel is read three times rather
+//! than nine. Passing `--compare` compares this against the original, see [`iterate_n_hash_set`].
+//!
+//! [`IterationCache`] is an optional layer over [`Image::iterate`], that memoises the result by the
+//! content of the image, and counts how often that helps. It doesn't help the puzzle input, but is
+//! useful for synthetic inputs that repeat themselves, so is only used when enabled, see
+//! [`Options`].
+//!
+//! Counting the lit pixels only makes sense while the pixels beyond the image data are dark. If
+//! the first bit of the bitmap is set and the last isn't, that is only after an even number of
+//! iterations, and if both are set, the infinite background stays lit after the first. Rather than
+//! count just the pixels in the image data in those cases, [`Image::count_pixels`] reports that
+//! the count is [`PixelCount::Infinite`].
+
+use crate::errors::{
+    check_grid, check_input, check_pattern, offset, sections, to_validation, InputError, Validation,
+};
+use crate::solution::{Alternative, Part};
+use crate::util::image::{save_if_requested, Bitmap};
+use crate::util::params::Params;
+use crate::util::render::{draw_frame, frame_delay, visualise_enabled, FrameSource, Renderable};
+use crate::util::repl::{repl_enabled, run_repl, Steppable};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::str::Lines;
+
+/// The number of times to enhance the image for each part, can be changed with
+/// `--param part_one_iterations=<n>` and `--param part_two_iterations=<n>`, and whether part two
+/// uses the [`IterationCache`], enabled with `--param use_cache=true`.
+#[derive(Eq, PartialEq, Debug)]
+struct Options {
+    part_one_iterations: usize,
+    part_two_iterations: usize,
+    use_cache: bool,
+}
+
+impl From<&Params> for Options {
+    fn from(params: &Params) -> Self {
+        Options {
+            part_one_iterations: params.get_or("part_one_iterations", 2),
+            part_two_iterations: params.get_or("part_two_iterations", 50),
+            use_cache: params.get_or("use_cache", false),
+        }
+    }
+}
+
+/// Represents an image as the pixels that are on, the bounds of the current image data, and the
+/// default value for pixels outside this area.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Image {
+    /// The active pixels within (min_x, min_y) .. (max_x, max_y), one bit per pixel. Each row
+    /// starts on a new word, and the bits past the end of the row are never set, so that equal
+    /// images have equal rows.
+    rows: Vec<u64>,
+    /// lower bound of the image data x co-ordinate values
+    min_x: isize,
+    /// upper bound of the image data x co-ordinate values
+    max_x: isize,
+    /// lower bound of the image data y co-ordinate values
+    min_y: isize,
+    /// upper bound of the image data y co-ordinate values
+    max_y: isize,
+    /// The value of all pixels outside the image data bounds
+    default_pixel: bool,
+}
+
+/// The number of lit pixels in an image
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+enum PixelCount {
+    Finite(usize),
+    /// The pixels beyond the image data are lit, and there are infinitely many of those
+    Infinite,
+}
+
+impl Display for PixelCount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PixelCount::Finite(count) => write!(f, "{}", count),
+            PixelCount::Infinite => write!(f, "infinite"),
+        }
+    }
+}
+
+impl<'a> From<(&mut Lines<'a>, isize, isize)> for Image {
+    /// Takes lines as [`parse_input`] needs to take the first two lines before this is called
+    fn from((lines, min_x, min_y): (&mut Lines<'a>, isize, isize)) -> Self {
+        let mut pixels = Vec::new();
+        // track the bounds of the image data
+        let mut max_x = min_x;
+        // decrease by one as it will be incremented at the start of each loop, including the
+        // first.
+        let mut max_y = min_y - 1;
+
+        // insert all the co-ordinates where we see a #
+        while let Some(line) = lines.next() {
+            // can't enumerate once already taken a value from the iterator
+            max_y += 1;
+            line.chars().enumerate().for_each(|(raw_x, chr)| {
+                let x = raw_x as isize + min_x;
+                if chr == '#' {
+                    pixels.push((x, max_y));
+                }
+
+                if max_x < x {
+                    max_x = x;
+                };
+            });
+        }
+
+        let mut image = Image::blank(min_x, max_x, min_y, max_y, false);
+        pixels.into_iter().for_each(|(x, y)| image.set(x, y));
+
+        image
+    }
+}
+
+impl Image {
+    /// Builds a new image by expanding the area by 1 pixel in all directions, and mapping those
+    /// based in the image data / default pixel value, finally calculating the new value for the
+    /// default.
+    fn iterate(&self, bitmap: &[bool]) -> Image {
+        // All pixels outside the new image area were surrounded entirely by other default pixels
+        // in the existing image. If it was previously unset, all bits in the index are unset, so
+        // index is 0, otherwise all bits are set the and index is 111111111 i.e. 511.
+        let &default_pixel = if self.default_pixel {
+            bitmap.get(511).unwrap()
+        } else {
+            bitmap.get(0).unwrap()
+        };
+
+        // The area affected by non-default pixels grows by 1 each iteration
+        let mut image = Image::blank(
+            self.min_x - 1,
+            self.max_x + 1,
+            self.min_y - 1,
+            self.max_y + 1,
+            default_pixel,
+        );
+
+        // Slide a 3x3 window along each row of the new image area. Moving right shifts the two
+        // columns that are kept one bit left, dropping the column that has left the window, and the
+        // column that has entered it fills the gap. This avoids reading every pixel nine times.
+        for y in image.min_y..=image.max_y {
+            let mut index = 0;
+            for x in image.min_x - 1..=image.max_x + 1 {
+                let column = (self.get(x, y - 1) as usize) << 6
+                    | (self.get(x, y) as usize) << 3
+                    | self.get(x, y + 1) as usize;
+                index = (index << 1) & 0b110_110_110 | column;
+
+                // The window is centred on the previous column, once it is full
+                if x > image.min_x && bitmap[index] {
+                    image.set(x - 1, y);
+                }
+            }
+        }
+
+        image
+    }
+
+    /// Get if a specific pixel will be active in the next iteration. [`Image::iterate`] works out
+    /// the same thing a row at a time, rather than building each pixel's index from scratch.
+    #[allow(dead_code)]
+    fn map_pixel(&self, x: isize, y: isize, bitmap: &[bool]) -> bool {
+        let mut index: usize = 0;
+        // for each pixel in the 3x3 grid surrounding it - the order matters here
+        (y - 1..=y + 1)
+            .cartesian_product(x - 1..=x + 1)
+            .for_each(|(y1, x1)| {
+                // build by shifting the pixels on from the right
+                index = (index << 1) + (self.get(x1, y1) as usize);
+            });
+
+        // lookup the corresponding pixel in the bitmap
+        *bitmap.get(index).unwrap()
+    }
+
+    /// Repeatedly iterate the current image n times
+    fn iterate_n(&self, bitmap: &[bool], n: usize) -> Image {
+        (0..n).fold(self.clone(), |acc, _| acc.iterate(bitmap))
+    }
+
+    /// Count the lit pixels, including those outside the image data
+    fn count_pixels(&self) -> PixelCount {
+        if self.default_pixel {
+            PixelCount::Infinite
+        } else {
+            PixelCount::Finite(self.count_image_data())
+        }
+    }
+
+    /// Count the lit pixels within the image data bounds
+    fn count_image_data(&self) -> usize {
+        self.rows.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// An image with none of the image data set
+    fn blank(min_x: isize, max_x: isize, min_y: isize, max_y: isize, default_pixel: bool) -> Image {
+        let words_per_row = Image::words_per_row(min_x, max_x);
+        let height = (max_y - min_y + 1) as usize;
+
+        Image {
+            rows: vec![0; words_per_row * height],
+            min_x,
+            max_x,
+            min_y,
+            max_y,
+            default_pixel,
+        }
+    }
+
+    /// The number of words needed to store a row that covers `min_x..=max_x`
+    fn words_per_row(min_x: isize, max_x: isize) -> usize {
+        ((max_x - min_x + 1) as usize).div_ceil(64)
+    }
+
+    /// The word that stores a pixel in the image data, and the bit within that word
+    fn position(&self, x: isize, y: isize) -> (usize, u32) {
+        let column = (x - self.min_x) as usize;
+        let row = (y - self.min_y) as usize;
+
+        (
+            row * Image::words_per_row(self.min_x, self.max_x) + column / 64,
+            (column % 64) as u32,
+        )
+    }
+
+    /// Light a pixel within the image data bounds
+    fn set(&mut self, x: isize, y: isize) {
+        let (word, bit) = self.position(x, y);
+        self.rows[word] |= 1 << bit;
+    }
+
+    /// Is the pixel at these co-ordinates active, including those outside the image data
+    fn get(&self, x: isize, y: isize) -> bool {
+        if x < self.min_x || x > self.max_x || y < self.min_y || y > self.max_y {
+            self.default_pixel
+        } else {
+            let (word, bit) = self.position(x, y);
+            (self.rows[word] >> bit) & 1 == 1
+        }
+    }
+
+    /// The co-ordinates of the lit pixels within the image data bounds
+    fn lit_pixels(&self) -> HashSet<(isize, isize)> {
+        (self.min_x..=self.max_x)
+            .cartesian_product(self.min_y..=self.max_y)
+            .filter(|&(x, y)| self.get(x, y))
+            .collect()
+    }
+
+    /// Shrink the bounds to the smallest area that contains all the pixels that differ from the
+    /// default pixel, then move that area so it starts at the origin. Returns the normalised image,
+    /// and the offset needed to move it back. The infinite image represented is unchanged, apart
+    /// from that translation.
+    fn normalise(&self) -> (Image, (isize, isize)) {
+        let differs: Vec<(isize, isize)> = (self.min_x..=self.max_x)
+            .cartesian_product(self.min_y..=self.max_y)
+            .filter(|&(x, y)| self.get(x, y) != self.default_pixel)
+            .collect();
+
+        let (min_x, max_x) = differs
+            .iter()
+            .map(|&(x, _)| x)
+            .minmax()
+            .into_option()
+            .unwrap_or((0, 0));
+        let (min_y, max_y) = differs
+            .iter()
+            .map(|&(_, y)| y)
+            .minmax()
+            .into_option()
+            .unwrap_or((0, 0));
+
+        let mut normalised = Image::blank(0, max_x - min_x, 0, max_y - min_y, self.default_pixel);
+        (min_x..=max_x)
+            .cartesian_product(min_y..=max_y)
+            .filter(|&(x, y)| self.get(x, y))
+            .for_each(|(x, y)| normalised.set(x - min_x, y - min_y));
+
+        (normalised, (min_x, min_y))
+    }
+
+    /// Move the image data by the given offset. The rows are relative to the bounds, so are
+    /// unchanged.
+    fn translate(&self, (dx, dy): (isize, isize)) -> Image {
+        Image {
+            rows: self.rows.clone(),
+            min_x: self.min_x + dx,
+            max_x: self.max_x + dx,
+            min_y: self.min_y + dy,
+            max_y: self.max_y + dy,
+            default_pixel: self.default_pixel,
+        }
+    }
+}
+
+impl From<&Image> for Bitmap {
+    /// Draw the image data area, with lit pixels in black
+    fn from(image: &Image) -> Self {
+        let mut bitmap = Bitmap::new(
+            (image.max_x - image.min_x + 1) as usize,
+            (image.max_y - image.min_y + 1) as usize,
+        );
+        for y in image.min_y..=image.max_y {
+            for x in image.min_x..=image.max_x {
+                bitmap.set(
+                    (x - image.min_x) as usize,
+                    (y - image.min_y) as usize,
+                    image.get(x, y),
+                );
+            }
+        }
+
+        bitmap
+    }
+}
+
+impl Renderable for Image {
+    /// Draw the image data area, one row per line, using `#` for lit pixels and `.` for dark ones
+    fn render(&self) -> String {
+        (self.min_y..=self.max_y)
+            .map(|y| {
+                (self.min_x..=self.max_x)
+                    .map(|x| if self.get(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+}
+
+/// An image paired with the enhancement algorithm, so it can be stepped through with
+/// [`crate::util::repl`]
+#[derive(Clone)]
+struct Enhancement<'a> {
+    bitmap: &'a [bool],
+    image: Image,
+    iterations: usize,
+}
+
+impl<'a> Renderable for Enhancement<'a> {
+    fn render(&self) -> String {
+        self.image.render()
+    }
+}
+
+impl<'a> FrameSource for Enhancement<'a> {
+    /// The image can always be enhanced again
+    fn step(&mut self) -> bool {
+        self.image = self.image.iterate(self.bitmap);
+        self.iterations += 1;
+        true
+    }
+
+    fn current_step(&self) -> usize {
+        self.iterations
+    }
+}
+
+impl<'a> Steppable for Enhancement<'a> {
+    fn stats(&self) -> String {
+        format!(
+            "Iteration {}: {} active pixels in {}x{}, pixels outside are {}",
+            self.iterations,
+            self.image.count_image_data(),
+            self.image.max_x - self.image.min_x + 1,
+            self.image.max_y - self.image.min_y + 1,
+            if self.image.default_pixel {
+                "lit"
+            } else {
+                "dark"
+            }
+        )
+    }
+}
+
+/// The content of a normalised image, in a form that can be hashed: the size, the rows of active
+/// pixels, and the default pixel.
+type ImageKey = (isize, isize, Vec<u64>, bool);
+
+/// An optional cache for [`Image::iterate`]. Images are [`Image::normalise`]d before looking them
+/// up, so the same pattern is recognised wherever it is in the image. This won't help for the
+/// puzzle input, where the image keeps growing, but synthetic inputs often settle into patterns
+/// that repeat, or move across the image.
+///
+/// The images returned are equivalent to the uncached ones, but their bounds are the tighter ones
+/// from normalising.
+#[derive(Debug)]
+struct IterationCache {
+    /// The bitmap all the cached results were built with
+    bitmap: Vec<bool>,
+    /// Normalised images mapped to the normalised result of iterating them
+    results: HashMap<ImageKey, Image>,
+    /// The number of lookups that were already cached
+    hits: usize,
+    /// The number of lookups that needed to be calculated
+    misses: usize,
+}
+
+impl IterationCache {
+    fn new(bitmap: &[bool]) -> IterationCache {
+        IterationCache {
+            bitmap: bitmap.to_vec(),
+            results: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// As [`Image::iterate`], but using the cached result if this content has been seen before
+    fn iterate(&mut self, image: &Image) -> Image {
+        let (normalised, offset) = image.normalise();
+        let key: ImageKey = (
+            normalised.max_x,
+            normalised.max_y,
+            normalised.rows.clone(),
+            normalised.default_pixel,
+        );
+
+        let result = match self.results.get(&key) {
+            Some(result) => {
+                self.hits += 1;
+                result.clone()
+            }
+            None => {
+                self.misses += 1;
+                let result = normalised.iterate(&self.bitmap);
+                self.results.insert(key, result.clone());
+                result
+            }
+        };
+
+        result.translate(offset)
+    }
+
+    /// As [`Image::iterate_n`], but using the cache for each iteration
+    fn iterate_n(&mut self, image: &Image, n: usize) -> Image {
+        (0..n).fold(image.clone(), |acc, _| self.iterate(&acc))
+    }
+
+    /// The proportion of lookups that were cache hits
+    fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+/// How [`Image::iterate_n`] worked before the image data was stored as a bitset, with the active
+/// pixels in a `HashSet`. Kept to compare against, see [`alternatives`]. Returns the active
+/// pixels within the final bounds, and the default pixel.
+fn iterate_n_hash_set(image: &Image, bitmap: &[bool], n: usize) -> (HashSet<(isize, isize)>, bool) {
+    let mut pixels = image.lit_pixels();
+    let (mut min_x, mut max_x) = (image.min_x, image.max_x);
+    let (mut min_y, mut max_y) = (image.min_y, image.max_y);
+    let mut default_pixel = image.default_pixel;
+
+    for _ in 0..n {
+        let get = |x: isize, y: isize| {
+            if x < min_x || x > max_x || y < min_y || y > max_y {
+                default_pixel
+            } else {
+                pixels.contains(&(x, y))
+            }
+        };
+
+        let next = (min_x - 1..=max_x + 1)
+            .cartesian_product(min_y - 1..=max_y + 1)
+            .filter(|&(x, y)| {
+                let index = (y - 1..=y + 1)
+                    .cartesian_product(x - 1..=x + 1)
+                    .fold(0, |index, (y1, x1)| (index << 1) + get(x1, y1) as usize);
+                bitmap[index]
+            })
+            .collect();
+
+        pixels = next;
+        min_x -= 1;
+        max_x += 1;
+        min_y -= 1;
+        max_y += 1;
+        default_pixel = bitmap[if default_pixel { 511 } else { 0 }];
+    }
+
+    (pixels, default_pixel)
+}
+
+/// The ways of enhancing the image for part two, to compare with `--compare`: with
+/// [`Image::iterate_n`] alone, as part two does by default, with the [`IterationCache`], and with
+/// the original `HashSet` of pixels, [`iterate_n_hash_set`].
+pub fn alternatives() -> Vec<Alternative<(Vec<bool>, Image)>> {
+    fn iterations() -> usize {
+        Options::from(&Params::from_args()).part_two_iterations
+    }
+
+    vec![
+        Alternative::new(Part::Two, "Image::iterate_n", |(bitmap, image)| {
+            Some(image.iterate_n(bitmap, iterations()).count_pixels().to_string())
+        }),
+        Alternative::new(Part::Two, "IterationCache::iterate_n", |(bitmap, image)| {
+            let mut cache = IterationCache::new(bitmap);
+            Some(cache.iterate_n(image, iterations()).count_pixels().to_string())
+        }),
+        Alternative::new(Part::Two, "iterate_n_hash_set", |(bitmap, image)| {
+            let (pixels, default_pixel) = iterate_n_hash_set(image, bitmap, iterations());
+            let count = match default_pixel {
+                true => PixelCount::Infinite,
+                false => PixelCount::Finite(pixels.len()),
+            };
+            Some(count.to_string())
+        }),
+    ]
+}
+
+/// Check the input is the enhancement algorithm, then the image, see [`validate`], and parse them
+/// for [`part_one`] and [`part_two`] to enhance.
+pub fn parse(contents: &str) -> (Vec<bool>, Image) {
+    check_input(20, contents, validate);
+    parse_input(contents)
+}
+
+/// Count the pixels lit after the iterations for part one, which can be changed, see [`Options`]
+pub fn part_one((bitmap, image): &(Vec<bool>, Image)) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    let count_1 = image
+        .iterate_n(bitmap, options.part_one_iterations)
+        .count_pixels();
+    say!(
+        "After {} iterations there are {} active pixels.",
+        options.part_one_iterations,
+        count_1
+    );
+
+    Some(count_1.to_string())
+}
+
+/// Count the pixels lit after the iterations for part two, which can be changed, see [`Options`].
+///
+/// - Pass `--param use_cache=true` to iterate using an [`IterationCache`], see [`Options`].
+/// - Pass `--visualise` to watch the image being enhanced.
+/// - Pass `--repl` to enhance the image interactively, see [`crate::util::repl`].
+/// - Pass `--output=<path>` with a `.png` or `.pbm` extension to save the final image.
+/// - Pass `--compare` to compare the bitset image against the original `HashSet` of pixels, see
+///   [`alternatives`].
+pub fn part_two((bitmap, image): &(Vec<bool>, Image)) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+
+    if visualise_enabled() {
+        visualise(image, bitmap, options.part_two_iterations);
+    }
+
+    if repl_enabled() {
+        run_repl(&Enhancement {
+            bitmap,
+            image: image.clone(),
+            iterations: 0,
+        });
+    }
+
+    let iterated_2 = if options.use_cache {
+        let mut cache = IterationCache::new(bitmap);
+        let iterated = cache.iterate_n(image, options.part_two_iterations);
+        say!(
+            "The iteration cache had {} hits and {} misses ({:.0}%).",
+            cache.hits,
+            cache.misses,
+            cache.hit_rate() * 100.0
+        );
+        iterated
+    } else {
+        image.iterate_n(bitmap, options.part_two_iterations)
+    };
+    let count_2 = iterated_2.count_pixels();
+    say!(
+        "After {} iterations there are {} active pixels.",
+        options.part_two_iterations,
+        count_2
+    );
+    save_if_requested(&Bitmap::from(&iterated_2));
+
+    Some(count_2.to_string())
+}
+
+/// The input should be the 512 character enhancement algorithm, then the image as a rectangular
+/// grid
+pub fn validate(input: &str) -> Validation {
+    let sections = sections(input);
+    if sections.len() != 2 {
+        return Err(vec![InputError::new(
+            1,
+            "Expected the algorithm and the image separated by a blank line",
+        )]);
+    }
+
+    let (algorithm_start, algorithm) = sections[0];
+    let (image_start, image) = sections[1];
+    let mut errors = offset(
+        check_pattern(algorithm, "[#.]{512}", "512 '#' or '.' characters"),
+        algorithm_start,
+    );
+    errors.extend(offset(check_grid(image, "#."), image_start));
+
+    to_validation(errors)
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input has an enhancement algorithm, so it
+/// is enough for it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// Animate the trench map image being enhanced the given number of times
+fn visualise(image: &Image, bitmap: &[bool], iterations: usize) {
+    let delay = frame_delay();
+    let mut current = image.clone();
+    draw_frame(&current, "Iteration 0", delay);
+
+    for iteration in 1..=iterations {
+        current = current.iterate(bitmap);
+        draw_frame(&current, &format!("Iteration {}", iteration), delay);
+    }
+}
+
+/// Extract the first line as the bitmap lookup, then delegate parsing the seed image to
+/// [`Image::from`]
+fn parse_input(input: &str) -> (Vec<bool>, Image) {
+    let mut lines = input.lines();
+    let bitmap: Vec<bool> = lines
+        .next()
+        .expect("Empty input")
+        .chars()
+        .map(|c| c == '#')
+        .collect();
+    lines.next(); // skip blank line
+
+    (bitmap, Image::from((&mut lines, 0, 0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::image::{assert_golden, Bitmap};
+    use crate::util::params::Params;
+    use crate::util::render::Renderable;
+    use crate::year_2021::day_20::{
+        iterate_n_hash_set, parse_input, Image, IterationCache, Options, PixelCount,
+    };
+    use std::collections::HashSet;
+
+    fn sample_input() -> String {
+        "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###"
+            .to_string()
+    }
+
+    #[test]
+    fn can_parse() {
+        let (bitmap, image) = parse_input(&sample_input());
+
+        assert_eq!(bitmap.len(), 512);
+        assert_eq!(bitmap[0], false);
+        assert_eq!(bitmap[1], false);
+        assert_eq!(bitmap[2], true);
+        assert_eq!(bitmap[3], false);
+        assert_eq!(bitmap[4], true);
+        assert_eq!(bitmap[5], false);
+        assert_eq!(bitmap[6], false);
+        assert_eq!(bitmap[7], true);
+        assert_eq!(bitmap[8], true);
+        assert_eq!(bitmap[9], true);
+        assert_eq!(bitmap[10], true);
+        assert_eq!(bitmap[20], true);
+        assert_eq!(bitmap[30], true);
+        assert_eq!(bitmap[40], true);
+        assert_eq!(bitmap[50], true);
+        assert_eq!(bitmap[60], false);
+        assert_eq!(bitmap[70], false);
+        assert_eq!(bitmap[511], true);
+
+        assert_eq!(image.min_x, 0);
+        assert_eq!(image.min_y, 0);
+        assert_eq!(image.max_x, 4);
+        assert_eq!(image.max_y, 4);
+
+        let expected = HashSet::from([
+            (0, 0),
+            (3, 0),
+            (0, 1),
+            (0, 2),
+            (1, 2),
+            (4, 2),
+            (2, 3),
+            (2, 4),
+            (3, 4),
+            (4, 4),
+        ]);
+
+        let lit_pixels = image.lit_pixels();
+        let missing: HashSet<&(isize, isize)> = expected.difference(&lit_pixels).collect();
+        let additional: HashSet<&(isize, isize)> = lit_pixels.difference(&expected).collect();
+
+        assert_eq!(missing, HashSet::new());
+        assert_eq!(additional, HashSet::new());
+    }
+
+    #[test]
+    fn can_map_pixel() {
+        let (bitmap, image) = parse_input(&sample_input());
+
+        assert_eq!(image.map_pixel(2, 2, &bitmap), true);
+        assert_eq!(image.map_pixel(-1, -1, &bitmap), false);
+        assert_eq!(image.map_pixel(0, -1, &bitmap), true);
+        assert_eq!(image.map_pixel(-1, 0, &bitmap), true);
+    }
+
+    #[test]
+    fn can_iterate() {
+        let (bitmap, image) = parse_input(&sample_input());
+
+        let mut expected_lines = ".##.##.
+#..#.#.
+##.#..#
+####..#
+.#..##.
+..##..#
+...#.#."
+            .lines();
+
+        let mut expected = Image::from((&mut expected_lines, -1, -1));
+        // isn't needed for the test input, but here for completeness
+        expected.default_pixel = *bitmap.get(0).unwrap();
+
+        assert_eq!(image.iterate(&bitmap), expected);
+
+        assert_eq!(expected.iterate(&bitmap).lit_pixels().len(), 35);
+    }
+
+    #[test]
+    fn can_iterate_n() {
+        let (bitmap, image) = parse_input(&sample_input());
+
+        assert_eq!(image.iterate_n(&bitmap, 2).lit_pixels().len(), 35);
+        assert_eq!(image.iterate_n(&bitmap, 50).lit_pixels().len(), 3351);
+        assert_eq!(
+            image
+                .iterate_n(&bitmap, 2)
+                .iterate_n(&bitmap, 48)
+                .lit_pixels()
+                .len(),
+            3351
+        );
+    }
+
+    #[test]
+    fn bitset_matches_hash_set() {
+        let (bitmap, image) = parse_input(&sample_input());
+
+        for n in [1, 2, 50] {
+            let iterated = image.iterate_n(&bitmap, n);
+            assert_eq!(
+                iterate_n_hash_set(&image, &bitmap, n),
+                (iterated.lit_pixels(), iterated.default_pixel)
+            );
+        }
+
+        // And when the default pixel flips
+        let mut flashing = bitmap.clone();
+        flashing[0] = true;
+        flashing[511] = false;
+        for n in [1, 2, 3] {
+            let iterated = image.iterate_n(&flashing, n);
+            assert_eq!(
+                iterate_n_hash_set(&image, &flashing, n),
+                (iterated.lit_pixels(), iterated.default_pixel)
+            );
+        }
+    }
+
+    #[test]
+    fn can_count_infinite_pixels() {
+        let (_, image) = parse_input(&sample_input());
+        let bitmap = |first: bool, last: bool| {
+            let mut bitmap = vec![false; 512];
+            bitmap[0] = first;
+            bitmap[511] = last;
+            bitmap
+        };
+
+        assert_eq!(image.count_pixels(), PixelCount::Finite(10));
+
+        // The background stays dark
+        let dark = bitmap(false, false);
+        assert_eq!(image.iterate(&dark).count_pixels(), PixelCount::Finite(0));
+        assert_eq!(image.iterate_n(&dark, 2).count_pixels(), PixelCount::Finite(0));
+
+        // The background is never lit, so the last bit isn't used
+        let unused = bitmap(false, true);
+        assert_eq!(image.iterate(&unused).count_pixels(), PixelCount::Finite(0));
+        assert_eq!(image.iterate_n(&unused, 2).count_pixels(), PixelCount::Finite(0));
+
+        // The background flashes, and is only dark after an even number of iterations
+        let flashing = bitmap(true, false);
+        assert_eq!(image.iterate(&flashing).count_pixels(), PixelCount::Infinite);
+        assert!(matches!(
+            image.iterate_n(&flashing, 2).count_pixels(),
+            PixelCount::Finite(_)
+        ));
+
+        // Once lit, the background stays lit
+        let lit = bitmap(true, true);
+        assert_eq!(image.iterate(&lit).count_pixels(), PixelCount::Infinite);
+        assert_eq!(image.iterate_n(&lit, 2).count_pixels(), PixelCount::Infinite);
+
+        assert_eq!(PixelCount::Finite(35).to_string(), "35");
+        assert_eq!(PixelCount::Infinite.to_string(), "infinite");
+    }
+
+    #[test]
+    fn can_normalise() {
+        let mut lines = ".....\n.#...\n..#..\n.....".lines();
+        let image = Image::from((&mut lines, 3, -2));
+        let (normalised, offset) = image.normalise();
+
+        assert_eq!(offset, (4, -1));
+        assert_eq!(normalised.lit_pixels(), HashSet::from([(0, 0), (1, 1)]));
+        assert_eq!((normalised.max_x, normalised.max_y), (1, 1));
+        assert_eq!(normalised.translate(offset).lit_pixels(), image.lit_pixels());
+    }
+
+    #[test]
+    fn can_iterate_with_cache() {
+        let (bitmap, image) = parse_input(&sample_input());
+        let mut cache = IterationCache::new(&bitmap);
+
+        assert_eq!(cache.iterate_n(&image, 2).lit_pixels().len(), 35);
+        assert_eq!((cache.hits, cache.misses), (0, 2));
+
+        // A bitmap that moves every pixel one to the right, so the content repeats every iteration
+        let shift_right: Vec<bool> = (0..512).map(|i| (i >> 5) & 1 == 1).collect();
+        let mut lines = "#.\n.#".lines();
+        let image = Image::from((&mut lines, 0, 0));
+        let mut cache = IterationCache::new(&shift_right);
+
+        let shifted = cache.iterate_n(&image, 10);
+        assert_eq!(shifted.lit_pixels(), HashSet::from([(10, 0), (11, 1)]));
+        assert_eq!(shifted.lit_pixels(), image.iterate_n(&shift_right, 10).lit_pixels());
+        assert_eq!((cache.hits, cache.misses), (9, 1));
+        assert_eq!(cache.hit_rate(), 0.9);
+    }
+
+    #[test]
+    fn can_render() {
+        let (_, image) = parse_input(&sample_input());
+
+        assert_eq!(image.render(), "#..#.\n#....\n##..#\n..#..\n..###");
+    }
+
+    #[test]
+    fn can_convert_to_bitmap() {
+        let (_, image) = parse_input(&sample_input());
+        let bitmap = Bitmap::from(&image.translate((-2, 3)));
+
+        assert_eq!(bitmap.width, 5);
+        assert_eq!(bitmap.height, 5);
+        assert_eq!(
+            String::from_utf8(bitmap.to_pbm()).unwrap(),
+            "P1\n5 5\n1 0 0 1 0\n1 0 0 0 0\n1 1 0 0 1\n0 0 1 0 0\n0 0 1 1 1\n"
+        );
+    }
+
+    #[test]
+    fn enhanced_images_match_golden_images() {
+        let (bitmap, image) = parse_input(&sample_input());
+
+        for iterations in [1, 2, 50] {
+            let enhanced = image.iterate_n(&bitmap, iterations);
+            assert_golden(
+                &format!("day-20-sample-{}", iterations),
+                &Bitmap::from(&enhanced),
+            );
+        }
+    }
+
+    #[test]
+    fn can_build_options() {
+        let params = Params::parse(
+            ["--param=part_one_iterations=4", "--param=use_cache=true"]
+                .map(String::from)
+                .into_iter(),
+        );
+        assert_eq!(
+            Options::from(&params),
+            Options {
+                part_one_iterations: 4,
+                part_two_iterations: 50,
+                use_cache: true,
+            }
+        );
+    }
+}
@@ -0,0 +1,561 @@
+//! This is my solution for [Advent of Code - Day 21 - _Dirac Dice_](https://adventofcode.com/2021/day/21)
+//!
+//! Today was the first time I had to pretty much write entirely new code for part two, but looking
+//! at what I ended up with I can't see much overlap that could be reused.
+//!
+//! For part one I just modelled the [`Game`] and [`Player`]s, with [`Game::try_from`] that parses
+//! the input, and [`Game::play`] that runs the game until someone wins, returning the values needed
+//! for the puzzle solution. Parsing checks each line with [`parse_player`], and returns a
+//! [`ParseError`] rather than guessing at input that isn't in the expected format. Any number of
+//! players is supported, they take turns in the order they are listed.
+//!
+//! For part two, I ended up with a rehash of the optimisations used for [`super::day_6`] and
+//! [`super::day_14`], where I track the counts of each game state, rather than calculating them
+//! individually. This is implemented in [`play_quantum`], which now shares the counting with those
+//! days through [`CountMap`].
+//!
+//! Both games can be played with a different [`Dice`], changing the number of faces and how many
+//! times it is rolled each turn, see [`Options`]. The quantum game works out how many ways each
+//! total can be rolled with [`roll_counts`].
+
+use crate::errors::{check_input, check_lines, to_validation, Validation};
+use crate::util::params::Params;
+use crate::util::state_counts::{Count, CountMap};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+/// The score needed to win each game, can be changed with `--param deterministic_target=<n>` and
+/// `--param quantum_target=<n>`. The dice can be changed with `deterministic_faces`,
+/// `deterministic_rolls`, `quantum_faces`, and `quantum_rolls`, which are at least 1.
+#[derive(Eq, PartialEq, Debug)]
+struct Options {
+    deterministic_target: usize,
+    quantum_target: usize,
+    deterministic_dice: Dice,
+    quantum_dice: Dice,
+}
+
+impl From<&Params> for Options {
+    fn from(params: &Params) -> Self {
+        let dice = |name: &str, default: Dice| Dice {
+            faces: params
+                .get_or(&format!("{}_faces", name), default.faces)
+                .max(1),
+            rolls: params
+                .get_or(&format!("{}_rolls", name), default.rolls)
+                .max(1),
+        };
+
+        Options {
+            deterministic_target: params.get_or("deterministic_target", 1000),
+            quantum_target: params.get_or("quantum_target", 21),
+            deterministic_dice: dice("deterministic", Dice::DETERMINISTIC),
+            quantum_dice: dice("quantum", Dice::QUANTUM),
+        }
+    }
+}
+
+/// The die used in a game, and how many times it is rolled each turn
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+struct Dice {
+    /// The die has faces numbered `1..=faces`
+    faces: usize,
+    /// The number of times the die is rolled each turn, the player moves the total
+    rolls: usize,
+}
+
+impl Dice {
+    /// The puzzle's deterministic d100, rolled three times each turn
+    const DETERMINISTIC: Dice = Dice {
+        faces: 100,
+        rolls: 3,
+    };
+    /// The puzzle's quantum d3, rolled three times each turn
+    const QUANTUM: Dice = Dice { faces: 3, rolls: 3 };
+}
+
+/// A player in the dice game, tracks their current score and the position of their pawn
+#[derive(Eq, PartialEq, Debug, Hash, Clone, Copy)]
+struct Player {
+    /// Position of the player's pawn
+    position: usize,
+    /// Players current total score
+    score: usize,
+}
+
+/// The ways the starting positions can fail to parse
+#[derive(Eq, PartialEq, Debug)]
+pub enum ParseError {
+    /// A line that isn't in the format `Player <n> starting position: <p>`
+    InvalidLine(String),
+    /// A player's starting position is not on the board, i.e. not in `1..=10`
+    PositionOutOfRange { player: usize, position: usize },
+    /// Two lines have the same player number
+    DuplicatePlayer(usize),
+    /// There were no players listed
+    NoPlayers,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidLine(line) => write!(f, "invalid starting position \"{}\"", line),
+            ParseError::PositionOutOfRange { player, position } => write!(
+                f,
+                "player {} starts at {}, which is not in 1..=10",
+                player, position
+            ),
+            ParseError::DuplicatePlayer(player) => write!(f, "player {} is listed twice", player),
+            ParseError::NoPlayers => write!(f, "there are no players"),
+        }
+    }
+}
+
+/// Players are listed in the input as "Player n starting position: p". Returns the player number
+/// `n`, and the [`Player`] at position `p`.
+fn parse_player(line: &str) -> Result<(usize, Player), ParseError> {
+    let invalid = || ParseError::InvalidLine(line.to_string());
+
+    let (label, position) = line
+        .trim()
+        .split_once(" starting position: ")
+        .ok_or_else(invalid)?;
+    let number: usize = label
+        .strip_prefix("Player ")
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(invalid)?;
+    let position: usize = position.parse().map_err(|_| invalid())?;
+
+    if !(1..=10).contains(&position) {
+        return Err(ParseError::PositionOutOfRange {
+            player: number,
+            position,
+        });
+    }
+
+    Ok((number, Player { position, score: 0 }))
+}
+
+/// Represents a deterministic game of Dirac dice, tracking the current value of the deterministic
+/// die, the players, whose turn it is, and how many rolls have occurred.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Game {
+    /// List of the players of the game
+    players: Vec<Player>,
+    /// The index of the player that will take the next turn
+    current_player: usize,
+    /// The die being rolled, the puzzle uses [`Dice::DETERMINISTIC`]
+    dice: Dice,
+    /// The next face that the deterministic die will roll
+    next_die_face: usize,
+    /// how many times the die has been rolled so far
+    rolls: usize,
+}
+
+impl TryFrom<&str> for Game {
+    type Error = ParseError;
+
+    /// Pass the non-blank lines of the input to [`parse_player`] to turn it into the player list,
+    /// checking the player numbers are unique, and set the counters to their initial values.
+    fn try_from(str: &str) -> Result<Self, Self::Error> {
+        let mut numbers = HashSet::new();
+        let mut players = Vec::new();
+
+        for line in str.lines().filter(|line| !line.trim().is_empty()) {
+            let (number, player) = parse_player(line)?;
+            if !numbers.insert(number) {
+                return Err(ParseError::DuplicatePlayer(number));
+            }
+            players.push(player);
+        }
+
+        if players.is_empty() {
+            return Err(ParseError::NoPlayers);
+        }
+
+        Ok(Game {
+            players,
+            current_player: 0,
+            dice: Dice::DETERMINISTIC,
+            next_die_face: 1,
+            rolls: 0,
+        })
+    }
+}
+
+impl Game {
+    /// Play with a different die to the puzzle's d100
+    fn with_dice(self, dice: Dice) -> Game {
+        Game { dice, ..self }
+    }
+
+    /// Get the next `number` rolls from the game's deterministic die, and update the next facing
+    /// and roll count. The die goes back to 1 after its highest face.
+    fn roll(&mut self, number: usize) -> Vec<usize> {
+        self.rolls += number;
+
+        (0..number)
+            .map(|_| {
+                let face = self.next_die_face;
+                self.next_die_face = face % self.dice.faces + 1;
+                face
+            })
+            .collect()
+    }
+
+    /// Play the game until a player reaches `target_score` returning the score of the loser at that
+    /// point, and the number of rolls made. With more than two players the loser is the one with
+    /// the lowest score.
+    fn play(&mut self, target_score: usize) -> (usize, usize) {
+        loop {
+            // Roll the dice and sum them
+            let spaces: usize = self.roll(self.dice.rolls).iter().sum();
+            let current_player = self.current_player;
+            let player = self.players.get_mut(current_player).unwrap();
+            // Move the pawn a number of spaces determined by the roll
+            player.position = (player.position + spaces) % 10;
+            // Positions are 1..10 so the 0 space needs special handling
+            if player.position == 0 {
+                player.score += 10
+            } else {
+                player.score += player.position as usize
+            }
+            // Check if the player wins
+            if player.score >= target_score {
+                // If so return the results needed
+                let losing_score = self
+                    .players
+                    .iter()
+                    .enumerate()
+                    .filter(|&(index, _)| index != current_player)
+                    .map(|(_, player)| player.score)
+                    .min()
+                    .unwrap_or(0);
+                return (losing_score, self.rolls);
+            }
+
+            // Otherwise, next player's turn
+            self.current_player = (current_player + 1) % self.players.len();
+        }
+    }
+}
+
+/// Check each line is a player's starting position, see [`validate`], and parse them as the
+/// [`Game`] for [`part_one`] and [`part_two`] to play.
+pub fn parse(contents: &str) -> Game {
+    check_input(21, contents, validate);
+    Game::try_from(contents).unwrap_or_else(|err| panic!("Invalid starting positions: {}", err))
+}
+
+/// Play with the deterministic die, see [`Game::play`]. The target score and the die can be
+/// changed, see [`Options`].
+pub fn part_one(game: &Game) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    let (score, rolls) = game
+        .clone()
+        .with_dice(options.deterministic_dice)
+        .play(options.deterministic_target);
+    say!(
+        "The loser scored {} after {} deterministic rolls = {}",
+        score,
+        rolls,
+        score * rolls
+    );
+
+    Some((score * rolls).to_string())
+}
+
+/// Play with the quantum die, see [`play_quantum`]. The target score and the die can be changed,
+/// see [`Options`].
+pub fn part_two(game: &Game) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    let most_wins = play_quantum(
+        game.players.clone(),
+        options.quantum_target,
+        options.quantum_dice,
+    );
+    say!("The player with more quantum wins won {} times", most_wins);
+
+    Some(most_wins.to_string())
+}
+
+/// Each non-blank line should be a different player's starting position, see [`parse_player`]
+pub fn validate(input: &str) -> Validation {
+    let mut numbers = HashSet::new();
+    to_validation(check_lines(input, |_, line| {
+        if line.trim().is_empty() {
+            return None;
+        }
+
+        match parse_player(line) {
+            Ok((number, _)) if !numbers.insert(number) => {
+                Some(ParseError::DuplicatePlayer(number).to_string())
+            }
+            Ok(_) => None,
+            Err(err) => Some(err.to_string()),
+        }
+    }))
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input lists players, so it is enough for
+/// it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// The number of ways each total can be rolled with the dice, e.g. a d3 rolled three times can
+/// total 3 one way, `1 + 1 + 1`, and 4 three ways, `1 + 1 + 2`, `1 + 2 + 1`, and `2 + 1 + 1`.
+fn roll_counts(dice: Dice) -> HashMap<usize, usize> {
+    (0..dice.rolls)
+        .map(|_| 1..=dice.faces)
+        .multi_cartesian_product()
+        .map(|faces| faces.iter().sum())
+        .counts()
+}
+
+/// Calculate the permutations of possible games with a quantum die. Determine which player wins the
+/// most times, and return the count of their wins.
+fn play_quantum(players: Vec<Player>, target_score: usize, dice: Dice) -> Count {
+    // initialise the rest of the counters
+    let mut wins = vec![Count::default(); players.len()];
+    let mut current_player_index: usize = 0;
+
+    // Seed the map of game states with the single starting position. The players in each state are
+    // rotated each turn so that the player whose turn it is is always first.
+    let mut games: CountMap<Vec<Player>> = [players].into_iter().collect();
+    // Pre-calculate the number of rolls that give each possible sum
+    let roll_counts = roll_counts(dice);
+
+    // Once all permutations have found a winner the map will be empty
+    while !games.is_empty() {
+        // For each current game state and possible dice roll sum, the number of games that reach
+        // the new game state is the number of games in the current game state multiplied by the
+        // number of times the current sum will be rolled.
+        games = games.step(|players| {
+            roll_counts
+                .iter()
+                .map(|(&roll, &roll_count)| {
+                    let Player { position, score } = players[0];
+                    // Work out the new position and score for the current game state/roll pair
+                    let new_position = (position + roll) % 10;
+                    let new_score = if new_position == 0 { 10 } else { new_position } + score;
+                    let moved_player = Player {
+                        position: new_position,
+                        score: new_score,
+                    };
+                    // Move the current player to the back, so that the next player is first
+                    let mut rotated = players[1..].to_vec();
+                    rotated.push(moved_player);
+                    (rotated, roll_count)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        // The games where the player that just moved reached the target are won by that player,
+        // and don't need to be played any further
+        wins[current_player_index] += games.remove_where(|players| {
+            players
+                .last()
+                .is_some_and(|moved_player| moved_player.score >= target_score)
+        });
+
+        current_player_index = (current_player_index + 1) % wins.len();
+    }
+
+    wins.into_iter().max().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::params::Params;
+    use crate::util::state_counts::to_count;
+    use crate::year_2021::day_21::{
+        parse_player, play_quantum, roll_counts, Dice, Game, Options, ParseError, Player,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn can_parse() {
+        let input = "Player 1 starting position: 4
+Player 2 starting position: 8"
+            .to_string();
+
+        let expected = Game {
+            players: Vec::from([
+                Player {
+                    position: 4,
+                    score: 0,
+                },
+                Player {
+                    position: 8,
+                    score: 0,
+                },
+            ]),
+            current_player: 0,
+            dice: Dice::DETERMINISTIC,
+            next_die_face: 1,
+            rolls: 0,
+        };
+
+        assert_eq!(Game::try_from(input.as_str()), Ok(expected));
+        assert_eq!(
+            parse_player("Player 12 starting position: 10"),
+            Ok((
+                12,
+                Player {
+                    position: 10,
+                    score: 0
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn can_report_invalid_input() {
+        assert_eq!(
+            parse_player("Player 1 starts at 4"),
+            Err(ParseError::InvalidLine("Player 1 starts at 4".to_string()))
+        );
+        assert_eq!(
+            parse_player("Player one starting position: 4"),
+            Err(ParseError::InvalidLine(
+                "Player one starting position: 4".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_player("Player 2 starting position: 11"),
+            Err(ParseError::PositionOutOfRange {
+                player: 2,
+                position: 11
+            })
+        );
+        assert_eq!(
+            Game::try_from("Player 1 starting position: 4\nPlayer 1 starting position: 8"),
+            Err(ParseError::DuplicatePlayer(1))
+        );
+        assert_eq!(Game::try_from("\n"), Err(ParseError::NoPlayers));
+    }
+
+    #[test]
+    fn can_play_with_more_players() {
+        let input = "Player 1 starting position: 4
+Player 2 starting position: 8
+Player 3 starting position: 1
+";
+        let mut game = Game::try_from(input).unwrap();
+        let players = game.players.clone();
+
+        assert_eq!(game.play(1000), (855, 1380));
+        assert_eq!(
+            play_quantum(players, 10, Dice::QUANTUM),
+            to_count(4095622876)
+        );
+    }
+
+    #[test]
+    fn can_play() {
+        let mut game = Game {
+            players: Vec::from([
+                Player {
+                    position: 4,
+                    score: 0,
+                },
+                Player {
+                    position: 8,
+                    score: 0,
+                },
+            ]),
+            current_player: 0,
+            dice: Dice::DETERMINISTIC,
+            next_die_face: 1,
+            rolls: 0,
+        };
+
+        assert_eq!(game.play(1000), (745, 993))
+    }
+
+    #[test]
+    fn can_play_quantum() {
+        let players = Vec::from([
+            Player {
+                position: 4,
+                score: 0,
+            },
+            Player {
+                position: 8,
+                score: 0,
+            },
+        ]);
+
+        assert_eq!(
+            play_quantum(players, 21, Dice::QUANTUM),
+            to_count(444356092776315)
+        )
+    }
+
+    #[test]
+    fn can_build_options() {
+        let params = Params::parse(
+            [
+                "--param",
+                "quantum_target=10",
+                "--param",
+                "deterministic_target=x",
+                "--param",
+                "deterministic_faces=6",
+                "--param",
+                "quantum_rolls=0",
+            ]
+            .map(String::from)
+            .into_iter(),
+        );
+        assert_eq!(
+            Options::from(&params),
+            Options {
+                deterministic_target: 1000,
+                quantum_target: 10,
+                deterministic_dice: Dice { faces: 6, rolls: 3 },
+                quantum_dice: Dice { faces: 3, rolls: 1 },
+            }
+        );
+    }
+
+    #[test]
+    fn can_count_rolls() {
+        assert_eq!(
+            roll_counts(Dice::QUANTUM),
+            HashMap::from([(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)])
+        );
+        assert_eq!(
+            roll_counts(Dice { faces: 2, rolls: 2 }),
+            HashMap::from([(2, 1), (3, 2), (4, 1)])
+        );
+        assert_eq!(
+            roll_counts(Dice { faces: 4, rolls: 1 }),
+            HashMap::from([(1, 1), (2, 1), (3, 1), (4, 1)])
+        );
+    }
+
+    #[test]
+    fn can_play_with_other_dice() {
+        let input = "Player 1 starting position: 4\nPlayer 2 starting position: 8\n";
+
+        let mut game = Game::try_from(input)
+            .unwrap()
+            .with_dice(Dice { faces: 6, rolls: 3 });
+        assert_eq!(game.roll(4), vec![1, 2, 3, 4]);
+        assert_eq!(game.roll(4), vec![5, 6, 1, 2]);
+
+        let mut game = Game::try_from(input)
+            .unwrap()
+            .with_dice(Dice { faces: 6, rolls: 3 });
+        assert_eq!(game.play(20), (14, 21));
+
+        let players = Game::try_from(input).unwrap().players;
+        assert_eq!(
+            play_quantum(players, 10, Dice { faces: 2, rolls: 2 }),
+            to_count(228)
+        );
+    }
+}
@@ -0,0 +1,1306 @@
+//! This is my solution for [Advent of Code - Day 22 - _Reactor
+//! Reboot_](https://adventofcode.com/2021/day/22)
+//!
+//! Today was about toggling cubes of cells in a 3d grid. Part one limited this to a cubic volume
+//! 101 units³, but the puzzle input and description was very heavily hinting that part two was the
+//! same, but using all the data, making it vastly bigger. The program would take a long time to
+//! flip all the required bits, and likely run out of memory trying to represent each cell, so I was
+//! looking for some form of optimisation. I though about tracking for a cube which sections had
+//! been taken out / added in but that got pretty convoluted to track which cube was responsible for
+//! tracking each bit.
+//!
+//! This meant I needed a solution that guaranteed each cell was covered by
+//! exactly one cuboid. So I decided upon when a new cube intersects with existing active (on)
+//! cubes, explode the old cubes into up to 6 pieces, (slice of x on both sides, then y on both
+//! sides, then z on both sides), so that none of them intersect with the new cube or each other.
+//! Then if the new cube is on, also add it to the list of cubes.
+//!
+//! This could still get to a very large list of cubes, but in reality most cubes will only cause a
+//! small number to explode. The puzzle input in full mode ends up as ~3.5k cubes. The solution then
+//! becomes bounded by the relatively small number of instructions, and is independent of the
+//! grid-size, which is key to it running small and fast enough.
+//!
+//! [`Cuboid`] is used to track each cuboid by its corners, which are [`Vec3`]s shared with
+//! [`super::day_19`], and [`Instruction`] wraps a cuboid and whether it flips its contents to on or
+//! off. [`Instruction::try_from`] parses a line of input, and [`parse_input`] uses this to build
+//! the whole instruction list, returning a [`ParseError`] for a malformed line rather than
+//! panicking, so that the fuzz target in `fuzz/` can give it arbitrary input. [`volume_active`] is
+//! the entry point into the solution for both parts. It folds each instruction into a set of 'on'
+//! cubes calling [`merge_instruction`] to build each iteration from the previous iteration and the
+//! next instruction. The set is a [`CuboidSet`], which keeps its cubes disjoint by using
+//! [`Cuboid::diff_and_split`] on each existing cube whenever a cube is added or subtracted. It also
+//! has unions, bounding boxes, and the volume of its overlap with a cube, so can be used for other
+//! 3D geometry than the puzzle's.
+//!
+//! For part one, the instruction list is first filtered by [`limit_instructions`] to only
+//! the instructions with cuboids (or partial cuboids) that fit in [`initialisation_limit`]. For
+//! part two, the unaltered instruction set is used. Both [`Cuboid::diff_and_split`] and
+//! [`limit_instructions`] use [`Cuboid::intersect`] which returns the cuboid region where both
+//! overlap, or `None` if they are disjoint.
+//!
+//! Splitting can fragment badly when lots of thin cuboids cross each other, so there is also
+//! [`volume_active_compressed`], which instead splits space along every x boundary, and then
+//! compresses each slab to a grid of the y and z boundaries. This was slower on everything shaped
+//! like the puzzle input, but much faster once fragmentation takes off, so [`volume_active_auto`]
+//! starts splitting and switches over if the cuboid count gets too large for the number of
+//! instructions. Passing `--compare` compares all three on the full input.
+//!
+//! [`ReactorSession`] was added afterwards to allow exploring the instructions interactively. It
+//! applies instructions one at a time, with undo and redo, and keeps a snapshot of the cuboids
+//! every few instructions so that stepping back, or asking what the volume would be without one of
+//! the instructions, only replays the instructions since the nearest snapshot. Passing `--repl`
+//! steps through the puzzle input with one, see [`Reboot`].
+
+use crate::errors::{check_input, check_lines, to_validation, Validation};
+use crate::solution::{Alternative, Part};
+use crate::util::render::{FrameSource, Renderable};
+use crate::util::repl::{repl_enabled, run_repl, Steppable};
+use crate::util::vec3::Vec3;
+use regex::Regex;
+use std::fmt::{Display, Formatter};
+
+/// Represents a cuboid as its lowest and highest corners, see [`Vec3`]. Both are inclusive.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Cuboid {
+    min: Vec3,
+    max: Vec3,
+}
+
+/// The initialisation phase (part_one) is limited to a cube 50 units from the origin on all axes.
+fn initialisation_limit() -> Cuboid {
+    Cuboid::new(-50, 50, -50, 50, -50, 50)
+}
+
+/// Represents a line of input as the [`Cuboid`] region it intersects, and whether it toggles its
+/// contents on or off.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Instruction {
+    is_on: bool,
+    cuboid: Cuboid,
+}
+
+/// The ways a line can fail to parse as an [`Instruction`]
+#[derive(Eq, PartialEq, Debug)]
+pub enum ParseError {
+    /// A line that isn't in the format `on|off x=<n>..<n>,y=<n>..<n>,z=<n>..<n>`
+    InvalidLine(String),
+    /// A range with its upper bound below its lower bound
+    BackwardsRange { axis: char, min: isize, max: isize },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidLine(line) => write!(f, "invalid instruction \"{}\"", line),
+            ParseError::BackwardsRange { axis, min, max } => {
+                write!(f, "the {} range {}..{} is backwards", axis, min, max)
+            }
+        }
+    }
+}
+
+impl Display for Instruction {
+    /// Format the instruction as it appears in the puzzle input
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Cuboid { min, max } = self.cuboid;
+        write!(
+            f,
+            "{} x={}..{},y={}..{},z={}..{}",
+            if self.is_on { "on" } else { "off" },
+            min.x,
+            max.x,
+            min.y,
+            max.y,
+            min.z,
+            max.z
+        )
+    }
+}
+
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
+
+    /// Parse a line of the puzzle input as an [`Instruction`]
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let invalid = || ParseError::InvalidLine(line.to_string());
+
+        let (on_off, coords) = line.split_once(' ').ok_or_else(invalid)?;
+        let is_on = match on_off {
+            "on" => true,
+            "off" => false,
+            _ => return Err(invalid()),
+        };
+
+        let ranges: Vec<&str> = coords.split(',').collect();
+        if ranges.len() != 3 {
+            return Err(invalid());
+        }
+        let mut bounds = [0; 6];
+        for (i, (range, axis)) in ranges.iter().zip(['x', 'y', 'z']).enumerate() {
+            let (min, max) = range
+                .strip_prefix(axis)
+                .and_then(|range| range.strip_prefix('='))
+                .and_then(|range| range.split_once(".."))
+                .ok_or_else(invalid)?;
+            let min: isize = min.parse().map_err(|_| invalid())?;
+            let max: isize = max.parse().map_err(|_| invalid())?;
+            if min > max {
+                return Err(ParseError::BackwardsRange { axis, min, max });
+            }
+            bounds[i * 2] = min;
+            bounds[i * 2 + 1] = max;
+        }
+
+        Ok(Instruction {
+            is_on,
+            cuboid: Cuboid::new(
+                bounds[0], bounds[1], bounds[2], bounds[3], bounds[4], bounds[5],
+            ),
+        })
+    }
+}
+
+impl Instruction {
+    /// Utility for creating expected outcomes when testing
+    #[cfg(test)]
+    fn new(
+        is_on: bool,
+        x_min: isize,
+        x_max: isize,
+        y_min: isize,
+        y_max: isize,
+        z_min: isize,
+        z_max: isize,
+    ) -> Instruction {
+        Instruction {
+            is_on,
+            cuboid: Cuboid::new(x_min, x_max, y_min, y_max, z_min, z_max),
+        }
+    }
+}
+
+impl Cuboid {
+    /// Utility so that a Cuboid can be created on a single line (struct literals are always
+    /// split into multilines by rust-fmt)
+    pub fn new(
+        x_min: isize,
+        x_max: isize,
+        y_min: isize,
+        y_max: isize,
+        z_min: isize,
+        z_max: isize,
+    ) -> Cuboid {
+        Cuboid {
+            min: Vec3::new(x_min, y_min, z_min),
+            max: Vec3::new(x_max, y_max, z_max),
+        }
+    }
+
+    /// Return the [`Cuboid`] region where this and another [`Cuboid`] overlap, if they do.
+    pub fn intersect(&self, other: &Cuboid) -> Option<Cuboid> {
+        // For each axis take the largest minimum, and the smallest maximum as the intersection
+        let min = self.min.component_max(&other.min);
+        let max = self.max.component_min(&other.max);
+
+        // If all three axes have at least some overlap - there is an intersection
+        if min.x <= max.x && min.y <= max.y && min.z <= max.z {
+            Some(Cuboid { min, max })
+        } else {
+            None
+        }
+    }
+
+    /// If there is an overlap with the incoming [`Cuboid`], return a list of [`Cuboid`] slices that
+    /// do not that do not intersect, if any. If there is no intersection return this cube,
+    /// unaffected.
+    ///
+    /// E.g. in 2D do the following
+    /// ```text
+    /// -----------------          self.dff_and_split(&other);
+    /// | self          |
+    /// |   ---------   |
+    /// |   | other |   |
+    /// |   ---------   |
+    /// |               |
+    /// -----------------
+    ///
+    /// ----| |-------| |----      First split on the x axis creating rectangles  
+    /// | l | |       | | r |      left and right with their x bounds set to just
+    /// | e | |-------| | i |      outside the intersecting region.
+    /// | f | | other | | g |
+    /// | t | |-------| | h |
+    /// |   | |       | | t |
+    /// ----| |-------| |----
+    ///
+    ///       ----------
+    ///       |  top   |
+    /// ----| ---------- |----     Then split on the y-axis creating rectangles   
+    /// | l |            | r |     top and bottom. Their y bounds set to just
+    /// | e | ---------- | i |     outside the intersecting region, but their x
+    /// | f | | other  | | g |     bounds are the same as the intersecting region's
+    /// | t | ---------- | h |     so that they don't overlap left and right at the
+    /// |   |            | t |     corners
+    /// ----| ---------- |----
+    ///       | bottom |
+    ///       ----------
+    ///
+    /// ----| ---------- |----      Just return the slices from self. Other will
+    /// | l | |  top   | | r |      only be added to the next iteration if it is
+    /// | e | ---------- | i |      'on', which is done elsewhere.
+    /// | f |            | g |      
+    /// | t | ---------- | h |
+    /// |   | | bottom | | t |
+    /// ----| ---------- |----
+    ///
+    /// Note: Not all slices always get returned e.g. a corner intersect
+    /// just creates two slices.
+    ///
+    ///                                        -----            
+    ///                                        |top|            
+    /// ---------|      ----| |----      ----| -----      ----| -----                   
+    /// |  self  |      | L | |   |      | L |            | L | |top|
+    /// |   -------- => |   | |------ => |   | ------- => |   | -----
+    /// ----|other |    |---| |other|    ----| |other|    ----|
+    ///     --------          |------          |------         
+    /// ```
+    /// The above is expanded to three dimensions, but the logic is the same.
+    fn diff_and_split(&self, other: &Cuboid) -> Vec<Cuboid> {
+        // Is there an intersection?
+        match self.intersect(other) {
+            Some(diff) => {
+                let mut splits = Vec::new();
+                // lower x-axis slice
+                if diff.min.x > self.min.x {
+                    splits.push(Cuboid::new(
+                        self.min.x,
+                        diff.min.x - 1,
+                        self.min.y,
+                        self.max.y,
+                        self.min.z,
+                        self.max.z,
+                    ))
+                }
+                // upper x-axis slice
+                if diff.max.x < self.max.x {
+                    splits.push(Cuboid::new(
+                        diff.max.x + 1,
+                        self.max.x,
+                        self.min.y,
+                        self.max.y,
+                        self.min.z,
+                        self.max.z,
+                    ))
+                }
+                // lower y-axis slice
+                if diff.min.y > self.min.y {
+                    splits.push(Cuboid::new(
+                        diff.min.x,
+                        diff.max.x,
+                        self.min.y,
+                        diff.min.y - 1,
+                        self.min.z,
+                        self.max.z,
+                    ))
+                }
+                // upper y-axis slice
+                if diff.max.y < self.max.y {
+                    splits.push(Cuboid::new(
+                        diff.min.x,
+                        diff.max.x,
+                        diff.max.y + 1,
+                        self.max.y,
+                        self.min.z,
+                        self.max.z,
+                    ))
+                }
+                // lower z-axis slice
+                if diff.min.z > self.min.z {
+                    splits.push(Cuboid::new(
+                        diff.min.x,
+                        diff.max.x,
+                        diff.min.y,
+                        diff.max.y,
+                        self.min.z,
+                        diff.min.z - 1,
+                    ))
+                }
+                // upper z-axis slice
+                if diff.max.z < self.max.z {
+                    splits.push(Cuboid::new(
+                        diff.min.x,
+                        diff.max.x,
+                        diff.min.y,
+                        diff.max.y,
+                        diff.max.z + 1,
+                        self.max.z,
+                    ))
+                }
+                // could be empty if other cuboid covers the entirety of this cuboids region.
+                splits
+            }
+            // No intersection, this cuboid remains covering the same region
+            None => Vec::from([self.clone()]),
+        }
+    }
+
+    /// Calculates the volume of this [`Cuboid`]. Note the +1s because both limits are inclusive.
+    pub fn volume(&self) -> isize {
+        let Vec3 { x, y, z } = self.max - self.min + Vec3::new(1, 1, 1);
+        x * y * z
+    }
+}
+
+/// A set of cells in 3D space, stored as a list of [`Cuboid`]s that never overlap, so the volume is
+/// just the sum of theirs. Adding or subtracting a cuboid uses [`Cuboid::diff_and_split`] to cut
+/// the region out of the existing cuboids, so the set can fragment into many more cuboids than were
+/// added.
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct CuboidSet {
+    cuboids: Vec<Cuboid>,
+}
+
+impl CuboidSet {
+    /// Create an empty set
+    pub fn new() -> CuboidSet {
+        CuboidSet::default()
+    }
+
+    /// Add all the cells in `cuboid` to the set
+    pub fn add(&mut self, cuboid: Cuboid) {
+        self.subtract(&cuboid);
+        self.cuboids.push(cuboid);
+    }
+
+    /// Remove all the cells in `cuboid` from the set
+    pub fn subtract(&mut self, cuboid: &Cuboid) {
+        self.cuboids = self
+            .cuboids
+            .iter()
+            .flat_map(|existing| existing.diff_and_split(cuboid))
+            .collect();
+    }
+
+    /// Add all the cells in `other` to the set
+    pub fn union(&mut self, other: &CuboidSet) {
+        for &cuboid in &other.cuboids {
+            self.add(cuboid);
+        }
+    }
+
+    /// Turn the cells in the instruction's cuboid on or off
+    fn apply(&mut self, instruction: &Instruction) {
+        if instruction.is_on {
+            self.add(instruction.cuboid);
+        } else {
+            self.subtract(&instruction.cuboid);
+        }
+    }
+
+    /// The number of cells in the set
+    pub fn volume(&self) -> isize {
+        self.cuboids.iter().map(|c| c.volume()).sum()
+    }
+
+    /// The number of cells in the set that are also in `cuboid`
+    pub fn intersect_volume(&self, cuboid: &Cuboid) -> isize {
+        self.cuboids
+            .iter()
+            .flat_map(|existing| existing.intersect(cuboid))
+            .map(|overlap| overlap.volume())
+            .sum()
+    }
+
+    /// The smallest [`Cuboid`] that contains every cell in the set, or `None` if it is empty
+    pub fn bounding_box(&self) -> Option<Cuboid> {
+        self.cuboids.iter().copied().reduce(|a, b| Cuboid {
+            min: a.min.component_min(&b.min),
+            max: a.max.component_max(&b.max),
+        })
+    }
+
+    /// The disjoint cuboids that make up the set, in no particular order
+    pub fn cuboids(&self) -> &[Cuboid] {
+        &self.cuboids
+    }
+
+    /// The number of disjoint cuboids the set is currently split into
+    pub fn len(&self) -> usize {
+        self.cuboids.len()
+    }
+
+    /// True if the set has no cells
+    pub fn is_empty(&self) -> bool {
+        self.cuboids.is_empty()
+    }
+}
+
+/// Check each line turns a cuboid on or off, see [`validate`], and parse them as the reboot steps
+/// for [`part_one`] and [`part_two`].
+pub fn parse(contents: &str) -> Vec<Instruction> {
+    check_input(22, contents, validate);
+    parse_input(contents).unwrap_or_else(|err| panic!("Invalid instructions: {}", err))
+}
+
+/// Count the cubes left on by the instructions within the initialisation region
+pub fn part_one(instructions: &[Instruction]) -> Option<String> {
+    let part_one_instructions = limit_instructions(instructions, initialisation_limit());
+    let initialisation_volume = volume_active_auto(&part_one_instructions);
+    say!(
+        "There are {} cubes active in the initialisation procedure",
+        initialisation_volume
+    );
+
+    Some(initialisation_volume.to_string())
+}
+
+/// Count the cubes left on by all the instructions.
+///
+/// - Pass `--compare` to compare the timings of the approaches to counting the volume, see
+///   [`alternatives`].
+/// - Pass `--repl` to apply and undo the instructions interactively, see [`crate::util::repl`].
+pub fn part_two(instructions: &[Instruction]) -> Option<String> {
+    if repl_enabled() {
+        run_repl(&Reboot {
+            instructions,
+            session: ReactorSession::new(REPL_SNAPSHOT_INTERVAL),
+        });
+    }
+
+    let full_volume = volume_active_auto(instructions);
+    say!("There are {} cubes active in the full reactor", full_volume);
+
+    Some(full_volume.to_string())
+}
+
+/// Each line should turn a cuboid on or off, with the lower bound of each range first
+pub fn validate(input: &str) -> Validation {
+    let matcher =
+        Regex::new(r"^(on|off) x=(-?\d+)\.\.(-?\d+),y=(-?\d+)\.\.(-?\d+),z=(-?\d+)\.\.(-?\d+)$")
+            .unwrap();
+
+    to_validation(check_lines(input, |_, line| match matcher.captures(line) {
+        None => Some(format!(
+            "Expected 'on|off x=<n>..<n>,y=<n>..<n>,z=<n>..<n>', found '{}'",
+            line
+        )),
+        Some(cap) => ["x", "y", "z"].iter().enumerate().find_map(|(i, axis)| {
+            let min: isize = cap[i * 2 + 2].parse().unwrap();
+            let max: isize = cap[i * 2 + 3].parse().unwrap();
+            if min > max {
+                Some(format!("The {} range {}..{} is backwards", axis, min, max))
+            } else {
+                None
+            }
+        }),
+    }))
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input is a list of cuboids, so it is
+/// enough for it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// Parse the puzzle input as a list of instructions, stopping at the first line that fails
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input.lines().map(Instruction::try_from).collect()
+}
+
+/// Merge an instruction into the current set of active cuboids, returning the new set. The
+/// instruction's cuboid is removed from any cuboids it overlaps, then if it is itself on, it is
+/// added to mark that its entire region is now active.
+fn merge_instruction(instruction: Instruction, cuboids: &CuboidSet) -> CuboidSet {
+    let mut merged = cuboids.clone();
+    merged.apply(&instruction);
+
+    merged
+}
+
+/// Fold the list of instructions into a set of cuboids that describe the entire active area, then
+/// take its volume to get the total active volume.
+fn volume_active(instructions: &[Instruction]) -> isize {
+    instructions
+        .iter()
+        .fold(CuboidSet::new(), |acc, &inst| merge_instruction(inst, &acc))
+        .volume()
+}
+
+/// Past this many cuboids per instruction, [`volume_active`] is fragmenting badly enough that
+/// [`volume_active_compressed`] is faster. On generated inputs, ones shaped like the puzzle's stay
+/// under 10 per instruction, where compressing is 2-20x slower, and ones made of interleaved thin
+/// plates reach 20 by 30 instructions and keep climbing, where compressing ends up 10x faster.
+const MAX_CUBOIDS_PER_INSTRUCTION: usize = 50;
+
+/// Use whichever of [`volume_active`] and [`volume_active_compressed`] suits the instructions. The
+/// split approach is tried first, but abandoned for compression if the number of cuboids outgrows
+/// a budget based on the instruction count, see [`MAX_CUBOIDS_PER_INSTRUCTION`].
+fn volume_active_auto(instructions: &[Instruction]) -> isize {
+    let limit = MAX_CUBOIDS_PER_INSTRUCTION * instructions.len();
+    let mut cuboids = CuboidSet::new();
+
+    for instruction in instructions {
+        cuboids.apply(instruction);
+        if cuboids.len() > limit {
+            return volume_active_compressed(instructions);
+        }
+    }
+
+    cuboids.volume()
+}
+
+/// The ways of counting the volume for part two, to compare with `--compare`. Part two uses
+/// [`volume_active_auto`], which is one of the other two depending on the instructions.
+pub fn alternatives() -> Vec<Alternative<[Instruction]>> {
+    vec![
+        Alternative::new(Part::Two, "volume_active_auto", |instructions| {
+            Some(volume_active_auto(instructions).to_string())
+        }),
+        Alternative::new(Part::Two, "volume_active", |instructions| {
+            Some(volume_active(instructions).to_string())
+        }),
+        Alternative::new(Part::Two, "volume_active_compressed", |instructions| {
+            Some(volume_active_compressed(instructions).to_string())
+        }),
+    ]
+}
+
+/// The sorted, distinct boundaries of the instructions' cuboids along one axis. `axis` returns the
+/// inclusive range a cuboid covers, which is stored as its start and the start of the next range,
+/// so that the space between each pair of consecutive boundaries is either fully inside or fully
+/// outside each cuboid.
+fn boundaries<'a>(
+    instructions: impl Iterator<Item = &'a Instruction>,
+    axis: impl Fn(&Cuboid) -> (isize, isize),
+) -> Vec<isize> {
+    let mut bounds: Vec<isize> = instructions
+        .flat_map(|inst| {
+            let (min, max) = axis(&inst.cuboid);
+            [min, max + 1]
+        })
+        .collect();
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    bounds
+}
+
+/// An alternative to [`volume_active`] that doesn't fragment cuboids. The x boundaries of all the
+/// cuboids split the space into slabs, and within a slab each instruction either covers all of it
+/// or none of it, so the slab's volume is its width times the active area of the instructions that
+/// cover it, see [`area_active_compressed`]. The work depends only on the number of instructions,
+/// rather than how many pieces the cuboids have been split into.
+fn volume_active_compressed(instructions: &[Instruction]) -> isize {
+    boundaries(instructions.iter(), |c| (c.min.x, c.max.x))
+        .windows(2)
+        .map(|slab| {
+            let covering: Vec<&Instruction> = instructions
+                .iter()
+                .filter(|inst| inst.cuboid.min.x <= slab[0] && slab[0] <= inst.cuboid.max.x)
+                .collect();
+            (slab[1] - slab[0]) * area_active_compressed(&covering)
+        })
+        .sum()
+}
+
+/// The active area in the y/z plane of instructions that are known to cover a whole slab. The y and
+/// z boundaries compress the plane into a grid of rectangles, and applying each instruction in
+/// order to the rectangles it covers leaves each rectangle's final state.
+fn area_active_compressed(instructions: &[&Instruction]) -> isize {
+    let ys = boundaries(instructions.iter().copied(), |c| (c.min.y, c.max.y));
+    let zs = boundaries(instructions.iter().copied(), |c| (c.min.z, c.max.z));
+    let index = |bounds: &[isize], value: isize| bounds.binary_search(&value).unwrap();
+
+    let mut grid = vec![false; ys.len() * zs.len()];
+    for inst in instructions {
+        let Cuboid { min, max } = inst.cuboid;
+        for y in index(&ys, min.y)..index(&ys, max.y + 1) {
+            for z in index(&zs, min.z)..index(&zs, max.z + 1) {
+                grid[y * zs.len() + z] = inst.is_on;
+            }
+        }
+    }
+
+    let mut area = 0;
+    for (y, y_range) in ys.windows(2).enumerate() {
+        for (z, z_range) in zs.windows(2).enumerate() {
+            if grid[y * zs.len() + z] {
+                area += (y_range[1] - y_range[0]) * (z_range[1] - z_range[0]);
+            }
+        }
+    }
+
+    area
+}
+
+/// Applies instructions one at a time, allowing them to be undone and redone. Undoing is done by
+/// replaying instructions from a snapshot of the cuboids, which is taken every `snapshot_interval`
+/// instructions. A smaller interval makes undo quicker, a larger one uses less memory.
+#[derive(Debug, Clone)]
+struct ReactorSession {
+    /// Every instruction applied, including any that have been undone and can be redone
+    history: Vec<Instruction>,
+    /// The number of instructions from `history` that are currently applied
+    applied: usize,
+    /// The active cuboids after applying the first `applied` instructions
+    cuboids: CuboidSet,
+    /// The number of instructions between snapshots
+    snapshot_interval: usize,
+    /// `snapshots[n]` is the set of cuboids after the first `n * snapshot_interval` instructions
+    snapshots: Vec<CuboidSet>,
+}
+
+impl ReactorSession {
+    /// Start a session with everything off. A `snapshot_interval` of 0 is treated as 1.
+    fn new(snapshot_interval: usize) -> ReactorSession {
+        ReactorSession {
+            history: Vec::new(),
+            applied: 0,
+            cuboids: CuboidSet::new(),
+            snapshot_interval: snapshot_interval.max(1),
+            snapshots: vec![CuboidSet::new()],
+        }
+    }
+
+    /// Apply a new instruction. Any undone instructions are discarded, as with undo in an editor.
+    fn apply(&mut self, instruction: Instruction) {
+        self.history.truncate(self.applied);
+        self.snapshots
+            .truncate(self.applied / self.snapshot_interval + 1);
+        self.history.push(instruction);
+        self.step_forward();
+    }
+
+    /// Undo the last applied instruction, returning false if there was nothing to undo
+    fn undo(&mut self) -> bool {
+        if self.applied == 0 {
+            return false;
+        }
+
+        self.applied -= 1;
+        self.cuboids = self.replay(self.applied, None);
+        true
+    }
+
+    /// Re-apply the last undone instruction, returning false if there was nothing to redo
+    fn redo(&mut self) -> bool {
+        if self.applied == self.history.len() {
+            return false;
+        }
+
+        self.step_forward();
+        true
+    }
+
+    /// The total volume that is currently on
+    fn volume(&self) -> isize {
+        self.cuboids.volume()
+    }
+
+    /// The volume that would be on if the applied instruction at `index` had been skipped, without
+    /// changing the session. Returns `None` if there is no applied instruction at that index.
+    fn volume_without(&self, index: usize) -> Option<isize> {
+        if index >= self.applied {
+            return None;
+        }
+
+        Some(self.replay(self.applied, Some(index)).volume())
+    }
+
+    /// Merge `history[applied]` into the current cuboids, taking a snapshot if one is due
+    fn step_forward(&mut self) {
+        self.cuboids.apply(&self.history[self.applied]);
+        self.applied += 1;
+
+        if self.applied.is_multiple_of(self.snapshot_interval)
+            && self.snapshots.len() == self.applied / self.snapshot_interval
+        {
+            self.snapshots.push(self.cuboids.clone());
+        }
+    }
+
+    /// Build the cuboids after the first `count` instructions, starting from the latest snapshot
+    /// before any instruction that needs to be skipped.
+    fn replay(&self, count: usize, skip: Option<usize>) -> CuboidSet {
+        let start = skip.unwrap_or(count).min(count) / self.snapshot_interval;
+        let start = start.min(self.snapshots.len() - 1);
+
+        (start * self.snapshot_interval..count)
+            .filter(|&i| Some(i) != skip)
+            .fold(self.snapshots[start].clone(), |mut acc, i| {
+                acc.apply(&self.history[i]);
+                acc
+            })
+    }
+}
+
+/// How often [`Reboot`] snapshots the cuboids. The puzzle input has a few hundred instructions, so
+/// this keeps undo to replaying at most a handful of them.
+const REPL_SNAPSHOT_INTERVAL: usize = 10;
+
+/// The number of applied instructions [`Reboot`] shows when it is printed
+const REPL_PRINTED_INSTRUCTIONS: usize = 10;
+
+/// The puzzle input paired with a [`ReactorSession`], so the instructions can be applied and undone
+/// one at a time with [`crate::util::repl`]
+#[derive(Clone)]
+struct Reboot<'a> {
+    instructions: &'a [Instruction],
+    session: ReactorSession,
+}
+
+impl<'a> Renderable for Reboot<'a> {
+    /// The most recently applied instructions, numbered from 1
+    fn render(&self) -> String {
+        let applied = self.session.applied;
+        let first = applied.saturating_sub(REPL_PRINTED_INSTRUCTIONS);
+        (first..applied)
+            .map(|i| format!("{:>4}: {}", i + 1, self.instructions[i]))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl<'a> FrameSource for Reboot<'a> {
+    /// Redo an undone instruction if there is one, otherwise apply the next from the input
+    fn step(&mut self) -> bool {
+        if self.session.redo() {
+            return true;
+        }
+
+        match self.instructions.get(self.session.applied) {
+            Some(&instruction) => {
+                self.session.apply(instruction);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn current_step(&self) -> usize {
+        self.session.applied
+    }
+}
+
+impl<'a> Steppable for Reboot<'a> {
+    /// Includes how much the last instruction changed the volume, see
+    /// [`ReactorSession::volume_without`]
+    fn stats(&self) -> String {
+        let applied = self.session.applied;
+        let volume = self.session.volume();
+        let change = match applied.checked_sub(1) {
+            Some(last) => self
+                .session
+                .volume_without(last)
+                .map_or(0, |without| volume - without),
+            None => 0,
+        };
+
+        format!(
+            "Instruction {} of {}: {} cubes on ({:+}) in {} cuboids",
+            applied,
+            self.instructions.len(),
+            volume,
+            change,
+            self.session.cuboids.len()
+        )
+    }
+
+    fn step_back(&mut self) -> bool {
+        self.session.undo()
+    }
+}
+
+/// Filter the list of instructions to just the region that intersects the limit [`Cuboid`]. If an
+/// instruction's cuboid is partially in the area, instead include a modified instruction that just
+/// contains the intersection with the limit.
+fn limit_instructions(instructions: &[Instruction], limit: Cuboid) -> Vec<Instruction> {
+    instructions
+        .iter()
+        .flat_map(|inst| {
+            limit.intersect(&inst.cuboid).map(|cuboid| Instruction {
+                is_on: inst.is_on,
+                cuboid,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::InputError;
+    use crate::testing::{check, Arbitrary, Gen, DEFAULT_CASES};
+    use crate::year_2021::day_22::{
+        initialisation_limit, limit_instructions, merge_instruction, parse_input, validate,
+        volume_active, volume_active_auto, volume_active_compressed, Cuboid, CuboidSet,
+        Instruction, ParseError, ReactorSession, Reboot,
+    };
+    use crate::util::render::{FrameSource, Renderable};
+    use crate::util::repl::Steppable;
+
+    impl Arbitrary for Cuboid {
+        /// A cuboid near the origin, so that pairs of them overlap often
+        fn arbitrary(gen: &mut Gen) -> Self {
+            let mut range = || {
+                let (a, b) = (gen.between(-10, 10), gen.between(-10, 10));
+                (a.min(b), a.max(b))
+            };
+            let (x_min, x_max) = range();
+            let (y_min, y_max) = range();
+            let (z_min, z_max) = range();
+
+            Cuboid::new(x_min, x_max, y_min, y_max, z_min, z_max)
+        }
+    }
+
+    fn sample_instructions() -> Vec<Instruction> {
+        Vec::from([
+            Instruction::new(true, 10, 12, 10, 12, 10, 12),
+            Instruction::new(true, 11, 13, 11, 13, 11, 13),
+            Instruction::new(false, 9, 11, 9, 11, 9, 11),
+            Instruction::new(true, 10, 10, 10, 10, 10, 10),
+        ])
+    }
+
+    fn large_sample() -> Vec<Instruction> {
+        let input = "on x=-5..47,y=-31..22,z=-19..33
+on x=-44..5,y=-27..21,z=-14..35
+on x=-49..-1,y=-11..42,z=-10..38
+on x=-20..34,y=-40..6,z=-44..1
+off x=26..39,y=40..50,z=-2..11
+on x=-41..5,y=-41..6,z=-36..8
+off x=-43..-33,y=-45..-28,z=7..25
+on x=-33..15,y=-32..19,z=-34..11
+off x=35..47,y=-46..-34,z=-11..5
+on x=-14..36,y=-6..44,z=-16..29
+on x=-57795..-6158,y=29564..72030,z=20435..90618
+on x=36731..105352,y=-21140..28532,z=16094..90401
+on x=30999..107136,y=-53464..15513,z=8553..71215
+on x=13528..83982,y=-99403..-27377,z=-24141..23996
+on x=-72682..-12347,y=18159..111354,z=7391..80950
+on x=-1060..80757,y=-65301..-20884,z=-103788..-16709
+on x=-83015..-9461,y=-72160..-8347,z=-81239..-26856
+on x=-52752..22273,y=-49450..9096,z=54442..119054
+on x=-29982..40483,y=-108474..-28371,z=-24328..38471
+on x=-4958..62750,y=40422..118853,z=-7672..65583
+on x=55694..108686,y=-43367..46958,z=-26781..48729
+on x=-98497..-18186,y=-63569..3412,z=1232..88485
+on x=-726..56291,y=-62629..13224,z=18033..85226
+on x=-110886..-34664,y=-81338..-8658,z=8914..63723
+on x=-55829..24974,y=-16897..54165,z=-121762..-28058
+on x=-65152..-11147,y=22489..91432,z=-58782..1780
+on x=-120100..-32970,y=-46592..27473,z=-11695..61039
+on x=-18631..37533,y=-124565..-50804,z=-35667..28308
+on x=-57817..18248,y=49321..117703,z=5745..55881
+on x=14781..98692,y=-1341..70827,z=15753..70151
+on x=-34419..55919,y=-19626..40991,z=39015..114138
+on x=-60785..11593,y=-56135..2999,z=-95368..-26915
+on x=-32178..58085,y=17647..101866,z=-91405..-8878
+on x=-53655..12091,y=50097..105568,z=-75335..-4862
+on x=-111166..-40997,y=-71714..2688,z=5609..50954
+on x=-16602..70118,y=-98693..-44401,z=5197..76897
+on x=16383..101554,y=4615..83635,z=-44907..18747
+off x=-95822..-15171,y=-19987..48940,z=10804..104439
+on x=-89813..-14614,y=16069..88491,z=-3297..45228
+on x=41075..99376,y=-20427..49978,z=-52012..13762
+on x=-21330..50085,y=-17944..62733,z=-112280..-30197
+on x=-16478..35915,y=36008..118594,z=-7885..47086
+off x=-98156..-27851,y=-49952..43171,z=-99005..-8456
+off x=2032..69770,y=-71013..4824,z=7471..94418
+on x=43670..120875,y=-42068..12382,z=-24787..38892
+off x=37514..111226,y=-45862..25743,z=-16714..54663
+off x=25699..97951,y=-30668..59918,z=-15349..69697
+off x=-44271..17935,y=-9516..60759,z=49131..112598
+on x=-61695..-5813,y=40978..94975,z=8655..80240
+off x=-101086..-9439,y=-7088..67543,z=33935..83858
+off x=18020..114017,y=-48931..32606,z=21474..89843
+off x=-77139..10506,y=-89994..-18797,z=-80..59318
+off x=8476..79288,y=-75520..11602,z=-96624..-24783
+on x=-47488..-1262,y=24338..100707,z=16292..72967
+off x=-84341..13987,y=2429..92914,z=-90671..-1318
+off x=-37810..49457,y=-71013..-7894,z=-105357..-13188
+off x=-27365..46395,y=31009..98017,z=15428..76570
+off x=-70369..-16548,y=22648..78696,z=-1892..86821
+on x=-53470..21291,y=-120233..-33476,z=-44150..38147
+off x=-93533..-4276,y=-16170..68771,z=-104985..-24507"
+            .to_string();
+
+        parse_input(&input).unwrap()
+    }
+
+    #[test]
+    fn can_parse() {
+        let input = "on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10"
+            .to_string();
+
+        let expected = sample_instructions();
+
+        let actual = parse_input(&input).unwrap();
+
+        assert_eq!(actual.len(), expected.len());
+        actual
+            .iter()
+            .zip(expected)
+            .for_each(|(&act, exp)| assert_eq!(act, exp));
+    }
+
+    #[test]
+    fn can_report_malformed_instructions() {
+        let invalid = |line: &str| ParseError::InvalidLine(line.to_string());
+
+        for line in [
+            "",
+            "on",
+            "toggle x=1..2,y=1..2,z=1..2",
+            "on x=1..2,y=1..2",
+            "on x=1..2,y=1..2,z=1..2,w=1..2",
+            "on x=1..2,z=1..2,y=1..2",
+            "on x=1-2,y=1..2,z=1..2",
+            "on x=1..2,y=1..,z=1..2",
+            "on x=1..2,y=1..2,z=1..99999999999999999999",
+        ] {
+            assert_eq!(Instruction::try_from(line), Err(invalid(line)));
+        }
+
+        assert_eq!(
+            Instruction::try_from("off x=1..2,y=5..-5,z=1..2"),
+            Err(ParseError::BackwardsRange {
+                axis: 'y',
+                min: 5,
+                max: -5
+            })
+        );
+        assert_eq!(
+            parse_input("on x=1..2,y=1..2,z=1..2\noff"),
+            Err(invalid("off"))
+        );
+    }
+
+    #[test]
+    fn can_intersect() {
+        let cuboids: Vec<Cuboid> = sample_instructions().iter().map(|i| i.cuboid).collect();
+
+        assert_eq!(cuboids[0].intersect(&cuboids[3]), Some(cuboids[3]));
+        assert_eq!(
+            cuboids[0].intersect(&cuboids[1]),
+            Some(Cuboid::new(11, 12, 11, 12, 11, 12))
+        );
+        assert_eq!(cuboids[1].intersect(&cuboids[3]), None);
+    }
+
+    #[test]
+    fn can_diff_and_split() {
+        let cuboids: Vec<Cuboid> = sample_instructions().iter().map(|i| i.cuboid).collect();
+
+        let expected1 = Vec::from([
+            Cuboid::new(11, 12, 10, 12, 10, 12),
+            Cuboid::new(10, 10, 11, 12, 10, 12),
+            Cuboid::new(10, 10, 10, 10, 11, 12),
+        ]);
+        assert_eq!(cuboids[0].diff_and_split(&cuboids[3]), expected1);
+
+        let expected2 = Vec::from([cuboids[1]]);
+        assert_eq!(cuboids[1].diff_and_split(&cuboids[3]), expected2);
+
+        let expected3 = Vec::from([
+            Cuboid::new(9, 9, 9, 11, 9, 11),
+            Cuboid::new(11, 11, 9, 11, 9, 11),
+            Cuboid::new(10, 10, 9, 9, 9, 11),
+            Cuboid::new(10, 10, 11, 11, 9, 11),
+            Cuboid::new(10, 10, 10, 10, 9, 9),
+            Cuboid::new(10, 10, 10, 10, 11, 11),
+        ]);
+        assert_eq!(cuboids[2].diff_and_split(&cuboids[3]), expected3);
+        assert_eq!(cuboids[0].diff_and_split(&cuboids[0]), Vec::new());
+    }
+
+    #[test]
+    fn can_merge_instruction() {
+        let instructions = sample_instructions();
+
+        let pass1 = merge_instruction(instructions[0], &CuboidSet::new());
+        assert_eq!(pass1.cuboids(), [instructions[0].cuboid]);
+
+        let pass2 = merge_instruction(instructions[1], &pass1);
+        let expected2 = Vec::from([
+            Cuboid::new(10, 10, 10, 12, 10, 12),
+            Cuboid::new(11, 12, 10, 10, 10, 12),
+            Cuboid::new(11, 12, 11, 12, 10, 10),
+            Cuboid::new(11, 13, 11, 13, 11, 13),
+        ]);
+        assert_eq!(pass2.cuboids(), expected2);
+
+        let mut single = CuboidSet::new();
+        single.add(instructions[3].cuboid);
+        assert!(merge_instruction(instructions[2], &single).is_empty());
+    }
+
+    #[test]
+    fn can_use_cuboid_sets() {
+        let first = Cuboid::new(10, 12, 10, 12, 10, 12);
+        let second = Cuboid::new(11, 13, 11, 13, 11, 13);
+
+        let mut set = CuboidSet::new();
+        assert!(set.is_empty());
+        assert_eq!(set.bounding_box(), None);
+
+        set.add(first);
+        set.add(second);
+        // The two overlap by 2 units³
+        assert_eq!(set.volume(), 27 + 27 - 8);
+        assert_eq!(set.len(), 4);
+        assert_eq!(
+            set.bounding_box(),
+            Some(Cuboid::new(10, 13, 10, 13, 10, 13))
+        );
+        assert_eq!(
+            set.intersect_volume(&Cuboid::new(10, 10, 10, 12, 10, 12)),
+            9
+        );
+        assert_eq!(set.intersect_volume(&Cuboid::new(0, 1, 0, 1, 0, 1)), 0);
+
+        set.subtract(&Cuboid::new(9, 11, 9, 11, 9, 11));
+        assert_eq!(set.volume(), 38);
+
+        let mut other = CuboidSet::new();
+        other.add(Cuboid::new(20, 21, 20, 21, 20, 21));
+        other.add(first);
+        set.union(&other);
+        assert_eq!(set.volume(), 46 + 8);
+        assert_eq!(
+            set.bounding_box(),
+            Some(Cuboid::new(10, 21, 10, 21, 10, 21))
+        );
+    }
+
+    #[test]
+    fn can_calc_volume() {
+        let volumes: Vec<isize> = sample_instructions()
+            .iter()
+            .map(|i| i.cuboid.volume())
+            .collect();
+        assert_eq!(volumes, Vec::from([27, 27, 27, 1]));
+        assert_eq!(Cuboid::new(10, 10, 10, 12, 10, 12).volume(), 9);
+        assert_eq!(Cuboid::new(10, 10, 11, 12, 9, 12).volume(), 8);
+    }
+
+    #[test]
+    fn can_sum_active_volumes() {
+        assert_eq!(volume_active(&sample_instructions()), 39);
+        assert_eq!(volume_active(&large_sample()), 2758514936282235);
+    }
+
+    #[test]
+    fn can_sum_active_volumes_compressed() {
+        assert_eq!(volume_active_compressed(&sample_instructions()), 39);
+        assert_eq!(volume_active_compressed(&large_sample()), 2758514936282235);
+        assert_eq!(
+            volume_active_compressed(&limit_instructions(&large_sample(), initialisation_limit())),
+            474140
+        );
+        assert_eq!(volume_active_compressed(&[]), 0);
+    }
+
+    /// Alternating thin plates across each axis, which [`volume_active`] has to split into a lot of
+    /// small cuboids
+    fn fragmenting_plates(count: isize) -> Vec<Instruction> {
+        (0..count)
+            .map(|i| {
+                let (min, max) = ((i / 3) * 10, (i / 3) * 10 + 4);
+                match i % 3 {
+                    0 => Instruction::new(i % 2 == 0, min, max, -100, 100, -100, 100),
+                    1 => Instruction::new(i % 2 == 0, -100, 100, min, max, -100, 100),
+                    _ => Instruction::new(i % 2 == 0, -100, 100, -100, 100, min, max),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn can_sum_active_volumes_with_either_approach() {
+        assert_eq!(volume_active_auto(&sample_instructions()), 39);
+        assert_eq!(volume_active_auto(&large_sample()), 2758514936282235);
+
+        // Enough fragmentation to switch to compression
+        let plates = fragmenting_plates(60);
+        let expected = volume_active(&plates);
+        assert_eq!(volume_active_compressed(&plates), expected);
+        assert_eq!(volume_active_auto(&plates), expected);
+    }
+
+    #[test]
+    fn can_limit() {
+        assert_eq!(
+            limit_instructions(&sample_instructions(), Cuboid::new(10, 10, 10, 10, 10, 10)),
+            Vec::from([
+                Instruction::new(true, 10, 10, 10, 10, 10, 10),
+                Instruction::new(false, 10, 10, 10, 10, 10, 10),
+                Instruction::new(true, 10, 10, 10, 10, 10, 10),
+            ])
+        )
+    }
+
+    #[test]
+    fn can_sum_active_volumes_with_limit() {
+        let input = "on x=-20..26,y=-36..17,z=-47..7
+on x=-20..33,y=-21..23,z=-26..28
+on x=-22..28,y=-29..23,z=-38..16
+on x=-46..7,y=-6..46,z=-50..-1
+on x=-49..1,y=-3..46,z=-24..28
+on x=2..47,y=-22..22,z=-23..27
+on x=-27..23,y=-28..26,z=-21..29
+on x=-39..5,y=-6..47,z=-3..44
+on x=-30..21,y=-8..43,z=-13..34
+on x=-22..26,y=-27..20,z=-29..19
+off x=-48..-32,y=26..41,z=-47..-37
+on x=-12..35,y=6..50,z=-50..-2
+off x=-48..-32,y=-32..-16,z=-15..-5
+on x=-18..26,y=-33..15,z=-7..46
+off x=-40..-22,y=-38..-28,z=23..41
+on x=-16..35,y=-41..10,z=-47..6
+off x=-32..-23,y=11..30,z=-14..3
+on x=-49..-5,y=-3..45,z=-29..18
+off x=18..30,y=-20..-8,z=-3..13
+on x=-41..9,y=-7..43,z=-33..15
+on x=-54112..-39298,y=-85059..-49293,z=-27449..7877
+on x=967..23432,y=45373..81175,z=27513..53682"
+            .to_string();
+
+        let instructions = parse_input(&input).unwrap();
+        let instructions = limit_instructions(&instructions, initialisation_limit());
+
+        assert_eq!(volume_active(&instructions), 590784);
+        assert_eq!(
+            volume_active(&limit_instructions(&large_sample(), initialisation_limit())),
+            474140
+        );
+    }
+
+    #[test]
+    fn diff_and_split_conserves_volume() {
+        check(DEFAULT_CASES, |(a, b): &(Cuboid, Cuboid)| {
+            let splits = a.diff_and_split(b);
+            let overlap = a.intersect(b).map(|c| c.volume()).unwrap_or(0);
+
+            // The pieces cover the original minus the overlap, without covering the other cuboid or each other
+            splits.iter().map(Cuboid::volume).sum::<isize>() == a.volume() - overlap
+                && splits.iter().all(|split| split.intersect(b).is_none())
+                && splits.iter().enumerate().all(|(i, split)| {
+                    splits[i + 1..]
+                        .iter()
+                        .all(|other| split.intersect(other).is_none())
+                })
+        });
+    }
+
+    #[test]
+    fn can_undo_and_redo() {
+        let instructions = sample_instructions();
+        let mut session = ReactorSession::new(2);
+
+        assert!(!session.undo());
+        assert!(!session.redo());
+
+        let mut volumes = vec![session.volume()];
+        for &instruction in &instructions {
+            session.apply(instruction);
+            volumes.push(session.volume());
+        }
+        assert_eq!(volumes, vec![0, 27, 46, 38, 39]);
+
+        // Undo all the way back, passing snapshots, then redo all the way forwards
+        for expected in volumes.iter().rev().skip(1) {
+            assert!(session.undo());
+            assert_eq!(session.volume(), *expected);
+        }
+        assert!(!session.undo());
+
+        for expected in volumes.iter().skip(1) {
+            assert!(session.redo());
+            assert_eq!(session.volume(), *expected);
+        }
+        assert!(!session.redo());
+
+        // Applying after an undo discards the redo history
+        session.undo();
+        session.undo();
+        session.apply(instructions[3]);
+        assert_eq!(session.volume(), 46);
+        assert!(!session.redo());
+        assert!(session.undo());
+        assert_eq!(session.volume(), 46);
+    }
+
+    #[test]
+    fn can_step_through_a_reboot() {
+        let instructions = sample_instructions();
+        let mut reboot = Reboot {
+            instructions: &instructions,
+            session: ReactorSession::new(2),
+        };
+
+        assert_eq!(reboot.stats(), "Instruction 0 of 4: 0 cubes on (+0) in 0 cuboids");
+        assert!(!reboot.step_back());
+        assert!(reboot.skip_to(3));
+        assert_eq!(reboot.stats(), "Instruction 3 of 4: 38 cubes on (-8) in 9 cuboids");
+        assert_eq!(
+            reboot.render(),
+            [
+                "   1: on x=10..12,y=10..12,z=10..12",
+                "   2: on x=11..13,y=11..13,z=11..13",
+                "   3: off x=9..11,y=9..11,z=9..11"
+            ]
+            .join("\n")
+        );
+
+        assert!(reboot.step_back());
+        assert!(reboot.step_back());
+        assert_eq!(reboot.session.volume(), 27);
+        assert!(!reboot.skip_to(5));
+        assert_eq!(reboot.session.volume(), 39);
+        assert_eq!(reboot.current_step(), 4);
+    }
+
+    #[test]
+    fn can_calculate_volume_without_an_instruction() {
+        let instructions = large_sample();
+
+        for interval in [1, 7, 100] {
+            let mut session = ReactorSession::new(interval);
+            instructions
+                .iter()
+                .for_each(|&instruction| session.apply(instruction));
+
+            assert_eq!(session.volume(), 2758514936282235);
+
+            for skip in [0, 9, 37, instructions.len() - 1] {
+                let mut without = instructions.clone();
+                without.remove(skip);
+                assert_eq!(session.volume_without(skip), Some(volume_active(&without)));
+            }
+            assert_eq!(session.volume_without(instructions.len()), None);
+            // The session is unchanged
+            assert_eq!(session.volume(), 2758514936282235);
+        }
+    }
+
+    #[test]
+    fn can_validate() {
+        assert_eq!(
+            validate("on x=10..12,y=10..12,z=10..12\noff x=-9..11,y=9..11,z=9..11\n"),
+            Ok(())
+        );
+        assert_eq!(
+            validate("on x=10..12,y=10..12\noff x=9..11,y=11..9,z=9..11\n"),
+            Err(vec![
+                InputError::new(
+                    1,
+                    "Expected 'on|off x=<n>..<n>,y=<n>..<n>,z=<n>..<n>', found 'on x=10..12,y=10..12'"
+                ),
+                InputError::new(2, "The y range 11..9 is backwards"),
+            ])
+        );
+    }
+}
@@ -0,0 +1,1546 @@
+//! This is my solution for [Advent of Code - Day 23 - _Amphipod_](https://adventofcode.com/2021/day/23)
+//!
+//! This one I really struggled with. Almost entirely due to me misreading the puzzle description, and missing the
+//! restrictions on Amphipod movement that vastly restrict the graph of moves. It took eventually despairing, looking
+//! up others' solutions, seeing a bunch of things that seemed weird, and going back to work out why. By this point
+//! I'd rewritten my state representation as an integer of upto 23 3-bit sections from which I could unpack the state,
+//! so that I could more efficiently store the states in a Binary Heap. I was able to shoe-horn in the restrictions to
+//! my existing code, but that journey has left it pretty unreadable. I have left it as is, and will try to make it
+//! clearer what is going on with some comments. It is worth noting that my initial, less restricted solution still
+//! worked for part one, though it did take about 6 minutes to complete.
+//!
+//! [`Burrow`] is the previously mentioned state-as-integer, which contains the state, and the length for ease of
+//! determining how many leading 0's are significant. [`Burrow::try_from`] is used for creating burrows from a string in
+//! tests, and returns a [`ParseError`] rather than panicking or overflowing the integer, as the fuzz target in `fuzz/`
+//! checks. [`Burrow::fmt`] turns the integer into a string of letters so debugging is possible. [`Burrow::get_at`] does
+//! some bit-manipulation to get the Amphipod type, if any, at that position. [`Burrow::set_at`] uses more bit tricks to
+//! mutate the state of one of the positions and [Burrow::swap] uses these to swap the state between two positions, used
+//! to move Amphipods. Burrows are the nodes in a graph search using Dijkstra's algorithm, which originally had its own
+//! `State` wrapper and heap here, but is now shared with [`super::day_15`] in [`crate::util::dijkstra`].
+//!
+//! [`parse_letter`] turns an `.`, `A`, `B`, `C`, or `D` into a number 0-4 to represent the possible state for each
+//! cell. [`parse_input`] parses the ascii diagram of the burrow, mostly by ignoring every thing that isn't `A`, `B`,
+//! `C`, or `D`. [`build_goal`] builds the burrow representing the expected final state of the burrow for a given depth
+//! of side-tunnels. [`build_states`] returns a list of possible states, and the cost to move there from the given
+//! state. This is where the worst of the mess is, as it relies on a lot of number manipulation tricks to turn the flat
+//! 15/23 cell list of cells into something that represents the more complex burrow structure. [`find_shortest_path`]
+//! hands [`build_states`] to the shared Dijkstra's Algorithm as the adjacency/cost implementation. Since then I've
+//! added [`find_move_home`], which checks for an Amphipod that can go straight to its final cell, including directly
+//! from another tunnel. When there is one, that is the only next state considered, which cuts down the branching a
+//! lot. Finally [`expand_burrow`] handles turning the input for part one into the input for part two.
+//!
+//! [`SearchOptions`] allows experimenting with the search. As well as turning off [`find_move_home`], the costs can be
+//! changed, and when they are the same for A and D, and for B and C, a burrow and its [`Burrow::mirror`] image cost the
+//! same to solve. [`Burrow::canonical`] is then used as the key in the Dijkstra's distance map, so that only one of
+//! each mirrored pair is searched. [`Burrow::is_deadlocked`] spots some burrows that can never be solved, so they can
+//! be dropped rather than searched.
+//!
+//! Passing `--trace` prints the moves of each solution, using [`find_shortest_route`], which runs the same search but
+//! keeps track of the route to the goal, and [`format_route`].
+//!
+//! Dijkstra's algorithm keeps every state it has seen, which can add up for the deeper burrows. [`Solver::Ida`] swaps
+//! it for [`find_shortest_path_ida`], an iterative deepening A* search that keeps the current route and a fixed size
+//! [`IdaTable`] of recently seen states, and may have to explore states again. It uses the same [`next_states`], and
+//! [`min_energy_remaining`] as the estimate of the energy still needed. Pass `--param solver=ida` to use it, or
+//! `--benchmark` to compare the two.
+//!
+//! The deeper burrow can take a while, so [`find_shortest_path_reporting`] reports the number of states expanded as a
+//! [`Progress`], which the runner shows when there is a terminal to show it on.
+//!
+//! When experimenting with the pruning it helps to see more than the final count, so [`next_states`] records what it
+//! did in a [`SearchStats`], which also picks up the [`QueueStats`] from the Dijkstra search. Pass `--verbose` to print
+//! them after each search.
+
+use crate::errors::{check_input, check_lines, to_validation, InputError, Validation};
+use crate::util::cache::cached;
+use crate::util::dijkstra::{dijkstra_by_key_with_stats, dijkstra_path_by_key, QueueStats};
+use crate::util::params::Params;
+use crate::util::progress::{self, NoProgress, Progress};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+use std::time::Instant;
+
+/// The number of entries in the [`IdaTable`] used by [`find_shortest_path_ida`], which fixes the memory it uses
+const IDA_TABLE_SIZE: usize = 1 << 16;
+
+/// The cost to move each type of Amphipod in order A-D
+const COSTS: [usize; 4] = [1, 10, 100, 1000];
+
+/// The horizontal position of each of the hallway cells an Amphipod can stop in, counting the unrepresented cells
+/// at the tunnel entrances. Used by [`path_home`] to work out distances for moves that don't stop in the hallway.
+const HALLWAY_X: [usize; 7] = [0, 1, 3, 5, 7, 9, 10];
+
+/// Represents a burrow as an integer that can be used as a list of 3-bit sections. 0-6 are the 7 cells in the hallway
+/// where a Amphipod can stop, the cells adjacent to each side tunnel are not represented here, and instead handled by
+/// [`build_states`] accounting for them when calculating costs. The remaining cells represent the side-tunnels, reading
+/// like a book. You can walk down a tunnel by staring at indices 7, 8, 9 or 10, and increasing by 4 each step. It is
+/// possible to represent a burrow of up to depth 8 ([`MAX_DEPTH`]) in the u128 used.
+///
+/// The cells themselves use the numbers 0-4 to represent the types, 5 - 7 are unused:
+/// - 0 - Empty
+/// - 1 - Amber
+/// - 2 - Bronze
+/// - 3 - Copper
+/// - 4 - Desert
+#[derive(Eq, PartialEq, Hash, Ord, PartialOrd, Clone)]
+pub struct Burrow {
+    /// The number of cells in the grid. 15 for depth 2 (part one) and 23 for depth 4 (part two)
+    len: usize,
+    /// Each cell maps to 3 bits in this integer
+    positions: u128,
+}
+
+/// The deepest the side-tunnels can be for the cells to fit in a [`Burrow`]'s u128, at three bits each
+const MAX_DEPTH: usize = 8;
+
+/// The ways a string can fail to parse as a [`Burrow`]
+#[derive(Eq, PartialEq, Debug)]
+pub enum ParseError {
+    /// A character other than `.` or one of the Amphipods `A`-`D`
+    InvalidCell(char),
+    /// A number of cells that isn't the hallway and four side-tunnels of the same depth, up to [`MAX_DEPTH`]
+    InvalidLength(usize),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidCell(chr) => write!(f, "'{}' is not a burrow cell", chr),
+            ParseError::InvalidLength(len) => write!(
+                f,
+                "{} cells can't make a hallway and four tunnels up to {} deep",
+                len, MAX_DEPTH
+            ),
+        }
+    }
+}
+
+impl TryFrom<&str> for Burrow {
+    type Error = ParseError;
+
+    /// Parses a string in the format `.......BCBDDCBADBACADCA` as a Burrow. Used mostly for testing. See [`parse_input]
+    /// for parsing the actual puzzle input
+    fn try_from(str: &str) -> Result<Self, Self::Error> {
+        let cells = str
+            .chars()
+            .map(|chr| parse_letter(chr).ok_or(ParseError::InvalidCell(chr)))
+            .collect::<Result<Vec<u128>, ParseError>>()?;
+        Burrow::from_cells(&cells)
+    }
+}
+
+impl Display for Burrow {
+    /// Output a representation of the burrow in `.......BCBDDCBADBACADCA` format
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut chars: String = "".to_string();
+        for i in 0..self.len {
+            chars = chars
+                + (match self.get_at(i) {
+                    0 => ".",
+                    1 => "A",
+                    2 => "B",
+                    3 => "C",
+                    4 => "D",
+                    _ => "?",
+                })
+        }
+
+        write!(f, "{}", chars)
+    }
+}
+
+impl Debug for Burrow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Burrow {
+    /// Pack a list of cells, numbered as by [`parse_letter`], into a burrow, checking they make up the hallway and four
+    /// side-tunnels of the same depth.
+    fn from_cells(cells: &[u128]) -> Result<Burrow, ParseError> {
+        let len = cells.len();
+        if len < 11 || !(len - 7).is_multiple_of(4) || (len - 7) / 4 > MAX_DEPTH {
+            return Err(ParseError::InvalidLength(len));
+        }
+
+        let positions = cells.iter().fold(0, |pos, num| (pos << 3) + num);
+        Ok(Burrow { len, positions })
+    }
+
+    /// Return the number representing the state at a given cell offset.
+    fn get_at(&self, pos: usize) -> u128 {
+        if pos >= self.len {
+            panic!("Burrow overflow")
+        }
+        // shift later cells off the end, then & with `111` to mask earlier cell values
+        (self.positions >> ((self.len - pos - 1) * 3)) & 7
+    }
+
+    /// update the positions integer with the `value` in `pos`.
+    ///
+    /// Set cell offset 1 in a 4-cell burrow to 4, original value = 4321 (`100 011 010 001`):
+    /// ```text
+    /// let offset = 6; // i.e. the first bit after the 3 bits of the cell.'
+    /// let bits = (1 << self.len /*4*/ * 3)       // 1 000 000 000 000
+    ///            -1;                             //   111 111 111 111
+    ///
+    /// let hole = 7u128 << offset;                //   000 111 000 000
+    ///
+    /// let mask = hole ^ bits;                    //   111 000 111 111
+    ///
+    /// let zeroed = self.positions & mask;        //   100 011 010 001
+    ///                                            // & 111 000 111 111
+    ///                                            //   ---------------
+    ///                                            //   100 000 010 001
+    ///
+    /// self.positions = zeroed | (val << offset); //   100 000 010 001
+    ///                                            // | 000 100 000 000
+    ///                                            //   ---------------
+    ///                                            //   100 100 010 001  
+    ///                                            // = 4421
+    /// ```
+    fn set_at(&mut self, pos: usize, val: u128) {
+        // the offset of the cell to update
+        let offset = ((self.len - pos - 1) * 3) as u128;
+        // fill a bitmap matching the length of the positions with `1`s
+        let bits = (1 << self.len * 3) - 1;
+        // A mask that just has the three bits of the cell set
+        let hole = 7u128 << offset;
+        // Invert that so all bits except the cell we're updating are set
+        let mask = hole ^ bits;
+        // & the current value with the mask to set all the bits in the cell to 0, leaving others as is.
+        let zeroed = self.positions & mask;
+        // finally or with the desired new value sifted into the cell's position .
+        self.positions = zeroed | (val << offset);
+    }
+
+    /// Return a new burrow with the values at a and b swapped
+    fn swap(&self, a: usize, b: usize) -> Burrow {
+        let mut burrow = self.clone();
+        burrow.set_at(a, self.get_at(b));
+        burrow.set_at(b, self.get_at(a));
+        burrow
+    }
+
+    /// Return the burrow reflected left to right. As well as moving each Amphipod to the opposite cell, the types are
+    /// swapped A <-> D and B <-> C, so that they are still the same distance from their tunnel. The goal state is its
+    /// own mirror image.
+    fn mirror(&self) -> Burrow {
+        let mut mirrored = Burrow {
+            len: self.len,
+            positions: 0,
+        };
+
+        for i in 0..self.len {
+            let mirrored_pos = if i < 7 {
+                6 - i
+            } else {
+                // Keep the tunnel row, but flip the column
+                let row_start = i - (i - 7) % 4;
+                row_start + 3 - (i - row_start)
+            };
+            let amphipod = self.get_at(i);
+            mirrored.set_at(mirrored_pos, if amphipod == 0 { 0 } else { 5 - amphipod });
+        }
+
+        mirrored
+    }
+
+    /// Pick one of the burrow and its mirror image to represent both, so they can be treated as the same state
+    fn canonical(&self) -> Burrow {
+        self.clone().min(self.mirror())
+    }
+
+    /// Check for two Amphipods in the hallway that each need to get past the other to reach their tunnel. An Amphipod
+    /// in the hallway can only move into its own tunnel, so neither of them can ever move, and the burrow can't be
+    /// solved. This doesn't catch every burrow that can't be solved, just the common case that is cheap to check.
+    fn is_deadlocked(&self) -> bool {
+        let hallway: Vec<(usize, u128)> = (0..7)
+            .map(|i| (HALLWAY_X[i], self.get_at(i)))
+            .filter(|&(_, amphipod)| amphipod != 0)
+            .collect();
+
+        // The hallway is in order left to right, so only need to check each Amphipod against those to its right
+        hallway.iter().enumerate().any(|(i, &(left_x, left))| {
+            hallway[i + 1..]
+                .iter()
+                .any(|&(right_x, right)| tunnel_x(left) > right_x && tunnel_x(right) < left_x)
+        })
+    }
+}
+
+/// Check the input is a diagram of the burrow, see [`validate`], and parse it for [`part_one`] and [`part_two`] to
+/// organise.
+pub fn parse(contents: &str) -> Burrow {
+    check_input(23, contents, validate);
+    parse_input(contents).unwrap_or_else(|err| panic!("Invalid burrow: {}", err))
+}
+
+/// The lowest energy to organise the small burrow.
+///
+/// - Pass `--trace` to also print the moves for the solution, see [`format_route`].
+/// - Pass `--param solver=ida` to search with [`find_shortest_path_ida`].
+/// - Pass `--verbose` to print the [`SearchStats`] for the search.
+/// - Pass `--cache` to save the result of the search, and load it on later runs with the same burrow and options,
+///   see [`crate::util::cache`].
+pub fn part_one(burrow: &Burrow) -> Option<String> {
+    solve(burrow, "small burrow", "Searching small burrow")
+}
+
+/// The lowest energy to organise the burrow once it has been expanded with the folded part of the diagram.
+///
+/// - The same flags as [`part_one`] apply.
+/// - Pass `--benchmark` to compare the solvers on the expanded burrow.
+pub fn part_two(burrow: &Burrow) -> Option<String> {
+    let expanded_burrow = expand_burrow(burrow);
+    if env::args().any(|arg| arg == "--benchmark") {
+        benchmark_solvers(&expanded_burrow);
+    }
+
+    solve(
+        &expanded_burrow,
+        "expanded burrow",
+        "Searching expanded burrow",
+    )
+}
+
+/// Find, print and return the lowest energy for `burrow`, with the extra output requested by the flags. The search's
+/// progress is reported with `label`.
+fn solve(burrow: &Burrow, description: &str, label: &'static str) -> Option<String> {
+    let options = SearchOptions::from(&Params::from_args());
+    let (result, stats) = cached("day-23-search", &format!("{} {:?}", burrow, options), || {
+        find_shortest_path_reporting(burrow, &options, progress::reporter(label).as_mut())
+    });
+    let result = result.unwrap();
+    say!("Lowest energy for {} is {}", description, result);
+    verbose!("{}", stats);
+    if env::args().any(|arg| arg == "--trace") {
+        say!("{}", format_route(&find_shortest_route(burrow, &options).unwrap()));
+    }
+
+    Some(result.to_string())
+}
+
+/// The burrow should be an empty hallway, then rows of side rooms with one amphipod of each type in
+/// each row
+pub fn validate(input: &str) -> Validation {
+    let room = Regex::new(r"^ *#*#([ABCD])#([ABCD])#([ABCD])#([ABCD])#+ *$").unwrap();
+    let last_line = input.lines().count();
+
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    let mut errors = check_lines(input, |number, line| match number {
+        1 if line.trim() != "#############" => Some("Expected the top wall".to_string()),
+        2 if line.trim() != "#...........#" => Some("Expected an empty hallway".to_string()),
+        1 | 2 => None,
+        n if n == last_line => {
+            if line.trim().chars().all(|c| c == '#') {
+                None
+            } else {
+                Some("Expected the bottom wall".to_string())
+            }
+        }
+        _ => match room.captures(line) {
+            Some(cap) => {
+                (1..=4)
+                    .for_each(|i| *counts.entry(cap[i].chars().next().unwrap()).or_insert(0) += 1);
+                None
+            }
+            None => Some(format!("Expected four side rooms, found '{}'", line)),
+        },
+    });
+
+    let rows = last_line.saturating_sub(3);
+    for letter in ['A', 'B', 'C', 'D'] {
+        let count = counts.get(&letter).copied().unwrap_or(0);
+        if errors.is_empty() && count != rows {
+            errors.push(InputError::new(
+                3,
+                format!(
+                    "Expected {} amphipods of type {}, found {}",
+                    rows, letter, count
+                ),
+            ));
+        }
+    }
+
+    to_validation(errors)
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input is a burrow, so it is enough for it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// Turn a letter in the ascii-art into the number we use to represent it internally
+///
+/// - 0 - Empty
+/// - 1 - Amber
+/// - 2 - Bronze
+/// - 3 - Copper
+/// - 4 - Desert
+fn parse_letter(letter: char) -> Option<u128> {
+    match letter {
+        '.' => Some(0u128),
+        'A' => Some(1u128),
+        'B' => Some(2u128),
+        'C' => Some(3u128),
+        'D' => Some(4u128),
+        _ => None,
+    }
+}
+
+/// Parse the ascii-art diagram into the internal representation.
+pub fn parse_input(input: &str) -> Result<Burrow, ParseError> {
+    // start with the 7 cells of the hallway, all `000`
+    let mut cells = vec![0; 7];
+    cells.extend(
+        input
+            .lines()
+            // the upper wall of `#` and the hallway ca be assumed to be empty
+            .skip(2)
+            // using flat_map means all non-relevant characters are filtered out ([`parse_letter`] returns None)
+            .flat_map(|line| line.chars().flat_map(parse_letter)),
+    );
+
+    Burrow::from_cells(&cells)
+}
+
+/// Return a burrow that represents the target state for a given depth.
+fn build_goal(depth: usize) -> Burrow {
+    // hallway + four tunnels each of length `depth`
+    let len = depth * 4 + 7;
+    // ech row should be `1234` in order. Shift each cell on in turn
+    let row = (1 << 9) + (2 << 6) + (3 << 3) + 4;
+    // for each depth shift another full row onto the end
+    let positions = (0..depth).fold(0, |acc, _| (acc << 12) + row);
+
+    Burrow { len, positions }
+}
+
+/// This handles building the possible next states respecting the limits on Amphipod movement returning a list of the
+/// possible states and the cost for each.
+/// - For each hallway cell:
+///     - If there is an Amphipod there walk towards its desired tunnel, aborting if there is a non-empty cell in the
+///       way. Track the distance, `0 -> 1` or `6 -> 5` are 1 distance, all others are 2 to account for the
+///       unrepresented cells the Amphipod can't stop in.
+///     - Then walk down the  the tunnel, until a non-empty cell, or the bottom. Note the position of and distance
+///       to the final empty cell. Continue to increment the distance, the first step is worth an extra 1 as the
+///       Amphipod first steps into the cell adjacent to the tunnel that is not represented.
+///     - Continue through any remaining cells, if any have an Amphipod that wants to be in a different tunnel, abort.
+///     - If the move is valid, use [`Burrow::swap`] to copy the burrow with that move applied, and calculate the cost.
+///       Add these to the output `Vec`.
+/// - For each tunnel:
+///     - Walk down it until you reach a non-empty cell.
+///     - Starting at the cell left of the top of this tunnel, i.e. the first one the Amphipod can stop at, check if
+///       the cell is empty, and, if so  use [`Burrow::swap`] to copy the burrow with that move applied, and calculate
+///       the cost. Add these to the output `Vec`.
+///     - Keep stepping leftwards until a non-empty cell, or the end of the hallway (`0`) is reached.
+///     - Repeat for the cell to the right, stepping rightwards.
+fn build_states(burrow: &Burrow, costs: &[usize; 4]) -> Vec<(usize, Burrow)> {
+    let mut out = Vec::new();
+
+    // start with the hallway, check each cell in turn
+    'outer: for i in 0..7 {
+        let curr = burrow.get_at(i);
+        // if empty, nothing to move
+        if curr == 0 {
+            continue;
+        }
+        // Look up the cost based on the type (the costs array is 0 indexed, but Amber starts at 1
+        let cost = costs[curr as usize - 1];
+        // Does this Amphipod need to head left or right to reach its desired tunnel
+        let delta: isize = if i <= curr as usize { 1 } else { -1 };
+        // Aiming for the cell just to the left, or right of the tunnel entrance, depending on direction, as the
+        // entrance itself can't be stopped at so isn't represented.
+        let target = if i <= curr as usize { curr } else { curr + 1 };
+        // track where we are horizontally
+        let mut h_pos = i as usize;
+        // Start at 1 to include the entrance to the tunnel in the distance
+        let mut dist = 1;
+        // walk towards the target - the middle steps cost more to cover passing the tunnel entrances
+        while h_pos != target as usize {
+            if [0, 6].contains(&h_pos) {
+                dist += 1
+            } else {
+                dist += 2
+            };
+            h_pos = (h_pos as isize + delta) as usize;
+            // check the path is clear, if not continue to the next cell
+            if burrow.get_at(h_pos) != 0 {
+                continue 'outer;
+            }
+        }
+        // Now start moving down the tunnel, Because the type we have matches the tunnel we can use that to calculate
+        // the offset of the first cell in that tunnel.
+        let mut v_pos = curr as usize + 6;
+        // We need to walk the whole tunnel to validate it but remember which was the final empty cell
+        let mut final_pos = v_pos;
+        while v_pos < burrow.len {
+            if burrow.get_at(v_pos) == 0 {
+                final_pos = v_pos;
+                dist += 1
+            }
+            // All mismatched Amphipods need to leave before the correct ones will enter
+            else if burrow.get_at(v_pos) != curr {
+                continue 'outer;
+            }
+            // There are four tunnels so stepping in increments of 4 moves down this tunnel
+            v_pos += 4;
+        }
+        // Invalid tunnels continue to the next cell explicitly. If this is reached it's a valid move - add it to the
+        // output
+        out.push((cost * dist, burrow.swap(i, final_pos)));
+    }
+
+    // Now check the four tunnels to see if an Amphipod can move out
+    for i in 0..4 {
+        // Skip the hallway and offset to the current tunnel
+        let mut pos = 7 + i;
+        // Two steps to tunnel entrance where the Amphipod can't stop, and the first cell it can stop at
+        let mut dist = 2;
+        // walk down the tunnel until we reach the bottom
+        while pos < burrow.len {
+            let curr = burrow.get_at(pos);
+            // until a non-empty cell is found
+            if burrow.get_at(pos) != 0 {
+                // Look up the cost based on the type (the costs array is 0 indexed, but Amber starts at 1
+                let cost = costs[curr as usize - 1];
+                // first cell to the left of this tunnel's entrance
+                let mut left_pos = i + 1;
+                let mut left_dist = 0;
+                // while the current cell is empty walk leftwards
+                while burrow.get_at(left_pos) == 0 {
+                    // add the new state and cost to the output
+                    out.push((cost * (dist + left_dist), burrow.swap(pos, left_pos)));
+                    // need to explicitly abort at the hallway end so as not to go to -1 which is invalid for `usize`
+                    if left_pos == 0 {
+                        break;
+                    }
+                    // it's just a jump to the left (1 cell)
+                    left_pos -= 1;
+                    // middle cells require crossing a tunnel entrance as well
+                    left_dist += if left_pos == 0 { 1 } else { 2 };
+                }
+                // now do the same, but on the right
+                let mut right_pos = i + 2;
+                let mut right_dist = 0;
+                // as the boundary is positive here we can do the check for hallway end in the loop condition
+                while right_pos <= 6 && burrow.get_at(right_pos) == 0 {
+                    // add the new state and cost to the output
+                    out.push((cost * (dist + right_dist), burrow.swap(pos, right_pos)));
+                    // ... and then a step to the right
+                    right_pos += 1;
+                    right_dist += if right_pos == 6 { 1 } else { 2 };
+                }
+                // having found and possibly moved an Amphipod, continue to the next tunnel
+                break;
+            }
+            // There are four tunnels so stepping in increments of 4 moves down this tunnel, also track the extra
+            // distance needed to leave the tunnel
+            pos += 4;
+            dist += 1
+        }
+    }
+
+    out
+}
+
+/// The horizontal position of the entrance to the tunnel for an Amphipod type (1-4)
+fn tunnel_x(amphipod: u128) -> usize {
+    amphipod as usize * 2
+}
+
+/// If an Amphipod that is level with the hallway at horizontal position `from_x` can walk straight into its tunnel,
+/// return the distance to, and position of, the cell it would end up in. The hallway cells between must be empty, and
+/// the tunnel must only contain Amphipods of the same type.
+fn path_home(burrow: &Burrow, from_x: usize, amphipod: u128) -> Option<(usize, usize)> {
+    let target_x = tunnel_x(amphipod);
+    let (min_x, max_x) = (from_x.min(target_x), from_x.max(target_x));
+
+    // The cell the Amphipod is leaving is never strictly between the two, so doesn't need special handling
+    let blocked = HALLWAY_X
+        .iter()
+        .enumerate()
+        .any(|(cell, &x)| x > min_x && x < max_x && burrow.get_at(cell) != 0);
+    if blocked {
+        return None;
+    }
+
+    let mut dist = max_x - min_x;
+    // Walk down the tunnel as in [`build_states`], remembering the final empty cell
+    let mut v_pos = amphipod as usize + 6;
+    let mut final_pos = None;
+    while v_pos < burrow.len {
+        match burrow.get_at(v_pos) {
+            0 => {
+                final_pos = Some(v_pos);
+                dist += 1;
+            }
+            other if other != amphipod => return None,
+            _ => {}
+        }
+        v_pos += 4;
+    }
+
+    final_pos.map(|pos| (dist, pos))
+}
+
+/// Find a move that takes an Amphipod straight to its final cell, either from the hallway, or directly from the top of
+/// another tunnel without stopping in the hallway. Moving an Amphipod home never blocks anything that wasn't already
+/// blocked, so if one of these is available it can be taken without considering any other moves.
+fn find_move_home(burrow: &Burrow, costs: &[usize; 4]) -> Option<(usize, Burrow)> {
+    let from_hallway = (0..7).find_map(|i| {
+        let curr = burrow.get_at(i);
+        if curr == 0 {
+            return None;
+        }
+
+        path_home(burrow, HALLWAY_X[i], curr)
+            .map(|(dist, final_pos)| (costs[curr as usize - 1] * dist, burrow.swap(i, final_pos)))
+    });
+
+    from_hallway.or_else(|| {
+        (0..4).find_map(|i| {
+            // Find the top Amphipod in this tunnel, and how many steps it needs to reach the hallway
+            let (depth, pos) = (7 + i..burrow.len)
+                .step_by(4)
+                .enumerate()
+                .find(|&(_, pos)| burrow.get_at(pos) != 0)?;
+            let curr = burrow.get_at(pos);
+            // Already in the right tunnel, moving within it isn't useful
+            if curr as usize == i + 1 {
+                return None;
+            }
+
+            path_home(burrow, tunnel_x(i as u128 + 1), curr).map(|(dist, final_pos)| {
+                (
+                    costs[curr as usize - 1] * (dist + depth + 1),
+                    burrow.swap(pos, final_pos),
+                )
+            })
+        })
+    })
+}
+
+/// Use Dijkstra's algorithm to represent the puzzle as a graph of states, and find the shortest path (i.e. lowest
+/// total move energy) for the Amphipods to all reach their desired tunnel.
+#[allow(dead_code)]
+fn find_shortest_path(start: &Burrow) -> Option<usize> {
+    find_shortest_path_with(start, &SearchOptions::default()).0
+}
+
+/// Options to tweak [`find_shortest_path_with`], used to check the optimisations find the same answers
+#[derive(Debug)]
+struct SearchOptions {
+    /// Enable the [`find_move_home`] optimisation. Any state where an Amphipod can go straight home only has that one
+    /// next state, rather than every possible move from [`build_states`].
+    move_home_first: bool,
+    /// Treat a burrow and its [`Burrow::mirror`] image as the same state. This only applies when the costs are mirror
+    /// symmetric, i.e. A and D cost the same, as do B and C, otherwise the two aren't equivalent. With costs of
+    /// `[1, 10, 10, 1]` the gain depends on the start: the sample burrow only drops from 12,728 to 12,678 states
+    /// expanded, as mirrored states rarely meet, but the burrow `.......BADCCDAB`, which is its own mirror image,
+    /// drops from 48,737 to 24,413.
+    mirror_symmetry: bool,
+    /// Drop next states that [`Burrow::is_deadlocked`], rather than searching them for a solution that doesn't exist
+    prune_deadlocks: bool,
+    /// The cost to move each type of Amphipod in order A-D
+    costs: [usize; 4],
+    /// The search algorithm to use
+    solver: Solver,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            move_home_first: true,
+            mirror_symmetry: true,
+            prune_deadlocks: true,
+            costs: COSTS,
+            solver: Solver::Dijkstra,
+        }
+    }
+}
+
+impl From<&Params> for SearchOptions {
+    fn from(params: &Params) -> Self {
+        SearchOptions {
+            solver: params.get_or("solver", Solver::Dijkstra),
+            ..SearchOptions::default()
+        }
+    }
+}
+
+/// The algorithms that can be used to find the lowest energy solution
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+enum Solver {
+    /// Dijkstra's algorithm, fast but keeps every state it has seen, see [`crate::util::dijkstra`]
+    Dijkstra,
+    /// Iterative deepening A*, which uses a fixed amount of memory, see [`find_shortest_path_ida`]
+    Ida,
+}
+
+impl FromStr for Solver {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "dijkstra" => Ok(Solver::Dijkstra),
+            "ida" => Ok(Solver::Ida),
+            other => Err(format!(
+                "Unknown solver '{}', expected dijkstra or ida",
+                other
+            )),
+        }
+    }
+}
+
+/// As [`find_shortest_path`], but with the given [`SearchOptions`]. Also returns the number of states that were
+/// expanded during the search, to allow measuring how much each option reduces the work needed.
+fn find_shortest_path_with(start: &Burrow, options: &SearchOptions) -> (Option<usize>, usize) {
+    let (result, stats) = find_shortest_path_reporting(start, options, &mut NoProgress);
+    (result, stats.expanded)
+}
+
+/// What a search did, to give feedback when trying out heuristics and pruning
+#[derive(Eq, PartialEq, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+struct SearchStats {
+    /// The number of states [`next_states`] was asked for the moves from
+    expanded: usize,
+    /// The number of next states it returned
+    generated: usize,
+    /// How many of the expanded states had a move found by [`find_move_home`], so were not branched from
+    moved_home: usize,
+    /// The number of next states dropped as they were deadlocked, see [`SearchOptions::prune_deadlocks`]
+    deadlocked: usize,
+    /// What happened to Dijkstra's priority queue, left empty for [`Solver::Ida`] which doesn't have one
+    queue: QueueStats,
+}
+
+impl Display for SearchStats {
+    /// Output the counts over a couple of lines, for `--verbose`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Expanded {} states, generating {} next states, {} by moving home, and dropping {} deadlocked",
+            self.expanded, self.generated, self.moved_home, self.deadlocked
+        )?;
+        write!(
+            f,
+            "Queue pushed {}, popped {}, skipped {} duplicates, held at most {}",
+            self.queue.pushed, self.queue.popped, self.queue.skipped, self.queue.max_size
+        )
+    }
+}
+
+/// As [`find_shortest_path_with`], reporting the number of states expanded to `progress` as the search goes. The
+/// total isn't known until the goal is reached. Only Dijkstra's algorithm reports progress, [`Solver::Ida`] doesn't.
+/// Returns the full [`SearchStats`] rather than just the number of states expanded.
+fn find_shortest_path_reporting(
+    start: &Burrow,
+    options: &SearchOptions,
+    progress: &mut dyn Progress,
+) -> (Option<usize>, SearchStats) {
+    if options.solver == Solver::Ida {
+        let (result, stats) = find_shortest_path_ida(start, options);
+        return (result, stats.search);
+    }
+
+    let goal = build_goal((start.len - 7) / 4);
+    let mut stats = SearchStats::default();
+
+    let (result, queue) = dijkstra_by_key_with_stats(
+        start.clone(),
+        |burrow| *burrow == goal,
+        |burrow| {
+            let next = next_states(burrow, options, &mut stats);
+            progress.update(stats.expanded, None);
+            next
+        },
+        |burrow| search_key(burrow, options),
+    );
+    progress.finish();
+    stats.queue = queue;
+
+    (result, stats)
+}
+
+/// A lower bound on the energy still needed to solve the burrow, used to prune the search in
+/// [`find_shortest_path_ida`]. Each Amphipod that isn't settled at the bottom of its own tunnel will need to at least
+/// walk to the entrance of its tunnel:
+/// - From the hallway, across to the tunnel entrance.
+/// - From another tunnel, up to the hallway and across.
+/// - From its own tunnel when it is blocking in an Amphipod of a different type, up to the hallway, and one step aside
+///   and back.
+///
+/// Then the `n` Amphipods of a type that are heading home will fill the `n` cells above those already settled, which
+/// takes at least `1 + 2 + ... + n` steps down the tunnel between them.
+fn min_energy_remaining(burrow: &Burrow, costs: &[usize; 4]) -> usize {
+    let mut heading_home = [0; 4];
+
+    let to_entrances: usize = (0..burrow.len)
+        .map(|pos| {
+            let amphipod = burrow.get_at(pos);
+            if amphipod == 0 {
+                return 0;
+            }
+
+            let target_x = tunnel_x(amphipod);
+            let dist = if pos < 7 {
+                HALLWAY_X[pos].abs_diff(target_x)
+            } else {
+                let tunnel = (pos - 7) % 4 + 1;
+                let depth = (pos - 7) / 4;
+                if tunnel as u128 != amphipod {
+                    depth + 1 + tunnel_x(tunnel as u128).abs_diff(target_x)
+                } else if (pos..burrow.len)
+                    .step_by(4)
+                    .all(|below| burrow.get_at(below) == amphipod)
+                {
+                    return 0;
+                } else {
+                    depth + 3
+                }
+            };
+
+            heading_home[amphipod as usize - 1] += 1;
+            costs[amphipod as usize - 1] * dist
+        })
+        .sum();
+
+    let into_tunnels: usize = heading_home
+        .iter()
+        .zip(costs)
+        .map(|(&count, cost)| cost * count * (count + 1) / 2)
+        .sum();
+
+    to_entrances + into_tunnels
+}
+
+/// How much work [`find_shortest_path_ida`] did
+#[derive(Eq, PartialEq, Debug, Default)]
+struct IdaStats {
+    /// The states expanded and generated, across all passes
+    search: SearchStats,
+    /// The number of deepening passes made
+    passes: usize,
+    /// The most moves held on the route at once, the states the search keeps as well as its [`IdaTable`]
+    max_depth: usize,
+}
+
+/// Iterative deepening A*, an alternative to [`find_shortest_path_with`] that doesn't need to keep a map of every state
+/// it has seen. Each pass is a depth first search that abandons a route once the energy used so far plus
+/// [`min_energy_remaining`] is over the bound, which starts at the estimate for the start burrow. Every move uses some
+/// energy, so the bound also limits how deep the search goes.
+///
+/// Raising the bound to just the lowest estimate that went over it takes over a thousand passes for the expanded
+/// sample, so each pass raises it by at least a quarter. Once a pass finds a solution it carries on looking for cheaper
+/// ones, with the bound lowered to just under the best found, so the answer is still the lowest energy.
+fn find_shortest_path_ida(start: &Burrow, options: &SearchOptions) -> (Option<usize>, IdaStats) {
+    let goal = build_goal((start.len - 7) / 4);
+    let mut stats = IdaStats::default();
+    let mut bound = min_energy_remaining(start, &options.costs);
+    let mut table = IdaTable::new(IDA_TABLE_SIZE);
+
+    loop {
+        stats.passes += 1;
+        let mut pass = Pass {
+            goal: &goal,
+            options,
+            bound,
+            best: None,
+            next_bound: None,
+            stats: &mut stats,
+            table: &mut table,
+        };
+        pass.search(start, 0, 0);
+
+        match (pass.best, pass.next_bound) {
+            (Some(energy), _) => return (Some(energy), stats),
+            (None, Some(next_bound)) => bound = next_bound.max(bound + bound / 4),
+            (None, None) => return (None, stats),
+        }
+    }
+}
+
+/// The state of one depth first pass of [`find_shortest_path_ida`]
+struct Pass<'a> {
+    goal: &'a Burrow,
+    options: &'a SearchOptions,
+    /// Routes whose estimated total energy is over this are abandoned
+    bound: usize,
+    /// The lowest energy solution found so far in this pass
+    best: Option<usize>,
+    /// The lowest estimate that was over the bound, the next pass's bound is at least this
+    next_bound: Option<usize>,
+    stats: &'a mut IdaStats,
+    /// A fixed size cache of the lowest energy each state has been reached with in this pass, see [`IdaTable`]
+    table: &'a mut IdaTable,
+}
+
+/// A fixed size table of `(pass, positions, energy)`, indexed by a hash of the burrow's positions. If a state is
+/// reached again in the same pass with at least the energy recorded, everything after it has already been searched, so
+/// the route can be abandoned. Colliding states replace each other, so this only saves work, and never uses more
+/// memory.
+struct IdaTable {
+    entries: Vec<(usize, u128, usize)>,
+}
+
+impl IdaTable {
+    fn new(capacity: usize) -> IdaTable {
+        IdaTable {
+            entries: vec![(0, 0, 0); capacity],
+        }
+    }
+
+    /// Record reaching `burrow` with `energy` in `pass`, returning false if it has already been reached as cheaply
+    fn visit(&mut self, pass: usize, burrow: &Burrow, energy: usize) -> bool {
+        let index = (burrow.positions.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 64) as usize
+            % self.entries.len();
+        let entry = &mut self.entries[index];
+        if entry.0 == pass && entry.1 == burrow.positions && entry.2 <= energy {
+            return false;
+        }
+        *entry = (pass, burrow.positions, energy);
+        true
+    }
+}
+
+impl<'a> Pass<'a> {
+    /// Continue the depth first search from `burrow`, which took `energy` and `depth` moves to reach
+    fn search(&mut self, burrow: &Burrow, energy: usize, depth: usize) {
+        let estimate = energy + min_energy_remaining(burrow, &self.options.costs);
+        if estimate > self.bound {
+            self.next_bound = Some(self.next_bound.map_or(estimate, |next| next.min(estimate)));
+            return;
+        }
+        if burrow == self.goal {
+            // Only look for cheaper solutions from now on
+            self.best = Some(energy);
+            self.bound = energy.saturating_sub(1);
+            return;
+        }
+
+        if !self.table.visit(self.stats.passes, burrow, energy) {
+            return;
+        }
+
+        self.stats.max_depth = self.stats.max_depth.max(depth);
+
+        for (next_burrow, move_energy) in next_states(burrow, self.options, &mut self.stats.search) {
+            self.search(&next_burrow, energy + move_energy, depth + 1);
+        }
+    }
+}
+
+/// Compare the time taken and states expanded by each [`Solver`] on a burrow. Enabled by passing `--benchmark`. This
+/// should be run with a release build to get meaningful timings.
+fn benchmark_solvers(burrow: &Burrow) {
+    let start = Instant::now();
+    let (_, expanded) = find_shortest_path_with(burrow, &SearchOptions::default());
+    say!(
+        "dijkstra took {:.2?}, expanding and storing {} states",
+        start.elapsed(),
+        expanded
+    );
+
+    let start = Instant::now();
+    let (_, stats) = find_shortest_path_ida(burrow, &SearchOptions::default());
+    say!(
+        "ida took {:.2?}, expanding {} states over {} passes, storing at most {} plus a table of {}",
+        start.elapsed(),
+        stats.search.expanded,
+        stats.passes,
+        stats.max_depth + 1,
+        IDA_TABLE_SIZE
+    );
+}
+
+/// As [`find_shortest_path_with`], but returns each burrow state along the way, with the total energy used to reach
+/// it.
+fn find_shortest_route(start: &Burrow, options: &SearchOptions) -> Option<Vec<(Burrow, usize)>> {
+    let goal = build_goal((start.len - 7) / 4);
+
+    dijkstra_path_by_key(
+        start.clone(),
+        |burrow| *burrow == goal,
+        |burrow| next_states(burrow, options, &mut SearchStats::default()),
+        |burrow| search_key(burrow, options),
+    )
+}
+
+/// The states reachable from `burrow` as the neighbours for the Dijkstra search, using [`find_move_home`] first if
+/// that is enabled, and [`build_states`] otherwise, dropping any that are deadlocked if
+/// [`SearchOptions::prune_deadlocks`] is enabled. Counts the expansion and the states found in `stats`.
+fn next_states(
+    burrow: &Burrow,
+    options: &SearchOptions,
+    stats: &mut SearchStats,
+) -> Vec<(Burrow, usize)> {
+    stats.expanded += 1;
+    let next_states = match options
+        .move_home_first
+        .then(|| find_move_home(burrow, &options.costs))
+        .flatten()
+    {
+        Some(move_home) => {
+            stats.moved_home += 1;
+            vec![move_home]
+        }
+        None => build_states(burrow, &options.costs),
+    };
+    // Moving home never causes a deadlock, so it is only moving into the hallway that needs checking, but this is
+    // cheap enough to do for all of them
+    let before_pruning = next_states.len();
+    let next_states: Vec<(usize, Burrow)> = next_states
+        .into_iter()
+        .filter(|(_, next_burrow)| !options.prune_deadlocks || !next_burrow.is_deadlocked())
+        .collect();
+    stats.deadlocked += before_pruning - next_states.len();
+    stats.generated += next_states.len();
+
+    next_states
+        .into_iter()
+        .map(|(energy, next_burrow)| (next_burrow, energy))
+        .collect()
+}
+
+/// The key for a burrow in the Dijkstra search, merging mirror images if [`SearchOptions::mirror_symmetry`] is
+/// enabled and the costs allow it.
+fn search_key(burrow: &Burrow, options: &SearchOptions) -> Burrow {
+    let costs = &options.costs;
+    if options.mirror_symmetry && costs[0] == costs[3] && costs[1] == costs[2] {
+        burrow.canonical()
+    } else {
+        burrow.clone()
+    }
+}
+
+/// Format a route from [`find_shortest_route`] with one line per state, showing the energy for the move that reached
+/// it, and the running total.
+fn format_route(route: &[(Burrow, usize)]) -> String {
+    let mut previous_energy = 0;
+    route
+        .iter()
+        .enumerate()
+        .map(|(step, (burrow, energy))| {
+            let line = format!(
+                "{:>3}: {} {:>+6} = {}",
+                step,
+                burrow,
+                energy - previous_energy,
+                energy
+            );
+            previous_energy = *energy;
+            line
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Add in the two extra lines that were hidden behind the fold for part two.
+fn expand_burrow(burrow: &Burrow) -> Burrow {
+    let mut expanded = format!("{}", burrow);
+    expanded.insert_str(11, "DCBADBAC");
+    Burrow::try_from(expanded.as_str()).expect("Burrow is too deep to expand")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{check, Arbitrary, Gen, DEFAULT_CASES};
+    use crate::util::params::Params;
+    use crate::util::dijkstra::QueueStats;
+    use crate::util::progress::{NoProgress, RecordedProgress};
+    use crate::year_2021::day_23::{
+        build_goal, build_states, expand_burrow, find_move_home, find_shortest_path,
+        find_shortest_path_ida, find_shortest_path_reporting, find_shortest_path_with,
+        find_shortest_route, format_route,
+        min_energy_remaining, next_states, parse_input, Burrow, ParseError, SearchOptions, SearchStats, Solver, COSTS,
+        IDA_TABLE_SIZE, MAX_DEPTH,
+    };
+    use std::collections::HashSet;
+
+    impl Arbitrary for Burrow {
+        /// A starting position, with an empty hallway and the amphipods for a burrow of depth 2 or 4 in a random order
+        fn arbitrary(gen: &mut Gen) -> Self {
+            let depth = if gen.one_in(2) { 2 } else { 4 };
+            let mut amphipods: Vec<char> = "ABCD".chars().cycle().take(depth * 4).collect();
+            gen.shuffle(&mut amphipods);
+
+            Burrow::try_from(format!(".......{}", amphipods.iter().collect::<String>()).as_str()).unwrap()
+        }
+    }
+
+    fn sample_start() -> Burrow {
+        Burrow::try_from(".......BCBDADCA").unwrap()
+    }
+
+    #[test]
+    fn can_parse() {
+        let input = "#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########"
+            .to_string();
+
+        let burrow = parse_input(&input).unwrap();
+        assert_eq!(burrow, sample_start());
+        assert_eq!(format!("{}", burrow), ".......BCBDADCA".to_string())
+    }
+
+    #[test]
+    fn can_report_malformed_burrows() {
+        assert_eq!(
+            Burrow::try_from(".......BCBDADCE"),
+            Err(ParseError::InvalidCell('E'))
+        );
+        assert_eq!(
+            Burrow::try_from(".......BCBDADC"),
+            Err(ParseError::InvalidLength(14))
+        );
+        assert_eq!(Burrow::try_from("......."), Err(ParseError::InvalidLength(7)));
+
+        // Depth 8 just fits in the u128, depth 9 would overflow it
+        let deepest = format!(".......{}", "ABCD".repeat(MAX_DEPTH));
+        assert_eq!(Burrow::try_from(deepest.as_str()), Ok(build_goal(MAX_DEPTH)));
+        let too_deep = format!(".......{}", "ABCD".repeat(MAX_DEPTH + 1));
+        assert_eq!(
+            Burrow::try_from(too_deep.as_str()),
+            Err(ParseError::InvalidLength(43))
+        );
+
+        assert_eq!(
+            parse_input("#############\n#...........#\n###B#C#B#D###\n  #A#D#C#\n  #########"),
+            Err(ParseError::InvalidLength(14))
+        );
+    }
+
+    #[test]
+    fn can_swap() {
+        let burrow = sample_start();
+        let swapped = burrow.swap(0, 14);
+        assert_eq!(format!("{}", swapped), "A......BCBDADC.".to_string())
+    }
+
+    #[test]
+    fn can_build_goal() {
+        assert_eq!(build_goal(2), Burrow::try_from(".......ABCDABCD").unwrap());
+        assert_eq!(build_goal(4), Burrow::try_from(".......ABCDABCDABCDABCD").unwrap());
+    }
+
+    #[test]
+    fn can_calc_next_state() {
+        let actual = build_states(&sample_start(), &COSTS);
+        let expected = HashSet::from([
+            (30, Burrow::try_from("B.......CBDADCA").unwrap()),
+            (20, Burrow::try_from(".B......CBDADCA").unwrap()),
+            (20, Burrow::try_from("..B.....CBDADCA").unwrap()),
+            (40, Burrow::try_from("...B....CBDADCA").unwrap()),
+            (60, Burrow::try_from("....B...CBDADCA").unwrap()),
+            (80, Burrow::try_from(".....B..CBDADCA").unwrap()),
+            (90, Burrow::try_from("......B.CBDADCA").unwrap()),
+            (500, Burrow::try_from("C......B.BDADCA").unwrap()),
+            (400, Burrow::try_from(".C.....B.BDADCA").unwrap()),
+            (200, Burrow::try_from("..C....B.BDADCA").unwrap()),
+            (200, Burrow::try_from("...C...B.BDADCA").unwrap()),
+            (400, Burrow::try_from("....C..B.BDADCA").unwrap()),
+            (600, Burrow::try_from(".....C.B.BDADCA").unwrap()),
+            (700, Burrow::try_from("......CB.BDADCA").unwrap()),
+            (70, Burrow::try_from("B......BC.DADCA").unwrap()),
+            (60, Burrow::try_from(".B.....BC.DADCA").unwrap()),
+            (40, Burrow::try_from("..B....BC.DADCA").unwrap()),
+            (20, Burrow::try_from("...B...BC.DADCA").unwrap()),
+            (20, Burrow::try_from("....B..BC.DADCA").unwrap()),
+            (40, Burrow::try_from(".....B.BC.DADCA").unwrap()),
+            (50, Burrow::try_from("......BBC.DADCA").unwrap()),
+            (9000, Burrow::try_from("D......BCB.ADCA").unwrap()),
+            (8000, Burrow::try_from(".D.....BCB.ADCA").unwrap()),
+            (6000, Burrow::try_from("..D....BCB.ADCA").unwrap()),
+            (4000, Burrow::try_from("...D...BCB.ADCA").unwrap()),
+            (2000, Burrow::try_from("....D..BCB.ADCA").unwrap()),
+            (2000, Burrow::try_from(".....D.BCB.ADCA").unwrap()),
+            (3000, Burrow::try_from("......DBCB.ADCA").unwrap()),
+        ]);
+
+        for entry in &actual {
+            assert!(expected.contains(entry))
+        }
+        assert_eq!(actual.len(), expected.len());
+
+        let actual2 = build_states(&Burrow::try_from("....D.............B...C").unwrap(), &COSTS);
+        let expected2 = HashSet::from([
+            (40, Burrow::try_from("....DB................C").unwrap()),
+            (50, Burrow::try_from("....D.B...............C").unwrap()),
+        ]);
+        for entry in &actual2 {
+            assert!(expected2.contains(entry))
+        }
+        assert_eq!(actual2.len(), expected2.len());
+    }
+
+    #[test]
+    fn can_calc_shortest_path() {
+        assert_eq!(
+            find_shortest_path(&Burrow::try_from(".A......BCDABCD").unwrap()),
+            Some(2)
+        );
+        assert_eq!(
+            find_shortest_path(&Burrow::try_from(".B.....A.CDABCD").unwrap()),
+            Some(40)
+        );
+        assert_eq!(
+            find_shortest_path(&Burrow::try_from(".C.....AB.DABCD").unwrap()),
+            Some(600)
+        );
+        assert_eq!(
+            find_shortest_path(&Burrow::try_from(".......BACDABCD").unwrap()),
+            Some(46)
+        );
+        assert_eq!(find_shortest_path(&sample_start()), Some(12521));
+
+        assert_eq!(
+            find_shortest_path(&expand_burrow(&sample_start())),
+            Some(44169)
+        );
+    }
+
+    #[test]
+    fn can_find_move_home() {
+        assert_eq!(find_move_home(&sample_start(), &COSTS), None);
+        // From the hallway
+        assert_eq!(
+            find_move_home(&Burrow::try_from(".B.....A.CDABCD").unwrap(), &COSTS),
+            Some((40, Burrow::try_from(".......ABCDABCD").unwrap()))
+        );
+        // Directly between tunnels, B is 1 up, 2 across, and 1 down
+        assert_eq!(
+            find_move_home(&Burrow::try_from(".......B..DABCD").unwrap(), &COSTS),
+            Some((40, Burrow::try_from("........B.DABCD").unwrap()))
+        );
+        // Blocked by the A in the hallway
+        assert_eq!(
+            find_move_home(&Burrow::try_from("..A....B..DABCD").unwrap(), &COSTS),
+            None
+        );
+    }
+
+    #[test]
+    fn can_detect_deadlocks() {
+        // D needs to get right past A, which needs to get left past D
+        assert!(Burrow::try_from("..DA...BC..ABCD").unwrap().is_deadlocked());
+        // The same with a gap between them
+        assert!(Burrow::try_from("..D.A..BC..ABCD").unwrap().is_deadlocked());
+        // Heading away from each other
+        assert!(!Burrow::try_from("..AD...BC..ABCD").unwrap().is_deadlocked());
+        // D is blocked by A, but A can get home first, then D
+        assert!(!Burrow::try_from(".D...A.BC..ABCD").unwrap().is_deadlocked());
+        // Neither needs to pass the other
+        assert!(!Burrow::try_from("D.....ABC..ABCD").unwrap().is_deadlocked());
+        assert!(!sample_start().is_deadlocked());
+        assert!(!build_goal(4).is_deadlocked());
+        // A mirror image is deadlocked in the same way
+        assert!(Burrow::try_from("..DA...BC..ABCD").unwrap().mirror().is_deadlocked());
+    }
+
+    #[test]
+    fn pruning_deadlocks_gives_same_costs() {
+        let without_pruning = SearchOptions {
+            prune_deadlocks: false,
+            ..SearchOptions::default()
+        };
+        for burrow in [sample_start(), expand_burrow(&sample_start())] {
+            let (energy, stats) =
+                find_shortest_path_reporting(&burrow, &SearchOptions::default(), &mut NoProgress);
+            let (expected_energy, expected_stats) =
+                find_shortest_path_reporting(&burrow, &without_pruning, &mut NoProgress);
+
+            assert_eq!(energy, expected_energy);
+            assert!(stats.deadlocked > 0);
+            assert_eq!(expected_stats.deadlocked, 0);
+            assert!(stats.expanded < expected_stats.expanded);
+        }
+    }
+
+    #[test]
+    fn move_home_first_gives_same_costs() {
+        let burrows = [
+            Burrow::try_from(".A......BCDABCD").unwrap(),
+            Burrow::try_from(".B.....A.CDABCD").unwrap(),
+            Burrow::try_from(".C.....AB.DABCD").unwrap(),
+            Burrow::try_from(".......BACDABCD").unwrap(),
+            sample_start(),
+            expand_burrow(&sample_start()),
+        ];
+
+        for burrow in burrows {
+            let without_move_home = SearchOptions {
+                move_home_first: false,
+                ..SearchOptions::default()
+            };
+            assert_eq!(
+                find_shortest_path_with(&burrow, &SearchOptions::default()).0,
+                find_shortest_path_with(&burrow, &without_move_home).0
+            );
+        }
+    }
+
+    #[test]
+    fn can_mirror_burrow() {
+        assert_eq!(format!("{}", sample_start().mirror()), ".......ACBCDBAD");
+        assert_eq!(
+            format!("{}", Burrow::try_from("A.B..C.D.BCDA..").unwrap().mirror()),
+            ".B..C.DBC.A..DA"
+        );
+
+        for depth in [2, 4] {
+            assert_eq!(build_goal(depth).mirror(), build_goal(depth));
+        }
+
+        for burrow in [sample_start(), expand_burrow(&sample_start())] {
+            assert_eq!(burrow.mirror().mirror(), burrow);
+            assert_eq!(burrow.canonical(), burrow.mirror().canonical());
+        }
+    }
+
+    #[test]
+    fn mirror_symmetry_gives_same_costs() {
+        let symmetric_costs = [1, 10, 10, 1];
+        let burrows = [
+            Burrow::try_from(".A......BCDABCD").unwrap(),
+            Burrow::try_from(".D.....A.CDABCD").unwrap(),
+            Burrow::try_from(".......ACBCDBAD").unwrap(),
+            sample_start(),
+            sample_start().mirror(),
+            expand_burrow(&sample_start()),
+        ];
+
+        for burrow in burrows {
+            let with_mirror = SearchOptions {
+                costs: symmetric_costs,
+                ..SearchOptions::default()
+            };
+            let without_mirror = SearchOptions {
+                mirror_symmetry: false,
+                costs: symmetric_costs,
+                ..SearchOptions::default()
+            };
+
+            let (energy, expanded) = find_shortest_path_with(&burrow, &with_mirror);
+            let (expected_energy, expected_expanded) =
+                find_shortest_path_with(&burrow, &without_mirror);
+
+            assert_eq!(energy, expected_energy);
+            assert!(expanded <= expected_expanded);
+            // A mirror image is the same puzzle
+            assert_eq!(
+                find_shortest_path_with(&burrow.mirror(), &with_mirror).0,
+                energy
+            );
+        }
+
+        // Most of the gain is seen when the burrow is its own mirror image, as then every state's mirror is reachable
+        let symmetric_start = Burrow::try_from(".......BADCCDAB").unwrap();
+        assert_eq!(symmetric_start.mirror(), symmetric_start);
+        let (energy, expanded) = find_shortest_path_with(
+            &symmetric_start,
+            &SearchOptions {
+                costs: symmetric_costs,
+                ..SearchOptions::default()
+            },
+        );
+        let (expected_energy, expected_expanded) = find_shortest_path_with(
+            &symmetric_start,
+            &SearchOptions {
+                mirror_symmetry: false,
+                costs: symmetric_costs,
+                ..SearchOptions::default()
+            },
+        );
+        assert_eq!(energy, expected_energy);
+        assert!(expanded < expected_expanded * 2 / 3);
+
+        // The actual costs aren't symmetric, so the option is ignored and the search is unchanged
+        let default = find_shortest_path_with(&sample_start(), &SearchOptions::default());
+        let without_mirror = SearchOptions {
+            mirror_symmetry: false,
+            ..SearchOptions::default()
+        };
+        assert_eq!(
+            default,
+            find_shortest_path_with(&sample_start(), &without_mirror)
+        );
+    }
+
+    #[test]
+    fn can_report_progress() {
+        let mut progress = RecordedProgress::default();
+        let (energy, stats) =
+            find_shortest_path_reporting(&sample_start(), &SearchOptions::default(), &mut progress);
+
+        assert_eq!(energy, Some(12521));
+        assert_eq!(progress.updates.len(), stats.expanded);
+        assert_eq!(progress.updates.last(), Some(&(stats.expanded, None)));
+        assert!(progress.finished);
+    }
+
+    #[test]
+    fn can_collect_search_stats() {
+        let (energy, stats) =
+            find_shortest_path_reporting(&sample_start(), &SearchOptions::default(), &mut NoProgress);
+
+        assert_eq!(energy, Some(12521));
+        // Every pop either expands a state, skips a duplicate, or is the goal
+        assert_eq!(stats.queue.popped, stats.expanded + stats.queue.skipped + 1);
+        // Only improvements are pushed, so there can't be more pushes than states generated, plus the start
+        assert!(stats.queue.pushed <= stats.generated + 1);
+        assert!(stats.queue.pushed >= stats.queue.popped);
+        assert!(stats.moved_home > 0 && stats.moved_home < stats.expanded);
+        assert!(stats.queue.max_size <= stats.queue.pushed);
+
+        let ida = SearchOptions {
+            solver: Solver::Ida,
+            ..SearchOptions::default()
+        };
+        let (energy, stats) = find_shortest_path_reporting(&sample_start(), &ida, &mut NoProgress);
+        assert_eq!(energy, Some(12521));
+        assert!(stats.generated > 0);
+        assert_eq!(stats.queue, QueueStats::default());
+
+        let display = format!("{}", stats);
+        assert!(display.starts_with(&format!("Expanded {} states", stats.expanded)));
+        assert_eq!(display.lines().count(), 2);
+    }
+
+    #[test]
+    fn can_expand_burrow() {
+        assert_eq!(
+            format!("{}", expand_burrow(&sample_start())),
+            ".......BCBDDCBADBACADCA"
+        )
+    }
+
+    #[test]
+    fn burrows_round_trip_and_mirror() {
+        check(DEFAULT_CASES, |burrow: &Burrow| {
+            Burrow::try_from(format!("{}", burrow).as_str()) == Ok(burrow.clone())
+                && burrow.mirror().mirror() == *burrow
+                && burrow.canonical() == burrow.mirror().canonical()
+        });
+    }
+
+    #[test]
+    fn mirror_images_cost_the_same_with_symmetric_costs() {
+        let options = SearchOptions {
+            costs: [1, 10, 10, 1],
+            ..SearchOptions::default()
+        };
+
+        check(20, |burrow: &Burrow| {
+            burrow.len > 15
+                || find_shortest_path_with(burrow, &options).0
+                    == find_shortest_path_with(&burrow.mirror(), &options).0
+        });
+    }
+
+    #[test]
+    fn can_trace_shortest_route() {
+        let options = SearchOptions::default();
+        let route = find_shortest_route(&sample_start(), &options).unwrap();
+
+        assert_eq!(route.first(), Some(&(sample_start(), 0)));
+        assert_eq!(route.last(), Some(&(build_goal(2), 12521)));
+        // Each step is one of the moves available from the step before, for the energy it says
+        for pair in route.windows(2) {
+            let (from, from_energy) = &pair[0];
+            let (to, to_energy) = &pair[1];
+            assert!(next_states(from, &options, &mut SearchStats::default())
+                .contains(&(to.clone(), to_energy - from_energy)));
+        }
+
+        let trace = format_route(&route);
+        let lines: Vec<&str> = trace.lines().collect();
+        assert_eq!(lines.len(), route.len());
+        assert_eq!(lines[0], "  0: .......BCBDADCA     +0 = 0");
+        assert_eq!(lines[10], " 10: .......ABCDABCD     +8 = 12521");
+
+        // The same moves as the walkthrough in the puzzle description
+        let energies: Vec<usize> = route.windows(2).map(|pair| pair[1].1 - pair[0].1).collect();
+        assert_eq!(
+            energies,
+            vec![40, 400, 3000, 30, 40, 2000, 3, 3000, 4000, 8]
+        );
+    }
+
+    #[test]
+    fn can_estimate_min_energy() {
+        assert_eq!(min_energy_remaining(&build_goal(2), &COSTS), 0);
+        // 1 across and 1 down
+        assert_eq!(
+            min_energy_remaining(&Burrow::try_from(".A......BCDABCD").unwrap(), &COSTS),
+            2
+        );
+        // B 3 across and 1 down, the A is already settled
+        assert_eq!(
+            min_energy_remaining(&Burrow::try_from(".B.....A.CDABCD").unwrap(), &COSTS),
+            40
+        );
+        // The top A and B are in their own tunnels, but have to step out and back to let the others out. Then two of
+        // each type go down into their tunnels, taking at least 1 + 2 steps.
+        assert_eq!(
+            min_energy_remaining(&Burrow::try_from(".......ABCDBACD").unwrap(), &COSTS),
+            (3 + 4 + 3) + (30 + 40 + 30)
+        );
+        // The estimate never exceeds the actual energy needed
+        for burrow in [sample_start(), expand_burrow(&sample_start())] {
+            assert!(min_energy_remaining(&burrow, &COSTS) <= find_shortest_path(&burrow).unwrap());
+        }
+    }
+
+    #[test]
+    fn ida_gives_same_costs() {
+        let ida = SearchOptions {
+            solver: Solver::Ida,
+            ..SearchOptions::default()
+        };
+        let burrows = [
+            Burrow::try_from(".A......BCDABCD").unwrap(),
+            Burrow::try_from(".B.....A.CDABCD").unwrap(),
+            Burrow::try_from(".C.....AB.DABCD").unwrap(),
+            Burrow::try_from(".......BACDABCD").unwrap(),
+            sample_start(),
+            expand_burrow(&sample_start()),
+        ];
+
+        for burrow in burrows {
+            let (energy, stats) = find_shortest_path_ida(&burrow, &ida);
+            assert_eq!(energy, find_shortest_path(&burrow));
+            assert_eq!(find_shortest_path_with(&burrow, &ida).0, energy);
+            assert!(stats.passes >= 1);
+        }
+
+        // Dijkstra's algorithm has to keep at least every state it expanded, IDA* only keeps the current route and the
+        // fixed size table. For the expanded sample that is 91,450 states against 24 and the table of 65,536.
+        let (_, dijkstra_expanded) =
+            find_shortest_path_with(&expand_burrow(&sample_start()), &SearchOptions::default());
+        let (_, stats) = find_shortest_path_ida(&expand_burrow(&sample_start()), &ida);
+        assert!(stats.max_depth + 1 + IDA_TABLE_SIZE < dijkstra_expanded);
+    }
+
+    #[test]
+    fn can_choose_solver() {
+        let params = Params::parse(["--param", "solver=ida"].map(String::from).into_iter());
+        assert_eq!(SearchOptions::from(&params).solver, Solver::Ida);
+
+        let params = Params::parse(["--param", "solver=bfs"].map(String::from).into_iter());
+        assert_eq!(SearchOptions::from(&params).solver, Solver::Dijkstra);
+        assert_eq!(
+            "bfs".parse::<Solver>(),
+            Err("Unknown solver 'bfs', expected dijkstra or ida".to_string())
+        );
+    }
+}
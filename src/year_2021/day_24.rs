@@ -0,0 +1,903 @@
+//! This is my solution for [Advent of Code - Day 24 - _Arithmetic Logic Unit_](https://adventofcode.com/2021/day/24)
+//!
+//! Today was a challenge of interpreting an assembly language adjacent program and working out the underlying logic
+//! about which inputs it accepts or rejects. Looking through the puzzle data it seemed like a lot of statements were
+//! no-ops so I first wondered if the puzzle was just to factor out the redundant commands and brute0force it, but after
+//! a bit of trying to write a parser that would reduce the program to just the key bits in calculating the final `z`
+//! value. This was a bit of a dead end, but did clue me into the repeating nature of the input. The no-op
+//! operations we're repeated later on, but in the later context they did do something. Also I had written [parse_input]
+//! and [`Instruction::try_from`], and [`Param`] and [`OpType`] to have a structured representation of the commands.
+//! These return a [`ParseError`] for a malformed line rather than panicking, so that the fuzz target in `fuzz/` can
+//! give them arbitrary input.
+//!
+//! At this point I was working things out in a [rather disorganised spreadsheet](https://docs.google.com/spreadsheets/d/1EvNOOa-1rTDfxe4yj2pe-3x6HPskKhz1nLXUFkL9M64/edit)
+//! Sheet 1 is me stepping through the logic. Copy of sheet 1 shows the program split into its repeating sections.
+//!
+//! ![Working out spreadsheet](../../assets/img/day-24.png "A screenshot of my working out spread sheet showing the
+//! program split into 18 row sections laid out next to each other. Underneath there is working showing variables
+//! being added/removed from the stack represented by `z`")
+//!
+//! The MONAD program is a loop that repeats the same 18 lines, with a few variable values. Here is a walk through of
+//! my first 18 line section:
+//! ```text
+//!  1. inp w      |  Read the next input into `w`  
+//!  2. mul x 0    |  Reset `x` to 0
+//!  3. add x z    |  Copy z into x
+//!  4. mod x 26   |  z is treated like a stack of base 26 numbers, this mod sets
+//!                |  `x` to the top of the stack
+//!  5. div z 1    |  The number here is always 1 or 26. `z / 1` is a no-op,
+//!                |  `z / 26` pops the `prev` number from the top of the `z`
+//!                |  stack.
+//!  6. add x 13   |  The number here is variable per section, I'll call it `n`.
+//!                |  This is adding `n` to `prev`.
+//!  7. eql x w    |  `1` if `prev` + `n` == `inout` (`w` is the latest digit
+//!                |  input.)
+//!  8. eql x 0    |  This is inverting line 7 so `prev` + `n` != `input`
+//!  9. mul y 0    |  Reset `y` to 0
+//! 10. add y 25   |  Set `y` to 25
+//! 11. mul y x    |  `x` is 1 if the modified previous stack value != `input`, 0
+//!                |  otherwise. So `y` is now either `0` or `25` based on the
+//!                |  same condition.
+//! 12. add y 1    |  `y` is now either 1 or 26 based on the same condition.
+//! 13. mul z y    |  If `y` is 1, then this is a no-op otherwise this is shifting
+//!                |  0 onto the top of the `z` stack
+//! 14. mul y 0    |  Reset `y` to 0 again
+//! 15. add y w    |  Copy the input into `y`
+//! 16. add y 15   |  The literal here varies, I'll call it `p`.
+//! 17. mul y x    |  Same idea as line 11, if `prev` + `n` != `input` this is a
+//!                |  no-op, if `prev` + `n` == `input` y is now 0.
+//! 18. add z y    |  If `y` is still non-zero, update the 0 we pushed onto the  
+//!                |  stack to be `input` + `p`.
+//! ```
+//!
+//! So written as rust code this could be seen as:
+//! ```rust
+//! fn section(input: isize, z: &mut Vec<isize>, pop: bool, n: isize, p: isize) {
+//!     let x = z.last().unwrap() + n;
+//!
+//!     if pop {z.pop()};
+//!
+//!     if input != x {
+//!         z.push(input + p);
+//!     };
+//! }
+//! ```
+//! It is also worth noting that as `input` is in the range `1` - `9`. So for some of the inputs it is guaranteed
+//! that they'll be pushed onto the stack regardless of input. I stepped through the whole 14 sections by hand and
+//! worked out that for z to be 0 (i.e. the stack is empty) each time `prev + n` could equal the input, it had to
+//! match as the guaranteed pop operations matched the count of the guaranteed pushes, so each time it was possible
+//! not to push we had to take it, or numbers would be left on the stack. This gave me the criteria for the valid
+//! numbers. I made some human errors stepping through the constraints so the number I worked out failed, but since
+//! this is a coding problem I should eliminate that by coding the analysis instead. [`analyse_program`] does just
+//! that. It originally took the program in fixed 18 line chunks, and read the variables from fixed lines. Now
+//! [`split_sections`] splits on the `inp` instructions and [`section_variables`] pattern matches for the instructions
+//! that hold the variables, so small differences in layout still work, and a program with a different structure
+//! returns an [`AnalysisError`] rather than a wrong answer. [`monad_parameters`] exposes the extracted variables as a
+//! list of [`ChunkParams`] for anything else that wants to build on the analysis. Working out the maximum valid model
+//! number (part one), and then part two (the minimum) was a minor modification.
+//!
+//! Later I added an [`Alu`] that interprets any program, so that each part can check the numbers from the analysis
+//! actually result in `z == 0` using [`validate_model_number`]. If the analysis fails, [`search_model_number`] falls
+//! back to searching digit by digit with the [`Alu`], remembering which states are dead ends.
+//!
+//! Overall, whilst there is some satisfaction in having worked out what was going on, I was not a fan of today's
+//! puzzle. The answer was in deduction, so examples that actually helped a solver would have given the game away.
+//! This meant the usual plan of build some tests from the example as a guide doesn't apply and makes the whole
+//! experience more frustrating when it doesn't work (as it didn't for me when stepping through by hand). The only
+//! feedback is that your answer is wrong, but you also can't go looking for hints as to why, as that gives the whole
+//! game away.
+
+use crate::errors::{check_input, check_pattern, to_validation, Validation};
+use crate::year_2021::day_24::Instruction::{Inp, Op};
+use crate::year_2021::day_24::OpType::{Add, Div, Eql, Mod, Mul};
+use crate::year_2021::day_24::Param::{Lit, W, X, Y, Z};
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// Represents a operation's parameter(s) as either one of the four memory addresses or a literal number
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Param {
+    W,
+    X,
+    Y,
+    Z,
+    Lit(isize),
+}
+
+impl TryFrom<&str> for Param {
+    type Error = ParseError;
+
+    /// If the string parses as a number treat it as a literal, otherwise match it to a memory address
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        if let Ok(num) = s.parse() {
+            Ok(Lit(num))
+        } else {
+            match s {
+                "w" => Ok(W),
+                "x" => Ok(X),
+                "y" => Ok(Y),
+                "z" => Ok(Z),
+                _ => Err(ParseError::InvalidParam(s.to_string())),
+            }
+        }
+    }
+}
+
+/// Whilst there are six instructions the `Inp` is different enough from the others that it is easier to split it out.
+/// This then encodes the type of the remaining five op codes.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum OpType {
+    Add,
+    Mul,
+    Div,
+    Mod,
+    Eql,
+}
+
+/// Encode each line as either a read from input, or an operation
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Instruction {
+    Inp(Param),
+    Op(OpType, Param, Param),
+}
+
+/// The ways a line can fail to parse as an [`Instruction`]
+#[derive(Eq, PartialEq, Debug)]
+pub enum ParseError {
+    /// An op code that isn't one of the six the ALU supports
+    UnknownOp(String),
+    /// A parameter that is neither a register nor a number
+    InvalidParam(String),
+    /// An instruction with the wrong number of parameters for its op code
+    WrongParamCount(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnknownOp(op) => write!(f, "unknown op code \"{}\"", op),
+            ParseError::InvalidParam(param) => write!(f, "\"{}\" is not a register or a number", param),
+            ParseError::WrongParamCount(line) => write!(f, "wrong number of parameters in \"{}\"", line),
+        }
+    }
+}
+
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
+
+    /// Parses a line of the input
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let parts: Vec<&str> = s.split(' ').collect();
+        let op_type = match parts[0] {
+            "inp" => None,
+            "add" => Some(Add),
+            "mul" => Some(Mul),
+            "div" => Some(Div),
+            "mod" => Some(Mod),
+            "eql" => Some(Eql),
+            op => return Err(ParseError::UnknownOp(op.to_string())),
+        };
+
+        match (op_type, &parts[1..]) {
+            (None, &[a]) => Ok(Inp(Param::try_from(a)?)),
+            (Some(op_type), &[a, b]) => Ok(Op(op_type, Param::try_from(a)?, Param::try_from(b)?)),
+            _ => Err(ParseError::WrongParamCount(s.to_string())),
+        }
+    }
+}
+
+/// The four registers of the ALU, in the order `w`, `x`, `y`, `z`
+type Registers = [isize; 4];
+
+/// The ways running a program on the [`Alu`] can fail
+#[derive(Eq, PartialEq, Debug)]
+enum AluError {
+    /// A `div` instruction with `b == 0`
+    DivisionByZero,
+    /// A `mod` instruction with `a < 0` or `b <= 0`
+    InvalidModulo,
+    /// An `inp` instruction was reached after all the inputs had been read
+    InputExhausted,
+    /// An instruction tried to write to a literal rather than a register
+    InvalidDestination,
+    /// An `add`, `mul` or `div` instruction with a result that doesn't fit in an `isize`
+    Overflow,
+}
+
+/// A general interpreter for the ALU programs. Unlike [`analyse_program`] this makes no assumptions about the
+/// structure of the program, so is mostly useful to check the results of that analysis.
+#[derive(Eq, PartialEq, Debug, Default)]
+struct Alu {
+    registers: Registers,
+}
+
+impl Alu {
+    fn new() -> Alu {
+        Alu::default()
+    }
+
+    /// The register a parameter refers to, for use as the destination of an instruction
+    fn register_index(param: Param) -> Result<usize, AluError> {
+        match param {
+            W => Ok(0),
+            X => Ok(1),
+            Y => Ok(2),
+            Z => Ok(3),
+            Lit(_) => Err(AluError::InvalidDestination),
+        }
+    }
+
+    /// The current value of a parameter, either the literal value or the contents of the register
+    fn value_of(&self, param: Param) -> isize {
+        match param {
+            Lit(value) => value,
+            register => self.registers[Alu::register_index(register).unwrap()],
+        }
+    }
+
+    /// Run a single instruction, taking the next input from `inputs` if needed.
+    fn step(
+        &mut self,
+        instruction: &Instruction,
+        inputs: &mut impl Iterator<Item = isize>,
+    ) -> Result<(), AluError> {
+        match *instruction {
+            Inp(a) => {
+                let register = Alu::register_index(a)?;
+                self.registers[register] = inputs.next().ok_or(AluError::InputExhausted)?;
+            }
+            Op(op_type, a, b) => {
+                let register = Alu::register_index(a)?;
+                let (a, b) = (self.registers[register], self.value_of(b));
+                self.registers[register] = match op_type {
+                    Add => a.checked_add(b).ok_or(AluError::Overflow)?,
+                    Mul => a.checked_mul(b).ok_or(AluError::Overflow)?,
+                    Div if b == 0 => return Err(AluError::DivisionByZero),
+                    Div => a.checked_div(b).ok_or(AluError::Overflow)?,
+                    Mod if a < 0 || b <= 0 => return Err(AluError::InvalidModulo),
+                    Mod => a % b,
+                    Eql => (a == b) as isize,
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run a whole program, reading from `inputs` in order, and return the final state of the registers.
+    fn execute(
+        &mut self,
+        program: &[Instruction],
+        inputs: &[isize],
+    ) -> Result<Registers, AluError> {
+        let mut inputs = inputs.iter().copied();
+        for instruction in program {
+            self.step(instruction, &mut inputs)?;
+        }
+
+        Ok(self.registers)
+    }
+}
+
+/// Split a model number into its digits, and run them through the program on a fresh [`Alu`]. A model number is valid
+/// if `z` is `0` at the end.
+fn validate_model_number(program: &[Instruction], model_number: isize) -> Result<bool, AluError> {
+    let digits: Vec<isize> = model_number
+        .to_string()
+        .chars()
+        .map(|c| c.to_digit(10).unwrap() as isize)
+        .collect();
+
+    let [_, _, _, z] = Alu::new().execute(program, &digits)?;
+    Ok(z == 0)
+}
+
+/// Check each line is an ALU instruction, see [`validate`], and parse them as the MONAD program for [`part_one`]
+/// and [`part_two`].
+pub fn parse(contents: &str) -> Vec<Instruction> {
+    check_input(24, contents, validate);
+    parse_input(contents).unwrap_or_else(|err| panic!("Invalid program: {}", err))
+}
+
+/// The largest model number the program accepts
+pub fn part_one(program: &[Instruction]) -> Option<String> {
+    let max = find_model_number(program, true);
+    say!(
+        "The maximum model number is {}, valid: {:?}.",
+        max,
+        validate_model_number(program, max)
+    );
+
+    Some(max.to_string())
+}
+
+/// The smallest model number the program accepts
+pub fn part_two(program: &[Instruction]) -> Option<String> {
+    let min = find_model_number(program, false);
+    say!(
+        "The minimum model number is {}, valid: {:?}.",
+        min,
+        validate_model_number(program, min)
+    );
+
+    Some(min.to_string())
+}
+
+/// Find the largest or smallest model number with [`analyse_program`], falling back to [`search_model_number`] if the
+/// program can't be analysed
+fn find_model_number(program: &[Instruction], largest: bool) -> isize {
+    match analyse_program(program.to_vec()) {
+        Ok((min, max)) => {
+            if largest {
+                max
+            } else {
+                min
+            }
+        }
+        Err(err) => {
+            say!("Analysis failed: {:?}, searching instead.", err);
+            search_model_number(program, largest).expect("No valid model number")
+        }
+    }
+}
+
+/// Each line should be an ALU instruction, see [`Instruction`]
+pub fn validate(input: &str) -> Validation {
+    to_validation(check_pattern(
+        input,
+        r"inp [wxyz]|(add|mul|div|mod|eql) [wxyz] ([wxyz]|-?\d+)",
+        "an ALU instruction",
+    ))
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input is a program, so it is enough for it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// Parse each line of the puzzle input program return with [`Instruction::try_from`], return the program as a list fo
+/// instructions, or the error for the first line that fails.
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input.lines().map(Instruction::try_from).collect()
+}
+
+/// The ways a program can fail to match the MONAD structure that [`analyse_program`] relies on
+#[derive(Eq, PartialEq, Debug)]
+pub enum AnalysisError {
+    /// The program doesn't start with an `inp` instruction, so can't be split into sections
+    NoSections,
+    /// The section with the given index is missing one of the instructions, or variables, the analysis needs
+    UnexpectedStructure(usize, &'static str),
+    /// The section with the given index can avoid a push, but there is nothing on the stack to compare with
+    EmptyStack(usize),
+    /// There are this many values that will always be left on the stack, so `z` can never be `0`
+    UnbalancedStack(usize),
+}
+
+/// Split the program into sections, each starting with an `inp` instruction. This doesn't assume the sections are a
+/// fixed length.
+fn split_sections(program: &[Instruction]) -> Result<Vec<&[Instruction]>, AnalysisError> {
+    if !matches!(program.first(), Some(Inp(_))) {
+        return Err(AnalysisError::NoSections);
+    }
+
+    let starts: Vec<usize> = program
+        .iter()
+        .positions(|instruction| matches!(instruction, Inp(_)))
+        .chain([program.len()])
+        .collect();
+
+    Ok(starts
+        .iter()
+        .tuple_windows()
+        .map(|(&start, &end)| &program[start..end])
+        .collect())
+}
+
+/// Find the `(div, n, p)` variables for a section by looking for the instructions that use them, rather than relying
+/// on them being at fixed lines. See the walk through in the module documentation for what each is.
+/// - `div` is from `div z <div>`, and must be `1` or `26`
+/// - `n` is from `add x <n>`, after `mod x 26`, and the section must then compare `x` with the input using `eql x w`
+/// - `p` is from `add y <p>` directly after `add y w`
+fn section_variables(
+    index: usize,
+    section: &[Instruction],
+) -> Result<(isize, isize, isize), AnalysisError> {
+    let error = |reason| AnalysisError::UnexpectedStructure(index, reason);
+
+    let mod_pos = section
+        .iter()
+        .position(|&instruction| instruction == Op(Mod, X, Lit(26)))
+        .ok_or(error("missing mod x 26"))?;
+
+    let div = section
+        .iter()
+        .find_map(|&instruction| match instruction {
+            Op(Div, Z, Lit(div)) if div == 1 || div == 26 => Some(div),
+            _ => None,
+        })
+        .ok_or(error("missing div z 1 or div z 26"))?;
+
+    let (n_pos, n) = section
+        .iter()
+        .enumerate()
+        .skip(mod_pos)
+        .find_map(|(pos, &instruction)| match instruction {
+            Op(Add, X, Lit(n)) => Some((pos, n)),
+            _ => None,
+        })
+        .ok_or(error("missing add x <n>"))?;
+
+    if !section[n_pos..].contains(&Op(Eql, X, W)) {
+        return Err(error("missing eql x w"));
+    }
+
+    let p = section
+        .iter()
+        .tuple_windows()
+        .find_map(|pair| match pair {
+            (&Op(Add, Y, W), &Op(Add, Y, Lit(p))) => Some(p),
+            _ => None,
+        })
+        .ok_or(error("missing add y w, add y <p>"))?;
+
+    Ok((div, n, p))
+}
+
+/// The constants that vary between the sections of a MONAD program. See the walk through in the module documentation
+/// for how each is used.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct ChunkParams {
+    /// True if the section divides `z` by 26, popping the top of the stack
+    pub pops: bool,
+    /// Added to the top of the stack before comparing it with the input
+    pub n: isize,
+    /// Added to the input when it is pushed to the stack
+    pub p: isize,
+}
+
+/// Extract the [`ChunkParams`] for each section of a MONAD program, using [`split_sections`] and
+/// [`section_variables`]. This is the structured view of the program that [`analyse_program`] works from, and fails
+/// with the index of the first section that doesn't match the expected structure.
+pub fn monad_parameters(program: &[Instruction]) -> Result<Vec<ChunkParams>, AnalysisError> {
+    split_sections(program)?
+        .iter()
+        .enumerate()
+        .map(|(i, section)| {
+            let (div, n, p) = section_variables(i, section)?;
+            Ok(ChunkParams {
+                pops: div == 26,
+                n,
+                p,
+            })
+        })
+        .collect()
+}
+
+/// First extract the variables for each section with [`monad_parameters`]. Tracking what `input + p` values are on the
+/// stack, and where it is possible to avoid pushing to the stack, storing that as a condition. Then iterate through
+/// these conditions working out where parts of the input are constrained by them and updating the minimum and maximum
+/// numbers as appropriate. Finally return this minimum (part two) and maximum (part one), or an error if the program
+/// doesn't have the expected structure.
+fn analyse_program(program: Vec<Instruction>) -> Result<(isize, isize), AnalysisError> {
+    // track the guaranteed push and pop operations
+    let mut stack: Vec<(usize, isize)> = Vec::new();
+    // track the conditions that prevent pushing to the stack
+    let mut conditions: Vec<(usize, usize, isize)> = Vec::new();
+    let parameters = monad_parameters(&program)?;
+
+    for (i, &ChunkParams { pops, n, p }) in parameters.iter().enumerate() {
+        // peek at the top of the stack, an empty stack is only a problem if we need to compare with it.
+        let prev = stack.last().copied();
+
+        // `div` was either 1 or 26. If it's 26 this causes a pop from the stack.
+        if pops {
+            stack.pop();
+        }
+
+        // The previous input plus the previous `p` plus the current `n` must equal the current input to prevent
+        // pushing to the stack. Since both inputs are 1 - 9, this is only possible if `n + prev_p` is between -8 and 8
+        let prev_p = prev.map(|(_, prev_p)| prev_p).unwrap_or(0);
+        if (-8..=8).contains(&(n + prev_p)) {
+            let (prev_key, _) = prev.ok_or(AnalysisError::EmptyStack(i))?;
+            // We have to prevent all unnecessary pushes, so record the condition that will prevent this push
+            conditions.push((i, prev_key, n + prev_p));
+        } else {
+            // Otherwise record that this `input + p` must be pushed to the top of the stack
+            stack.push((i, p));
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(AnalysisError::UnbalancedStack(stack.len()));
+    }
+
+    // Without conditions the min is 11111111111111 and the max is 99999999999999. Use these as starting values...
+    let mut min = vec![9; parameters.len()];
+    let mut max = vec![1; parameters.len()];
+    // then loop through the conditions applying their constraints, which are in the form `input_a` == `input_b` + `v`
+    for (a, b, v) in conditions {
+        // b - something == a so b can be as high as possible (9) a can be as low as possible (1) and a can only go
+        // up to `9 - mod(v)`, and b can only go down to `9 + mod(v)`
+        if v < 0 {
+            max[a] = 9 + v;
+            max[b] = 9;
+
+            min[b] = 1 - v;
+            min[a] = 1;
+        }
+        // otherwise v is positive and it works the other way round.
+        else {
+            max[b] = 9 - v;
+            max[a] = 9;
+
+            min[a] = 1 + v;
+            min[b] = 1;
+        }
+    }
+
+    // convert the calculated arrays of digits into numbers and return the min/max pair.
+    Ok((
+        min.iter().fold(0, |acc, &v| (acc * 10) + v),
+        max.iter().fold(0, |acc, &v| (acc * 10) + v),
+    ))
+}
+
+/// A fallback for programs that [`analyse_program`] can't handle. Run the program a section at a time on the [`Alu`],
+/// trying each digit in turn, from 9 down when looking for the `largest` model number, or from 1 up otherwise. The
+/// first complete number found that leaves `z` as `0` is the answer.
+///
+/// In MONAD only `z` carries information from one section to the next, so the outcome of the rest of the program only
+/// depends on the section reached and `z`. That allows memoising the states that are known to be dead ends, which is
+/// what keeps the search tractable. So that this holds for other programs, the memo is keyed on the values of the
+/// registers the section reads before it writes to them, see [`registers_read`], rather than just `z`. Any section that
+/// errors on the [`Alu`] is treated as rejecting that digit.
+fn search_model_number(program: &[Instruction], largest: bool) -> Option<isize> {
+    fn search(
+        sections: &[(&[Instruction], [bool; 4])],
+        section: usize,
+        registers: Registers,
+        digits: &[isize],
+        dead_ends: &mut HashSet<(usize, Registers)>,
+    ) -> Option<isize> {
+        if section == sections.len() {
+            return if registers[3] == 0 { Some(0) } else { None };
+        }
+
+        let (instructions, read) = sections[section];
+        let mut key = registers;
+        (0..4).filter(|&i| !read[i]).for_each(|i| key[i] = 0);
+        if dead_ends.contains(&(section, key)) {
+            return None;
+        }
+
+        let found = digits.iter().find_map(|&digit| {
+            let mut alu = Alu { registers };
+            let next = alu.execute(instructions, &[digit]).ok()?;
+            let rest = search(sections, section + 1, next, digits, dead_ends)?;
+            let place = 10isize.pow((sections.len() - section - 1) as u32);
+
+            Some(digit * place + rest)
+        });
+
+        if found.is_none() {
+            dead_ends.insert((section, key));
+        }
+
+        found
+    }
+
+    let sections: Vec<(&[Instruction], [bool; 4])> = split_sections(program)
+        .ok()?
+        .into_iter()
+        .map(|section| (section, registers_read(section)))
+        .collect();
+    let digits: Vec<isize> = if largest {
+        (1..=9).rev().collect()
+    } else {
+        (1..=9).collect()
+    };
+
+    search(&sections, 0, Registers::default(), &digits, &mut HashSet::new())
+}
+
+/// Which of the registers, in the order `w`, `x`, `y`, `z`, a section reads before writing to them, i.e. the ones it
+/// takes from the sections before it. `mul a 0` is treated as a write to `a`, as MONAD uses it to clear registers.
+fn registers_read(section: &[Instruction]) -> [bool; 4] {
+    let mut written = [false; 4];
+    let mut read = [false; 4];
+    let mut reads = |param: Param, written: &[bool; 4]| {
+        if let Ok(register) = Alu::register_index(param) {
+            read[register] |= !written[register];
+        }
+    };
+
+    for &instruction in section {
+        let destination = match instruction {
+            Inp(a) => a,
+            Op(Mul, a, Lit(0)) => a,
+            Op(_, a, b) => {
+                reads(a, &written);
+                reads(b, &written);
+                a
+            }
+        };
+        if let Ok(register) = Alu::register_index(destination) {
+            written[register] = true;
+        }
+    }
+
+    read
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year_2021::day_24::Instruction::{Inp, Op};
+    use crate::year_2021::day_24::OpType::{Eql, Mul};
+    use crate::year_2021::day_24::Param::{Lit, X, Z};
+    use crate::year_2021::day_24::{
+        analyse_program, monad_parameters, parse_input, registers_read, search_model_number, section_variables,
+        split_sections, validate_model_number, Alu, AluError, AnalysisError, ChunkParams,
+        Instruction, ParseError,
+    };
+
+    /// The (div, n, p) variables for each 18-line section of a MONAD program. Taken from a real puzzle input.
+    const MONAD_VARIABLES: [(isize, isize, isize); 14] = [
+        (1, 13, 15),
+        (1, 13, 16),
+        (1, 10, 4),
+        (1, 15, 14),
+        (26, -8, 1),
+        (26, -10, 5),
+        (1, 11, 1),
+        (26, -3, 3),
+        (1, 14, 3),
+        (26, -4, 7),
+        (1, 14, 5),
+        (26, -5, 13),
+        (26, -8, 3),
+        (26, -11, 10),
+    ];
+
+    /// Build a MONAD program from the (div, n, p) variables for each section
+    fn monad_program(variables: &[(isize, isize, isize)]) -> Vec<Instruction> {
+        let source: Vec<String> = variables
+            .iter()
+            .map(|(div, n, p)| {
+                format!(
+                    "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\nmul y 0\n\
+                    add y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\nmul y x\nadd z y",
+                    div, n, p
+                )
+            })
+            .collect();
+
+        parse_input(&source.join("\n")).unwrap()
+    }
+
+    #[test]
+    fn can_parse() {
+        assert_eq!(
+            parse_input("inp x\nmul x -1").unwrap(),
+            Vec::from([Inp(X), Op(Mul, X, Lit(-1))])
+        );
+        assert_eq!(
+            parse_input("inp z\ninp x\nmul z 3\neql z x").unwrap(),
+            Vec::from([Inp(Z), Inp(X), Op(Mul, Z, Lit(3)), Op(Eql, Z, X)])
+        )
+    }
+
+    #[test]
+    fn can_report_malformed_instructions() {
+        assert_eq!(parse_input("nop x"), Err(ParseError::UnknownOp("nop".to_string())));
+        assert_eq!(parse_input("inp x\n"), Ok(Vec::from([Inp(X)])));
+        assert_eq!(parse_input("inp x\n\ninp y"), Err(ParseError::UnknownOp("".to_string())));
+        assert_eq!(parse_input("add x a"), Err(ParseError::InvalidParam("a".to_string())));
+        assert_eq!(
+            parse_input("mul x 99999999999999999999"),
+            Err(ParseError::InvalidParam("99999999999999999999".to_string()))
+        );
+        for line in ["inp", "inp x y", "add x", "eql x y z", "mod  x y"] {
+            assert_eq!(parse_input(line), Err(ParseError::WrongParamCount(line.to_string())));
+        }
+    }
+
+    #[test]
+    fn can_execute() {
+        let negate = parse_input("inp x\nmul x -1").unwrap();
+        assert_eq!(Alu::new().execute(&negate, &[7]), Ok([0, -7, 0, 0]));
+
+        let is_triple = parse_input("inp z\ninp x\nmul z 3\neql z x").unwrap();
+        assert_eq!(Alu::new().execute(&is_triple, &[3, 9]), Ok([0, 9, 0, 1]));
+        assert_eq!(Alu::new().execute(&is_triple, &[3, 8]), Ok([0, 8, 0, 0]));
+
+        let binary = parse_input(
+            "inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\ndiv w 2\nadd x w\nmod x 2\n\
+            div w 2\nmod w 2",
+        ).unwrap();
+        assert_eq!(Alu::new().execute(&binary, &[13]), Ok([1, 1, 0, 1]));
+    }
+
+    #[test]
+    fn can_report_alu_errors() {
+        let program = parse_input("inp x\ninp y\ndiv x y").unwrap();
+        assert_eq!(
+            Alu::new().execute(&program, &[1]),
+            Err(AluError::InputExhausted)
+        );
+        assert_eq!(
+            Alu::new().execute(&program, &[1, 0]),
+            Err(AluError::DivisionByZero)
+        );
+
+        let program = parse_input("inp x\ninp y\nmod x y").unwrap();
+        assert_eq!(
+            Alu::new().execute(&program, &[-1, 2]),
+            Err(AluError::InvalidModulo)
+        );
+        assert_eq!(
+            Alu::new().execute(&program, &[1, 0]),
+            Err(AluError::InvalidModulo)
+        );
+
+        let program = parse_input("add 1 x").unwrap();
+        assert_eq!(
+            Alu::new().execute(&program, &[]),
+            Err(AluError::InvalidDestination)
+        );
+
+        let program = parse_input("inp x\ninp y\ndiv x y").unwrap();
+        assert_eq!(
+            Alu::new().execute(&program, &[isize::MIN, -1]),
+            Err(AluError::Overflow)
+        );
+        let program = parse_input("inp x\nmul x x").unwrap();
+        assert_eq!(
+            Alu::new().execute(&program, &[isize::MAX]),
+            Err(AluError::Overflow)
+        );
+        let program = parse_input("inp x\nadd x 1").unwrap();
+        assert_eq!(
+            Alu::new().execute(&program, &[isize::MAX]),
+            Err(AluError::Overflow)
+        );
+    }
+
+    #[test]
+    fn analysed_model_numbers_are_valid() {
+        let program = monad_program(&MONAD_VARIABLES);
+        let (min, max) = analyse_program(program.clone())
+            .unwrap_or_else(|err| panic!("Failed to analyse program: {:?}", err));
+
+        assert_eq!(validate_model_number(&program, max), Ok(true));
+        assert_eq!(validate_model_number(&program, min), Ok(true));
+        assert_eq!(validate_model_number(&program, max - 1), Ok(false));
+    }
+
+    #[test]
+    fn can_extract_section_variables() {
+        let program = monad_program(&MONAD_VARIABLES);
+        let sections = split_sections(&program).unwrap();
+        assert_eq!(sections.len(), 14);
+
+        for (i, section) in sections.iter().enumerate() {
+            assert_eq!(section.len(), 18);
+            assert_eq!(section_variables(i, section), Ok(MONAD_VARIABLES[i]));
+        }
+
+        // Reordering the independent parts of the section, and adding a no-op, should still work
+        let section = parse_input("inp w\nmul x 0\nadd x z\nmod x 26\nadd x 13\ndiv z 1\neql x w\neql x 0\nadd z 0\n\
+            mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y 15\nmul y x\nadd z y").unwrap();
+        assert_eq!(section_variables(0, &section), Ok((1, 13, 15)));
+
+        let section = parse_input("inp w\nmul x 0\nadd x z\ndiv z 1").unwrap();
+        assert_eq!(
+            section_variables(3, &section),
+            Err(AnalysisError::UnexpectedStructure(3, "missing mod x 26"))
+        );
+    }
+
+    #[test]
+    fn can_extract_monad_parameters() {
+        let parameters = monad_parameters(&monad_program(&MONAD_VARIABLES)).unwrap();
+        let expected: Vec<ChunkParams> = MONAD_VARIABLES
+            .iter()
+            .map(|&(div, n, p)| ChunkParams {
+                pops: div == 26,
+                n,
+                p,
+            })
+            .collect();
+        assert_eq!(parameters, expected);
+
+        let mut program = monad_program(&MONAD_VARIABLES[0..3]);
+        program.retain(|instruction| *instruction != parse_input("add y w").unwrap()[0]);
+        assert_eq!(
+            monad_parameters(&program),
+            Err(AnalysisError::UnexpectedStructure(
+                0,
+                "missing add y w, add y <p>"
+            ))
+        );
+    }
+
+    #[test]
+    fn can_analyse_other_monad_variants() {
+        // A shorter program, with two pairs of digits
+        let program = monad_program(&[(1, 12, 4), (1, 11, 2), (26, -6, 1), (26, -1, 7)]);
+        let (min, max) = analyse_program(program.clone()).unwrap();
+        assert_eq!((min, max), (1514, 6959));
+        assert_eq!(validate_model_number(&program, min), Ok(true));
+        assert_eq!(validate_model_number(&program, max), Ok(true));
+
+        // A push that is never popped
+        let program = monad_program(&[(1, 12, 4), (1, 11, 2), (26, -6, 1)]);
+        assert_eq!(
+            analyse_program(program),
+            Err(AnalysisError::UnbalancedStack(1))
+        );
+
+        // Not a MONAD program
+        let program = parse_input("inp x\nmul x -1").unwrap();
+        assert_eq!(
+            analyse_program(program),
+            Err(AnalysisError::UnexpectedStructure(0, "missing mod x 26"))
+        );
+        assert_eq!(analyse_program(Vec::new()), Err(AnalysisError::NoSections));
+    }
+
+    #[test]
+    fn can_search_for_model_numbers() {
+        let program = monad_program(&[(1, 12, 4), (1, 11, 2), (26, -6, 1), (26, -1, 7)]);
+        assert_eq!(search_model_number(&program, true), Some(6959));
+        assert_eq!(search_model_number(&program, false), Some(1514));
+
+        // The search isn't limited to the MONAD structure, here the digits have to sum to 12, and the last digit has
+        // to be even.
+        let program = parse_input(
+            "inp w\nadd z w\ninp w\nadd z w\ninp w\nadd z w\nmod w 2\nmul w 12\nadd z w\n\
+            add z -12",
+        ).unwrap();
+        assert!(analyse_program(program.clone()).is_err());
+        assert_eq!(search_model_number(&program, true), Some(912));
+        assert_eq!(search_model_number(&program, false), Some(138));
+
+        // Here the first digit is carried to the second section in y, rather than z, so has to be 3
+        let program = parse_input("inp w\nadd y w\ninp w\nadd z y\nadd z -3").unwrap();
+        assert_eq!(search_model_number(&program, true), Some(39));
+        assert_eq!(search_model_number(&program, false), Some(31));
+
+        // No number is valid, z will never be 0
+        let program = parse_input("inp z").unwrap();
+        assert_eq!(search_model_number(&program, true), None);
+    }
+
+    #[test]
+    fn can_find_registers_read() {
+        let monad = monad_program(&[(1, 12, 4)]);
+        assert_eq!(registers_read(&monad), [false, false, false, true]);
+
+        let program = parse_input("inp w\nadd x y\nmul y 0\nadd y w\neql z x").unwrap();
+        assert_eq!(registers_read(&program), [false, true, true, true]);
+    }
+
+    #[test]
+    fn search_agrees_with_analysis() {
+        // The full 14 digits takes too long to search in an unoptimised test build
+        let program = monad_program(&[
+            (1, 12, 4),
+            (1, 11, 2),
+            (1, 10, 5),
+            (26, -6, 1),
+            (1, 13, 8),
+            (26, -12, 3),
+            (26, -1, 7),
+            (26, -3, 2),
+        ]);
+        let (min, max) = analyse_program(program.clone()).unwrap();
+
+        assert_eq!(search_model_number(&program, true), Some(max));
+        assert_eq!(search_model_number(&program, false), Some(min));
+    }
+}
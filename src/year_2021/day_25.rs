@@ -0,0 +1,910 @@
+//! This is my solution for [Advent of Code - Day 25 - _Sea Cucumber_](https://adventofcode.com/2021/day/25)
+//!
+//! After the last two days, today was a refreshing change back to a more 'normal' Advent of Code puzzle. We were given
+//! a grid representing herds of Sea Cucumbers wanting to move in a certain direction and had to iterate those moves
+//! until the grid stabilised with all the sea cucumbers blocked by another one. A slight twist was some 'strong
+//! currents' that caused the grid to wrap around in both x and y.
+//!
+//! [`Cell`] represents the three possibilities for any cell in the grid: Empty, Rightwards moving cucumber,
+//! downwards moving cucumber. [`Grid`] stores the whole grid similar to [`crate::util::grid`], but different enough
+//! that it was easier to just re-implement it. [Grid::from] parses the puzzle input with help from [`Cell::try_from`].
+//! [`Grid::fmt`] and [`Cell::fmt`] go the other way for ease of testing. [`Grid::get`], [`Grid::pos_of`],
+//! [`Grid::swap`], and [`Grid::can_move`] are all utilities that help with iterating the grid. [`Grid::iterate`]
+//! completes a single iteration step of each herd trying to move. This is where the one efficiency trick of the day is
+//! apparent. The grid has a caches of the cucumbers that *might* be able to move. Starting with all the cucumbers
+//! assigned to a cache for their direction, only 1) the sea cucumbers that have moved, and 2) the sea cucumbers
+//! positioned so they could move into the vacated cell, are added to the active set for the next iteration. This
+//! achieves two things: It limits the cells we need to check each iteration, and it is a handy indicator of if the
+//! grid has stabilised. As only moving cucumbers cause additions to the active sets, the grid is stable if and only
+//! if the two caches are empty.
+//!
+//! The bookkeeping for the active sets is easy to get wrong though, and it turns out not to be faster. Hashing the
+//! positions costs more than just checking every cell, so part one now uses [`DoubleBuffer`], which works out each
+//! herd's move from a copy of the whole grid before it moved, and took about half the time on a full sized grid. The
+//! active sets are still used to watch the herds with `--visualise` or `--repl`, where they show how much of the grid
+//! is still moving, and `--compare` runs both to check they agree, see [`alternatives`]. Checking them against each
+//! other on random grids found that a grid with nothing to move was counted as stable after `0` steps rather than `1`.
+//!
+//! Not every grid stabilises, a herd with a gap it can keep moving round never stops. [`Grid::iterate_until_static`]
+//! watches for the grid repeating with [`crate::util::cycle`] as it goes, so that it reports the loop rather than never
+//! returning.
+//! [`Grid::step_history`] lists how many of each herd moved at each step, and how many were blocked, to show how
+//! quickly the herds settle, and [`Grid::count_moved`] gives the total for the last step, which `--repl` also shows.
+//!
+//! That solves part one, and part two was the traditional "finish all the tasks and click the button to resolve the
+//! plot" task. I was able to complete each task on the day this year (just - day 24 was finally done at 2am on 25th
+//! UTC, so 3 hours before the cutoff), so this was already complete for me.
+//!
+//! ```text
+//!       --------Part 1--------   --------Part 2--------
+//! Day       Time   Rank  Score       Time   Rank  Score
+//!  25   15:06:43   8974      0   15:07:35   5460      0
+//!  24   21:15:47   5841      0   21:16:30   5687      0
+//!  23   08:47:15   5672      0   19:27:15   6218      0
+//!  22   07:00:04   9762      0   07:01:31   3769      0
+//!  21   05:37:25  11193      0   06:36:50   6331      0
+//!  20   07:51:38   8898      0   07:58:09   8586      0
+//!  19   13:14:31   5465      0   13:37:20   5294      0
+//!  18   19:49:26  12383      0   20:00:08  12192      0
+//!  17   04:47:21  10448      0   05:52:20  10205      0
+//!  16   07:07:32   9928      0   07:16:09   8599      0
+//!  15   05:47:19  12724      0   07:05:51  10514      0
+//!  14   05:39:34  20863      0   07:04:42  14630      0
+//!  13   05:19:33  17344      0   05:40:01  16739      0
+//!  12   14:30:26  26961      0   14:55:24  24961      0
+//!  11   08:27:10  21478      0   08:32:38  21179      0
+//!  10   07:15:30  27171      0   08:06:12  26391      0
+//!   9   08:32:37  32148      0   11:48:09  27638      0
+//!   8   07:01:34  32052      0   08:06:36  19511      0
+//!   7   04:14:51  28137      0   04:40:14  27200      0
+//!   6   04:50:17  27578      0   04:53:08  20498      0
+//!   5   07:05:00  26127      0   07:56:49  24779      0
+//!   4   14:56:36  42423      0   15:45:56  40155      0
+//!   3   06:10:06  46408      0   08:06:50  34497      0
+//!   2   04:22:10  39520      0   04:29:07  37024      0
+//!   1   08:11:39  47103      0   09:01:48  43667      0
+//! ```
+
+use crate::errors::{check_grid, check_input, to_validation, Validation};
+use crate::solution::{Alternative, Part};
+use crate::util::cycle::{cycle_start, Cycle, CycleDetector};
+use crate::util::render::{animate, frame_delay, visualise_enabled, FrameSource, Renderable};
+use crate::util::repl::{repl_enabled, run_repl, Steppable};
+use std::collections::HashSet;
+use std::fmt::{Debug, Display, Formatter};
+use Cell::{DOWN, EMPTY, RIGHT};
+/// Represent the current state of a cell in the grid
+#[derive(Eq, PartialEq, Copy, Clone)]
+enum Cell {
+    EMPTY,
+    DOWN,
+    RIGHT,
+}
+
+impl TryFrom<char> for Cell {
+    type Error = ();
+    /// Parse the the ascii for a cell as the relevant [`Cell`] value
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(EMPTY),
+            '>' => Ok(RIGHT),
+            'v' => Ok(DOWN),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Debug for Cell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for Cell {
+    /// Display cells as their puzzle inout character representation
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                EMPTY => ".",
+                DOWN => "v",
+                RIGHT => ">",
+            }
+        )
+    }
+}
+
+/// Represent a grid as a vector of cells, with a width and height to enable quick lookups from x/y co-ordinates, and
+/// to help with wrapping around logic. Also keep [`HashSet`]s of the RIGHT and DOWN cells that may be able to move,
+/// to limit the cells we need to check when iterating the grid
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Grid {
+    /// The cells of the grid as a single list
+    cells: Vec<Cell>,
+    /// Cache the grid width
+    width: usize,
+    /// Cache teh grid height
+    height: usize,
+    /// The cells with a RIGHTwards moving sea cucumber that may be able to move
+    active_right: HashSet<(usize, usize)>,
+    /// The cells with a DOWNwards moving sea cucumber that may be able to move
+    active_down: HashSet<(usize, usize)>,
+    /// The number of iterations completed
+    steps: usize,
+    /// The number of RIGHT and DOWN sea cucumbers that moved in the last iteration
+    moved: (usize, usize),
+}
+
+/// How the herds moved in one iteration of a [`Grid`], see [`Grid::step_history`]
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct StepStats {
+    /// The iteration these are for, the first being `1`
+    pub step: usize,
+    /// The number of RIGHTwards moving sea cucumbers that moved
+    pub moved_right: usize,
+    /// The number of DOWNwards moving sea cucumbers that moved
+    pub moved_down: usize,
+    /// The number of sea cucumbers in either herd that couldn't move
+    pub blocked: usize,
+}
+
+impl From<&str> for Grid {
+    /// Parse the puzzle input as a grid, building the initial active sets to include all the sea cucumbers of the
+    /// relevant type
+    fn from(s: &str) -> Self {
+        let mut width = 0;
+        let mut height = 0;
+        let mut active_right = HashSet::new();
+        let mut active_down = HashSet::new();
+        let mut cells = Vec::new();
+        for (y, line) in s.lines().enumerate() {
+            width = width.max(line.len());
+            height += 1;
+            for (x, chr) in line.chars().enumerate() {
+                match Cell::try_from(chr) {
+                    Ok(RIGHT) => {
+                        active_right.insert((x, y));
+                        cells.push(RIGHT)
+                    }
+                    Ok(DOWN) => {
+                        active_down.insert((x, y));
+                        cells.push(DOWN)
+                    }
+                    _ => cells.push(EMPTY),
+                };
+            }
+        }
+
+        Grid {
+            cells,
+            width,
+            height,
+            active_right,
+            active_down,
+            steps: 0,
+            moved: (0, 0),
+        }
+    }
+}
+
+impl Grid {
+    /// Get the current value of a given cell co-ordinate, or None if it is out of bounds for the grid
+    fn get(&self, x: usize, y: usize) -> Option<&Cell> {
+        if x >= self.width || y >= self.height {
+            None
+        } else {
+            self.cells.get(self.pos_of(x, y))
+        }
+    }
+
+    /// Convert x, y co-ordinates to an index in the underlying list of cells.
+    fn pos_of(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    /// Swap the values of two cells - used when sea cucumbers move
+    fn swap(&mut self, (x1, y1): (usize, usize), (x2, y2): (usize, usize)) {
+        let pos1 = self.pos_of(x1, y1);
+        let pos2 = self.pos_of(x2, y2);
+        self.cells.swap(pos1, pos2)
+    }
+
+    /// We are overly optimistic when building the active sets. Given the co-ordinates of a sea cucumber and it's
+    /// direction of travel, check if it's next cell is actually available to move into.
+    fn can_move(&self, x: usize, y: usize, direction: Cell) -> bool {
+        match direction {
+            RIGHT => self.get((x + 1) % self.width, y) == Some(&EMPTY),
+            DOWN => self.get(x, (y + 1) % self.height) == Some(&EMPTY),
+            _ => false,
+        }
+    }
+
+    /// Do a full iteration of the grid in-place, moving RIGHTs that can move rightwards, then DOWNs that can move
+    /// downwards. Calculate the new active set as all the cucumbers that moved, plus any that can move into the
+    /// vacated space.
+    fn iterate(&mut self) {
+        let mut new_active_right = HashSet::new();
+
+        let move_right: Vec<(usize, usize)> = self
+            .active_right
+            .iter()
+            .filter(|(x, y)| self.can_move(*x, *y, RIGHT))
+            .map(|&(x, y)| (x, y))
+            .collect();
+
+        self.moved.0 = move_right.len();
+        for (x, y) in move_right.clone() {
+            let next_x = (x + 1) % self.width;
+            self.swap((x, y), (next_x, y));
+
+            new_active_right.insert((next_x, y));
+
+            let prev_x = if x == 0 { self.width - 1 } else { x - 1 };
+            if self.get(prev_x, y) == Some(&RIGHT) {
+                new_active_right.insert((prev_x, y));
+            }
+
+            let prev_y = if y == 0 { self.height - 1 } else { y - 1 };
+            if self.get(x, prev_y) == Some(&DOWN) {
+                self.active_down.insert((x, prev_y));
+            }
+        }
+
+        self.active_right = new_active_right;
+
+        let mut new_active_down = HashSet::new();
+        let move_down: Vec<(usize, usize)> = self
+            .active_down
+            .iter()
+            .filter(|(x, y)| self.can_move(*x, *y, DOWN))
+            .map(|&(x, y)| (x, y))
+            .collect();
+
+        self.moved.1 = move_down.len();
+        for (x, y) in move_down.clone() {
+            let next_y = (y + 1) % self.height;
+            self.swap((x, y), (x, next_y));
+
+            new_active_down.insert((x, next_y));
+
+            let prev_x = if x == 0 { self.width - 1 } else { x - 1 };
+            if self.get(prev_x, y) == Some(&RIGHT) {
+                self.active_right.insert((prev_x, y));
+            }
+
+            let prev_y = if y == 0 { self.height - 1 } else { y - 1 };
+            if self.get(x, prev_y) == Some(&DOWN) {
+                new_active_down.insert((x, prev_y));
+            }
+        }
+
+        self.active_down = new_active_down;
+        self.steps += 1;
+    }
+
+    /// The number of sea cucumbers that moved in the last iteration, or `0` before the first
+    pub fn count_moved(&self) -> usize {
+        self.moved.0 + self.moved.1
+    }
+
+    /// The [`StepStats`] for each iteration from this point, up to and including the first where nothing moves, e.g. to
+    /// plot how quickly the herds settle. The grid itself isn't changed. For a grid that never stabilises, see
+    /// [`Grid::iterate_until_static`], this never ends, so only take as many steps as are needed.
+    pub fn step_history(&self) -> impl Iterator<Item = StepStats> {
+        let cucumbers = self.cells.iter().filter(|&&cell| cell != EMPTY).count();
+        let mut grid = self.clone();
+        let mut settled = false;
+
+        std::iter::from_fn(move || {
+            if settled {
+                return None;
+            }
+
+            grid.iterate();
+            settled = grid.count_moved() == 0;
+
+            let (moved_right, moved_down) = grid.moved;
+            Some(StepStats {
+                step: grid.steps,
+                moved_right,
+                moved_down,
+                blocked: cucumbers - moved_right - moved_down,
+            })
+        })
+    }
+
+    /// True once every sea cucumber is blocked, and iterating won't change the grid
+    fn is_static(&self) -> bool {
+        self.active_right.is_empty() && self.active_down.is_empty()
+    }
+
+    /// Iterate until every sea cucumber is blocked, returning the number of steps taken. Not every grid stabilises,
+    /// e.g. a row with a gap that the herd can keep moving round, so each state is also passed to a [`CycleDetector`].
+    /// A grid that stops changing is static before it can repeat, so if the states repeat first this returns the
+    /// [`Cycle`] the herds loop through.
+    fn iterate_until_static(&mut self) -> Result<usize, Cycle> {
+        let initial = self.clone();
+        let mut detector = CycleDetector::new(self.cells.clone());
+
+        // There is always at least one step, the first where nothing moves, even if there is nothing to move
+        let mut states = 0;
+        loop {
+            self.iterate();
+            states += 1;
+            if self.is_static() {
+                return Ok(states);
+            }
+            if let Some(length) = detector.observe(self.cells.clone()) {
+                let start = cycle_start(&initial, Grid::iterate, |grid| grid.cells.clone(), length);
+                return Err(Cycle { start, length });
+            }
+        }
+    }
+}
+
+/// The simpler way of stepping the grid that part one uses. Rather than tracking which sea cucumbers might move, as
+/// [`Grid::iterate`] does, each herd's move reads every cell of the grid as it was before the herd moved, and writes
+/// the result into a second buffer, which then becomes the current grid.
+#[derive(Eq, PartialEq, Debug, Clone)]
+struct DoubleBuffer {
+    /// The cells of the grid as a single list, in the same layout as [`Grid::cells`]
+    current: Vec<Cell>,
+    /// Where the next state is written, its contents are only meaningful while a herd is moving
+    next: Vec<Cell>,
+    width: usize,
+    height: usize,
+}
+
+impl From<&Grid> for DoubleBuffer {
+    fn from(grid: &Grid) -> Self {
+        DoubleBuffer {
+            current: grid.cells.clone(),
+            next: grid.cells.clone(),
+            width: grid.width,
+            height: grid.height,
+        }
+    }
+}
+
+impl DoubleBuffer {
+    /// Move every sea cucumber in `herd` whose next cell was empty before any of them moved, then swap the buffers.
+    /// Returns the number of them that moved.
+    fn move_herd(&mut self, herd: Cell) -> usize {
+        self.next.copy_from_slice(&self.current);
+        let mut moved = 0;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let pos = y * self.width + x;
+                if self.current[pos] != herd {
+                    continue;
+                }
+
+                let target = match herd {
+                    RIGHT => y * self.width + (x + 1) % self.width,
+                    _ => ((y + 1) % self.height) * self.width + x,
+                };
+                if self.current[target] == EMPTY {
+                    self.next[target] = herd;
+                    self.next[pos] = EMPTY;
+                    moved += 1;
+                }
+            }
+        }
+
+        std::mem::swap(&mut self.current, &mut self.next);
+        moved
+    }
+
+    /// One step of both herds, as [`Grid::iterate`]. Returns the number of RIGHT and DOWN sea cucumbers that moved.
+    fn iterate(&mut self) -> (usize, usize) {
+        let right = self.move_herd(RIGHT);
+        let down = self.move_herd(DOWN);
+
+        (right, down)
+    }
+
+    /// As [`Grid::iterate_until_static`], counting the steps up to and including the first where nothing moves
+    fn iterate_until_static(&mut self) -> Result<usize, Cycle> {
+        let initial = self.clone();
+        let mut detector = CycleDetector::new(self.current.clone());
+
+        let mut steps = 0;
+        loop {
+            steps += 1;
+            if self.iterate() == (0, 0) {
+                return Ok(steps);
+            }
+            if let Some(length) = detector.observe(self.current.clone()) {
+                let start = cycle_start(
+                    &initial,
+                    |buffer| {
+                        buffer.iterate();
+                    },
+                    |buffer| buffer.current.clone(),
+                    length,
+                );
+                return Err(Cycle { start, length });
+            }
+        }
+    }
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.cells.iter().enumerate().try_for_each(|(i, cell)| {
+            write!(
+                f,
+                "{}{}",
+                cell,
+                if i % self.width == self.width - 1 {
+                    "\n"
+                } else {
+                    ""
+                }
+            )
+        })
+    }
+}
+
+impl Renderable for Grid {
+    /// The sea cucumbers are already drawn by [`Grid::fmt`], just without the trailing new line
+    fn render(&self) -> String {
+        self.to_string().trim_end().to_string()
+    }
+}
+
+impl FrameSource for Grid {
+    /// Each frame is one iteration of both herds
+    fn step(&mut self) -> bool {
+        if self.is_static() {
+            return false;
+        }
+        self.iterate();
+        true
+    }
+
+    fn current_step(&self) -> usize {
+        self.steps
+    }
+}
+
+impl Steppable for Grid {
+    /// How many sea cucumbers moved in the last step, and the size of each herd that may still be able to move
+    fn stats(&self) -> String {
+        format!(
+            "Step {}: {} moved, {} east and {} south facing sea cucumbers may still move",
+            self.steps,
+            self.count_moved(),
+            self.active_right.len(),
+            self.active_down.len()
+        )
+    }
+}
+
+/// Animate the herds moving until they are all blocked, returning the number of steps taken
+fn visualise(grid: &mut Grid) -> usize {
+    animate(grid, frame_delay())
+}
+
+/// Check the input is a rectangular grid of sea cucumbers, see [`validate`], and parse it for [`part_one`]. There
+/// is no part two on the last day.
+pub fn parse(contents: &str) -> Grid {
+    check_input(25, contents, validate);
+    Grid::from(contents)
+}
+
+/// Count the steps until the sea cucumbers stop moving.
+///
+/// - Pass `--visualise` to watch the sea cucumbers move, and `--skip-to=<step>` to start watching part way through.
+/// - Pass `--repl` to step through the herds moving interactively, see [`crate::util::repl`].
+pub fn part_one(grid: &Grid) -> Option<String> {
+    if repl_enabled() {
+        run_repl(grid);
+    }
+
+    let count = DoubleBuffer::from(grid)
+        .iterate_until_static()
+        .unwrap_or_else(|cycle| panic!("The sea cucumbers never stabilise, the grid {}", cycle));
+    if visualise_enabled() {
+        visualise(&mut grid.clone());
+    }
+    say!("The sea cucumbers stabilise in {} steps", count);
+
+    Some(count.to_string())
+}
+
+/// For `--compare`, the double buffered grid part one uses, and the active sets it is checked against
+pub fn alternatives() -> Vec<Alternative<Grid>> {
+    vec![
+        Alternative::new(Part::One, "double_buffered", |grid| {
+            DoubleBuffer::from(grid)
+                .iterate_until_static()
+                .ok()
+                .map(|count| count.to_string())
+        }),
+        Alternative::new(Part::One, "active_sets", |grid| {
+            grid.clone()
+                .iterate_until_static()
+                .ok()
+                .map(|count| count.to_string())
+        }),
+    ]
+}
+
+/// The sea floor should be a rectangular grid of sea cucumbers and empty spaces
+pub fn validate(input: &str) -> Validation {
+    to_validation(check_grid(input, ">v."))
+}
+
+/// For `--detect`, see [`crate::detect`]. The only other grid that isn't made of digits is day 20's image, which comes
+/// after the algorithm, so it is enough for it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::cycle::Cycle;
+    use crate::util::random::Random;
+    use crate::util::render::FrameSource;
+    use crate::year_2021::day_25::Cell::{DOWN, EMPTY, RIGHT};
+    use crate::year_2021::day_25::{DoubleBuffer, Grid, StepStats};
+    use std::collections::HashSet;
+
+    #[test]
+    fn can_parse() {
+        let grid = Grid::from("...>>>>>...");
+        assert_eq!(grid.width, 11);
+        assert_eq!(grid.height, 1);
+        assert_eq!(
+            grid.cells,
+            Vec::from([
+                EMPTY, EMPTY, EMPTY, RIGHT, RIGHT, RIGHT, RIGHT, RIGHT, EMPTY, EMPTY, EMPTY,
+            ])
+        );
+
+        assert_eq!(grid.active_right.len(), 5);
+        assert_eq!(grid.active_down.len(), 0);
+
+        let grid2 = Grid::from(
+            "..........
+.>v....v..
+.......>..
+..........",
+        );
+
+        assert_eq!(grid2.width, 10);
+        assert_eq!(grid2.height, 4);
+        assert_eq!(grid2.get(1, 1), Some(&RIGHT));
+        assert_eq!(grid2.get(2, 1), Some(&DOWN));
+        assert_eq!(grid2.get(7, 1), Some(&DOWN));
+        assert_eq!(grid2.get(7, 2), Some(&RIGHT));
+        assert_eq!(grid2.cells.iter().filter(|&&c| c == EMPTY).count(), 36);
+        assert_eq!(grid2.active_right, HashSet::from([(1, 1), (7, 2)]));
+        assert_eq!(grid2.active_down, HashSet::from([(2, 1), (7, 1)]));
+    }
+
+    #[test]
+    fn can_display() {
+        let grid = Grid {
+            cells: Vec::from([
+                EMPTY, EMPTY, EMPTY, RIGHT, RIGHT, RIGHT, RIGHT, RIGHT, EMPTY, EMPTY, EMPTY,
+            ]),
+            height: 1,
+            width: 11,
+            active_right: HashSet::new(),
+            active_down: HashSet::new(),
+            steps: 0,
+            moved: (0, 0),
+        };
+
+        assert_eq!(format!("{}", grid), "...>>>>>...\n".to_string());
+
+        let grid2 = "..........
+.>v....v..
+.......>..
+..........\n"
+            .to_string();
+
+        assert_eq!(format!("{}", Grid::from(grid2.as_str())), grid2);
+    }
+
+    #[test]
+    fn can_iterate() {
+        let mut grid = Grid::from("...>>>>>...\n");
+
+        grid.iterate();
+        assert_eq!(format!("{}", grid), "...>>>>.>..\n");
+        assert_eq!(grid.active_right, HashSet::from([(6, 0), (8, 0)]));
+        assert_eq!(grid.active_down.len(), 0);
+
+        grid.iterate();
+        assert_eq!(format!("{}", grid), "...>>>.>.>.\n");
+        assert_eq!(grid.active_right, HashSet::from([(5, 0), (7, 0), (9, 0)]));
+
+        grid.iterate();
+        grid.iterate();
+
+        assert_eq!(format!("{}", grid), ">..>.>.>.>.\n");
+
+        let mut grid2 = Grid::from(
+            "..........
+.>v....v..
+.......>..
+..........",
+        );
+
+        grid2.iterate();
+
+        assert_eq!(
+            format!("{}", grid2),
+            "..........
+.>........
+..v....v>.
+..........\n"
+        );
+
+        let mut grid3 = Grid::from(
+            "...>...
+.......
+......>
+v.....>
+......>
+.......
+..vvv..",
+        );
+
+        grid3.iterate();
+
+        assert_eq!(
+            format!("{}", grid3),
+            "..vv>..
+.......
+>......
+v.....>
+>......
+.......
+....v..\n"
+        );
+
+        grid3.iterate();
+
+        assert_eq!(
+            format!("{}", grid3),
+            "....v>.
+..vv...
+.>.....
+......>
+v>.....
+.......
+.......\n"
+        );
+
+        grid3.iterate();
+
+        assert_eq!(
+            format!("{}", grid3),
+            "......>
+..v.v..
+..>v...
+>......
+..>....
+v......
+.......\n"
+        );
+
+        grid3.iterate();
+
+        assert_eq!(
+            format!("{}", grid3),
+            ">......
+..v....
+..>.v..
+.>.v...
+...>...
+.......
+v......\n"
+        );
+    }
+
+    #[test]
+    fn can_iterate_until_static() {
+        let mut grid = Grid::from(
+            "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>",
+        );
+
+        let count = grid.iterate_until_static();
+        assert_eq!(count, Ok(58));
+        assert_eq!(
+            format!("{}", grid),
+            "..>>v>vv..
+..v.>>vv..
+..>>v>>vv.
+..>>>>>vv.
+v......>vv
+v>v....>>v
+vvv.....>>
+>vv......>
+.>v.vv.v..\n"
+        );
+    }
+
+    #[test]
+    fn can_skip_to_step() {
+        let input = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>";
+
+        let mut grid = Grid::from(input);
+        assert!(grid.skip_to(10));
+        assert_eq!(grid.current_step(), 10);
+
+        let mut expected = Grid::from(input);
+        for _ in 0..10 {
+            expected.iterate();
+        }
+        assert_eq!(grid, expected);
+
+        // The last 20 steps before stabilising
+        assert!(grid.skip_to(38));
+        let mut frames = 0;
+        while grid.step() {
+            frames += 1;
+        }
+        assert_eq!(frames, 20);
+        assert_eq!(grid.current_step(), 58);
+
+        assert!(!Grid::from(input).skip_to(100));
+    }
+
+    #[test]
+    fn can_detect_grids_that_never_stabilise() {
+        // The herd can always move into the gap it left, which wraps round to the start
+        assert_eq!(
+            Grid::from(">>.\n...").iterate_until_static(),
+            Err(Cycle {
+                start: 0,
+                length: 3
+            })
+        );
+        // Only the sea cucumber behind the gap can move each step, so the gap moves back up the column
+        assert_eq!(
+            Grid::from("v.\nv.\n..\nv.").iterate_until_static(),
+            Err(Cycle {
+                start: 0,
+                length: 4
+            })
+        );
+    }
+
+    /// Step both implementations together, checking they agree on the grid after each step, and on when, or if, it
+    /// stabilises
+    fn assert_implementations_agree(input: &str, steps: usize) {
+        let mut grid = Grid::from(input);
+        let mut buffer = DoubleBuffer::from(&grid);
+        for _ in 0..steps {
+            grid.iterate();
+            // Also checks the active sets didn't miss any sea cucumbers that could move
+            assert_eq!(buffer.iterate(), grid.moved, "{} moved differently", input);
+            assert_eq!(buffer.current, grid.cells, "{} diverged", input);
+        }
+
+        let mut grid = Grid::from(input);
+        let mut buffer = DoubleBuffer::from(&grid);
+        assert_eq!(
+            buffer.iterate_until_static(),
+            grid.iterate_until_static(),
+            "{} stabilised differently",
+            input
+        );
+        assert_eq!(buffer.current, grid.cells);
+    }
+
+    #[test]
+    fn double_buffer_matches_examples() {
+        assert_implementations_agree("...>>>>>...", 4);
+        assert_implementations_agree("..........\n.>v....v..\n.......>..\n..........", 1);
+        assert_implementations_agree("...>...\n.......\n......>\nv.....>\n......>\n.......\n..vvv..", 4);
+        assert_implementations_agree(
+            "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>",
+            60,
+        );
+        assert_implementations_agree(">>.\n...", 5);
+        assert_implementations_agree("v.\nv.\n..\nv.", 5);
+
+        // Already blocked, or nothing to move, still takes the one step where nothing moves
+        assert_implementations_agree("v>\n>v", 1);
+        assert_eq!(Grid::from("v>\n>v").iterate_until_static(), Ok(1));
+        assert_implementations_agree("..\n..", 1);
+        assert_eq!(Grid::from("..\n..").iterate_until_static(), Ok(1));
+    }
+
+    #[test]
+    fn double_buffer_matches_active_sets_on_random_grids() {
+        let mut random = Random::new(25);
+        for _ in 0..200 {
+            let width = 1 + random.below(8);
+            let height = 1 + random.below(8);
+            let input: Vec<String> = (0..height)
+                .map(|_| (0..width).map(|_| ['.', '.', '>', 'v'][random.below(4)]).collect())
+                .collect();
+
+            assert_implementations_agree(&input.join("\n"), 20);
+        }
+    }
+
+    #[test]
+    fn can_record_step_history() {
+        let stats = |step, moved_right, moved_down, blocked| StepStats {
+            step,
+            moved_right,
+            moved_down,
+            blocked,
+        };
+
+        let mut grid = Grid::from("...>>>>>...");
+        assert_eq!(grid.count_moved(), 0);
+        assert_eq!(
+            grid.step_history().take(2).collect::<Vec<StepStats>>(),
+            vec![stats(1, 1, 0, 4), stats(2, 2, 0, 3)]
+        );
+        // The history is of a copy
+        assert_eq!(grid.steps, 0);
+
+        grid.iterate();
+        grid.iterate();
+        assert_eq!(grid.count_moved(), 2);
+        // Carries on from the current step
+        assert_eq!(grid.step_history().next(), Some(stats(3, 3, 0, 2)));
+
+        let grid = Grid::from(
+            "..........
+.>v....v..
+.......>..
+..........",
+        );
+        assert_eq!(grid.step_history().next(), Some(stats(1, 1, 2, 1)));
+
+        let history: Vec<StepStats> = Grid::from(
+            "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>",
+        )
+        .step_history()
+        .collect();
+        assert_eq!(history.len(), 58);
+        assert_eq!(history[57], stats(58, 0, 0, 49));
+        assert!(history[..57].iter().all(|step| step.moved_right + step.moved_down > 0));
+        assert!(history.iter().all(|step| step.moved_right + step.moved_down + step.blocked == 49));
+    }
+}
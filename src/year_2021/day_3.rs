@@ -6,7 +6,7 @@
 //! [`parse_input`] is used to covert the binary strings into numbers, and also returns the number
 //! of bits per string as that is needed for some of the bitwise tricks later.
 //! [`analyse_diagnostics`] solves part one, deferring some logic to [`count_bit`]. Originally this
-//! was a double for loop over data and bit position, storing the counts into a mutable Vec<usize>.
+//! was a double for loop over data and bit position, storing the counts into a mutable `Vec<usize>`.
 //! I needed [`count_bit`] for my original solution to part two, and once written I refactored
 //! [`analyse_diagnostics`] to use it as well. When I later refactored [`analyse_life_support`] to
 //! no longer need [`count_bit`], [`analyse_diagnostics`] was still cleaner when using
@@ -15,32 +15,57 @@
 //! [`analyse_life_support`] solves part two. Originally it used [`count_bit`] to determine if
 //! the bits at the current position were majority set or not, then filtered the current subset
 //! based on that. The current partition based approach is easier to understand what is going on.
+//!
+//! The bit checks now go through [`SmallSet`], which was pulled out of [`super::day_8`] so both
+//! days can name the operations rather than writing out the masks.
 
+use crate::errors::{check_grid, check_input, to_validation, Validation};
+use crate::util::bitset::SmallSet;
 use itertools::partition;
-use std::fs;
 
-/// The entry point for running the solutions with the 'real' puzzle input.
-///
-/// - The puzzle input is expected to be at `<project_root>/res/day-3-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 3.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-3-input").expect("Failed to read file");
-    let (data, length) = parse_input(contents);
+/// Check each line is a binary number of the same length, see [`validate`], and parse them, along
+/// with that length, for [`part_one`] and [`part_two`].
+pub fn parse(contents: &str) -> (Vec<usize>, usize) {
+    check_input(3, contents, validate);
+    parse_input(contents.lines())
+}
 
-    let (gamma, epsilon) = analyse_diagnostics(&data, length);
-    println!(
+/// Find the gamma and epsilon rates, see [`analyse_diagnostics`]
+pub fn part_one((data, length): &(Vec<usize>, usize)) -> Option<String> {
+    let (gamma, epsilon) = analyse_diagnostics(data, *length);
+    say!(
         "Gamma: {} x Epsilon: {} = {}",
         gamma,
         epsilon,
         gamma * epsilon
     );
 
-    let (oxygen, co2) = analyse_life_support(&data, length);
-    println!("Oxygen: {} x CO2: {} = {}", oxygen, co2, oxygen * co2);
+    Some((gamma * epsilon).to_string())
+}
+
+/// Find the oxygen and CO2 ratings, see [`analyse_life_support`]
+pub fn part_two((data, length): &(Vec<usize>, usize)) -> Option<String> {
+    let (oxygen, co2) = analyse_life_support(data, *length);
+    say!("Oxygen: {} x CO2: {} = {}", oxygen, co2, oxygen * co2);
+
+    Some((oxygen * co2).to_string())
+}
+
+/// The report should be binary numbers that are all the same length
+pub fn validate(input: &str) -> Validation {
+    to_validation(check_grid(input, "01"))
+}
+
+/// For `--detect`, see [`crate::detect`]. The other digit grids, from days 9, 11 and 15, use more
+/// digits than 0 and 1, so it is enough for it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
 }
 
 /// Returns a pair of the parsed data and the length of the bit strings. Delegates to the built in
-/// [`usifix ze::from_str_radix`]. The length is needed for some of the bitwise tricks.
+/// [`usifix ze::from_str_radix`]. The length is needed for some of the bitwise tricks. Takes the
+/// lines one at a time, so they can be streamed from [`crate::util::input::read_lines`] as well as
+/// taken from the checked input text.
 ///
 /// # Example from puzzle specification
 /// ```rust
@@ -48,7 +73,7 @@ pub fn run() {
 ///     "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010"
 ///         .to_string();
 ///
-/// let (data, length) = parse_input(input);
+/// let (data, length) = parse_input(input.lines());
 ///
 /// assert_eq!(length, 5);
 /// assert_eq!(
@@ -69,16 +94,18 @@ pub fn run() {
 ///     ]
 /// )
 /// ```
-fn parse_input(contents: String) -> (Vec<usize>, usize) {
-    let data: Vec<usize> = contents
-        .lines()
+fn parse_input<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> (Vec<usize>, usize) {
+    let mut length = None;
+    let data: Vec<usize> = lines
+        .into_iter()
         .map(|line| {
+            let line = line.as_ref();
+            length.get_or_insert(line.len());
             usize::from_str_radix(line, 2).expect(format!("Unexpected input '{}'", line).as_str())
         })
         .collect();
-    let length = contents.lines().next().expect("Input file is empty").len();
 
-    return (data, length);
+    return (data, length.expect("Input file is empty"));
 }
 
 /// Return the number of values in the data where all the bits set in the bitmask are also set in
@@ -108,9 +135,10 @@ fn parse_input(contents: String) -> (Vec<usize>, usize) {
 /// assert_eq!(count_bit(&test_data, 1 << 3), 5);
 /// assert_eq!(count_bit(&test_data, 1 << 4), 7);
 /// ```
-fn count_bit(data: &Vec<usize>, bitmask: usize) -> usize {
+fn count_bit(data: &[usize], bitmask: usize) -> usize {
+    let mask = SmallSet::from_bits(bitmask);
     data.iter()
-        .filter(|&&value| value & bitmask == bitmask)
+        .filter(|&&value| SmallSet::from_bits(value).contains_all(&mask))
         .count()
 }
 
@@ -137,7 +165,7 @@ fn count_bit(data: &Vec<usize>, bitmask: usize) -> usize {
 ///
 /// assert_eq!(analyse_diagnostics(&test_data, 5), (22, 9));
 /// ```
-fn analyse_diagnostics(data: &Vec<usize>, length: usize) -> (usize, usize) {
+fn analyse_diagnostics(data: &[usize], length: usize) -> (usize, usize) {
     let mut gamma: usize = 0;
     let threshold = data.len() / 2;
 
@@ -199,7 +227,7 @@ fn analyse_diagnostics(data: &Vec<usize>, length: usize) -> (usize, usize) {
 ///
 /// assert_eq!(analyse_life_support(&test_data(), 5), (23, 10));
 /// ```
-fn analyse_life_support(data: &Vec<usize>, length: usize) -> (usize, usize) {
+fn analyse_life_support(data: &[usize], length: usize) -> (usize, usize) {
     fn iter(mut current: Vec<usize>, position: usize, keep_smallest: bool) -> usize {
         // base case
         if current.len() == 1 {
@@ -215,10 +243,11 @@ fn analyse_life_support(data: &Vec<usize>, length: usize) -> (usize, usize) {
             )
         }
 
-        let bitmask = 1 << position - 1;
         // partition in place, all the values before split_index have the bit set, the value at that
         // position and later do not.
-        let split_index = partition(current.as_mut_slice(), |value| value & bitmask != 0);
+        let split_index = partition(current.as_mut_slice(), |&value| {
+            SmallSet::from_bits(value).contains(position - 1)
+        });
         let (left, right) = current.split_at(split_index);
 
         // The xor here lets the keep_smallest flag invert the size comparison when set
@@ -238,7 +267,9 @@ fn analyse_life_support(data: &Vec<usize>, length: usize) -> (usize, usize) {
 
 #[cfg(test)]
 mod tests {
-    use crate::day_3::{analyse_diagnostics, analyse_life_support, count_bit, parse_input};
+    use crate::year_2021::day_3::{
+        analyse_diagnostics, analyse_life_support, count_bit, parse_input,
+    };
 
     fn test_data() -> Vec<usize> {
         vec![
@@ -253,7 +284,7 @@ mod tests {
             "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010"
                 .to_string();
 
-        let (data, length) = parse_input(input);
+        let (data, length) = parse_input(input.lines());
 
         assert_eq!(length, 5);
         assert_eq!(data, test_data())
@@ -0,0 +1,837 @@
+//! This is my solution for [Advent of Code - Day 4 - _Giant Squid_](https://adventofcode.com/2021/day/4)
+//!
+//! Today we had to build an engine to track games of Bingo. Given a set of numbers that would be
+//! called, and a set of 100 bingo cards, the challenge was to work out the one that would win first
+//! (part one), and the one that would win last (part two). I'm quite pleased with the data
+//! structure I came up with to store the bingo cards, but there was a lot of fighting with the
+//! borrow checker so I've ended up with some ugly bits of code.
+//!
+//! The key to the solution is [`BingoCard`] and [`parse_card`] that turns the raw input into this
+//! internal representation. The game is then simulated by repeatedly calling
+//! [`BingoCard::mark_number`] until the criteria for the current part have been met.
+//! [`play_bingo`] implements part one and just runs until a card wins, [`play_bingo_until_last`]
+//! implements part two and removes cards from the set as they win until none are left. There is
+//! a final small helper [`BingoCard::sum_remaining`] that calculates the number needed for the
+//! final submission.
+//!
+//! Later I refactored the simulation into [`BingoGame`], which doesn't consume the cards so the same
+//! game can be replayed. [`BingoGame::play`] finds the first and last winners in a single pass,
+//! and [`BingoGame::play_all`] runs a set of what-if calling sequences across several threads,
+//! which part two uses to replay the game in other orders when given `--param orders=<n>`.
+//! [`play_bingo`] and [`play_bingo_until_last`] are now thin wrappers over it.
+//!
+//! The cards were originally fixed at 5 x 5, and only won on a full row or column. To simulate
+//! variants of the game, a card is now as wide as the first row of its input, with as many rows,
+//! and keeps a count for its two diagonals as well. Diagonals only win when enabled with
+//! `--param diagonals=true`, see [`Options`].
+//!
+//! [`BingoGame::play`] used to stop at the first and last winners. It now ranks every card by when
+//! it won, with cards that win on the same number sharing a place, and [`GameOutcome`] has the
+//! first winner, last winner and full ranking. Pass `--ranking` to print it.
+
+use crate::errors::{
+    check_input, check_lines, check_pattern, offset, sections, to_validation, InputError,
+    Validation,
+};
+use crate::util::params::Params;
+use regex::Regex;
+use std::collections::HashMap;
+use std::env;
+use std::thread;
+
+/// The rules of the game, can be changed with `--param diagonals=true`. Passing
+/// `--param orders=<n>` also replays the game with `n` other orders of the numbers, see
+/// [`alternate_orders`]. This is skipped if `orders` is 0, which is the default.
+#[derive(Eq, PartialEq, Debug)]
+struct Options {
+    /// Whether completing either of a card's diagonals wins, as well as a row or column
+    diagonals: bool,
+    /// How many other orders of the numbers to replay the game with
+    orders: usize,
+}
+
+impl From<&Params> for Options {
+    fn from(params: &Params) -> Self {
+        Options {
+            diagonals: params.get_or("diagonals", false),
+            orders: params.get_or("orders", 0),
+        }
+    }
+}
+
+/// This represents the key information to know if an N x N bingo card has won.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct BingoCard {
+    /// A Map indexing the remaining numbers to their co-ordinates on the grid
+    numbers: HashMap<u8, (usize, usize)>,
+    /// A counter for each row, tracking how many numbers in that row have been removed
+    rows: Vec<u8>,
+    /// A counter for each column, tracking how many numbers in that column have been removed
+    columns: Vec<u8>,
+    /// Counters for the diagonal from the top left, and the one from the top right
+    diagonals: [u8; 2],
+}
+
+impl BingoCard {
+    /// An empty card with `size` rows and columns, for [`parse_card`] to fill in
+    fn new(size: usize) -> BingoCard {
+        BingoCard {
+            numbers: HashMap::new(),
+            rows: vec![0; size],
+            columns: vec![0; size],
+            diagonals: [0; 2],
+        }
+    }
+
+    /// If the card contains the provided number, remove it from the unmarked numbers, increment
+    /// the count of marked numbers in the relevant row and column, and diagonal if it is on one,
+    /// then if any of these are now full, the card has won - return true, otherwise return false.
+    /// A full diagonal only counts if `diagonals` is set.
+    ///
+    /// If the number is not on the card, nothing changes, and return false.
+    fn mark_number(&mut self, number: u8, diagonals: bool) -> bool {
+        match self.numbers.remove(&number) {
+            Some((x, y)) => {
+                let size = self.rows.len();
+                self.columns[x] += 1;
+                self.rows[y] += 1;
+                if x == y {
+                    self.diagonals[0] += 1;
+                }
+                if x + y == size - 1 {
+                    self.diagonals[1] += 1;
+                }
+
+                let full = size as u8;
+                self.columns[x] == full
+                    || self.rows[y] == full
+                    || (diagonals && self.diagonals.contains(&full))
+            }
+            None => false,
+        }
+    }
+
+    /// The remaining numbers are the keys of the numbers hash map, as marked numbers are removed
+    /// from the map.
+    fn sum_remaining(&self) -> usize {
+        self.numbers.keys().map(|&k| k as usize).sum()
+    }
+}
+
+/// A card winning a game: its position in the list of cards, the card as it was when it won, the
+/// number that was called to complete it, and the position of that number in the calling sequence.
+/// `place` is where the card finished, counting from 1. Cards that win on the same number share a
+/// place, and the next card to win after them skips the places they took.
+#[derive(Eq, PartialEq, Debug, Clone)]
+struct Win {
+    index: usize,
+    card: BingoCard,
+    number: u8,
+    turn: usize,
+    place: usize,
+}
+
+impl Win {
+    /// The puzzle's score for a winning card
+    fn score(&self) -> usize {
+        self.card.sum_remaining() * self.number as usize
+    }
+}
+
+/// The result of playing one calling sequence, every card that won in the order they won. Cards
+/// that win on the same number are in the order they were dealt.
+#[derive(Eq, PartialEq, Debug, Clone)]
+struct GameOutcome {
+    ranking: Vec<Win>,
+    cards: usize,
+}
+
+impl GameOutcome {
+    /// The first card to win, if any did. If several cards win on the same number, this is the
+    /// first of those to be dealt.
+    fn first_winner(&self) -> Option<&Win> {
+        self.ranking.first()
+    }
+
+    /// The last card to win, only if every card won. If several cards are last to win on the same
+    /// number, this is the last of those to be dealt.
+    fn last_winner(&self) -> Option<&Win> {
+        if self.ranking.len() == self.cards {
+            self.ranking.last()
+        } else {
+            None
+        }
+    }
+
+    /// Every card that won, in the order they won
+    fn ranking(&self) -> &[Win] {
+        &self.ranking
+    }
+}
+
+/// A set of cards that can be replayed against as many calling sequences as needed, as playing
+/// works on a copy of the cards. `diagonals` is passed on to [`BingoCard::mark_number`].
+struct BingoGame {
+    cards: Vec<BingoCard>,
+    diagonals: bool,
+}
+
+impl BingoGame {
+    fn new(cards: Vec<BingoCard>, diagonals: bool) -> BingoGame {
+        BingoGame { cards, diagonals }
+    }
+
+    /// Call the numbers in turn, marking a fresh copy of the cards, until every card has won or the
+    /// numbers run out. Each number is marked on every card before moving on to the next, so that
+    /// the cards that win on the same number all get the same place. Cards that have already won
+    /// are skipped, so each card wins at most once.
+    fn play(&self, numbers: &[u8]) -> GameOutcome {
+        let mut cards = self.cards.clone();
+        let mut won = vec![false; cards.len()];
+        let mut ranking = Vec::new();
+
+        for (turn, &number) in numbers.iter().enumerate() {
+            if ranking.len() == cards.len() {
+                break;
+            }
+
+            let place = ranking.len() + 1;
+            for (index, card) in cards.iter_mut().enumerate() {
+                if !won[index] && card.mark_number(number, self.diagonals) {
+                    won[index] = true;
+                    ranking.push(Win {
+                        index,
+                        card: card.clone(),
+                        number,
+                        turn,
+                        place,
+                    });
+                }
+            }
+        }
+
+        GameOutcome {
+            ranking,
+            cards: cards.len(),
+        }
+    }
+
+    /// Play each calling sequence against the same cards, spreading the sequences across the
+    /// available cores. The outcomes are returned in the same order as the sequences. With one
+    /// core, or in WebAssembly where threads can't be spawned, they are played in turn instead.
+    fn play_all(&self, sequences: &[Vec<u8>]) -> Vec<GameOutcome> {
+        let threads = if cfg!(target_arch = "wasm32") {
+            1
+        } else {
+            thread::available_parallelism().map_or(1, |n| n.get())
+        };
+        if threads <= 1 {
+            return sequences
+                .iter()
+                .map(|numbers| self.play(numbers))
+                .collect();
+        }
+
+        let chunk_size = sequences.len().div_ceil(threads).max(1);
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = sequences
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|numbers| self.play(numbers))
+                            .collect::<Vec<GameOutcome>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("Bingo thread panicked"))
+                .collect()
+        })
+    }
+}
+
+/// Check the input is a line of numbers to call, then the bingo cards, see [`validate`], and parse
+/// them for [`part_one`] and [`part_two`] to play.
+pub fn parse(contents: &str) -> (Vec<u8>, Vec<BingoCard>) {
+    check_input(4, contents, validate);
+    parse_input(contents)
+}
+
+/// Find the score of the first card to win, see [`play_bingo`].
+///
+/// - Diagonal wins can be turned on, see [`Options`].
+pub fn part_one((numbers, cards): &(Vec<u8>, Vec<BingoCard>)) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    let (winning_card, winning_number) = play_bingo(numbers, cards, options.diagonals);
+    let winning_remaining = winning_card.sum_remaining();
+    say!(
+        "Remaining Sum: {} x Winning Number: {} = {}",
+        winning_remaining,
+        winning_number,
+        winning_remaining * winning_number as usize
+    );
+
+    Some((winning_remaining * winning_number as usize).to_string())
+}
+
+/// Find the score of the last card to win, see [`play_bingo_until_last`].
+///
+/// - Diagonal wins can be turned on, see [`Options`].
+/// - Pass `--param orders=<n>` to also print the first winning scores if the numbers were called
+///   in `n` other orders, see [`alternate_orders`].
+/// - Pass `--ranking` to also print the order every card won in, see [`format_ranking`].
+pub fn part_two((numbers, cards): &(Vec<u8>, Vec<BingoCard>)) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    let (losing_card, losing_number) = play_bingo_until_last(numbers, cards, options.diagonals);
+    let losing_remaining = losing_card.sum_remaining();
+
+    say!(
+        "Remaining Sum: {} x Losing Number: {} = {}",
+        losing_remaining,
+        losing_number,
+        losing_remaining * losing_number as usize
+    );
+
+    let game = BingoGame::new(cards.clone(), options.diagonals);
+    if options.orders > 0 {
+        let scores: Vec<String> = game
+            .play_all(&alternate_orders(numbers, options.orders))
+            .iter()
+            .map(|outcome| match outcome.first_winner() {
+                Some(win) => win.score().to_string(),
+                None => "-".to_string(),
+            })
+            .collect();
+        say!(
+            "First winning scores for alternate orders: {}",
+            scores.join(", ")
+        );
+    }
+
+    if env::args().any(|arg| arg == "--ranking") {
+        say!("{}", format_ranking(game.play(numbers).ranking()));
+    }
+
+    Some((losing_remaining * losing_number as usize).to_string())
+}
+
+/// What if the numbers were called in reverse, or starting from the second, third, etc. number and
+/// wrapping around? Returns the first `count` of these orders, of which there are as many as there
+/// are numbers.
+fn alternate_orders(numbers: &[u8], count: usize) -> Vec<Vec<u8>> {
+    let reversed = numbers.iter().rev().copied().collect::<Vec<u8>>();
+    let rotations = (1..numbers.len()).map(|i| [&numbers[i..], &numbers[..i]].concat());
+
+    std::iter::once(reversed).chain(rotations).take(count).collect()
+}
+
+/// The first section should be the numbers to call, each subsequent section a square bingo card,
+/// as many rows as there are numbers in its first row. Numbers are stored as `u8`s so must be at
+/// most 255.
+pub fn validate(input: &str) -> Validation {
+    let check_numbers = |numbers: &str| {
+        numbers
+            .split([',', ' '])
+            .filter(|number| !number.is_empty())
+            .find(|number| number.parse::<u8>().is_err())
+            .map(|number| format!("'{}' is not a number from 0 to 255", number))
+    };
+
+    let mut errors = Vec::new();
+    for (index, (first_line, section)) in sections(input).into_iter().enumerate() {
+        if index == 0 {
+            errors.extend(check_pattern(
+                section,
+                r"\d+(,\d+)*",
+                "comma separated numbers",
+            ));
+            errors.extend(check_lines(section, |_, line| check_numbers(line)));
+        } else {
+            let size = section
+                .lines()
+                .next()
+                .map_or(0, |row| row.split_whitespace().count())
+                .max(1);
+            let rows = section.lines().count();
+            if rows != size {
+                errors.push(InputError::new(
+                    first_line,
+                    format!("Expected a card with {} rows, found {}", size, rows),
+                ));
+            }
+            errors.extend(offset(
+                check_pattern(
+                    section,
+                    &format!(r" *\d+( +\d+){{{}}}", size - 1),
+                    &format!("a row of {} numbers", size),
+                ),
+                first_line,
+            ));
+            errors.extend(offset(
+                check_lines(section, |_, line| check_numbers(line)),
+                first_line,
+            ));
+        }
+    }
+
+    to_validation(errors)
+}
+
+/// For `--detect`, see [`crate::detect`]. The numbers drawn would pass [`validate`] on their own,
+/// and look like the input for days 6 and 7, so there must also be at least one card.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok() && sections(input).len() > 1
+}
+
+/// Use [`BingoGame::play`] to return the first card to win and the number that triggered it, as
+/// both are needed to calculate the puzzle solution.
+fn play_bingo(numbers: &[u8], cards: &[BingoCard], diagonals: bool) -> (BingoCard, u8) {
+    let win = BingoGame::new(cards.to_vec(), diagonals)
+        .play(numbers)
+        .first_winner()
+        .cloned()
+        // This is unreachable for the puzzle input
+        .expect("No winner after numbers exhausted");
+
+    (win.card, win.number)
+}
+
+/// Very similar to [`play_bingo`] except it needs to keep going until all cards have won.
+fn play_bingo_until_last(numbers: &[u8], cards: &[BingoCard], diagonals: bool) -> (BingoCard, u8) {
+    let win = BingoGame::new(cards.to_vec(), diagonals)
+        .play(numbers)
+        .last_winner()
+        .cloned()
+        // This is unreachable for the puzzle input
+        .expect("No winner after numbers exhausted");
+
+    (win.card, win.number)
+}
+
+/// A line for each card in the ranking, with its place, which is marked with `=` when shared with
+/// other cards that won on the same number, and the number and score it won with.
+fn format_ranking(ranking: &[Win]) -> String {
+    ranking
+        .iter()
+        .map(|win| {
+            let tied = ranking
+                .iter()
+                .filter(|other| other.place == win.place)
+                .count()
+                > 1;
+            format!(
+                "{:>4}: card {} won on call {} ({}) with a score of {}",
+                format!("{}{}", if tied { "=" } else { "" }, win.place),
+                win.index + 1,
+                win.turn + 1,
+                win.number,
+                win.score()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Parse the puzzle input into the internal representation. first there is a line of numbers in
+/// the sequence the will be called to mark on the cards, then 100 5 x 5 grids of numbers
+/// representing each card. The first line and each card are separated by blank lines.
+fn parse_input(contents: &str) -> (Vec<u8>, Vec<BingoCard>) {
+    // Split on the double new lines that separate each section.
+    let mut sections = contents.split("\n\n");
+    // The first section is comma separated numbers
+    let numbers: Vec<u8> = sections
+        .next()
+        .expect("Input file was empty")
+        .split(",")
+        .map(|num| {
+            num.parse::<u8>()
+                .expect(format!("Invalid number: '{}'", num).as_str())
+        })
+        .collect();
+
+    // Each remaining section is a bing card
+    let cards: Vec<BingoCard> = sections.map(|input| parse_card(input)).collect();
+
+    (numbers, cards)
+}
+
+/// This takes a string with N lines, each with N space-separated numbers, representing an N x N
+/// bingo card. A regex is used to split the numbers on a line as single digit numbers cause
+/// there to be two spaces prefixing those numbers. [`Iterator::enumerate`] is used to track the
+/// current co-ordinates for building the map of unmarked numbers. The row and column counts are
+/// initialised to 0s as no numbers have yet been marked.
+fn parse_card(input: &str) -> BingoCard {
+    let splitter = Regex::new(" +").unwrap();
+
+    let mut card = BingoCard::new(input.lines().count());
+    card.numbers = input
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            splitter
+                .split(line.trim())
+                .enumerate()
+                .flat_map(move |(x, num_s)| num_s.parse::<u8>().ok().map(|num| (num, (x, y))))
+        })
+        .collect();
+
+    card
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::InputError;
+    use crate::geninput::generate;
+    use crate::year_2021::day_4::{
+        alternate_orders, format_ranking, parse_card, parse_input, play_bingo, play_bingo_until_last, validate, BingoCard, BingoGame,
+        Options,
+    };
+    use crate::util::params::Params;
+    use std::collections::HashMap;
+
+    fn test_card() -> BingoCard {
+        #[rustfmt::skip] // keep map literal in grid format
+        let expected_numbers: HashMap<u8, (usize, usize)> =
+            HashMap::from([
+                (22, (0, 0)), (13, (1, 0)), (17, (2, 0)), (11, (3, 0)),  (0, (4, 0)),
+                 (8, (0, 1)),  (2, (1, 1)), (23, (2, 1)),  (4, (3, 1)), (24, (4, 1)),
+                (21, (0, 2)),  (9, (1, 2)), (14, (2, 2)), (16, (3, 2)),  (7, (4, 2)),
+                 (6, (0, 3)), (10, (1, 3)),  (3, (2, 3)), (18, (3, 3)),  (5, (4, 3)),
+                 (1, (0, 4)), (12, (1, 4)), (20, (2, 4)), (15, (3, 4)), (19, (4, 4)),
+            ]);
+
+        let expected_card = BingoCard {
+            numbers: expected_numbers,
+            rows: vec![0; 5],
+            columns: vec![0; 5],
+            diagonals: [0; 2],
+        };
+        expected_card
+    }
+
+    #[test]
+    fn can_parse_card() {
+        let expected_card = test_card();
+
+        let parsed_card = parse_card(
+            "22 13 17 11  0\n\
+                    8  2 23  4 24\n\
+                   21  9 14 16  7\n\
+                    6 10  3 18  5\n\
+                    1 12 20 15 19",
+        );
+
+        assert_eq!(parsed_card, expected_card)
+    }
+
+    #[test]
+    fn can_parse() {
+        let (numbers, cards) = parse_input(&test_input());
+
+        assert_eq!(
+            numbers,
+            vec![
+                7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24, 10, 16, 13, 6, 15, 25, 12, 22, 18, 20, 8,
+                19, 3, 26, 1
+            ]
+        );
+
+        assert_eq!(cards.get(0), Some(&test_card()));
+
+        assert_eq!(
+            cards.get(1),
+            Some(&parse_card(
+                " 3 15  0  2 22\n\
+                  9 18 13 17  5\n\
+                 19  8  7 25 23\n\
+                 20 11 10 24  4\n\
+                 14 21 16 12  6",
+            ))
+        );
+
+        assert_eq!(
+            cards.get(2),
+            Some(&parse_card(
+                "14 21 17 24  4\n\
+                 10 16 15  9 19\n\
+                 18  8 23 26 20\n\
+                 22 11 13  6  5\n\
+                  2  0 12  3  7",
+            ))
+        );
+    }
+
+    fn test_input() -> String {
+        "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7"
+            .to_string()
+    }
+
+    #[test]
+    fn can_mark_card() {
+        let mut card = test_card();
+        let result = card.mark_number(22, false);
+        assert_eq!(result, false);
+        assert_eq!(card.rows, vec![1, 0, 0, 0, 0]);
+        assert_eq!(card.columns, vec![1, 0, 0, 0, 0]);
+        assert_eq!(card.numbers.get(&22), None);
+
+        card.mark_number(13, false);
+        card.mark_number(17, false);
+        card.mark_number(11, false);
+        let result = card.mark_number(0, false);
+
+        assert_eq!(result, true);
+        assert_eq!(card.rows, vec![5, 0, 0, 0, 0]);
+        assert_eq!(card.columns, vec![1, 1, 1, 1, 1]);
+
+        // missing number ignored
+        card.mark_number(99, false);
+        // duplicate number ignored
+        card.mark_number(22, false);
+        assert_eq!(result, true);
+        assert_eq!(card.rows, vec![5, 0, 0, 0, 0]);
+        assert_eq!(card.columns, vec![1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn can_mark_diagonals() {
+        let input = "1 2 3\n4 5 6\n7 8 9";
+        let mut card = parse_card(input);
+        assert_eq!(card.rows, vec![0; 3]);
+        assert_eq!(card.numbers.get(&6), Some(&(2, 1)));
+
+        // The middle is on both diagonals
+        assert!(!card.mark_number(5, true));
+        assert_eq!(card.diagonals, [1, 1]);
+        assert!(!card.mark_number(1, true));
+        assert!(card.mark_number(9, true));
+        assert_eq!(card.diagonals, [3, 1]);
+
+        // Without diagonal wins a full diagonal isn't enough, but the bottom row still wins
+        let mut card = parse_card(input);
+        assert!(!card.mark_number(3, false));
+        assert!(!card.mark_number(5, false));
+        assert!(!card.mark_number(7, false));
+        assert_eq!(card.diagonals, [1, 3]);
+        assert!(!card.mark_number(8, false));
+        assert!(card.mark_number(9, false));
+    }
+
+    #[test]
+    fn can_play_with_diagonals() {
+        let (numbers, cards) = parse_input(&test_input());
+        let game = BingoGame::new(cards.clone(), true);
+
+        // The third card's diagonal 4, 9, 23, 11, 2 is complete on the eighth number, long before
+        // its top row
+        let outcome = game.play(&numbers);
+        let first = outcome.first_winner().unwrap();
+        assert_eq!((first.index, first.number), (2, 2));
+        assert_eq!(play_bingo(&numbers, &cards, true).1, 2);
+
+        // Cards of different sizes can play together
+        let (numbers, cards) = parse_input("5,1,9,8\n\n1 2 3\n4 5 6\n7 8 9\n\n9 8\n7 6");
+        let (card, number) = play_bingo(&numbers, &cards, false);
+        assert_eq!((card.sum_remaining(), number), (13, 8));
+        let (card, number) = play_bingo(&numbers, &cards, true);
+        assert_eq!((card.sum_remaining(), number), (30, 9));
+        assert_eq!(play_bingo_until_last(&numbers, &cards, true).1, 8);
+    }
+
+    #[test]
+    fn can_build_options() {
+        assert_eq!(
+            Options::from(&Params::default()),
+            Options {
+                diagonals: false,
+                orders: 0
+            }
+        );
+
+        let params = Params::parse(
+            ["--param", "diagonals=true", "--param", "orders=3"]
+                .map(String::from)
+                .into_iter(),
+        );
+        assert_eq!(
+            Options::from(&params),
+            Options {
+                diagonals: true,
+                orders: 3
+            }
+        );
+    }
+
+    #[test]
+    fn can_play_bingo() {
+        let (numbers, cards) = parse_input(&test_input());
+        let (winning_card, number) = play_bingo(&numbers, &cards, false);
+
+        assert_eq!(number, 24);
+        assert_eq!(winning_card.sum_remaining(), 188)
+    }
+
+    #[test]
+    fn can_play_bingo_until_exhausted() {
+        let (numbers, cards) = parse_input(&test_input());
+        // The real result set has multiple cards that win with some numbers, so include duplicates
+        // in the test to ensure this is covered.
+        let cards_with_duplicates = cards
+            .iter()
+            .flat_map(|c| [c.clone(), c.clone()])
+            .collect::<Vec<BingoCard>>();
+        let (losing_card, number) = play_bingo_until_last(&numbers, &cards_with_duplicates, false);
+
+        assert_eq!(number, 13);
+        assert_eq!(losing_card.sum_remaining(), 148)
+    }
+
+    #[test]
+    fn can_replay_game() {
+        let (numbers, cards) = parse_input(&test_input());
+        let game = BingoGame::new(cards, false);
+
+        let outcome = game.play(&numbers);
+        let first = outcome.first_winner().unwrap();
+        let last = outcome.last_winner().unwrap();
+        assert_eq!((first.index, first.number, first.score()), (2, 24, 4512));
+        assert_eq!((last.index, last.number, last.score()), (1, 13, 1924));
+
+        // Playing doesn't change the game, so replaying gives the same result
+        assert_eq!(game.play(&numbers).first_winner().unwrap().score(), 4512);
+
+        // Not every card wins with the first few numbers
+        let outcome = game.play(&numbers[0..12]);
+        assert_eq!(outcome.first_winner().map(|win| win.index), Some(2));
+        assert_eq!(outcome.last_winner(), None);
+        assert_eq!(game.play(&[]), game.play(&numbers[0..5]));
+    }
+
+    #[test]
+    fn can_rank_simultaneous_wins() {
+        let (numbers, cards) = parse_input(&test_input());
+        let cards_with_duplicates = cards
+            .iter()
+            .flat_map(|c| [c.clone(), c.clone()])
+            .collect::<Vec<BingoCard>>();
+        let outcome = BingoGame::new(cards_with_duplicates, false).play(&numbers);
+
+        let ranking: Vec<(usize, usize, u8, usize)> = outcome
+            .ranking()
+            .iter()
+            .map(|win| (win.place, win.index, win.number, win.turn))
+            .collect();
+        assert_eq!(
+            ranking,
+            vec![
+                (1, 4, 24, 11),
+                (1, 5, 24, 11),
+                (3, 0, 16, 13),
+                (3, 1, 16, 13),
+                (5, 2, 13, 14),
+                (5, 3, 13, 14),
+            ]
+        );
+
+        // Ties go to the first card dealt for the first winner, and the last for the last winner
+        assert_eq!(outcome.first_winner().map(|win| win.index), Some(4));
+        assert_eq!(outcome.last_winner().map(|win| win.index), Some(3));
+
+        let lines = format_ranking(outcome.ranking());
+        assert_eq!(
+            lines.lines().take(3).collect::<Vec<&str>>(),
+            vec![
+                "  =1: card 5 won on call 12 (24) with a score of 4512",
+                "  =1: card 6 won on call 12 (24) with a score of 4512",
+                "  =3: card 1 won on call 14 (16) with a score of 2192",
+            ]
+        );
+    }
+
+    #[test]
+    fn can_play_many_sequences() {
+        let (numbers, cards) = parse_input(&test_input());
+        let game = BingoGame::new(cards, false);
+
+        let sequences: Vec<Vec<u8>> = (0..numbers.len())
+            .map(|i| [&numbers[i..], &numbers[..i]].concat())
+            .collect();
+        let outcomes = game.play_all(&sequences);
+
+        assert_eq!(outcomes.len(), sequences.len());
+        for (sequence, outcome) in sequences.iter().zip(outcomes) {
+            assert_eq!(outcome, game.play(sequence));
+        }
+        assert_eq!(game.play_all(&[]), Vec::new());
+    }
+
+    #[test]
+    fn can_list_alternate_orders() {
+        assert_eq!(
+            alternate_orders(&[1, 2, 3], 2),
+            vec![vec![3, 2, 1], vec![2, 3, 1]]
+        );
+        assert_eq!(alternate_orders(&[1, 2, 3], 10).len(), 3);
+        assert_eq!(alternate_orders(&[1, 2, 3], 0), Vec::<Vec<u8>>::new());
+    }
+
+    #[test]
+    fn can_validate() {
+        let input = "7,4,9\n\n22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19\n";
+        assert_eq!(validate(input), Ok(()));
+
+        let input = "7,4,900\n\n22 13 17 11  0\n 8  2 23  4\n21  9 14 16  7\n 6 10  3 18  5\n";
+        assert_eq!(
+            validate(input),
+            Err(vec![
+                InputError::new(1, "'900' is not a number from 0 to 255"),
+                InputError::new(3, "Expected a card with 5 rows, found 4"),
+                InputError::new(4, "Expected a row of 5 numbers, found ' 8  2 23  4'"),
+            ])
+        );
+
+        let input = "1,2,3\n\n1 2 3\n4 5 6\n7 8 9\n\n1 2\n3 4\n5 6\n";
+        assert_eq!(
+            validate(input),
+            Err(vec![InputError::new(7, "Expected a card with 2 rows, found 3")])
+        );
+        assert_eq!(validate("1,2,3\n\n1 2 3\n4 5 6\n7 8 9\n"), Ok(()));
+    }
+
+    #[test]
+    fn can_play_generated_input() {
+        let input = generate(4, 1).unwrap().input;
+        assert_eq!(validate(&input), Ok(()));
+
+        let (numbers, cards) = parse_input(&input);
+        assert_eq!(numbers.len(), 100);
+        assert_eq!(cards.len(), 100);
+
+        let (first, _) = play_bingo(&numbers, &cards, false);
+        let (last, _) = play_bingo_until_last(&numbers, &cards, false);
+        assert_ne!(first, last);
+    }
+}
@@ -0,0 +1,644 @@
+//! This is my solution for [Advent of Code - Day 5 - _Hydrothermal Venture_](https://adventofcode.com/2021/day/5)
+//!
+//! Today was filling co-ordinates on a grid. Both tasks only needed to know which points had been filled twice, so I
+//! was able to implement that with two HashSets of co-ordinates, only setting the second set if that co-ordinate was
+//! set in the first. This is implemented in `get_intersections`. The other key piece of logic is translating the
+//! lines of the input into the points along their path, implemented by [`Line::points`].
+//!
+//! Part one is just a limited version of part two, and my solution works the same for both.
+//! `get_axial_intersections` uses [`Line::is_axial`] to filter out the diagonal lines that are only used in part
+//! two. To implement part two I just had to add the test cases for the diagonal lines, everything else just worked.
+//!
+//! The sets get slow for long lines on a big grid, and can't say how many lines cross a point beyond two, so the
+//! solution now counts the lines crossing each point with [`count_visits`] instead. When the lines fill enough of their
+//! bounding box, that is a `Vec<u16>` with a cell for every point in the box, otherwise a map of only the points that
+//! were visited, see [`VisitCounts`]. The number of points crossed by at least some other number of lines can be
+//! printed with `--param overlaps=<n>`, see [`Options`].
+//!
+//! Stepping one point at a time along each axis only works for lines that are axial or at exactly 45 degrees, which
+//! is all the puzzle has, but any other slope silently gave the wrong points. [`Line::points`] keeps that as the fast
+//! path, and rasterises lines at any other angle with [`Bresenham`]'s algorithm, so the module can be used for any
+//! segment with integer ends. The puzzle input is still checked to only have the lines the puzzle describes.
+
+use crate::errors::{check_input, check_lines, to_validation, Validation};
+use crate::util::params::Params;
+use itertools::Either;
+use regex::Regex;
+use std::cmp::max;
+use std::collections::HashMap;
+#[cfg(test)]
+use std::collections::HashSet;
+
+/// Extra output, can be changed with `--param overlaps=<n>`.
+#[derive(Eq, PartialEq, Debug)]
+struct Options {
+    /// Also report the points crossed by at least this many lines, skipped if 0, which is the default
+    overlaps: u16,
+}
+
+impl From<&Params> for Options {
+    fn from(params: &Params) -> Self {
+        Options {
+            overlaps: params.get_or("overlaps", 0),
+        }
+    }
+}
+
+/// Represent a line using the co-ordinates of each end.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Line {
+    x1: usize,
+    y1: usize,
+    x2: usize,
+    y2: usize,
+}
+
+impl Line {
+    #[cfg(test)]
+    fn new(x1: usize, y1: usize, x2: usize, y2: usize) -> Line {
+        return Line { x1, y1, x2, y2 };
+    }
+
+    /// True if the line is parallel to either the x or y axis
+    fn is_axial(&self) -> bool {
+        self.x1 == self.x2 || self.y1 == self.y2
+    }
+
+    /// Return a set of the points on the grid this line intersects
+    #[cfg(test)]
+    fn get_points(&self) -> HashSet<(usize, usize)> {
+        self.points().collect()
+    }
+
+    /// The number of steps from one end of the line to the other - we need the max as either d_x or d_y will be 0 for
+    /// axial lines
+    fn length(&self) -> usize {
+        max(self.x1.abs_diff(self.x2), self.y1.abs_diff(self.y2))
+    }
+
+    /// True if the line is at exactly 45 degrees to the axes
+    fn is_diagonal(&self) -> bool {
+        self.x1.abs_diff(self.x2) == self.y1.abs_diff(self.y2)
+    }
+
+    /// Return an iterator of the points on the grid this line intersects. Axial and diagonal lines step along each axis
+    /// by the same amount for each point, lines at any other angle are rasterised with [`Bresenham`]. Either way there
+    /// are [`Line::length`] + 1 points.
+    fn points(&self) -> impl Iterator<Item = (usize, usize)> {
+        if !self.is_axial() && !self.is_diagonal() {
+            return Either::Right(Bresenham::new(self));
+        }
+
+        let (x1, y1) = (self.x1 as isize, self.y1 as isize);
+
+        // Helper function so we don't have to repeat the step logic for x and y
+        fn get_step(p1: usize, p2: usize) -> isize {
+            match (p1, p2) {
+                (a, b) if a < b => 1,
+                (a, b) if a == b => 0,
+                _ => -1,
+            }
+        }
+
+        // because the input lines are always axial or diagonal they have a regular step for each point.
+        let d_x = get_step(self.x1, self.x2);
+        let d_y = get_step(self.y1, self.y2);
+
+        // iterate through each point applying the calculated deltas
+        Either::Left((0..=self.length() as isize).map(move |i| ((x1 + i * d_x) as usize, (y1 + i * d_y) as usize)))
+    }
+}
+
+/// Iterates over the points of a line at any angle with
+/// [Bresenham's line algorithm](https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm), picking the point closest
+/// to the line at each step along the longer axis, using only integer arithmetic. `error` tracks how far the current
+/// point is from the true line, and decides whether each step moves along x, y, or both.
+struct Bresenham {
+    x: isize,
+    y: isize,
+    end: (isize, isize),
+    d_x: isize,
+    d_y: isize,
+    step_x: isize,
+    step_y: isize,
+    error: isize,
+    done: bool,
+}
+
+impl Bresenham {
+    fn new(line: &Line) -> Bresenham {
+        let (x1, y1, x2, y2) = (line.x1 as isize, line.y1 as isize, line.x2 as isize, line.y2 as isize);
+        let d_x = (x2 - x1).abs();
+        // Negative so that the error term works the same way for both axes
+        let d_y = -(y2 - y1).abs();
+
+        Bresenham {
+            x: x1,
+            y: y1,
+            end: (x2, y2),
+            d_x,
+            d_y,
+            step_x: (x2 - x1).signum(),
+            step_y: (y2 - y1).signum(),
+            error: d_x + d_y,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for Bresenham {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let point = (self.x as usize, self.y as usize);
+        if (self.x, self.y) == self.end {
+            self.done = true;
+            return Some(point);
+        }
+
+        let doubled = 2 * self.error;
+        if doubled >= self.d_y {
+            self.error += self.d_y;
+            self.x += self.step_x;
+        }
+        if doubled <= self.d_x {
+            self.error += self.d_x;
+            self.y += self.step_y;
+        }
+
+        Some(point)
+    }
+}
+
+/// How many lines cross each point, either for every point in the lines' bounding box, or only for those that were
+/// visited
+#[derive(Eq, PartialEq, Debug)]
+enum VisitCounts {
+    Sparse(HashMap<(usize, usize), u16>),
+    Dense {
+        min_x: usize,
+        min_y: usize,
+        width: usize,
+        cells: Vec<u16>,
+    },
+}
+
+impl VisitCounts {
+    /// The number of lines that cross a point
+    #[cfg(test)]
+    fn get(&self, (x, y): (usize, usize)) -> u16 {
+        match self {
+            VisitCounts::Sparse(counts) => counts.get(&(x, y)).copied().unwrap_or(0),
+            VisitCounts::Dense {
+                min_x,
+                min_y,
+                width,
+                cells,
+            } => {
+                if x < *min_x || y < *min_y || x - min_x >= *width {
+                    0
+                } else {
+                    cells.get((y - min_y) * width + (x - min_x)).copied().unwrap_or(0)
+                }
+            }
+        }
+    }
+
+    /// The number of points crossed by at least `k` lines
+    fn at_least(&self, k: u16) -> usize {
+        match self {
+            VisitCounts::Sparse(counts) => counts.values().filter(|&&count| count >= k).count(),
+            VisitCounts::Dense { cells, .. } => cells.iter().filter(|&&count| count >= k).count(),
+        }
+    }
+}
+
+/// Allocating a cell for every point in the bounding box is worth it if it is no more than this many times the number
+/// of points the lines cross, as a map entry costs much more than a `u16` to store and update.
+const DENSE_CELLS_PER_POINT: usize = 16;
+
+/// Count how many lines cross each point, picking [`count_visits_dense`] or [`count_visits_sparse`] by comparing the
+/// area of the lines' bounding box to the number of points the lines cross, see [`DENSE_CELLS_PER_POINT`].
+fn count_visits(lines: &[Line]) -> VisitCounts {
+    let points: usize = lines.iter().map(|line| line.length() + 1).sum();
+    match bounding_box(lines) {
+        Some(((min_x, min_y), (max_x, max_y)))
+            if (max_x - min_x + 1).saturating_mul(max_y - min_y + 1)
+                <= points.saturating_mul(DENSE_CELLS_PER_POINT) =>
+        {
+            count_visits_dense(lines)
+        }
+        _ => count_visits_sparse(lines),
+    }
+}
+
+/// The smallest and largest co-ordinates of the lines' ends, or `None` if there are no lines
+fn bounding_box(lines: &[Line]) -> Option<((usize, usize), (usize, usize))> {
+    lines
+        .iter()
+        .map(|line| {
+            (
+                (line.x1.min(line.x2), line.y1.min(line.y2)),
+                (line.x1.max(line.x2), line.y1.max(line.y2)),
+            )
+        })
+        .reduce(|((min_x, min_y), (max_x, max_y)), ((x1, y1), (x2, y2))| {
+            ((min_x.min(x1), min_y.min(y1)), (max_x.max(x2), max_y.max(y2)))
+        })
+}
+
+/// Increment a cell in a grid covering the lines' bounding box for every point on every line
+fn count_visits_dense(lines: &[Line]) -> VisitCounts {
+    let ((min_x, min_y), (max_x, max_y)) = bounding_box(lines).unwrap_or_default();
+    let width = max_x - min_x + 1;
+    let mut cells = vec![0u16; if lines.is_empty() { 0 } else { width * (max_y - min_y + 1) }];
+
+    for (x, y) in lines.iter().flat_map(|line| line.points()) {
+        let cell = &mut cells[(y - min_y) * width + (x - min_x)];
+        *cell = cell.saturating_add(1);
+    }
+
+    VisitCounts::Dense {
+        min_x,
+        min_y,
+        width,
+        cells,
+    }
+}
+
+/// Count the visits to each point in a map, so only the points on a line take up space
+fn count_visits_sparse(lines: &[Line]) -> VisitCounts {
+    let mut counts: HashMap<(usize, usize), u16> = HashMap::new();
+    for point in lines.iter().flat_map(|line| line.points()) {
+        let count = counts.entry(point).or_insert(0);
+        *count = count.saturating_add(1);
+    }
+
+    VisitCounts::Sparse(counts)
+}
+
+/// Check each line is a pair of points, see [`validate`], and parse them as the [`Line`]s both
+/// [`part_one`] and [`part_two`] count the overlaps of.
+pub fn parse(contents: &str) -> Vec<Line> {
+    check_input(5, contents, validate);
+    parse_input(contents)
+}
+
+/// Count the points where at least two horizontal or vertical lines cross
+pub fn part_one(lines: &[Line]) -> Option<String> {
+    let axial_lines: Vec<Line> = lines.iter().filter(|l| l.is_axial()).copied().collect();
+    let axial_intersections = count_visits(&axial_lines).at_least(2);
+    say!("There are {} axial intersections", axial_intersections);
+
+    Some(axial_intersections.to_string())
+}
+
+/// Count the points where at least two lines of any kind cross.
+///
+/// - Points crossed by more lines can also be counted, see [`Options`].
+pub fn part_two(lines: &[Line]) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    let visits = count_visits(lines);
+    let intersections = visits.at_least(2);
+    say!("There are {} full intersections", intersections);
+
+    if options.overlaps > 0 {
+        say!(
+            "There are {} points crossed by at least {} lines",
+            visits.at_least(options.overlaps),
+            options.overlaps
+        );
+    }
+
+    Some(intersections.to_string())
+}
+
+/// Each line should be two points, and horizontal, vertical, or at 45 degrees
+pub fn validate(input: &str) -> Validation {
+    let line_matcher = Regex::new(r"^(\d+),(\d+) -> (\d+),(\d+)$").unwrap();
+    to_validation(check_lines(input, |_, line| {
+        match line_matcher.captures(line) {
+            None => Some(format!("Expected 'x1,y1 -> x2,y2', found '{}'", line)),
+            Some(cap) => {
+                let coords: Vec<isize> = (1..=4).map(|i| cap[i].parse().unwrap()).collect();
+                let (dx, dy) = (coords[2] - coords[0], coords[3] - coords[1]);
+                if dx == 0 || dy == 0 || dx.abs() == dy.abs() {
+                    None
+                } else {
+                    Some(format!(
+                        "'{}' is not horizontal, vertical, or diagonal",
+                        line
+                    ))
+                }
+            }
+        }
+    }))
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input is a list of lines between two points, so it is enough
+/// for it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// Takes a string with lines in the form `(x1,y1) -> (x2,y2)` and converts it into a list of [`Line`]s. Parsed
+/// using a regular expression.
+fn parse_input(input: &str) -> Vec<Line> {
+    let line_matcher = Regex::new(r"(\d+),(\d+) -> (\d+),(\d+)").unwrap();
+    input
+        .lines()
+        .flat_map(|line| {
+            line_matcher
+                .captures(line)
+                // Use zip to merge the individual capturing group Option into a single `Option((x1, y1),(x2,y2))`
+                // form. The values are still strings here
+                .and_then(|cap| cap.get(1).zip(cap.get(2)).zip(cap.get(3).zip(cap.get(4))))
+                // Transform that option into the same shape, but with the strings parsed as `usize`s. Split out into
+                // variables for clarity, but mostly because `rustfmt` mangles it otherwise.
+                .and_then(|((x1, y1), (x2, y2))| {
+                    let x1_res = x1.as_str().parse::<usize>().ok();
+                    let y1_res = y1.as_str().parse::<usize>().ok();
+                    let start = x1_res.zip(y1_res);
+
+                    let x2_res = x2.as_str().parse::<usize>().ok();
+                    let y2_res = y2.as_str().parse::<usize>().ok();
+                    let end = x2_res.zip(y2_res);
+
+                    start.zip(end)
+                })
+                // and match that shape, mapping it into the required line
+                .map(|((x1, y1), (x2, y2))| Line { x1, y1, x2, y2 })
+        })
+        .collect()
+}
+
+/// Filter out diagonal lines before running the remaining lines through `get_intersections`
+#[cfg(test)]
+fn get_axial_intersections(lines: &[Line]) -> HashSet<(usize, usize)> {
+    let filtered = lines
+        .iter()
+        .filter(|l| l.is_axial())
+        .map(|&l| l)
+        .collect::<Vec<Line>>();
+    get_intersections(&filtered)
+}
+
+/// For each line, iterate over it's points adding each to a set (visited at least once). If that insert fails, we've
+/// already seen that point so add it to a second set (visited at least twice). Points repeated more than twice can
+/// be ignored, as this is not needed to provide the puzzle solution. Return that set, the length of the set will
+/// give the number of points where two of more lines intersect.
+///
+/// [`count_visits`] is used by the solution now, this is kept to check it against.
+#[cfg(test)]
+fn get_intersections(lines: &[Line]) -> HashSet<(usize, usize)> {
+    let mut visited: HashSet<(usize, usize)> = HashSet::new();
+    let mut intersected: HashSet<(usize, usize)> = HashSet::new();
+
+    lines.iter().flat_map(|l| l.get_points()).for_each(|point| {
+        if !visited.insert(point) {
+            intersected.insert(point);
+        }
+    });
+
+    intersected
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geninput::generate;
+    use crate::util::params::Params;
+    use crate::year_2021::day_5::{
+        count_visits, Bresenham, count_visits_dense, count_visits_sparse, get_axial_intersections, get_intersections,
+        parse_input, validate, Line, Options, VisitCounts,
+    };
+    use std::collections::HashSet;
+
+    fn test_lines() -> Vec<Line> {
+        vec![
+            Line::new(0, 9, 5, 9),
+            Line::new(8, 0, 0, 8),
+            Line::new(9, 4, 3, 4),
+            Line::new(2, 2, 2, 1),
+            Line::new(7, 0, 7, 4),
+            Line::new(6, 4, 2, 0),
+            Line::new(0, 9, 2, 9),
+            Line::new(3, 4, 1, 4),
+            Line::new(0, 0, 8, 8),
+            Line::new(5, 5, 8, 2),
+        ]
+    }
+
+    #[test]
+    fn can_parse() {
+        let input = "0,9 -> 5,9\n\
+                    8,0 -> 0,8\n\
+                    9,4 -> 3,4\n\
+                    2,2 -> 2,1\n\
+                    7,0 -> 7,4\n\
+                    6,4 -> 2,0\n\
+                    0,9 -> 2,9\n\
+                    3,4 -> 1,4\n\
+                    0,0 -> 8,8\n\
+                    5,5 -> 8,2"
+            .to_string();
+
+        let expected = test_lines();
+
+        assert_eq!(parse_input(&input), expected);
+    }
+
+    #[test]
+    fn can_check_for_axial_lines() {
+        assert_eq!(
+            test_lines()
+                .iter()
+                .map(|l| l.is_axial())
+                .collect::<Vec<bool>>(),
+            vec![true, false, true, true, true, false, true, true, false, false]
+        )
+    }
+
+    #[test]
+    fn can_get_points() {
+        let actual = test_lines()
+            .iter()
+            .map(|l| l.get_points())
+            .collect::<Vec<HashSet<(usize, usize)>>>();
+
+        let expected = vec![
+            HashSet::from([(0, 9), (1, 9), (2, 9), (3, 9), (4, 9), (5, 9)]),
+            HashSet::from([
+                (8, 0),
+                (7, 1),
+                (6, 2),
+                (5, 3),
+                (4, 4),
+                (3, 5),
+                (2, 6),
+                (1, 7),
+                (0, 8),
+            ]),
+            HashSet::from([(9, 4), (8, 4), (7, 4), (6, 4), (5, 4), (4, 4), (3, 4)]),
+            HashSet::from([(2, 2), (2, 1)]),
+            HashSet::from([(7, 0), (7, 1), (7, 2), (7, 3), (7, 4)]),
+            HashSet::from([(6, 4), (5, 3), (4, 2), (3, 1), (2, 0)]),
+            HashSet::from([(0, 9), (1, 9), (2, 9)]),
+            HashSet::from([(3, 4), (2, 4), (1, 4)]),
+            HashSet::from([
+                (0, 0),
+                (1, 1),
+                (2, 2),
+                (3, 3),
+                (4, 4),
+                (5, 5),
+                (6, 6),
+                (7, 7),
+                (8, 8),
+            ]),
+            HashSet::from([(5, 5), (6, 4), (7, 3), (8, 2)]),
+        ];
+
+        actual
+            .iter()
+            .zip(expected.iter())
+            .for_each(|(a, e)| assert_eq!(a, e));
+    }
+
+    #[test]
+    fn can_rasterise_any_angle() {
+        let line = Line::new(0, 0, 4, 2);
+        assert!(!line.is_axial() && !line.is_diagonal());
+        assert_eq!(
+            line.points().collect::<Vec<(usize, usize)>>(),
+            vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]
+        );
+
+        // Steep lines step along y, and lines can go in any direction
+        assert_eq!(
+            Line::new(3, 7, 2, 1).points().collect::<Vec<(usize, usize)>>(),
+            vec![(3, 7), (3, 6), (3, 5), (2, 4), (2, 3), (2, 2), (2, 1)]
+        );
+
+        for line in [
+            Line::new(0, 0, 17, 5),
+            Line::new(17, 5, 0, 0),
+            Line::new(9, 1, 2, 30),
+            Line::new(100, 3, 1, 2),
+        ] {
+            let points: Vec<(usize, usize)> = line.points().collect();
+            assert_eq!(points.len(), line.length() + 1, "{:?}", line);
+            assert_eq!(points[0], (line.x1, line.y1));
+            assert_eq!(points[points.len() - 1], (line.x2, line.y2));
+            // Each point is next to the last
+            for pair in points.windows(2) {
+                assert!(pair[0].0.abs_diff(pair[1].0) <= 1 && pair[0].1.abs_diff(pair[1].1) <= 1);
+            }
+        }
+
+        // Bresenham gives the same points as the fast path for the lines the puzzle uses
+        for line in test_lines() {
+            assert_eq!(
+                Bresenham::new(&line).collect::<HashSet<(usize, usize)>>(),
+                line.get_points()
+            );
+        }
+    }
+
+    #[test]
+    fn can_get_axial_intersections() {
+        let intersections = get_axial_intersections(&test_lines());
+        assert_eq!(intersections.len(), 5);
+        assert!(intersections.contains(&(3, 4)));
+        assert!(intersections.contains(&(7, 4)));
+        assert!(intersections.contains(&(0, 9)));
+        assert!(intersections.contains(&(1, 9)));
+        assert!(intersections.contains(&(2, 9)));
+    }
+
+    #[test]
+    fn can_get_intersections() {
+        let intersections = get_intersections(&test_lines());
+        assert_eq!(intersections.len(), 12);
+        assert!(intersections.contains(&(7, 1)));
+        assert!(intersections.contains(&(2, 2)));
+        assert!(intersections.contains(&(5, 3)));
+        assert!(intersections.contains(&(7, 3)));
+        assert!(intersections.contains(&(3, 4)));
+        assert!(intersections.contains(&(4, 4)));
+        assert!(intersections.contains(&(7, 4)));
+        assert!(intersections.contains(&(6, 4)));
+        assert!(intersections.contains(&(5, 5)));
+        assert!(intersections.contains(&(0, 9)));
+        assert!(intersections.contains(&(1, 9)));
+        assert!(intersections.contains(&(2, 9)));
+    }
+
+    #[test]
+    fn can_count_visits() {
+        let lines = test_lines();
+        let dense = count_visits_dense(&lines);
+        let sparse = count_visits_sparse(&lines);
+        assert!(matches!(dense, VisitCounts::Dense { width: 10, .. }));
+        assert!(matches!(sparse, VisitCounts::Sparse(_)));
+
+        for y in 0..12 {
+            for x in 0..12 {
+                assert_eq!(dense.get((x, y)), sparse.get((x, y)), "({}, {})", x, y);
+            }
+        }
+        assert_eq!(dense.get((4, 4)), 3);
+        assert_eq!(dense.get((6, 4)), 3);
+        assert_eq!(dense.get((0, 0)), 1);
+        assert_eq!(dense.get((9, 0)), 0);
+
+        for counts in [&dense, &sparse] {
+            assert_eq!(counts.at_least(1), 39);
+            assert_eq!(counts.at_least(2), get_intersections(&lines).len());
+            assert_eq!(counts.at_least(3), 2);
+            assert_eq!(counts.at_least(4), 0);
+        }
+
+        let axial: Vec<Line> = lines.iter().filter(|l| l.is_axial()).copied().collect();
+        assert_eq!(count_visits(&axial).at_least(2), 5);
+        assert_eq!(count_visits(&[]).at_least(1), 0);
+    }
+
+    #[test]
+    fn can_pick_counting_strategy() {
+        // The example lines cover most of their 10 x 10 bounding box
+        assert!(matches!(
+            count_visits(&test_lines()),
+            VisitCounts::Dense { .. }
+        ));
+
+        // Two short lines at opposite corners of a big grid would mostly be empty cells
+        let lines = vec![Line::new(0, 0, 3, 0), Line::new(900_000, 900_000, 900_000, 900_003)];
+        let counts = count_visits(&lines);
+        assert!(matches!(counts, VisitCounts::Sparse(_)));
+        assert_eq!(counts.at_least(1), 8);
+        assert_eq!(counts, count_visits_sparse(&lines));
+    }
+
+    #[test]
+    fn can_build_options() {
+        assert_eq!(Options::from(&Params::default()), Options { overlaps: 0 });
+
+        let params = Params::parse(["--param", "overlaps=3"].map(String::from).into_iter());
+        assert_eq!(Options::from(&params), Options { overlaps: 3 });
+    }
+
+    #[test]
+    fn can_solve_generated_input() {
+        let input = generate(5, 1).unwrap().input;
+        assert_eq!(validate(&input), Ok(()));
+
+        let lines = parse_input(&input);
+        assert_eq!(lines.len(), 500);
+        assert!(get_axial_intersections(&lines).len() <= get_intersections(&lines).len());
+        assert_eq!(count_visits(&lines).at_least(2), get_intersections(&lines).len());
+    }
+}
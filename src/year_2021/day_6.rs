@@ -0,0 +1,406 @@
+//! This is my solution for [Advent of Code - Day 6 - _Lanternfish_](https://adventofcode.com/2021/day/6)
+//!
+//! There is always at least one puzzle each year where a naive implementation is fairly simple, but
+//! part two expands the problem size so that the naive solution will take too long to run. This is
+//! one of those.
+//!
+//! Experience from previous years allowed me to spot that and implement a more performant solution
+//! to part one, `simulate`. This requires the population count for each day, so there is also
+//! [`parse_input`] that reduces the puzzle input to this format. Part two calls `simulate` again,
+//! but with a higher number of days.
+//!
+//! The same idea of counting states rather than items came up again on days 14 and 21, so it now
+//! lives in [`CountMap`](crate::util::state_counts::CountMap), and `simulate` just describes how
+//! each fish's timer changes. The sample population passes `u64::MAX` after 490 days, so the counts
+//! are a [`Count`], which is wide enough for a lot more days than the puzzle asks for.
+//!
+//! Each run of `simulate` started again from the first day, so asking for 80 and then 256 days
+//! did the first 80 twice. [`LanternfishSchool`] instead keeps the counts between calls, so
+//! [`LanternfishSchool::advance`] only does the extra days, and as there are always exactly 9
+//! timers, a day is just rotating a fixed array rather than building a new map. `simulate` is
+//! kept to check the school against. For day counts far too large to step through, e.g. 10¹²,
+//! [`population_mod`] raises the day's transition matrix to the power of the number of days, which
+//! only takes ~40 squarings, and works modulo a number so that the count fits in a `u64`.
+
+use crate::errors::{check_input, check_pattern, check_single_line, to_validation, Validation};
+use crate::util::params::Params;
+#[cfg(test)]
+use crate::util::state_counts::CountMap;
+use crate::util::state_counts::{to_count, Count};
+
+/// The modulus used by [`population_mod`] unless `--param modulus=<n>` is given
+const DEFAULT_MODULUS: u64 = 1_000_000_007;
+
+/// The number of days to simulate for each part, can be changed with
+/// `--param part_one_days=<n>` and `--param part_two_days=<n>`. Passing `--param huge_days=<n>`
+/// also prints the population after that many days, modulo `--param modulus=<n>`, see
+/// [`population_mod`]. This is skipped if `huge_days` is 0, which is the default. A `modulus` of 0
+/// would divide by zero, so falls back to the default, [`DEFAULT_MODULUS`].
+#[derive(Eq, PartialEq, Debug)]
+struct Options {
+    part_one_days: usize,
+    part_two_days: usize,
+    huge_days: u64,
+    modulus: u64,
+}
+
+impl From<&Params> for Options {
+    fn from(params: &Params) -> Self {
+        Options {
+            part_one_days: params.get_or("part_one_days", 80),
+            part_two_days: params.get_or("part_two_days", 256),
+            huge_days: params.get_or("huge_days", 0),
+            modulus: match params.get_or("modulus", DEFAULT_MODULUS) {
+                0 => DEFAULT_MODULUS,
+                modulus => modulus,
+            },
+        }
+    }
+}
+
+/// Check the input is a single line of timers, see [`validate`], and count the fish with each timer
+/// value for [`part_one`] and [`part_two`].
+pub fn parse(contents: &str) -> [usize; 9] {
+    check_input(6, contents, validate);
+    parse_input(contents)
+}
+
+/// The population after the number of days for part one, which can be changed, see [`Options`]
+pub fn part_one(fish_pops: &[usize; 9]) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    let part_1_pop = LanternfishSchool::new(*fish_pops).population_after(options.part_one_days);
+    say!(
+        "Population count after {} days: {}",
+        options.part_one_days,
+        part_1_pop
+    );
+
+    Some(part_1_pop.to_string())
+}
+
+/// The population after the number of days for part two, and optionally a much larger number of
+/// days, modulo some number, see [`population_mod`]. The numbers of days can be changed, see
+/// [`Options`].
+pub fn part_two(fish_pops: &[usize; 9]) -> Option<String> {
+    let options = Options::from(&Params::from_args());
+    let part_2_pop = LanternfishSchool::new(*fish_pops).population_after(options.part_two_days);
+    say!(
+        "Population count after {} days: {}",
+        options.part_two_days,
+        part_2_pop
+    );
+
+    if options.huge_days > 0 {
+        say!(
+            "Population count after {} days: {} (mod {})",
+            options.huge_days,
+            population_mod(*fish_pops, options.huge_days, options.modulus),
+            options.modulus
+        );
+    }
+
+    Some(part_2_pop.to_string())
+}
+
+/// The input should be one line of timers, which are never more than 8
+pub fn validate(input: &str) -> Validation {
+    let mut errors = check_single_line(input);
+    errors.extend(check_pattern(
+        input,
+        r"[0-8](,[0-8])*",
+        "comma separated timers from 0 to 8",
+    ));
+    to_validation(errors)
+}
+
+/// For `--detect`, see [`crate::detect`]. Day 7's input is also one line of comma separated
+/// numbers, but some of its positions will be larger than any timer, so these are still told apart
+/// by [`validate`].
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// Reduces a comma-separated list of numbers representing the number of days until that fish will
+/// next reproduce, into a summary array that contains the count for each day.
+fn parse_input(input: &str) -> [usize; 9] {
+    // parse the initial input to a list of `usize`
+    let fish: Vec<usize> = input
+        .trim()
+        .split(',')
+        .flat_map(|num| num.parse::<usize>().ok())
+        .collect();
+
+    // iterate through the fish, incrementing the relevant count for each one.
+    let mut fish_population = [0usize; 9usize];
+    for f in fish {
+        fish_population[f] = fish_population[f] + 1;
+    }
+
+    fish_population
+}
+
+/// The count of fish with each timer, which can be moved on a number of days at a time and queried
+/// in between.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct LanternfishSchool {
+    initial: [usize; 9],
+    timers: [Count; 9],
+    days: usize,
+}
+
+impl LanternfishSchool {
+    /// Start a school from the count of fish with each timer, see [`parse_input`]
+    pub fn new(fish_pops: [usize; 9]) -> LanternfishSchool {
+        LanternfishSchool {
+            initial: fish_pops,
+            timers: fish_pops.map(to_count),
+            days: 0,
+        }
+    }
+
+    /// Move the school on by `days`. Each day the fish with a timer of 0 reproduce, which is the
+    /// same as rotating the timers one place to the left, so that they become the new fish with a
+    /// timer of 8, then adding them back in to the fish with a timer of 6.
+    pub fn advance(&mut self, days: usize) {
+        for _ in 0..days {
+            self.timers.rotate_left(1);
+            let (timers, new_fish) = self.timers.split_at_mut(8);
+            timers[6] += &new_fish[0];
+        }
+        self.days += days;
+    }
+
+    /// The total number of fish
+    pub fn population(&self) -> Count {
+        self.timers.iter().sum()
+    }
+
+    /// The total number of fish once `days` have passed since the school started. This only needs
+    /// to simulate the days since the last call, unless `days` is earlier than that, in which case
+    /// the school has to start again.
+    pub fn population_after(&mut self, days: usize) -> Count {
+        if days < self.days {
+            *self = LanternfishSchool::new(self.initial);
+        }
+        self.advance(days - self.days);
+
+        self.population()
+    }
+}
+
+/// A 9x9 matrix of counts modulo some number, see [`population_mod`]
+type Matrix = [[u64; 9]; 9];
+
+/// Multiply two matrices, keeping each value modulo `modulus`. The products are done as `u128` so
+/// that they can't overflow before being reduced.
+fn multiply(a: &Matrix, b: &Matrix, modulus: u64) -> Matrix {
+    std::array::from_fn(|row| {
+        std::array::from_fn(|col| {
+            ((0..9)
+                .map(|k| a[row][k] as u128 * b[k][col] as u128 % modulus as u128)
+                .sum::<u128>()
+                % modulus as u128) as u64
+        })
+    })
+}
+
+/// The population after `days`, modulo `modulus`, for day counts that are too large to step
+/// through. One day is a linear map from the old timer counts to the new ones, i.e. each fish moves
+/// one timer down, and those at 0 go to both 6 and 8. Applying it `days` times is the same as
+/// applying that matrix raised to the power `days`, which can be worked out by repeated squaring in
+/// `log2(days)` multiplications.
+pub fn population_mod(fish_pops: [usize; 9], days: u64, modulus: u64) -> u64 {
+    let mut step: Matrix = [[0; 9]; 9];
+    (0..8).for_each(|timer| step[timer][timer + 1] = 1);
+    step[6][0] = 1;
+    step[8][0] = 1;
+
+    // Start from the identity matrix, i.e. zero days
+    let mut result: Matrix = [[0; 9]; 9];
+    (0..9).for_each(|timer| result[timer][timer] = 1);
+    let mut remaining = days;
+    while remaining > 0 {
+        if remaining & 1 == 1 {
+            result = multiply(&result, &step, modulus);
+        }
+        step = multiply(&step, &step, modulus);
+        remaining >>= 1;
+    }
+
+    // Every fish counts towards the total, so the population is the sum of every cell of the matrix
+    // weighted by the count of fish that started with that cell's column as their timer.
+    let total: u128 = result
+        .iter()
+        .flat_map(|row| {
+            row.iter()
+                .zip(fish_pops)
+                .map(|(&cell, count)| cell as u128 * (count as u64 % modulus) as u128)
+        })
+        .map(|product| product % modulus as u128)
+        .sum();
+
+    (total % modulus as u128) as u64
+}
+
+/// Iterates the population `days` times, returning the resulting population summary. This was
+/// replaced by [`LanternfishSchool`] and is kept to check it against.
+#[cfg(test)]
+pub fn simulate(fish_pops: [usize; 9], days: usize) -> [Count; 9] {
+    let mut population: CountMap<usize> = CountMap::new();
+    for (timer, &count) in fish_pops.iter().enumerate() {
+        population.add(timer, to_count(count));
+    }
+
+    for _ in 0..days {
+        population = population.step(|&timer| {
+            if timer == 0 {
+                // Fish in the 0-day population reproduce - creating an equal number of fish that
+                // will reproduce in 9 days. These fish also reset, and will reproduce again
+                // themselves in 7 days.
+                vec![(6, 1), (8, 1)]
+            } else {
+                // otherwise each of the populations is moved one day earlier
+                vec![(timer - 1, 1)]
+            }
+        });
+    }
+
+    std::array::from_fn(|timer| population.get(&timer))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::params::Params;
+    use crate::util::state_counts::{to_count, Count};
+    use crate::year_2021::day_6::{
+        parse_input, population_mod, simulate, LanternfishSchool, Options,
+    };
+
+    #[test]
+    fn can_parse() {
+        assert_eq!(parse_input("3,4,3,1,2"), [0, 1, 1, 2, 1, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn can_simulate() {
+        assert_eq!(
+            simulate([0, 1, 1, 2, 1, 0, 0, 0, 0], 1),
+            [1, 1, 2, 1, 0, 0, 0, 0, 0].map(to_count)
+        );
+        assert_eq!(
+            simulate([0, 1, 1, 2, 1, 0, 0, 0, 0], 2),
+            [1, 2, 1, 0, 0, 0, 1, 0, 1].map(to_count)
+        );
+        assert_eq!(
+            simulate([0, 1, 1, 2, 1, 0, 0, 0, 0], 18)
+                .iter()
+                .sum::<Count>(),
+            to_count(26)
+        );
+        assert_eq!(
+            simulate([0, 1, 1, 2, 1, 0, 0, 0, 0], 80)
+                .iter()
+                .sum::<Count>(),
+            to_count(5934)
+        );
+        assert_eq!(
+            simulate([0, 1, 1, 2, 1, 0, 0, 0, 0], 256)
+                .iter()
+                .sum::<Count>(),
+            to_count(26984457539)
+        );
+    }
+
+    #[test]
+    fn can_simulate_past_u64() {
+        assert_eq!(
+            simulate([0, 1, 1, 2, 1, 0, 0, 0, 0], 500)
+                .iter()
+                .sum::<Count>()
+                .to_string(),
+            "45920814377410229085"
+        );
+    }
+
+    #[test]
+    fn school_matches_simulate() {
+        let fish_pops = [0, 1, 1, 2, 1, 0, 0, 0, 0];
+        let mut school = LanternfishSchool::new(fish_pops);
+        for days in 0..=300 {
+            assert_eq!(school.timers, simulate(fish_pops, days), "{} days", days);
+            school.advance(1);
+        }
+    }
+
+    #[test]
+    fn can_query_school_repeatedly() {
+        let mut school = LanternfishSchool::new([0, 1, 1, 2, 1, 0, 0, 0, 0]);
+        assert_eq!(school.population(), to_count(5));
+        assert_eq!(school.population_after(18), to_count(26));
+        assert_eq!(school.population_after(80), to_count(5934));
+        assert_eq!(school.days, 80);
+        assert_eq!(school.population_after(256), to_count(26984457539));
+        assert_eq!(school.population_after(18), to_count(26));
+        assert_eq!(school.days, 18);
+
+        school.advance(62);
+        assert_eq!(school.population(), to_count(5934));
+    }
+
+    #[test]
+    fn can_find_population_mod() {
+        let fish_pops = [0, 1, 1, 2, 1, 0, 0, 0, 0];
+        assert_eq!(population_mod(fish_pops, 0, 1_000_000_007), 5);
+        assert_eq!(population_mod(fish_pops, 18, 1_000_000_007), 26);
+        assert_eq!(population_mod(fish_pops, 80, 1_000_000_007), 5934);
+        assert_eq!(population_mod(fish_pops, 256, u64::MAX), 26984457539);
+        assert_eq!(
+            population_mod(fish_pops, 256, 1_000_000_007),
+            26984457539 % 1_000_000_007
+        );
+
+        for days in [100, 300, 500] {
+            let expected = simulate(fish_pops, days).iter().sum::<Count>() % to_count(999_999_937);
+            assert_eq!(
+                to_count(population_mod(fish_pops, days as u64, 999_999_937) as usize),
+                expected,
+                "{} days",
+                days
+            );
+        }
+
+        // Too many days to step through, but only ~40 matrix squarings
+        assert!(population_mod(fish_pops, 1_000_000_000_000, 1_000_000_007) < 1_000_000_007);
+    }
+
+    #[test]
+    fn can_build_options() {
+        assert_eq!(
+            Options::from(&Params::default()),
+            Options {
+                part_one_days: 80,
+                part_two_days: 256,
+                huge_days: 0,
+                modulus: 1_000_000_007,
+            }
+        );
+
+        let params = Params::parse(
+            ["--param", "part_two_days=100", "--param", "huge_days=1000000000000"]
+                .map(String::from)
+                .into_iter(),
+        );
+        assert_eq!(
+            Options::from(&params),
+            Options {
+                part_one_days: 80,
+                part_two_days: 100,
+                huge_days: 1_000_000_000_000,
+                modulus: 1_000_000_007,
+            }
+        );
+
+        let params = Params::parse(["--param", "modulus=0"].map(String::from).into_iter());
+        assert_eq!(Options::from(&params).modulus, 1_000_000_007);
+    }
+}
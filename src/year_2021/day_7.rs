@@ -0,0 +1,341 @@
+//! This is my solution for [Advent of Code - Day 7 - _The Treachery of Whales_](https://adventofcode.com/2021/day/7)
+//!
+//! Today's input was a bunch of 🦀 crabs 🦀 (in submarines ) who can only move sideways (despite
+//! being in submarines 🤷.) The challenge was to find the most efficient position to align them to,
+//! given a particular cost function for a crab submarine to move a certain distance.
+//!
+//! For part one this was a linear cost, which reduces to finding the median, and calculating the
+//! cost for each crab to move to that median. This is implemented by [`find_distance_to_median`].
+//! As an informal proof for this method consider: for any given position value, incrementing that
+//! position by one increases the overall cost by one per crab below that value, and decreases it by
+//! one per crab above that value. Decrementing the position has the opposite effect. For any crab
+//! other than the median, if you move the value towards the median, more crabs are on the side that
+//! decreases the cost (by definition), and so the overall cost decreases. An interesting note in
+//! the case of an even number of crabs, the value of either crab in the middle pair, and any values
+//! between them will produce the same optimal cost, so in that case we can just pick one
+//! arbitrarily.
+//!
+//! For part two the cost function was the triangular number of the distance, `1 => 1`, `2 => 3`,
+//! `3 => 6`, ... or `(n * (n+1) ) / 2`. This is implemented by [`find_triangular_distance_to_mean`].
+//! I have to admit I guessed the mean here. It seems reasonable - if the cost increases as you move
+//! further you want a lot of crabs to move the shortest distance they can. Given there was an issue
+//! where the rounding was slightly off (rounding the calculated mean worked for the sample, but
+//! floor was needed for the puzzle input) I suspect the mean is the limit as the steepness of the
+//! cost function increases, and it's close enough (±1) for triangular distance. But equally that
+//! may just be a weirdness of integer maths. If anyone has information on more concrete theory
+//! about this I'd be interested in a link.
+//!
+//! To try other cost functions without needing a similar insight for each, [`minimise_cost`] takes
+//! the cost of moving a distance as a closure, and checks every position between the outermost
+//! crabs. [`linear_cost`] and [`triangular_cost`] are the two from the puzzle. If the cost of each
+//! extra step never goes down, the total cost can only go down then up as the target moves from
+//! left to right, so [`minimise_convex_cost`] can use a ternary search to find the bottom of that
+//! curve instead of checking every position. Finally, if the crabs each had a different fuel cost
+//! per step, [`weighted_median`] finds the best position for part one's linear cost. Passing
+//! `--compare` checks these all agree with the answers from the median and mean, see
+//! [`alternatives`].
+
+use crate::errors::{check_input, check_pattern, check_single_line, to_validation, Validation};
+use crate::solution::{Alternative, Part};
+use crate::util::stats::{mean, median_low};
+use std::cmp::min;
+
+/// Check the input is a single line of crab positions, see [`validate`], and parse them for
+/// [`part_one`] and [`part_two`].
+pub fn parse(contents: &str) -> Vec<usize> {
+    check_input(7, contents, validate);
+    contents
+        .trim()
+        .split(',')
+        .flat_map(|pos| pos.parse())
+        .collect()
+}
+
+/// The fuel needed when each step costs the same, see [`find_distance_to_median`]
+pub fn part_one(positions: &[usize]) -> Option<String> {
+    let linear_fuel = find_distance_to_median(positions);
+    say!("Total fuel to align - linear: {}", linear_fuel);
+
+    Some(linear_fuel.to_string())
+}
+
+/// The fuel needed when each step costs one more than the last, see
+/// [`find_triangular_distance_to_mean`]
+pub fn part_two(positions: &[usize]) -> Option<String> {
+    let triangular_fuel = find_triangular_distance_to_mean(positions);
+    say!("Total fuel to align - triangular: {}", triangular_fuel);
+
+    Some(triangular_fuel.to_string())
+}
+
+/// The input should be one line of positions
+pub fn validate(input: &str) -> Validation {
+    let mut errors = check_single_line(input);
+    errors.extend(check_pattern(
+        input,
+        r"\d+(,\d+)*",
+        "comma separated positions",
+    ));
+    to_validation(errors)
+}
+
+/// For `--detect`, see [`crate::detect`]. Day 6's timers would also be valid positions, so at least
+/// one number has to be larger than a timer can be.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+        && input
+            .trim()
+            .split(',')
+            .filter_map(|position| position.parse::<usize>().ok())
+            .any(|position| position > 8)
+}
+
+/// First find the median, see [`median_low`]. As discussed in the summary, either midpoint is fine
+/// in the case of an even length list, so just use the lower one, as the target has to be a crab's
+/// position. Secondly iterate through the list to total the distance to the median and sum those
+/// values.
+fn find_distance_to_median(positions: &[usize]) -> usize {
+    let median = median_low(positions).expect("There are no crabs");
+
+    positions
+        .iter()
+        .map(|&pos| (pos as isize - median as isize).abs() as usize)
+        .sum()
+}
+
+/// Very similar to [`find_distance_to_median`] with three differences:
+/// - Calculate mean instead of median for the target position
+/// - Map the resulting fuel cost using the triangular number distance
+/// - Calculate the total for the integer values both sides of the mean and take the lowest (see
+///   main description)
+fn find_triangular_distance_to_mean(positions: &[usize]) -> usize {
+    let mean = mean(positions).expect("There are no crabs").floor() as usize;
+
+    min(
+        positions
+            .iter()
+            .map(|&pos| (pos as isize - mean as isize).abs() as usize)
+            .map(|distance| (distance * (distance + 1)) / 2)
+            .sum(),
+        positions
+            .iter()
+            .map(|&pos| (pos as isize - (mean as isize + 1)).abs() as usize)
+            .map(|distance| (distance * (distance + 1)) / 2)
+            .sum(),
+    )
+}
+
+/// The cost model for part one, each step costs one fuel
+pub fn linear_cost(distance: usize) -> usize {
+    distance
+}
+
+/// The cost model for part two, each step costs one more fuel than the last
+pub fn triangular_cost(distance: usize) -> usize {
+    (distance * (distance + 1)) / 2
+}
+
+/// The total fuel for all the crabs to move to `target`, where moving `n` steps costs `cost(n)`
+fn total_cost(positions: &[usize], target: usize, cost: &impl Fn(usize) -> usize) -> usize {
+    positions
+        .iter()
+        .map(|&pos| cost(pos.abs_diff(target)))
+        .sum()
+}
+
+/// Find the position that is cheapest for all the crabs to move to, and the total fuel that costs,
+/// by checking every position from the left-most to the right-most crab. Ties are resolved to the
+/// left-most position. Returns `None` if there are no crabs.
+pub fn minimise_cost(positions: &[usize], cost: impl Fn(usize) -> usize) -> Option<(usize, usize)> {
+    let (&lo, &hi) = (positions.iter().min()?, positions.iter().max()?);
+
+    (lo..=hi)
+        .map(|target| (target, total_cost(positions, target, &cost)))
+        .min_by_key(|&(target, total)| (total, target))
+}
+
+/// As [`minimise_cost`], but `cost` must be convex, i.e. the extra cost for each step never goes
+/// down. Each crab's cost then only falls as the target approaches it, and rises as it leaves, and
+/// so the total is also convex. This allows a ternary search: of two points a third of the way in
+/// from each end, the higher one can't have the minimum on its far side, so that third can be
+/// dropped. When they're equal the minimum is between them, or they are both on a flat minimum
+/// that may extend further left, so the right-hand third is dropped to keep ties resolving to the
+/// left-most position. This needs `O(log n)` totals rather than `n`.
+pub fn minimise_convex_cost(
+    positions: &[usize],
+    cost: impl Fn(usize) -> usize,
+) -> Option<(usize, usize)> {
+    let (mut lo, mut hi) = (*positions.iter().min()?, *positions.iter().max()?);
+
+    while hi - lo > 2 {
+        let third = (hi - lo) / 3;
+        let (left, right) = (lo + third, hi - third);
+        let left_cost = total_cost(positions, left, &cost);
+        let right_cost = total_cost(positions, right, &cost);
+
+        if left_cost > right_cost {
+            lo = left + 1;
+        } else {
+            hi = right - 1;
+        }
+    }
+
+    (lo..=hi)
+        .map(|target| (target, total_cost(positions, target, &cost)))
+        .min_by_key(|&(target, total)| (total, target))
+}
+
+/// Given `(position, weight)` pairs, where moving a crab one step costs its weight, find the
+/// position that minimises the total linear cost. This is the weighted median, the first position
+/// where the crabs at or to the left of it carry at least half the total weight. By the same
+/// argument as [`find_distance_to_median`], moving away from it moves further from more weight
+/// than it moves towards. Returns `None` if there is no weight.
+pub fn weighted_median(crabs: &[(usize, usize)]) -> Option<usize> {
+    let mut sorted = crabs.to_vec();
+    sorted.sort();
+    let total: usize = sorted.iter().map(|&(_, weight)| weight).sum();
+
+    let mut seen = 0;
+    sorted.into_iter().find_map(|(position, weight)| {
+        seen += weight;
+        (weight > 0 && seen * 2 >= total).then_some(position)
+    })
+}
+
+/// The general cost searches, to compare with the puzzle specific answers with `--compare`. Part
+/// one also tries the [`weighted_median`] with every crab weighing the same.
+pub fn alternatives() -> Vec<Alternative<[usize]>> {
+    vec![
+        Alternative::new(Part::One, "find_distance_to_median", |positions| {
+            Some(find_distance_to_median(positions).to_string())
+        }),
+        Alternative::new(Part::One, "minimise_cost", |positions| {
+            minimise_cost(positions, linear_cost).map(|(_, fuel)| fuel.to_string())
+        }),
+        Alternative::new(Part::One, "minimise_convex_cost", |positions| {
+            minimise_convex_cost(positions, linear_cost).map(|(_, fuel)| fuel.to_string())
+        }),
+        Alternative::new(Part::One, "weighted_median", |positions| {
+            let crabs: Vec<(usize, usize)> = positions.iter().map(|&pos| (pos, 1)).collect();
+            weighted_median(&crabs)
+                .map(|target| total_cost(positions, target, &linear_cost).to_string())
+        }),
+        Alternative::new(Part::Two, "find_triangular_distance_to_mean", |positions| {
+            Some(find_triangular_distance_to_mean(positions).to_string())
+        }),
+        Alternative::new(Part::Two, "minimise_cost", |positions| {
+            minimise_cost(positions, triangular_cost).map(|(_, fuel)| fuel.to_string())
+        }),
+        Alternative::new(Part::Two, "minimise_convex_cost", |positions| {
+            minimise_convex_cost(positions, triangular_cost).map(|(_, fuel)| fuel.to_string())
+        }),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::year_2021::day_7::{
+        find_distance_to_median, find_triangular_distance_to_mean, linear_cost,
+        minimise_convex_cost, minimise_cost, total_cost, triangular_cost, weighted_median,
+    };
+
+    fn sample_positions() -> Vec<usize> {
+        vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14]
+    }
+
+    #[test]
+    fn can_find_distance_to_median() {
+        assert_eq!(
+            find_distance_to_median(&vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14]),
+            37
+        )
+    }
+
+    #[test]
+    fn can_find_distance_to_median_of_even_counts() {
+        // Anywhere between the middle pair is as good, either end of it included
+        assert_eq!(find_distance_to_median(&[0, 10]), 10);
+        assert_eq!(find_distance_to_median(&[1, 2, 8, 9]), 14);
+        assert_eq!(find_distance_to_median(&[5]), 0);
+    }
+
+    #[test]
+    fn can_find_triangular_distance_to_mean() {
+        assert_eq!(
+            find_triangular_distance_to_mean(&vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14]),
+            168
+        )
+    }
+
+    #[test]
+    fn can_minimise_cost() {
+        let positions = sample_positions();
+
+        assert_eq!(minimise_cost(&positions, linear_cost), Some((2, 37)));
+        assert_eq!(minimise_cost(&positions, triangular_cost), Some((5, 168)));
+        assert_eq!(minimise_convex_cost(&positions, linear_cost), Some((2, 37)));
+        assert_eq!(
+            minimise_convex_cost(&positions, triangular_cost),
+            Some((5, 168))
+        );
+
+        // A user defined cost, squaring the distance
+        assert_eq!(
+            minimise_cost(&positions, |d| d * d),
+            minimise_convex_cost(&positions, |d| d * d)
+        );
+
+        // Even numbers of crabs have a plateau between the middle pair for linear cost
+        assert_eq!(minimise_cost(&[0, 10], linear_cost), Some((0, 10)));
+        assert_eq!(minimise_convex_cost(&[0, 10], linear_cost), Some((0, 10)));
+
+        assert_eq!(minimise_cost(&[], linear_cost), None);
+        assert_eq!(minimise_convex_cost(&[], linear_cost), None);
+        assert_eq!(minimise_convex_cost(&[3], linear_cost), Some((3, 0)));
+    }
+
+    #[test]
+    fn convex_search_agrees_with_full_scan() {
+        // A spread of crabs from a simple LCG, to avoid a dependency on rand
+        let mut seed: usize = 7;
+        let mut next = || {
+            seed = (seed * 1103515245 + 12345) % (1 << 31);
+            seed % 1000
+        };
+
+        for count in [2, 5, 11, 50] {
+            let positions: Vec<usize> = (0..count).map(|_| next()).collect();
+            for cost in [linear_cost, triangular_cost, |d: usize| d * d * d] {
+                assert_eq!(
+                    minimise_convex_cost(&positions, cost),
+                    minimise_cost(&positions, cost)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn can_find_weighted_median() {
+        let crabs: Vec<(usize, usize)> = sample_positions().into_iter().map(|p| (p, 1)).collect();
+        assert_eq!(weighted_median(&crabs), Some(2));
+
+        // A very inefficient crab at 16 pulls the best position to it
+        let mut crabs = crabs;
+        crabs[0] = (16, 20);
+        let median = weighted_median(&crabs).unwrap();
+        assert_eq!(median, 16);
+
+        // Check against the full scan, weighting by repeating each crab
+        let repeated: Vec<usize> = crabs
+            .iter()
+            .flat_map(|&(position, weight)| std::iter::repeat_n(position, weight))
+            .collect();
+        assert_eq!(
+            Some(total_cost(&repeated, median, &linear_cost)),
+            minimise_cost(&repeated, linear_cost).map(|(_, total)| total)
+        );
+
+        assert_eq!(weighted_median(&[]), None);
+        assert_eq!(weighted_median(&[(4, 0), (9, 1)]), Some(9));
+    }
+}
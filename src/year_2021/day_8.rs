@@ -56,12 +56,13 @@
 //! and 1 is a subset of 0 and 9. This hit a snag in that a `HashSet` doesn't itself implement
 //! `Hash` so can't be used as a key. I pondered sorting the strings and using that as the key, but
 //! the logic to calculate their intersections is pretty complex in Rust. Then I remembered
-//! [`crate::day_3`] and went with representing each of the 7 lines as a bit (giving a unique number
+//! [`super::day_3`] and went with representing each of the 7 lines as a bit (giving a unique number
 //! for each set). Further, bitwise `&` can be used to efficiently do the intersections. I gave this
 //! it's own type [`Digit`], and hooked into the built-in FromStr trait to make creating these from
 //! the input cleaner. For part one only the first pass through the 10 digits was implemented, but
 //! I'm happy to report that my plan worked and I did not need to change my implementation of part
-//! one to cope with the changes added to solve part two.
+//! one to cope with the changes added to solve part two. The bit twiddling has since moved to
+//! [`SmallSet`], shared with [`super::day_3`], so the checks below read as subset tests.
 //!
 //! Part two required working out the remaining six digits, interpreting the four output digits as a
 //! 4 digit decimal number, and summing those to get the puzzle solution. My implementation for part
@@ -88,15 +89,30 @@
 //! final step was to implement [`Display::get_output`] that converted the four output digits into
 //! the equivalent decimal `usize`, and I used built in iterate -> map -> sum to reduce the input
 //! to the solution.
-
+//!
+//! To help check custom inputs, [`Display::wire_mapping`] works back from the resolved digits to
+//! which segment each wire is connected to. Each segment is lit in a different set of the ten
+//! digits, e.g. only the bottom left is lit in exactly 0, 2, 6 and 8, so each wire can be matched
+//! by the digits it appears in. [`Display::render`] then draws the output with that mapping as
+//! seven-segment art, so a bad mapping shows up as malformed digits. Run with `--trace` to print
+//! these for every line.
+
+use crate::errors::{check_input, check_pattern, to_validation, Validation};
+use crate::util::bitset::SmallSet;
 use std::collections::HashMap;
-use std::fs;
+use std::env;
 use std::str::FromStr;
 
+/// The segments each decimal digit lights up when wired correctly, see the diagram in the module
+/// documentation for the positions.
+const DIGIT_SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
 #[derive(Eq, PartialEq, Debug)]
-struct Display {
+pub struct Display {
     /// Map of the sets of lines and the decimal digit they represent
-    digits: HashMap<usize, usize>,
+    digits: HashMap<SmallSet, usize>,
     /// The four output digits
     output: Vec<Digit>,
 }
@@ -108,20 +124,98 @@ impl Display {
             .iter()
             .map(|d| {
                 self.digits
-                    .get(&d.bits)
-                    .expect(format!("Missing {:?}", d).as_str())
+                    .get(&d.segments)
+                    .unwrap_or_else(|| panic!("Missing {}", d.segments))
             })
             .fold(0, |acc, digit| acc * 10 + digit)
     }
+
+    /// Deduce which segment each wire is connected to, indexed by wire with `a` at index 0. A
+    /// segment is identified by which of the ten digits light it up, so this finds the segment
+    /// lit by the same digits as the wire. Returns an error naming the wire if there isn't a
+    /// unique match, which can happen if the line's digits weren't all resolved.
+    fn wire_mapping(&self) -> Result<[char; 7], String> {
+        // The set of digits that light up each wire or segment, as a bit set of the digits
+        let digits_using = |index: usize, digit_segments: &dyn Fn(usize) -> Option<SmallSet>| {
+            (0..10)
+                .filter(|&digit| digit_segments(digit).is_some_and(|set| set.contains(index)))
+                .collect::<SmallSet>()
+        };
+
+        let by_digit: HashMap<usize, SmallSet> = self
+            .digits
+            .iter()
+            .map(|(&segments, &digit)| (digit, segments))
+            .collect();
+        let wired = |digit| by_digit.get(&digit).copied();
+        let correct = |digit| Some(SmallSet::from_letters(DIGIT_SEGMENTS[digit]));
+
+        let mut mapping = ['?'; 7];
+        for (wire, segment) in mapping.iter_mut().enumerate() {
+            let wire_digits = digits_using(wire, &wired);
+            let matches: Vec<usize> = (0..7)
+                .filter(|&index| digits_using(index, &correct) == wire_digits)
+                .collect();
+
+            match matches[..] {
+                [index] => *segment = (b'a' + index as u8) as char,
+                _ => {
+                    return Err(format!(
+                        "Wire {} is lit in digits {}, which doesn't match one segment",
+                        (b'a' + wire as u8) as char,
+                        wire_digits
+                    ))
+                }
+            }
+        }
+
+        Ok(mapping)
+    }
+
+    /// Draw the four output digits as seven-segment art, three rows high, using the segments the
+    /// wires are connected to according to [`Display::wire_mapping`].
+    fn render(&self) -> Result<String, String> {
+        let mapping = self.wire_mapping()?;
+
+        // The segment drawn in each cell of a digit, and the character used if it is lit
+        let layout: [[Option<(char, char)>; 3]; 3] = [
+            [None, Some(('a', '_')), None],
+            [Some(('b', '|')), Some(('d', '_')), Some(('c', '|'))],
+            [Some(('e', '|')), Some(('g', '_')), Some(('f', '|'))],
+        ];
+
+        let lit: Vec<Vec<char>> = self
+            .output
+            .iter()
+            .map(|digit| digit.segments.iter().map(|wire| mapping[wire]).collect())
+            .collect();
+
+        Ok(layout
+            .iter()
+            .map(|row| {
+                lit.iter()
+                    .map(|segments| {
+                        row.iter()
+                            .map(|cell| match cell {
+                                Some((segment, chr)) if segments.contains(segment) => *chr,
+                                _ => ' ',
+                            })
+                            .collect::<String>()
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" ")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n"))
+    }
 }
 
 #[derive(Eq, PartialEq, Debug)]
 struct Digit {
-    /// The set of bits that are lit up with a being least significant and g being most
-    bits: usize,
-    /// The number of bits that are set. This is known at creation so cache to avoid recalculating
-    /// later
-    len: usize,
+    /// The set of segments that are lit up, with a as index 0 and g as index 6
+    segments: SmallSet,
 }
 
 impl FromStr for Digit {
@@ -132,44 +226,76 @@ impl FromStr for Digit {
     /// Technically this accepts more than just sets of a-g, but that does not need to be handled
     /// for the puzzle input.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut digit = Digit { bits: 0, len: 0 };
-        s.chars().for_each(|c| {
-            // chars can be converted to their ascii int just by caching - so this calculates the
-            // offset from 'a'
-            let pos = (c as usize) - ('a' as usize);
-            // shift left once for each digit
-            digit.bits = digit.bits | (1 << pos);
-            // track the number of bits set
-            digit.len = digit.len + 1;
-        });
-
-        Ok(digit)
+        Ok(Digit {
+            segments: SmallSet::from_letters(s),
+        })
     }
 }
 
-/// The entry point for running the solutions with the 'real' puzzle input.
-///
-/// - The puzzle input is expected to be at `<project_root>/res/day-8-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 8.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-8-input").expect("Failed to read file");
-    let displays = parse_input(contents);
-
-    let unique_count = count_unique(&displays);
-    println!(
+/// Check each line is ten digit patterns and four output patterns, see [`validate`], and parse them
+/// as the [`Display`]s for [`part_one`] and [`part_two`].
+pub fn parse(contents: &str) -> Vec<Display> {
+    check_input(8, contents, validate);
+    parse_input(contents.lines())
+}
+
+/// Count the output digits that can be identified by their length, see [`count_unique`]
+pub fn part_one(displays: &[Display]) -> Option<String> {
+    let unique_count = count_unique(displays);
+    say!(
         "There are {} digits with unique lengths in the output.",
         unique_count
     );
 
+    Some(unique_count.to_string())
+}
+
+/// Decode each display, and sum their outputs.
+///
+/// - Pass `--trace` to also print the wiring and rendered output of each display.
+pub fn part_two(displays: &[Display]) -> Option<String> {
     let output_total: usize = displays.iter().map(Display::get_output).sum();
 
-    println!("The sum of the outputs is {}.", output_total);
+    say!("The sum of the outputs is {}.", output_total);
+
+    if env::args().any(|arg| arg == "--trace") {
+        for display in displays {
+            match (display.wire_mapping(), display.render()) {
+                (Ok(mapping), Ok(render)) => say!(
+                    "Wires abcdefg -> {}\n{}\n",
+                    mapping.iter().collect::<String>(),
+                    render
+                ),
+                (Err(err), _) | (_, Err(err)) => say!("Couldn't decode display: {}\n", err),
+            }
+        }
+    }
+
+    Some(output_total.to_string())
+}
+
+/// Each line should be the ten unique digits, then the four output digits, using wires `a` - `g`
+pub fn validate(input: &str) -> Validation {
+    to_validation(check_pattern(
+        input,
+        r"[a-g]+( [a-g]+){9} \| [a-g]+( [a-g]+){3}",
+        "ten digits, '|', then four digits",
+    ))
+}
+
+/// For `--detect`, see [`crate::detect`]. No other day's input is made of segment patterns, so it
+/// is enough for it to be valid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
 }
 
 /// Utility for the whole puzzle input that just defers to [`parse_line`] for each line of the
-/// input.
-fn parse_input(input: String) -> Vec<Display> {
-    input.lines().map(|line| parse_line(line)).collect()
+/// input, taking the lines one at a time so that the input can be streamed.
+fn parse_input<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> Vec<Display> {
+    lines
+        .into_iter()
+        .map(|line| parse_line(line.as_ref()))
+        .collect()
 }
 
 /// This does all of the hard work. Once the input is turned into a [`Display`] the puzzle solution
@@ -186,33 +312,33 @@ fn parse_line(line: &str) -> Display {
     // First split into the digits and output
     if let Some((digit_strings, output_strings)) = line.split_once(" | ") {
         // Setup an empty map to be populated as we resolve each digit
-        let mut digits: HashMap<usize, usize> = HashMap::new();
+        let mut digits: HashMap<SmallSet, usize> = HashMap::new();
 
         // First interpret the two halves into the internal Digit representation
         let unassigned_digits: Vec<Digit> = digit_strings.split(' ').map(parse_digit).collect();
         let output = output_strings.split(' ').map(parse_digit).take(4).collect();
 
         // Cache for the bit sets we'll need to isolate other digits later
-        let mut four: Option<usize> = None;
-        let mut one: Option<usize> = None;
-        let mut nine: Option<usize> = None;
+        let mut four: Option<SmallSet> = None;
+        let mut one: Option<SmallSet> = None;
+        let mut nine: Option<SmallSet> = None;
 
         // First pass - capture digits that have a unique length
         unassigned_digits.iter().for_each(|digit| {
-            match digit.len {
+            match digit.segments.len() {
                 2 => {
-                    digits.insert(digit.bits, 1);
-                    one = Some(digit.bits);
+                    digits.insert(digit.segments, 1);
+                    one = Some(digit.segments);
                 }
                 3 => {
-                    digits.insert(digit.bits, 7);
+                    digits.insert(digit.segments, 7);
                 }
                 4 => {
-                    digits.insert(digit.bits, 4);
-                    four = Some(digit.bits);
+                    digits.insert(digit.segments, 4);
+                    four = Some(digit.segments);
                 }
                 7 => {
-                    digits.insert(digit.bits, 8);
+                    digits.insert(digit.segments, 8);
                 }
                 _ => {}
             };
@@ -223,39 +349,45 @@ fn parse_line(line: &str) -> Display {
         // Second pass - capture 6, 9, 0 using their intersection with unique digits 1 and 4
         unassigned_digits
             .iter()
-            .filter(|digit| digit.len == 6)
+            .filter(|digit| digit.segments.len() == 6)
             .for_each(|digit| {
                 // 9 intersects with 4, 6 and 0 don't.
-                if digit.bits & four.expect("digits missing 4") == four.unwrap() {
-                    digits.insert(digit.bits, 9);
-                    nine = Some(digit.bits);
+                if digit
+                    .segments
+                    .contains_all(&four.expect("digits missing 4"))
+                {
+                    digits.insert(digit.segments, 9);
+                    nine = Some(digit.segments);
                 }
                 // 0 and 9 intersect with 1, but 9 is already captured above
-                else if digit.bits & one.expect("digits missing 1") == one.unwrap() {
-                    digits.insert(digit.bits, 0);
+                else if digit.segments.contains_all(&one.expect("digits missing 1")) {
+                    digits.insert(digit.segments, 0);
                 }
                 // Can only be 6 by process of elimination
                 else {
-                    digits.insert(digit.bits, 6);
+                    digits.insert(digit.segments, 6);
                 }
             });
 
         // Third pass - capture 2, 3, 5 using their intersections with digits 1 and 9
         unassigned_digits
             .iter()
-            .filter(|digit| digit.len == 5)
+            .filter(|digit| digit.segments.len() == 5)
             .for_each(|digit| {
                 // 1 is included in 3, but not 2 or 5
-                if digit.bits & one.expect("digits missing 1") == one.unwrap() {
-                    digits.insert(digit.bits, 3);
+                if digit.segments.contains_all(&one.expect("digits missing 1")) {
+                    digits.insert(digit.segments, 3);
                 }
                 // 5 is included in 9, but not in 2 and 3 are not
-                else if digit.bits & nine.expect("digits missing 9") == digit.bits {
-                    digits.insert(digit.bits, 5);
+                else if nine
+                    .expect("digits missing 9")
+                    .contains_all(&digit.segments)
+                {
+                    digits.insert(digit.segments, 5);
                 }
                 // Can only be 2 by process of elimination
                 else {
-                    digits.insert(digit.bits, 2);
+                    digits.insert(digit.segments, 2);
                 }
             });
 
@@ -268,7 +400,7 @@ fn parse_line(line: &str) -> Display {
 }
 
 /// Given a list of parsed displays, count the total number of 1s, 4s, 7s, and 8s in their outputs
-fn count_unique(displays: &Vec<Display>) -> usize {
+fn count_unique(displays: &[Display]) -> usize {
     displays
         .iter()
         .map(|display| {
@@ -277,7 +409,7 @@ fn count_unique(displays: &Vec<Display>) -> usize {
                 .iter()
                 // Flat map converts to the digit and filters out any unmatched digits to support
                 // the part one implementation did not include a total mapping for each digit
-                .flat_map(|digit| display.digits.get(&digit.bits))
+                .flat_map(|digit| display.digits.get(&digit.segments))
                 // Limit to just the four digits that part one cares about so that this still
                 // works when part two is implemented.
                 .filter(|digit| [1, 4, 7, 8].contains(digit))
@@ -292,87 +424,78 @@ mod tests {
     use std::collections::HashMap;
     use std::str::FromStr;
 
-    use crate::day_8::{count_unique, parse_input, parse_line, Digit, Display};
+    use crate::util::bitset::SmallSet;
+    use crate::year_2021::day_8::{count_unique, parse_input, parse_line, Digit, Display};
 
     #[test]
     fn can_parse_digit() {
         assert_eq!(
             Digit::from_str("acedgfb"),
             Ok(Digit {
-                bits: 0b1111111,
-                len: 7
+                segments: SmallSet::from_bits(0b1111111)
             })
         );
 
         assert_eq!(
             Digit::from_str("cdfbe"),
             Ok(Digit {
-                bits: 0b0111110,
-                len: 5
+                segments: SmallSet::from_bits(0b0111110)
             })
         );
 
         assert_eq!(
             Digit::from_str("gcdfa"),
             Ok(Digit {
-                bits: 0b1101101,
-                len: 5
+                segments: SmallSet::from_bits(0b1101101)
             })
         );
 
         assert_eq!(
             Digit::from_str("fbcad"),
             Ok(Digit {
-                bits: 0b0101111,
-                len: 5
+                segments: SmallSet::from_bits(0b0101111)
             })
         );
 
         assert_eq!(
             Digit::from_str("dab"),
             Ok(Digit {
-                bits: 0b0001011,
-                len: 3
+                segments: SmallSet::from_bits(0b0001011)
             })
         );
 
         assert_eq!(
             Digit::from_str("cefabd"),
             Ok(Digit {
-                bits: 0b0111111,
-                len: 6
+                segments: SmallSet::from_bits(0b0111111)
             })
         );
 
         assert_eq!(
             Digit::from_str("cdfgeb"),
             Ok(Digit {
-                bits: 0b1111110,
-                len: 6
+                segments: SmallSet::from_bits(0b1111110)
             })
         );
 
         assert_eq!(
             Digit::from_str("eafb"),
             Ok(Digit {
-                bits: 0b0110011,
-                len: 4
+                segments: SmallSet::from_bits(0b0110011)
             })
         );
 
         assert_eq!(
             Digit::from_str("cagedb"),
             Ok(Digit {
-                bits: 0b1011111,
-                len: 6
+                segments: SmallSet::from_bits(0b1011111)
             })
         );
 
         assert_eq!(
             Digit::from_str("ab"),
             Ok(Digit {
-                bits: 0b000011,
-                len: 2
+                segments: SmallSet::from_bits(0b000011)
             })
         );
     }
@@ -382,16 +505,16 @@ mod tests {
         let display = parse_line(get_sample_line());
 
         let digits = HashMap::from([
-            (Digit::from_str("cagedb").unwrap().bits, 0usize),
-            (Digit::from_str("ab").unwrap().bits, 1usize),
-            (Digit::from_str("gcdfa").unwrap().bits, 2usize),
-            (Digit::from_str("fbcad").unwrap().bits, 3usize),
-            (Digit::from_str("eafb").unwrap().bits, 4usize),
-            (Digit::from_str("cdfbe").unwrap().bits, 5usize),
-            (Digit::from_str("cdfgeb").unwrap().bits, 6usize),
-            (Digit::from_str("dab").unwrap().bits, 7usize),
-            (Digit::from_str("acedgfb").unwrap().bits, 8usize),
-            (Digit::from_str("cefabd").unwrap().bits, 9usize),
+            (Digit::from_str("cagedb").unwrap().segments, 0usize),
+            (Digit::from_str("ab").unwrap().segments, 1usize),
+            (Digit::from_str("gcdfa").unwrap().segments, 2usize),
+            (Digit::from_str("fbcad").unwrap().segments, 3usize),
+            (Digit::from_str("eafb").unwrap().segments, 4usize),
+            (Digit::from_str("cdfbe").unwrap().segments, 5usize),
+            (Digit::from_str("cdfgeb").unwrap().segments, 6usize),
+            (Digit::from_str("dab").unwrap().segments, 7usize),
+            (Digit::from_str("acedgfb").unwrap().segments, 8usize),
+            (Digit::from_str("cefabd").unwrap().segments, 9usize),
         ]);
 
         let output = vec![
@@ -411,7 +534,7 @@ mod tests {
         let expected_outputs: Vec<usize> =
             vec![8394, 9781, 1197, 9361, 4873, 8418, 4548, 1625, 8717, 4315];
 
-        parse_input(get_sample_input())
+        parse_input(get_sample_input().lines())
             .iter()
             .zip(expected_outputs)
             .for_each(|(display, expected_output)| {
@@ -423,9 +546,59 @@ mod tests {
         "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf"
     }
 
+    #[test]
+    fn can_find_wire_mapping() {
+        // Worked through in the puzzle description: the wire d is connected to the top segment a,
+        // wire e to the top left b, and so on.
+        let display = parse_line(get_sample_line());
+        assert_eq!(
+            display.wire_mapping(),
+            Ok(['c', 'f', 'g', 'a', 'b', 'd', 'e'])
+        );
+
+        // Correctly wired digits map every wire to itself
+        let display = parse_line(
+            "abcefg cf acdeg acdfg bcdf abdfg abdefg acf abcdefg abcdfg | cf acdeg acdfg bcdf",
+        );
+        assert_eq!(
+            display.wire_mapping(),
+            Ok(['a', 'b', 'c', 'd', 'e', 'f', 'g'])
+        );
+        assert_eq!(display.get_output(), 1234);
+
+        // Digits that weren't all resolved can't be mapped
+        let mut display = parse_line(get_sample_line());
+        display.digits.retain(|_, &mut digit| digit != 8);
+        assert!(display.wire_mapping().is_err());
+        assert!(display.render().is_err());
+    }
+
+    #[test]
+    fn can_render() {
+        // 5353
+        let expected = [" _   _   _   _", "|_   _| |_   _|", " _|  _|  _|  _|"].join("\n");
+        assert_eq!(parse_line(get_sample_line()).render(), Ok(expected));
+
+        let expected = [
+            "     _   _       _   _   _   _   _   _",
+            "  |  _|  _| |_| |_  |_    | |_| |_| | |",
+            "  | |_   _|   |  _| |_|   | |_|  _| |_|",
+        ]
+        .join("\n");
+        let mut display = parse_line(get_sample_line());
+        // Swap in every digit, in order, for the output
+        let mut digits: Vec<(usize, _)> = display.digits.iter().map(|(&s, &d)| (d, s)).collect();
+        digits.sort_by_key(|&(digit, _)| (digit + 9) % 10);
+        display.output = digits
+            .into_iter()
+            .map(|(_, segments)| Digit { segments })
+            .collect();
+        assert_eq!(display.render(), Ok(expected));
+    }
+
     #[test]
     fn can_count_unique() {
-        let displays: Vec<Display> = parse_input(get_sample_input());
+        let displays: Vec<Display> = parse_input(get_sample_input().lines());
 
         assert_eq!(count_unique(&displays), 26);
     }
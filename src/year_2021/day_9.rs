@@ -0,0 +1,500 @@
+//! This is my solution for [Advent of Code - Day 9 - _Smoke Basin_](https://adventofcode.com/2021/day/9)
+//!
+//! _**Note**: At the time of writing [`Grid`] was part of the Day 9 module. Parts that were reused for Day 11 have
+//! since been extracted into a utility module._
+//!
+//! Today's task was to find local minima (part one) of a grid of digits and the area within the watershed of those
+//! (part two). For this I built a type to represent a [`Grid`] and implemented a bunch of methods to build towards a
+//! solution. I've tried to lean more on the standard library and built in traits to accomplish some of this,
+//! specifically using `impl From<&str> for Grid` for the initial parsing, and providing [`Grid::iter`] which returns
+//! a [`crate::util::grid::GridCoords`] which has an implementation for [`Iterator`] as a standard way to iterate all
+//! the cells in the grid.
+//!
+//! I had to work at getting [`Grid::iter`] correct and my original code was implementing [`IntoIterator`] for Grid,
+//! and the iterator was contained the grid itself, which in turn was (because I was struggling with exactly where the
+//! lifetime constraints needed to go to satisfy the compiler. This meant I needed to clone the whole grid each time
+//! I wanted to iterate over it as the iterator took ownership of the grid it was created with. I eventually found an
+//! [article on Iterators and Reference Lifetimes](https://medium.com/@wastedintel/reference-iterators-in-rust-5603a51b5192)
+//! that explained how to get the lifetimes to work, and I got a small speed improvement now I wasn't copying the
+//! grid all over the place.
+//!
+//! To outline my solution, [`Grid`] is implemented as a one-dimensional list of numbers, that also has a record of
+//! the width of the grid to work out the correct offset in the list for a given x and y, implemented as [`Grid::get`].
+//! Working the other way, [`Grid::get_with_coords`] is used by the iterator to work out the 2D co-ordinates of its
+//! current position. [`Grid::get_low_points`] filters the iterator of all points in the grid to just the local minima,
+//! this defers to [`Grid::is_lowest`] which in turn uses [`Grid::get_orthogonal_surrounds`] to check the current value
+//! against its four neighbours. [`Grid::get_risk_level`] takes the result of [`Grid::get_low_points`] and reduces it to
+//! the puzzle solution for part one.
+//!
+//! To solve part two, [`Grid::get_basin`] uses [`Grid::get_orthogonal_surrounds`], filtering to only larger numbers
+//! less than the watershed of 9 to recursively build a set of co-ordinates by walking uphill.
+//! [`Grid::get_largest_basin_sizes`] is a wrapper that calls [`Grid::get_basin`] for each low point, and the reduces
+//! the returned data into the puzzle solution.
+//!
+//! Walking each basin recursively from its low point can revisit cells, and a big enough basin would overflow the
+//! stack. As every cell other than a 9 is in exactly one basin, the basins are really just the regions of the grid
+//! separated by 9s. [`Grid::get_basin_sizes`] now finds those in one pass, joining each cell to its neighbours below
+//! and to the right in a [`DisjointSet`], and [`Grid::get_largest_basin_sizes`] takes the largest three of them.
+//!
+//! The same sets also label every cell with the basin it is in, see [`Grid::basin_map`], which [`format_basins`] uses
+//! to draw the heightmap with each basin in a different colour, to check the basins are split where expected. Run with
+//! `--basins` to print it for part two.
+
+use crate::errors::{check_grid, check_input, to_validation, Validation};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::env;
+
+#[doc(inline)]
+pub use crate::util::grid::Grid;
+
+/// ANSI escape sequences to draw a cell with a coloured background, cycled through by basin, see [`format_basins`].
+/// Basins sharing a colour may be close together, but are always separated by 9s, which aren't coloured.
+const BASIN_COLOURS: [&str; 6] = [
+    "\x1b[41m",
+    "\x1b[42m",
+    "\x1b[43m",
+    "\x1b[44m",
+    "\x1b[45m",
+    "\x1b[46m",
+];
+/// ANSI escape sequence to reset the background colour after a cell in a basin
+const COLOUR_END: &str = "\x1b[0m";
+
+/// A union-find over the cells of a grid, by their position in [`Grid::numbers`]. Each set is a tree stored as a
+/// parent for each position, with the root as the set's representative. Merging attaches the smaller tree under the
+/// larger, and finding a root points every position on the way at the root, so both are close to constant time.
+struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSet {
+    /// Start with each position in a set of its own
+    fn new(len: usize) -> DisjointSet {
+        DisjointSet {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+        }
+    }
+
+    /// The representative of the set containing `pos`
+    fn find(&mut self, pos: usize) -> usize {
+        let mut root = pos;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // Compress the path so later lookups go straight to the root
+        let mut current = pos;
+        while current != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// Merge the sets containing `a` and `b`
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+
+        let (larger, smaller) = if self.sizes[a] >= self.sizes[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parents[smaller] = larger;
+        self.sizes[larger] += self.sizes[smaller];
+    }
+
+    /// The size of the set containing `pos`
+    fn size_of(&mut self, pos: usize) -> usize {
+        let root = self.find(pos);
+        self.sizes[root]
+    }
+}
+
+impl Grid {
+    /// Is the provided grid cell a local minimum
+    fn is_lowest(&self, y: usize, x: usize) -> bool {
+        self.get(y, x)
+            .map(|val| {
+                self.get_orthogonal_surrounds(y, x)
+                    .iter()
+                    .all(|&(_, adjacent)| val < adjacent)
+            })
+            .unwrap_or(false)
+    }
+
+    /// Return a list of the co-ordinates and values of all local minima
+    fn get_low_points(&self) -> Vec<((usize, usize), u8)> {
+        self.iter()
+            .filter(|((y, x), _)| self.is_lowest(*y, *x))
+            .collect()
+    }
+
+    /// The risk level of the grid is the sum of the risk level of each low point, which is the low point's height
+    /// plus one.
+    fn get_risk_level(&self) -> usize {
+        self.get_low_points()
+            .iter()
+            .map(|&(_, height)| height as usize + 1)
+            .sum()
+    }
+
+    /// Recursively walk to higher points from a starting minimum, stopping at the watershed of height 9. Returns the
+    /// set of co-ordinates found. The solution now uses [`Grid::get_basin_sizes`] instead.
+    #[allow(dead_code)]
+    fn get_basin(&self, y: usize, x: usize) -> HashSet<(usize, usize)> {
+        let mut basin = HashSet::new();
+        if let Some(height) = self.get(y, x) {
+            basin.insert((y, x));
+            self.get_orthogonal_surrounds(y, x)
+                .iter()
+                .filter(|(_, h)| *h > height && *h < 9)
+                .flat_map(|((y1, x1), _)| self.get_basin(*y1, *x1))
+                .for_each(|coord| {
+                    basin.insert(coord);
+                })
+        }
+
+        basin
+    }
+
+    /// True if the cell at a position in [`Grid::numbers`] is part of a basin, i.e. isn't a 9
+    fn in_basin(&self, pos: usize) -> bool {
+        self.numbers[pos] < 9
+    }
+
+    /// Each cell lower than 9 is joined with the neighbours below and to the right of it that are also lower than 9,
+    /// which covers every adjacent pair once. Each set left is a basin.
+    fn join_basins(&self) -> DisjointSet {
+        let mut basins = DisjointSet::new(self.len());
+
+        for pos in (0..self.len()).filter(|&pos| self.in_basin(pos)) {
+            let right = pos + 1;
+            if right % self.width != 0 && self.in_basin(right) {
+                basins.union(pos, right);
+            }
+
+            let below = pos + self.width;
+            if below < self.len() && self.in_basin(below) {
+                basins.union(pos, below);
+            }
+        }
+
+        basins
+    }
+
+    /// The size of every basin, in no particular order, see [`Grid::join_basins`]
+    fn get_basin_sizes(&self) -> Vec<usize> {
+        let mut basins = self.join_basins();
+
+        (0..self.len())
+            .filter_map(|pos| {
+                if self.in_basin(pos) && basins.find(pos) == pos {
+                    Some(basins.size_of(pos))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// The basin each cell is in, indexed as [`Grid::numbers`], or `None` for the 9s between them. Basins are numbered
+    /// from `0` in the order their first cell appears, reading the grid a row at a time.
+    fn basin_map(&self) -> Vec<Option<usize>> {
+        let mut basins = self.join_basins();
+        let mut ids = HashMap::new();
+
+        (0..self.len())
+            .map(|pos| {
+                if !self.in_basin(pos) {
+                    return None;
+                }
+
+                let next_id = ids.len();
+                Some(*ids.entry(basins.find(pos)).or_insert(next_id))
+            })
+            .collect()
+    }
+
+    /// Find the size of each basin, and return the highest three sizes found
+    fn get_largest_basin_sizes(&self) -> Vec<usize> {
+        self.get_basin_sizes()
+            .into_iter()
+            .sorted()
+            .rev()
+            .take(3)
+            .collect()
+    }
+}
+
+/// Check the input is a rectangular heightmap, see [`validate`], and parse it as the [`Grid`] both
+/// [`part_one`] and [`part_two`] search.
+pub fn parse(contents: &str) -> Grid {
+    check_input(9, contents, validate);
+    Grid::from(contents)
+}
+
+/// Sum the risk levels of the low points, see [`Grid::get_risk_level`]
+pub fn part_one(grid: &Grid) -> Option<String> {
+    let risk_level = grid.get_risk_level();
+    say!("Total risk level: {}", risk_level);
+
+    Some(risk_level.to_string())
+}
+
+/// Multiply the sizes of the three largest basins, see [`Grid::get_largest_basin_sizes`]. Pass `--basins` to also
+/// print the heightmap coloured by basin, see [`format_basins`].
+pub fn part_two(grid: &Grid) -> Option<String> {
+    if env::args().any(|arg| arg == "--basins") {
+        say!("{}", format_basins(grid));
+    }
+
+    let basin_sizes = grid.get_largest_basin_sizes();
+    say!(
+        "Largest Basins: {} * {} * {} = {}, ",
+        basin_sizes.get(0).unwrap(),
+        basin_sizes.get(1).unwrap(),
+        basin_sizes.get(2).unwrap(),
+        basin_sizes.iter().product::<usize>()
+    );
+
+    Some(basin_sizes.iter().product::<usize>().to_string())
+}
+
+/// Draw the heightmap one row per line, with each basin's cells given a background colour from [`BASIN_COLOURS`]
+fn format_basins(grid: &Grid) -> String {
+    let basins = grid.basin_map();
+
+    grid.numbers
+        .iter()
+        .zip(basins)
+        .map(|(height, basin)| match basin {
+            Some(id) => format!(
+                "{}{}{}",
+                BASIN_COLOURS[id % BASIN_COLOURS.len()],
+                height,
+                COLOUR_END
+            ),
+            None => height.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .chunks(grid.width)
+        .map(|row| row.concat())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// The heights should be a rectangular grid of digits
+pub fn validate(input: &str) -> Validation {
+    to_validation(check_grid(input, "0123456789"))
+}
+
+/// For `--detect`, see [`crate::detect`]. Days 3, 11 and 15 are also grids of digits. A heightmap has low points of
+/// height 0, which day 15's risk levels never are, and digits other than 0 and 1, unlike day 3's binary. Day 11's
+/// octopuses could have both, so a 10 by 10 grid may be reported as either. The rows are also longer than any of day
+/// 1's depths, as a few depths of the same length would make a grid.
+pub fn sniff(input: &str) -> bool {
+    validate(input).is_ok()
+        && input.lines().all(|line| line.len() > 6)
+        && input.contains('0')
+        && input.contains(|c: char| ('2'..='9').contains(&c))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::year_2021::day_9::{format_basins, DisjointSet, Grid, BASIN_COLOURS, COLOUR_END};
+
+    #[test]
+    fn can_parse() {
+        let grid = get_sample_grid();
+
+        assert_eq!(grid.width, 10);
+        assert_eq!(grid.get(0, 0), Some(2));
+        assert_eq!(grid.get(4, 9), Some(8));
+        assert_eq!(grid.get(5, 10), None);
+
+        assert_eq!(
+            grid.iter().take(3).collect::<Vec<((usize, usize), u8)>>(),
+            vec![((0, 0), 2), ((0, 1), 1), ((0, 2), 9)]
+        );
+        assert_eq!(
+            grid.iter().skip(48).collect::<Vec<((usize, usize), u8)>>(),
+            vec![((4, 8), 7), ((4, 9), 8)]
+        );
+    }
+
+    fn get_sample_grid() -> Grid {
+        let input = "2199943210\n\
+             3987894921\n\
+             9856789892\n\
+             8767896789\n\
+             9899965678"
+            .to_string();
+
+        let grid = Grid::from(input.as_str());
+        grid
+    }
+
+    #[test]
+    fn can_get_surrounds() {
+        let grid = get_sample_grid();
+
+        assert_eq!(
+            grid.get_orthogonal_surrounds(0, 0),
+            vec![((0, 1), 1), ((1, 0), 3)]
+        );
+        assert_eq!(
+            grid.get_orthogonal_surrounds(0, 1),
+            vec![((0, 2), 9), ((1, 1), 9), ((0, 0), 2)]
+        );
+        assert_eq!(
+            grid.get_orthogonal_surrounds(1, 1),
+            vec![((0, 1), 1), ((1, 2), 8), ((2, 1), 8), ((1, 0), 3)]
+        );
+    }
+
+    #[test]
+    fn can_determine_if_lowest() {
+        let grid = get_sample_grid();
+
+        assert_eq!(grid.is_lowest(0, 0), false);
+        assert_eq!(grid.is_lowest(0, 1), true);
+    }
+
+    #[test]
+    fn can_get_low_points() {
+        let grid = get_sample_grid();
+
+        assert_eq!(
+            grid.get_low_points(),
+            vec![((0, 1), 1), ((0, 9), 0), ((2, 2), 5), ((4, 6), 5)]
+        )
+    }
+
+    #[test]
+    fn can_get_risk_level() {
+        let grid = get_sample_grid();
+
+        assert_eq!(grid.get_risk_level(), 15)
+    }
+
+    #[test]
+    fn can_get_basin() {
+        let grid = get_sample_grid();
+
+        assert_eq!(
+            grid.get_basin(0, 1),
+            HashSet::from([(0, 0), (0, 1), (1, 0)])
+        );
+    }
+
+    #[test]
+    fn can_get_basin_sizes() {
+        let grid = get_sample_grid();
+
+        assert_eq!(grid.get_largest_basin_sizes(), vec![14, 9, 9]);
+    }
+
+    #[test]
+    fn can_get_all_basin_sizes() {
+        let grid = get_sample_grid();
+
+        let mut sizes = grid.get_basin_sizes();
+        sizes.sort();
+        assert_eq!(sizes, vec![3, 9, 9, 14]);
+
+        // A basin far too large to walk recursively
+        let size = 1000;
+        let grid = Grid::from(vec!["1".repeat(size); size].join("\n").as_str());
+        assert_eq!(grid.get_basin_sizes(), vec![size * size]);
+
+        // Basins that wrap across the end of a row aren't joined
+        let grid = Grid::from("119\n999\n911");
+        let mut sizes = grid.get_basin_sizes();
+        sizes.sort();
+        assert_eq!(sizes, vec![2, 2]);
+    }
+
+    #[test]
+    fn can_map_basins() {
+        let grid = get_sample_grid();
+        let basins = grid.basin_map();
+
+        assert_eq!(basins.len(), 50);
+        assert_eq!(&basins[0..10], &[Some(0), Some(0), None, None, None, Some(1), Some(1), Some(1), Some(1), Some(1)]);
+        assert_eq!(basins[10], Some(0));
+        assert_eq!(basins[12], Some(2));
+        assert_eq!(basins[49], Some(3));
+
+        let mut sizes: Vec<usize> = (0..4)
+            .map(|id| basins.iter().filter(|&&basin| basin == Some(id)).count())
+            .collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![3, 9, 9, 14]);
+        assert_eq!(basins.iter().filter(|basin| basin.is_none()).count(), 15);
+    }
+
+    #[test]
+    fn can_format_basins() {
+        // Swap each colour for a letter to make the expected output readable
+        let formatted = BASIN_COLOURS
+            .iter()
+            .enumerate()
+            .fold(format_basins(&get_sample_grid()), |formatted, (id, colour)| {
+                formatted.replace(colour, &((b'a' + id as u8) as char).to_string())
+            })
+            .replace(COLOUR_END, "");
+
+        assert_eq!(
+            formatted,
+            "a2a1999b4b3b2b1b0
+a39c8c7c89b49b2b1
+9c8c5c6c7c89d89b2
+c8c7c6c7c89d6d7d89
+9c8999d6d5d6d7d8"
+        );
+    }
+
+    #[test]
+    fn can_join_sets() {
+        let mut sets = DisjointSet::new(6);
+        sets.union(0, 1);
+        sets.union(2, 3);
+        sets.union(1, 3);
+
+        assert_eq!(sets.find(0), sets.find(2));
+        assert_ne!(sets.find(0), sets.find(4));
+        assert_eq!(sets.size_of(3), 4);
+        assert_eq!(sets.size_of(5), 1);
+    }
+
+    fn _debug_basin(grid: Grid, basin: HashSet<(usize, usize)>) {
+        let mut line = 0;
+        grid.iter().for_each(|((y, x), h)| {
+            if line != y {
+                print!("\n");
+                line = y;
+            }
+            print!(
+                "{}",
+                if basin.contains(&(y, x)) {
+                    h.to_string()
+                } else {
+                    "#".to_string()
+                }
+            )
+        });
+        print!("\n");
+    }
+}
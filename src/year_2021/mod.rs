@@ -0,0 +1,80 @@
+//! The solutions to the [2021 puzzles](https://adventofcode.com/2021), one module per day. Adding a day only needs a
+//! line in the `days!` list at the bottom, which declares the module and registers it with the runner.
+
+use crate::solution::{Day, Registry};
+
+const YEAR: u16 = 2021;
+
+/// Declare each day's module, and a `register` function that adds them all to the registry. Each day is given as
+/// `<day> => <module>, <feature>, <title>, <parts>;`. Each module provides `parse`, `validate`, `sniff`, `part_one`,
+/// and `part_two` if it has two parts, see [`Day`]. Days with more than one way of solving a part add `, alternatives`
+/// after the parts, and provide an `alternatives` function listing them for `--compare`. If the day's feature is
+/// disabled, the module isn't compiled, so the entry reports that instead of running the solution, and has no answers.
+macro_rules! days {
+    (@parts 1, $day:expr, $module:ident) => { $day };
+    (@parts 2, $day:expr, $module:ident) => { $day.with_part_two($module::part_two) };
+    (@not_included 1, $day:expr) => { $day.part_one_only() };
+    (@not_included 2, $day:expr) => { $day };
+
+    (
+        $(
+            $day:literal => $module:ident, $feature:literal, $name:literal, $parts:tt
+            $(, $alternatives:ident)?;
+        )*
+    ) => {
+        $(
+            #[cfg(feature = $feature)]
+            pub mod $module;
+        )*
+
+        /// Add each day's solution to the registry
+        pub fn register(registry: &mut Registry) {
+            $({
+                #[cfg(feature = $feature)]
+                let day = days!(
+                    @parts $parts,
+                    Day::new($name, $module::parse, $module::part_one)
+                        .with_sniff($module::sniff)
+                        .with_validate($module::validate)
+                        $(.with_alternatives($module::$alternatives()))?,
+                    $module
+                );
+                #[cfg(not(feature = $feature))]
+                let day = days!(
+                    @not_included $parts,
+                    crate::solution::NotIncluded::new($name, stringify!($module), $feature)
+                );
+
+                registry.register(YEAR, $day, day);
+            })*
+        }
+    };
+}
+
+days! {
+    1 => day_1, "day_1", "Sonar Sweep", 2;
+    2 => day_2, "day_2", "Dive!", 2;
+    3 => day_3, "day_3", "Binary Diagnostic", 2;
+    4 => day_4, "day_4", "Giant Squid", 2;
+    5 => day_5, "day_5", "Hydrothermal Venture", 2;
+    6 => day_6, "day_6", "Lanternfish", 2;
+    7 => day_7, "day_7", "The Treachery of Whales", 2, alternatives;
+    8 => day_8, "day_8", "Seven Segment Search", 2;
+    9 => day_9, "day_9", "Smoke Basin", 2;
+    10 => day_10, "day_10", "Syntax Scoring", 2;
+    11 => day_11, "day_11", "Dumbo Octopus", 2;
+    12 => day_12, "day_12", "Passage Pathing", 2;
+    13 => day_13, "day_13", "Transparent Origami", 2;
+    14 => day_14, "day_14", "Extended Polymerization", 2;
+    15 => day_15, "day_15", "Chiton", 2;
+    16 => day_16, "day_16", "Packet Decoder", 2;
+    17 => day_17, "day_17", "Trick Shot", 2, alternatives;
+    18 => day_18, "day_18", "Snailfish", 2;
+    19 => day_19, "day_19", "Beacon Scanner", 2, alternatives;
+    20 => day_20, "day_20", "Trench Map", 2, alternatives;
+    21 => day_21, "day_21", "Dirac Dice", 2;
+    22 => day_22, "day_22", "Reactor Reboot", 2, alternatives;
+    23 => day_23, "day_23", "Amphipod", 2;
+    24 => day_24, "day_24", "Arithmetic Logic Unit", 2;
+    25 => day_25, "day_25", "Sea Cucumber", 1, alternatives;
+}
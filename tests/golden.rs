@@ -0,0 +1,130 @@
+//! Runs every day through the compiled runner against the example from its puzzle description, and compares the answers
+//! to those recorded in `tests/golden/answers.json`. Where [`integration`](../integration/index.html) checks the text
+//! each day prints, this checks the answers themselves, all in one place, so a change to something shared, e.g. one of
+//! the `util` modules, can't quietly change any day's results.
+//!
+//! If a change to the answers is intended, run `UPDATE_GOLDEN=1 cargo test --test golden` to rewrite the file, and
+//! check the diff before committing. Days left out of the build by their cargo feature are skipped, and keep their
+//! recorded answers when the file is rewritten. Day 24 has no example input, so isn't included.
+
+use serde_json::{json, Value};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Set to rewrite the golden answers rather than compare against them, shared with the golden images
+const UPDATE_GOLDEN_ENV_VAR: &str = "UPDATE_GOLDEN";
+
+/// Each day with an example input, and whether it is included in the build
+const DAYS: [(u8, bool); 24] = [
+    (1, cfg!(feature = "day_1")),
+    (2, cfg!(feature = "day_2")),
+    (3, cfg!(feature = "day_3")),
+    (4, cfg!(feature = "day_4")),
+    (5, cfg!(feature = "day_5")),
+    (6, cfg!(feature = "day_6")),
+    (7, cfg!(feature = "day_7")),
+    (8, cfg!(feature = "day_8")),
+    (9, cfg!(feature = "day_9")),
+    (10, cfg!(feature = "day_10")),
+    (11, cfg!(feature = "day_11")),
+    (12, cfg!(feature = "day_12")),
+    (13, cfg!(feature = "day_13")),
+    (14, cfg!(feature = "day_14")),
+    (15, cfg!(feature = "day_15")),
+    (16, cfg!(feature = "day_16")),
+    (17, cfg!(feature = "day_17")),
+    (18, cfg!(feature = "day_18")),
+    (19, cfg!(feature = "day_19")),
+    (20, cfg!(feature = "day_20")),
+    (21, cfg!(feature = "day_21")),
+    (22, cfg!(feature = "day_22")),
+    (23, cfg!(feature = "day_23")),
+    (25, cfg!(feature = "day_25")),
+];
+
+/// The answers each day gave for its example when the file was last updated
+fn golden_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/answers.json")
+}
+
+/// Run `day` against its example input, returning its answers in the same format as the golden file
+fn answers_for(day: u8) -> Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples"))
+        .args(["--day", &day.to_string(), "--format", "json"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to start the runner");
+
+    assert!(
+        output.status.success(),
+        "Day {} failed:\n{}",
+        day,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let results: Value = serde_json::from_slice(&output.stdout).expect("Output is not valid JSON");
+    // The timings will differ each run, so only the answers are kept
+    json!({
+        "day": day,
+        "part1": results[0]["part1"],
+        "part2": results[0]["part2"],
+    })
+}
+
+/// The recorded answers for `day`, if there are any
+fn recorded(golden: &[Value], day: u8) -> Option<&Value> {
+    golden.iter().find(|answers| answers["day"] == day)
+}
+
+#[test]
+fn answers_match_golden() {
+    let path = golden_path();
+    let golden: Vec<Value> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default();
+
+    let answers: Vec<(u8, Option<Value>)> = DAYS
+        .iter()
+        .map(|&(day, included)| (day, included.then(|| answers_for(day))))
+        .collect();
+
+    if env::var_os(UPDATE_GOLDEN_ENV_VAR).is_some() {
+        let updated: Vec<Value> = answers
+            .into_iter()
+            .filter_map(|(day, actual)| actual.or_else(|| recorded(&golden, day).cloned()))
+            .collect();
+        fs::write(
+            &path,
+            serde_json::to_string_pretty(&updated).unwrap() + "\n",
+        )
+        .unwrap();
+        return;
+    }
+
+    let mismatches: Vec<String> = answers
+        .iter()
+        .filter_map(|(day, actual)| {
+            let actual = actual.as_ref()?;
+            match recorded(&golden, *day) {
+                Some(expected) if expected == actual => None,
+                Some(expected) => Some(format!(
+                    "Day {}: expected {}, {} but got {}, {}",
+                    day, expected["part1"], expected["part2"], actual["part1"], actual["part2"]
+                )),
+                None => Some(format!("Day {}: no answers recorded", day)),
+            }
+        })
+        .collect();
+
+    assert!(
+        mismatches.is_empty(),
+        "The answers don't match {}, run with {}=1 to update it if the change is intended.\n{}",
+        path.display(),
+        UPDATE_GOLDEN_ENV_VAR,
+        mismatches.join("\n")
+    );
+}
@@ -0,0 +1,537 @@
+//! Runs each day through the compiled runner against the example from its puzzle description, and checks the answers
+//! it prints. The unit tests in each day's module cover the pieces, this checks they are wired up to the runner and
+//! input loader correctly.
+//!
+//! The runner reads `res/day-N-input` relative to the working directory, so the examples are kept in
+//! `tests/samples/res`, and the runner is started in `tests/samples`. Day 24 has no example to run, as the puzzle only
+//! makes sense with a real input. Days left out of the build by their cargo feature are skipped.
+//!
+//! The JSON output format, and running a single part, are checked against a couple of days, rather than repeating
+//! every day. The `geninput` subcommand is checked by generating a day 19 input, and solving it with the answers the
+//! generator expects, and `--detect` by giving it a couple of the examples without saying which day they are for.
+//! `--input -` is checked by piping an example to the runner on stdin.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Ask the runner for `day`, and return what it printed. It is run with `--verbose`, so that the grids some days draw
+/// are printed as well.
+fn run_day(day: u8) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples"))
+        .arg("--verbose")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start the runner");
+
+    writeln!(child.stdin.take().unwrap(), "{}", day).unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(
+        output.status.success(),
+        "Day {} failed:\n{}",
+        day,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Ask the runner for the answers to `day` as JSON, passing the day as an argument rather than on stdin
+fn run_day_json(day: &str) -> serde_json::Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples"))
+        .args(["--day", day, "--format", "json"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to start the runner");
+
+    assert!(
+        output.status.success(),
+        "Day {} failed:\n{}",
+        day,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    serde_json::from_slice(&output.stdout).expect("Output is not valid JSON")
+}
+
+/// Declare a test that runs a day and checks each of the expected lines appear in the output
+macro_rules! sample_test {
+    ($name:ident, $feature:literal, $day:literal, [$($expected:literal),+ $(,)?]) => {
+        #[test]
+        fn $name() {
+            if !cfg!(feature = $feature) {
+                return;
+            }
+
+            let output = run_day($day);
+            $(
+                assert!(
+                    output.contains($expected),
+                    "Day {} output is missing '{}':\n{}",
+                    $day,
+                    $expected,
+                    output
+                );
+            )+
+        }
+    };
+}
+
+sample_test!(
+    day_1,
+    "day_1",
+    1,
+    [
+        "There are 7 windows of 1 that increment",
+        "There are 5 windows of 3 that increment",
+    ]
+);
+
+sample_test!(
+    day_2,
+    "day_2",
+    2,
+    [
+        "Final position (15, 10) = 150",
+        "Final position with aiming (15, 60) = 900",
+    ]
+);
+
+sample_test!(
+    day_3,
+    "day_3",
+    3,
+    ["Gamma: 22 x Epsilon: 9 = 198", "Oxygen: 23 x CO2: 10 = 230",]
+);
+
+sample_test!(
+    day_4,
+    "day_4",
+    4,
+    [
+        "Remaining Sum: 188 x Winning Number: 24 = 4512",
+        "Remaining Sum: 148 x Losing Number: 13 = 1924",
+    ]
+);
+
+sample_test!(
+    day_5,
+    "day_5",
+    5,
+    [
+        "There are 5 axial intersections",
+        "There are 12 full intersections",
+    ]
+);
+
+sample_test!(
+    day_6,
+    "day_6",
+    6,
+    [
+        "Population count after 80 days: 5934",
+        "Population count after 256 days: 26984457539",
+    ]
+);
+
+sample_test!(
+    day_7,
+    "day_7",
+    7,
+    [
+        "Total fuel to align - linear: 37",
+        "Total fuel to align - triangular: 168",
+    ]
+);
+
+sample_test!(
+    day_8,
+    "day_8",
+    8,
+    [
+        "There are 26 digits with unique lengths in the output.",
+        "The sum of the outputs is 61229.",
+    ]
+);
+
+sample_test!(
+    day_9,
+    "day_9",
+    9,
+    ["Total risk level: 15", "Largest Basins: 14 * 9 * 9 = 1134"]
+);
+
+sample_test!(
+    day_10,
+    "day_10",
+    10,
+    ["Syntax error score: 26397", "Autocomplete score: 288957"]
+);
+
+sample_test!(
+    day_11,
+    "day_11",
+    11,
+    [
+        "There were 1656 flashes in 100 cycles",
+        "It took 195 cycles for the flashes to synchronise.",
+    ]
+);
+
+sample_test!(
+    day_12,
+    "day_12",
+    12,
+    [
+        "There are 226 paths through the 10 caves.",
+        "There are 3509 paths through the caves with revisit.",
+    ]
+);
+
+// The example folds into a square rather than letters, so there is no code to read
+sample_test!(
+    day_13,
+    "day_13",
+    13,
+    [
+        "After the first fold there are 17 dots",
+        "▮▮▮▮▮\n▮   ▮\n▮   ▮\n▮   ▮\n▮▮▮▮▮",
+    ]
+);
+
+sample_test!(
+    day_14,
+    "day_14",
+    14,
+    [
+        "The max - min counts after 10 cycles = 1588.",
+        "The max - min counts after 40 cycles = 2188189693529,",
+    ]
+);
+
+sample_test!(
+    day_15,
+    "day_15",
+    15,
+    [
+        "The cost to traverse the grid is: Some(40)",
+        "The cost to traverse the grid tiles is: Some(315)",
+    ]
+);
+
+sample_test!(
+    day_16,
+    "day_16",
+    16,
+    [
+        "The version sum is: 20",
+        "The result of the operation is: 1",
+    ]
+);
+
+sample_test!(
+    day_17,
+    "day_17",
+    17,
+    [
+        "The highest point reached is 45.",
+        "The count of valid trajectories is 112.",
+    ]
+);
+
+sample_test!(
+    day_18,
+    "day_18",
+    18,
+    [
+        "The magnitude of the sum is: 4140.",
+        "The maximum sum of the permutations is: 3993.",
+    ]
+);
+
+sample_test!(
+    day_19,
+    "day_19",
+    19,
+    [
+        "There are 79 distinct beacons.",
+        "The greatest manhattan distance between scanners is 3621.",
+    ]
+);
+
+sample_test!(
+    day_20,
+    "day_20",
+    20,
+    [
+        "After 2 iterations there are 35 active pixels.",
+        "After 50 iterations there are 3351 active pixels.",
+    ]
+);
+
+sample_test!(
+    day_21,
+    "day_21",
+    21,
+    [
+        "The loser scored 745 after 993 deterministic rolls = 739785",
+        "The player with more quantum wins won 444356092776315 times",
+    ]
+);
+
+sample_test!(
+    day_22,
+    "day_22",
+    22,
+    [
+        "There are 474140 cubes active in the initialisation procedure",
+        "There are 2758514936282235 cubes active in the full reactor",
+    ]
+);
+
+sample_test!(
+    day_23,
+    "day_23",
+    23,
+    [
+        "Lowest energy for small burrow is 12521",
+        "Lowest energy for expanded burrow is 44169",
+    ]
+);
+
+sample_test!(
+    day_25,
+    "day_25",
+    25,
+    ["The sea cucumbers stabilise in 58 steps"]
+);
+
+#[test]
+fn can_output_json() {
+    if !cfg!(feature = "day_2") || !cfg!(feature = "day_25") {
+        return;
+    }
+
+    let results = run_day_json("2");
+    assert_eq!(results.as_array().unwrap().len(), 1);
+    assert_eq!(results[0]["day"], 2);
+    assert_eq!(results[0]["part1"], "150");
+    assert_eq!(results[0]["part2"], "900");
+    assert!(results[0]["micros"].is_u64());
+
+    // Day 25 only has one part
+    let results = run_day_json("25");
+    assert_eq!(results[0]["part1"], "58");
+    assert!(results[0]["part2"].is_null());
+}
+
+#[test]
+fn can_run_one_part() {
+    if !cfg!(feature = "day_2") {
+        return;
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples"))
+        .args(["--day", "2", "--part", "2"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to start the runner");
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(!text.contains("Final position (15, 10) = 150"));
+    assert!(text.contains("Final position with aiming (15, 60) = 900"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples"))
+        .args(["--day", "2", "--part", "1", "--format", "json"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to start the runner");
+    assert!(output.status.success());
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results[0]["part1"], "150");
+    assert!(results[0]["part2"].is_null());
+}
+
+#[test]
+fn can_list_days() {
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .arg("--list")
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to start the runner");
+    assert!(output.status.success());
+
+    let listing = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = listing.lines().collect();
+    assert_eq!(lines.len(), 26);
+    assert_eq!(lines[0], "Advent of Code 2021");
+    assert!(lines[1].starts_with(" 1: Sonar Sweep"));
+    assert!(lines[25].starts_with("25: Sea Cucumber (part one only"));
+}
+
+#[test]
+fn can_solve_generated_input() {
+    if !cfg!(feature = "day_19") {
+        return;
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .args(["geninput", "--day", "19", "--seed", "7"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to start the runner");
+    assert!(output.status.success());
+    let expected = String::from_utf8(output.stderr).unwrap();
+
+    let dir = std::env::temp_dir().join(format!("aoc-geninput-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("res")).unwrap();
+    std::fs::write(dir.join("res/day-19-input"), output.stdout).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .current_dir(&dir)
+        .args(["--day", "19", "--format", "json"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to start the runner");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(output.status.success());
+
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        expected.trim(),
+        format!(
+            "Expected answers: {}, {}",
+            results[0]["part1"].as_str().unwrap(),
+            results[0]["part2"].as_str().unwrap()
+        )
+    );
+}
+
+#[test]
+fn can_detect_the_day() {
+    if !cfg!(feature = "all-days") {
+        return;
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples"))
+        .args(["--detect", "res/day-2-input"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to start the runner");
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        text,
+        "res/day-2-input looks like the input for day 2: Dive!\n"
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples"))
+        .args(["--detect", "res/day-2-input", "--solve", "--format", "json"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to start the runner");
+    assert!(output.status.success());
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results[0]["day"], 2);
+    assert_eq!(results[0]["part1"], "150");
+    assert_eq!(results[0]["part2"], "900");
+
+    // The examples for days 11 and 15 can't be told apart, so neither is solved
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples"))
+        .args(["--detect", "res/day-15-input", "--solve"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to start the runner");
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.contains("- day 11: Dumbo Octopus\n- day 15: Chiton\n"));
+    assert!(text.contains("Not solving it"));
+}
+
+#[test]
+fn can_read_input_from_stdin() {
+    if !cfg!(feature = "day_2") {
+        return;
+    }
+
+    let run = |args: &[&str]| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+            // Somewhere without a res directory, so the input can only have come from stdin
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to start the runner");
+
+        let example = include_str!("samples/res/day-2-input");
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(example.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    };
+
+    let output = run(&["--day", "2", "--input", "-", "--format", "json"]);
+    assert!(output.status.success());
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results[0]["part1"], "150");
+    assert_eq!(results[0]["part2"], "900");
+
+    let output = run(&["--detect", "-"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "stdin looks like the input for day 2: Dive!\n"
+    );
+
+    // The day can't also be asked for on stdin
+    let output = run(&["--input", "-"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Pass --day with --input -"));
+}
+
+#[test]
+fn can_compare_implementations() {
+    if !cfg!(feature = "day_17") {
+        return;
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples"))
+        .args(["--day", "17", "--compare", "--runs", "2"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to start the runner");
+    assert!(output.status.success());
+
+    let text = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "==== Day 17: Trick Shot ====");
+    assert!(lines[1].starts_with("Part 2  count_trajectories_analytic  112  "));
+    assert!(lines[2].starts_with("Part 2  all_trajectories             112  "));
+    assert_eq!(lines[3], "All 2 implementations agree");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2021"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/samples"))
+        .args(["--day", "2", "--compare"])
+        .stdin(Stdio::null())
+        .output()
+        .expect("Failed to start the runner");
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.contains("There is only one way of solving each part"));
+}